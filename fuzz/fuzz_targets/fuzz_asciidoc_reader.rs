@@ -0,0 +1,12 @@
+#![no_main]
+use libfuzzer_sys::{fuzz_target, Corpus};
+use spec_forge::adapters::input::asciidoc_reader::AsciiDocReader;
+
+fuzz_target!(|data: &[u8]| -> Corpus {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return Corpus::Reject;
+    };
+    // parse_asciidoc must never panic
+    let _ = AsciiDocReader::parse_asciidoc(input);
+    Corpus::Keep
+});
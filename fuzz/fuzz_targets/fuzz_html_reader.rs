@@ -0,0 +1,12 @@
+#![no_main]
+use libfuzzer_sys::{fuzz_target, Corpus};
+use spec_forge::adapters::input::html_reader::HtmlReader;
+
+fuzz_target!(|data: &[u8]| -> Corpus {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return Corpus::Reject;
+    };
+    // parse_html must never panic
+    let _ = HtmlReader::parse_html(input);
+    Corpus::Keep
+});
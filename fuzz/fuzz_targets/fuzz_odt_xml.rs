@@ -0,0 +1,12 @@
+#![no_main]
+use libfuzzer_sys::{fuzz_target, Corpus};
+use spec_forge::adapters::input::odt_reader::OdtReader;
+
+fuzz_target!(|data: &[u8]| -> Corpus {
+    let Ok(xml) = std::str::from_utf8(data) else {
+        return Corpus::Reject;
+    };
+    // parse_content_xml must never panic
+    let _ = OdtReader::parse_content_xml(xml);
+    Corpus::Keep
+});
@@ -4,6 +4,7 @@ use std::path::PathBuf;
 
 use tauri::State;
 
+use spec_forge::application::input_paths::ExpandOptions;
 use spec_forge::application::pipeline::Pipeline;
 
 use crate::presentation::state::AppState;
@@ -27,11 +28,33 @@ pub async fn read_stories(
     serde_json::to_value(&story_set).map_err(|e| e.to_string())
 }
 
-/// Expanse les chemins d'entree (dossiers -> fichiers)
+/// Expanse les chemins d'entree (dossiers recursifs, globs, exclusions et
+/// `.specforgeignore`), avec les memes regles que la CLI
 #[tauri::command]
-pub async fn expand_input_paths(paths: Vec<String>) -> Result<Vec<String>, String> {
+pub async fn expand_input_paths(
+    paths: Vec<String>,
+    exclude: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
     let input_paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
-    let expanded = Pipeline::expand_paths(&input_paths).map_err(|e| e.to_string())?;
+    let pipeline_guard = state.pipeline.read().await;
+    // Memes formats que la lecture (`read_story_sets`) : lecteurs du pipeline
+    // configure, lecteurs par defaut sinon
+    let expanded = match pipeline_guard.as_ref() {
+        Some(pipeline) => {
+            let mut options = pipeline.expand_options();
+            options.excludes.extend(exclude.unwrap_or_default());
+            pipeline.expand_with_readers(&input_paths, &options)
+        }
+        None => {
+            let options = ExpandOptions {
+                excludes: exclude.unwrap_or_default(),
+                ..ExpandOptions::default()
+            };
+            Pipeline::expand_paths(&input_paths, &options)
+        }
+    }
+    .map_err(|e| e.to_string())?;
     Ok(expanded
         .iter()
         .map(|p| p.to_string_lossy().to_string())
@@ -0,0 +1,295 @@
+//! Adapter AsciiDocReader - Parse les User Stories depuis un fichier AsciiDoc
+//!
+//! Conversion ligne a ligne vers la forme attendue par `story_parser` :
+//! `=` -> `#` (epic), `==` -> `##` (story), `===`+ -> `###`, puces `*`/`-`/`.`
+//! -> `- `, `*ID*: PAY-42` -> `**ID**: PAY-42`. Les attributs (`:toc:`),
+//! commentaires, delimiteurs de blocs et lignes `[...]` sont ignores ; les
+//! tableaux `|===` sont lus comme les tableaux Word.
+
+use async_trait::async_trait;
+use std::path::Path;
+
+use crate::domain::errors::InputError;
+use crate::domain::user_story::{Language, UserStorySet};
+use crate::ports::input_reader::InputReader;
+
+use super::story_parser;
+use super::structured_document::{DocumentBuilder, StructuredDocument};
+
+/// Lit les User Stories depuis un fichier AsciiDoc
+pub struct AsciiDocReader;
+
+/// Delimiteur de bloc (`----`, `====`, `****`, `____`, `....`, `++++`) ou
+/// continuation de liste (`+`)
+fn is_block_delimiter(line: &str) -> bool {
+    if line == "+" || line == "--" {
+        return true;
+    }
+    let mut chars = line.chars();
+    match chars.next() {
+        Some(first @ ('-' | '=' | '*' | '_' | '.' | '+')) => {
+            line.len() >= 4 && chars.all(|c| c == first)
+        }
+        _ => false,
+    }
+}
+
+/// Ligne d'attribut de document (`:toc:`, `:lang: fr`)
+fn is_attribute_entry(line: &str) -> bool {
+    line.strip_prefix(':')
+        .and_then(|rest| rest.split_once(':'))
+        .is_some_and(|(name, _)| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '!')
+        })
+}
+
+/// Titre de section : (`= Titre` -> 1, `== Titre` -> 2, ...)
+fn section_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '=').count();
+    let title = line[level..].strip_prefix(' ')?.trim();
+    (level > 0 && !title.is_empty()).then_some((level, title))
+}
+
+/// Element de liste (`* a`, `** a`, `- a`, `. a`, `.. a`)
+fn list_item(line: &str) -> Option<&str> {
+    let marker = line.chars().next()?;
+    if !matches!(marker, '*' | '-' | '.') {
+        return None;
+    }
+    let depth = line.chars().take_while(|c| *c == marker).count();
+    let text = line[depth..].strip_prefix(' ')?.trim();
+    let text = text
+        .strip_prefix("[ ] ")
+        .or_else(|| text.strip_prefix("[x] "))
+        .or_else(|| text.strip_prefix("[*] "))
+        .unwrap_or(text);
+    (!text.is_empty()).then_some(text)
+}
+
+/// Gras AsciiDoc en tete de ligne (`*ID*: x`, `*ID:* x`) -> gras Markdown
+fn markdown_bold_prefix(line: &str) -> Option<String> {
+    let rest = line.strip_prefix('*')?;
+    let end = rest.find('*')?;
+    let (key, after) = (&rest[..end], &rest[end + 1..]);
+    if key.is_empty() || key.starts_with(' ') {
+        return None;
+    }
+    Some(format!("**{}**{}", key, after))
+}
+
+/// Cellules d'une ligne de tableau (`| a | b`)
+fn table_cells(line: &str) -> Vec<String> {
+    line.split('|')
+        .skip(1)
+        .map(|cell| cell.trim().to_string())
+        .collect()
+}
+
+/// Tableau `|===` en cours : le nombre de colonnes est donne par la premiere ligne
+#[derive(Default)]
+struct AsciiDocTable {
+    columns: usize,
+    cells: Vec<String>,
+}
+
+impl AsciiDocTable {
+    fn push_line(&mut self, line: &str) {
+        if line.starts_with('|') {
+            let cells = table_cells(line);
+            if self.columns == 0 {
+                self.columns = cells.len();
+            }
+            self.cells.extend(cells);
+        } else if let Some(last) = self.cells.last_mut() {
+            // Suite du contenu de la cellule precedente
+            last.push('\n');
+            last.push_str(line);
+        }
+    }
+
+    fn write_to(self, doc: &mut DocumentBuilder) {
+        doc.start_table();
+        for row in self.cells.chunks(self.columns.max(1)) {
+            doc.start_row();
+            for cell in row {
+                doc.start_cell();
+                for (i, line) in cell.lines().enumerate() {
+                    if i > 0 {
+                        doc.start_paragraph();
+                    }
+                    doc.push_text(line);
+                }
+                doc.end_cell();
+            }
+            doc.end_row();
+        }
+        doc.end_table();
+    }
+}
+
+impl AsciiDocReader {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Convertit un document AsciiDoc : texte Markdown + stories des tableaux
+    pub fn parse_asciidoc(content: &str) -> StructuredDocument {
+        let mut doc = DocumentBuilder::default();
+        let mut in_comment_block = false;
+        let mut table: Option<AsciiDocTable> = None;
+
+        for raw_line in content.lines() {
+            let line = raw_line.trim_end();
+
+            if line == "////" {
+                in_comment_block = !in_comment_block;
+                continue;
+            }
+            if in_comment_block || line.starts_with("//") {
+                continue;
+            }
+            if line.starts_with("|===") {
+                match table.take() {
+                    Some(t) => t.write_to(&mut doc),
+                    None => table = Some(AsciiDocTable::default()),
+                }
+                continue;
+            }
+            if let Some(ref mut t) = table {
+                if !line.trim().is_empty() {
+                    t.push_line(line.trim());
+                }
+                continue;
+            }
+            if is_attribute_entry(line)
+                || is_block_delimiter(line)
+                || (line.starts_with('[') && line.ends_with(']'))
+            {
+                continue;
+            }
+
+            doc.start_paragraph();
+            if let Some((level, title)) = section_heading(line) {
+                doc.set_heading(level);
+                doc.push_text(title);
+            } else if let Some(text) = list_item(line) {
+                doc.set_list_item();
+                doc.push_text(&markdown_bold_prefix(text).unwrap_or_else(|| text.to_string()));
+            } else if line.starts_with('.') && !line.starts_with("..") {
+                // Titre de bloc (`.Criteres`) : conserve comme texte simple
+                doc.push_text(&line[1..]);
+            } else {
+                doc.push_text(&markdown_bold_prefix(line).unwrap_or_else(|| line.to_string()));
+            }
+            doc.end_paragraph();
+        }
+        if let Some(t) = table {
+            t.write_to(&mut doc);
+        }
+
+        doc.finish()
+    }
+}
+
+impl Default for AsciiDocReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl InputReader for AsciiDocReader {
+    async fn read_stories(&self, path: &Path) -> Result<UserStorySet, InputError> {
+        if !path.exists() {
+            return Err(InputError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        super::check_file_size(path)?;
+
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| InputError::ReadError(e.to_string()))?;
+        let document = Self::parse_asciidoc(&content);
+        let language = self.detect_language(&document.language_sample());
+        let stories = document.into_stories(language)?;
+
+        Ok(UserStorySet {
+            stories,
+            source_files: vec![path.display().to_string()],
+            language,
+        })
+    }
+
+    fn detect_language(&self, content: &str) -> Language {
+        story_parser::detect_language(content)
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &["adoc", "asciidoc"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_sections_lists_and_metadata() {
+        let adoc = "= Paiements\n:toc: left\n:lang: fr\n\n// brouillon\n== Virement SEPA\n\n*ID*: PAY-42\n\n[.lead]\nEn tant que client, je veux virer de l'argent afin de payer un tiers.\n\n.Criteres\n* Le plafond est de 10 000 EUR\n** L'IBAN est valide\n\n////\n== Story commentee\n////\n";
+        let doc = AsciiDocReader::parse_asciidoc(adoc);
+        assert!(doc.text.contains("\n# Paiements"));
+        assert!(doc.text.contains("**ID**: PAY-42"));
+        assert!(!doc.text.contains("toc"));
+        assert!(!doc.text.contains("commentee"));
+
+        let stories = doc.into_stories(Language::French).unwrap();
+        assert_eq!(stories.len(), 1);
+        assert_eq!(stories[0].title, "Virement SEPA");
+        assert_eq!(stories[0].external_id.as_deref(), Some("PAY-42"));
+        assert_eq!(stories[0].epic.as_deref(), Some("Paiements"));
+        assert_eq!(
+            stories[0].acceptance_criteria,
+            vec!["Le plafond est de 10 000 EUR", "L'IBAN est valide"]
+        );
+    }
+
+    #[test]
+    fn test_story_table_one_cell_per_line() {
+        let adoc = "[cols=\"1,2,1,2,2\",options=\"header\"]\n|===\n|ID |Title |As a |I want |So that\n\n|DOC-1\n|Export\n|user\n|export my data\n|keep a copy\n|===\n";
+        let doc = AsciiDocReader::parse_asciidoc(adoc);
+        assert_eq!(doc.table_stories.len(), 1);
+        let us = &doc.table_stories[0];
+        assert_eq!(us.external_id.as_deref(), Some("DOC-1"));
+        assert_eq!(us.benefit, "keep a copy");
+    }
+
+    #[test]
+    fn test_line_helpers() {
+        assert!(is_block_delimiter("----"));
+        assert!(!is_block_delimiter("---x"));
+        assert!(is_attribute_entry(":toc: left"));
+        assert!(!is_attribute_entry(":: x"));
+        assert_eq!(section_heading("=== Sous-titre"), Some((3, "Sous-titre")));
+        assert_eq!(section_heading("==x"), None);
+        assert_eq!(list_item(". etape"), Some("etape"));
+        assert_eq!(list_item("*gras*"), None);
+    }
+
+    mod proptest_suite {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn parse_asciidoc_never_panics(input in "\\PC*") {
+                let _ = AsciiDocReader::parse_asciidoc(&input);
+            }
+        }
+    }
+}
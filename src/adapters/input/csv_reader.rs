@@ -0,0 +1,168 @@
+//! Adapter CsvReader - Parse les User Stories depuis un export tableur (.csv)
+//!
+//! Une ligne par story. Les colonnes sont reconnues par leur en-tete (FR/EN,
+//! insensible a la casse) ; le separateur `;` (Excel FR) ou `,` est detecte.
+
+use async_trait::async_trait;
+use std::path::Path;
+
+use crate::domain::errors::InputError;
+use crate::domain::user_story::{Language, UserStory, UserStorySet};
+use crate::ports::input_reader::InputReader;
+
+use super::story_parser;
+use super::story_table::StoryTable;
+
+/// Lit les User Stories depuis un fichier CSV
+pub struct CsvReader;
+
+/// Detecte le separateur a partir de la ligne d'en-tete
+fn detect_delimiter(content: &str) -> u8 {
+    let header = content.lines().next().unwrap_or("");
+    if header.matches(';').count() > header.matches(',').count() {
+        b';'
+    } else {
+        b','
+    }
+}
+
+impl CsvReader {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse le contenu CSV en User Stories
+    pub fn parse_csv(content: &str) -> Result<Vec<UserStory>, InputError> {
+        let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(detect_delimiter(content))
+            .flexible(true)
+            .from_reader(content.as_bytes());
+
+        let headers = reader
+            .headers()
+            .map_err(|e| InputError::ParseError(format!("En-tete CSV invalide: {e}")))?
+            .clone();
+        let table =
+            StoryTable::from_headers(&headers.iter().collect::<Vec<_>>()).ok_or_else(|| {
+                InputError::InvalidFormat {
+                    details: "CSV sans colonne 'title' ni 'action'".into(),
+                }
+            })?;
+
+        let mut stories = Vec::new();
+        for (idx, record) in reader.records().enumerate() {
+            let record = record
+                .map_err(|e| InputError::ParseError(format!("Ligne CSV {}: {e}", idx + 2)))?;
+            let row: Vec<&str> = record.iter().collect();
+            if let Some(mut us) = table.story_from_row(&row) {
+                let position = stories.len() + 1;
+                us.external_id
+                    .get_or_insert_with(|| format!("US-{:03}", position));
+                stories.push(us);
+            }
+        }
+
+        if stories.is_empty() {
+            return Err(InputError::NoStoriesFound);
+        }
+
+        Ok(stories)
+    }
+}
+
+impl Default for CsvReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl InputReader for CsvReader {
+    async fn read_stories(&self, path: &Path) -> Result<UserStorySet, InputError> {
+        if !path.exists() {
+            return Err(InputError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        super::check_file_size(path)?;
+
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| InputError::ReadError(e.to_string()))?;
+
+        let stories = Self::parse_csv(&content)?;
+        let language = self.detect_language(&content);
+
+        Ok(UserStorySet {
+            stories,
+            source_files: vec![path.display().to_string()],
+            language,
+        })
+    }
+
+    fn detect_language(&self, content: &str) -> Language {
+        story_parser::detect_language(content)
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &["csv"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::user_story::Priority;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_csv_semicolon_with_epic() {
+        let content = "ID;Titre;En tant que;Je veux;Afin de;Priorité;Critères;Tags;Epic\n\
+PAY-1;Virement;client;virer de l'argent;payer un tiers;P1;Plafond 10k | IBAN valide;sepa, virement;Paiements\n\
+CARD-1;Opposition;client;bloquer ma carte;eviter la fraude;Must;;;Cartes\n";
+        let stories = CsvReader::parse_csv(content).unwrap();
+        assert_eq!(stories.len(), 2);
+        assert_eq!(stories[0].external_id.as_deref(), Some("PAY-1"));
+        assert_eq!(stories[0].actor, "client");
+        assert_eq!(stories[0].priority, Some(Priority::P1));
+        assert_eq!(
+            stories[0].acceptance_criteria,
+            vec!["Plafond 10k", "IBAN valide"]
+        );
+        assert_eq!(stories[0].tags, vec!["sepa", "virement"]);
+        assert_eq!(stories[0].epic.as_deref(), Some("Paiements"));
+        assert_eq!(stories[1].epic.as_deref(), Some("Cartes"));
+        assert!(stories[1].acceptance_criteria.is_empty());
+    }
+
+    #[test]
+    fn test_parse_csv_comma_quoted_multiline_criteria() {
+        let content = "title,actor,action,benefit,acceptance criteria\n\
+Login,user,log in,see my account,\"- wrong password is rejected\n- account locks after 3 failures\"\n";
+        let stories = CsvReader::parse_csv(content).unwrap();
+        assert_eq!(stories.len(), 1);
+        assert_eq!(stories[0].external_id.as_deref(), Some("US-001"));
+        assert_eq!(stories[0].acceptance_criteria.len(), 2);
+        assert!(stories[0].epic.is_none());
+    }
+
+    #[test]
+    fn test_parse_csv_without_story_columns() {
+        let content = "foo,bar\n1,2\n";
+        assert!(matches!(
+            CsvReader::parse_csv(content),
+            Err(InputError::InvalidFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_csv_header_only() {
+        let content = "title,action\n";
+        assert!(matches!(
+            CsvReader::parse_csv(content),
+            Err(InputError::NoStoriesFound)
+        ));
+    }
+}
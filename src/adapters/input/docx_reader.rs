@@ -1,11 +1,15 @@
 //! Adapter DocxReader - Extrait les User Stories depuis un fichier Word (.docx)
 //!
-//! Un fichier .docx est une archive ZIP contenant du XML.
-//! Le texte se trouve dans `word/document.xml` dans les elements `<w:t>`.
+//! Un fichier .docx est une archive ZIP contenant du XML (`word/document.xml`).
+//! La structure Word est traduite en Markdown pour `story_parser` : styles
+//! `Heading1`/`Heading2` -> `#`/`##` (un `Heading1` sans `Heading2` sous lui
+//! delimite une story), paragraphes numerotes (`w:numPr`) -> puces `- `.
+//! Les tableaux de stories (ID | Titre | En tant que | ...) sont lus ligne
+//! par ligne ; les suppressions suivies (`w:del`) sont ignorees.
 
 use async_trait::async_trait;
 use quick_xml::Reader;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
 use std::io::Read;
 use std::path::Path;
 
@@ -14,94 +18,164 @@ use crate::domain::user_story::{Language, UserStorySet};
 use crate::ports::input_reader::InputReader;
 
 use super::story_parser;
+use super::structured_document::{DocumentBuilder, StructuredDocument, resolve_xml_ref};
 
 /// Lit les User Stories depuis un fichier Word (.docx)
 pub struct DocxReader;
 
+/// Elements dont le contenu est ignore (suppressions suivies, commentaires,
+/// rendu alternatif `mc:Fallback`)
+const SKIPPED_ELEMENTS: &[&[u8]] = &[b"del", b"moveFrom", b"comment", b"Fallback"];
+
+/// Niveau de titre d'un style de paragraphe ("Heading1", "Titre 2", "heading 1")
+fn heading_level(style: &str) -> Option<usize> {
+    let normalized: String = style
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-' && *c != '_')
+        .collect();
+    ["heading", "titre", "berschrift", "encabezado", "titulo"]
+        .iter()
+        .find_map(|prefix| {
+            let idx = normalized.find(prefix)?;
+            normalized[idx + prefix.len()..].parse::<usize>().ok()
+        })
+        .filter(|level| *level > 0)
+}
+
+/// Valeur de l'attribut `w:val` d'un element
+fn val_attribute(e: &BytesStart) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == b"val")
+        .and_then(|a| String::from_utf8(a.value.into_owned()).ok())
+}
+
+/// Parcours du XML Word (elements `w:*`)
+#[derive(Default)]
+struct DocxBuilder {
+    doc: DocumentBuilder,
+    in_paragraph: bool,
+    in_text: bool,
+    skip_depth: usize,
+}
+
+impl DocxBuilder {
+    fn push_text(&mut self, text: &str) {
+        if self.in_text {
+            self.push_raw(text);
+        }
+    }
+
+    fn push_raw(&mut self, text: &str) {
+        if self.in_paragraph && self.skip_depth == 0 {
+            self.doc.push_text(text);
+        }
+    }
+
+    fn start(&mut self, e: &BytesStart, is_empty: bool) {
+        let local_name = e.local_name();
+        let name = local_name.as_ref();
+        if SKIPPED_ELEMENTS.contains(&name) {
+            if !is_empty {
+                self.skip_depth += 1;
+            }
+            return;
+        }
+        match name {
+            b"p" => {
+                self.doc.start_paragraph();
+                self.in_paragraph = !is_empty;
+                if is_empty {
+                    self.doc.end_paragraph();
+                }
+            }
+            b"pStyle" if self.in_paragraph => {
+                if let Some(level) = val_attribute(e).as_deref().and_then(heading_level) {
+                    self.doc.set_heading(level);
+                }
+            }
+            b"outlineLvl" if self.in_paragraph => {
+                if let Some(level) = val_attribute(e).and_then(|v| v.parse::<usize>().ok())
+                    && level < 9
+                {
+                    self.doc.set_heading(level + 1);
+                }
+            }
+            b"numPr" if self.in_paragraph => self.doc.set_list_item(),
+            b"t" if !is_empty => self.in_text = true,
+            b"tab" => self.push_raw("\t"),
+            b"br" | b"cr" => self.push_raw("\n"),
+            b"tbl" if !is_empty => self.doc.start_table(),
+            b"tr" if !is_empty => self.doc.start_row(),
+            b"tc" if !is_empty => self.doc.start_cell(),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, name: &[u8]) {
+        if SKIPPED_ELEMENTS.contains(&name) {
+            self.skip_depth = self.skip_depth.saturating_sub(1);
+            return;
+        }
+        match name {
+            b"t" => self.in_text = false,
+            b"p" => {
+                self.in_paragraph = false;
+                self.doc.end_paragraph();
+            }
+            b"tc" => self.doc.end_cell(),
+            b"tr" => self.doc.end_row(),
+            b"tbl" => self.doc.end_table(),
+            _ => {}
+        }
+    }
+}
+
 impl DocxReader {
     pub fn new() -> Self {
         Self
     }
 
-    /// Extrait le texte brut depuis un fichier .docx
-    fn extract_text(path: &Path) -> Result<String, InputError> {
+    /// Lit `word/document.xml` depuis un fichier .docx
+    fn read_document_xml(path: &Path) -> Result<String, InputError> {
         let file = std::fs::File::open(path).map_err(|e| InputError::ReadError(e.to_string()))?;
 
         let mut archive = zip::ZipArchive::new(file)
             .map_err(|e| InputError::ParseError(format!("Erreur ouverture ZIP (docx): {e}")))?;
 
-        // Lire word/document.xml
         let mut document_xml = String::new();
-        {
-            let mut doc_file = archive.by_name("word/document.xml").map_err(|e| {
-                InputError::ParseError(format!("word/document.xml introuvable dans le .docx: {e}"))
-            })?;
-            doc_file
-                .read_to_string(&mut document_xml)
-                .map_err(|e| InputError::ReadError(e.to_string()))?;
-        }
-
-        // Parser le XML et extraire le texte des elements <w:t>
-        Self::extract_text_from_xml(&document_xml)
+        let mut doc_file = archive.by_name("word/document.xml").map_err(|e| {
+            InputError::ParseError(format!("word/document.xml introuvable dans le .docx: {e}"))
+        })?;
+        doc_file
+            .read_to_string(&mut document_xml)
+            .map_err(|e| InputError::ReadError(e.to_string()))?;
+        Ok(document_xml)
     }
 
-    /// Parse le XML du document Word et extrait le texte
-    pub fn extract_text_from_xml(xml: &str) -> Result<String, InputError> {
+    /// Parse le XML du document Word : texte Markdown + stories des tableaux
+    pub fn parse_document_xml(xml: &str) -> Result<StructuredDocument, InputError> {
         let mut reader = Reader::from_str(xml);
-        let mut text = String::new();
-        let mut in_text_element = false;
-        let mut in_paragraph = false;
+        let mut builder = DocxBuilder::default();
         let mut buf = Vec::new();
 
         loop {
             match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
-                    let local_name = e.local_name();
-                    match local_name.as_ref() {
-                        b"p" => {
-                            // Nouveau paragraphe : ajouter un saut de ligne
-                            if !text.is_empty() && !text.ends_with('\n') {
-                                text.push('\n');
-                            }
-                            in_paragraph = true;
-                        }
-                        b"t" if in_paragraph => {
-                            in_text_element = true;
-                        }
-                        b"tab" if in_paragraph => {
-                            text.push('\t');
-                        }
-                        b"br" if in_paragraph => {
-                            text.push('\n');
-                        }
-                        _ => {}
-                    }
-                }
-                Ok(Event::Text(ref e)) if in_text_element => {
+                Ok(Event::Start(ref e)) => builder.start(e, false),
+                Ok(Event::Empty(ref e)) => builder.start(e, true),
+                Ok(Event::Text(ref e)) => {
                     let decoded = e.decode().map_err(|err| {
                         InputError::ParseError(format!("Erreur decodage XML: {err}"))
                     })?;
-                    text.push_str(&decoded);
-                }
-                Ok(Event::GeneralRef(ref e)) if in_text_element => {
-                    let name: &[u8] = e.as_ref();
-                    match name {
-                        b"amp" => text.push('&'),
-                        b"lt" => text.push('<'),
-                        b"gt" => text.push('>'),
-                        b"apos" => text.push('\''),
-                        b"quot" => text.push('"'),
-                        _ => {}
-                    }
+                    builder.push_text(&decoded);
                 }
-                Ok(Event::End(ref e)) => {
-                    let local_name = e.local_name();
-                    match local_name.as_ref() {
-                        b"t" => in_text_element = false,
-                        b"p" => in_paragraph = false,
-                        _ => {}
+                Ok(Event::GeneralRef(ref e)) => {
+                    if let Some(c) = resolve_xml_ref(e) {
+                        builder.push_text(c.encode_utf8(&mut [0; 4]));
                     }
                 }
+                Ok(Event::End(ref e)) => builder.end(e.local_name().as_ref()),
                 Ok(Event::Eof) => break,
                 Err(e) => {
                     return Err(InputError::ParseError(format!(
@@ -113,7 +187,12 @@ impl DocxReader {
             buf.clear();
         }
 
-        Ok(text)
+        Ok(builder.doc.finish())
+    }
+
+    /// Parse le XML du document Word et extrait le texte (format Markdown)
+    pub fn extract_text_from_xml(xml: &str) -> Result<String, InputError> {
+        Ok(Self::parse_document_xml(xml)?.text)
     }
 }
 
@@ -134,9 +213,10 @@ impl InputReader for DocxReader {
 
         super::check_file_size(path)?;
 
-        let content = Self::extract_text(path)?;
-        let language = self.detect_language(&content);
-        let stories = story_parser::parse_stories(&content, language)?;
+        let xml = Self::read_document_xml(path)?;
+        let content = Self::parse_document_xml(&xml)?;
+        let language = self.detect_language(&content.language_sample());
+        let stories = content.into_stories(language)?;
 
         Ok(UserStorySet {
             stories,
@@ -152,6 +232,10 @@ impl InputReader for DocxReader {
     fn supported_extensions(&self) -> &[&str] {
         &["docx"]
     }
+
+    fn sniff(&self, path: &Path, head: &[u8]) -> bool {
+        head.starts_with(super::ZIP_MAGIC) && super::zip_has_entry(path, "word/document.xml")
+    }
 }
 
 #[cfg(test)]
@@ -246,6 +330,146 @@ mod tests {
         assert_eq!(paragraphs.len(), 3);
     }
 
+    const W_NS: &str = r#"xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main""#;
+
+    fn document(body: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><w:document {W_NS}><w:body>{body}</w:body></w:document>"#
+        )
+    }
+
+    fn paragraph(style: Option<&str>, numbered: bool, text: &str) -> String {
+        let style = style
+            .map(|s| format!(r#"<w:pStyle w:val="{s}"/>"#))
+            .unwrap_or_default();
+        let num = if numbered {
+            r#"<w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr>"#
+        } else {
+            ""
+        };
+        format!(
+            r#"<w:p><w:pPr>{style}{num}</w:pPr><w:r><w:t xml:space="preserve">{text}</w:t></w:r></w:p>"#
+        )
+    }
+
+    #[test]
+    fn test_headings_and_numbered_lists_split_stories() {
+        let body = [
+            paragraph(Some("Heading1"), false, "Paiements"),
+            paragraph(Some("Heading2"), false, "Virement SEPA"),
+            paragraph(
+                None,
+                false,
+                "En tant que client, je veux virer de l'argent afin de payer un tiers.",
+            ),
+            paragraph(None, true, "Le plafond est de 10 000 EUR"),
+            paragraph(None, true, "L'IBAN est valide"),
+            paragraph(Some("Titre2"), false, "Opposition carte"),
+            paragraph(
+                None,
+                false,
+                "En tant que client, je veux bloquer ma carte afin de limiter la fraude.",
+            ),
+            paragraph(None, true, "Le blocage est immediat"),
+        ]
+        .concat();
+        let content = DocxReader::parse_document_xml(&document(&body)).unwrap();
+        assert!(content.text.contains("\n# Paiements"));
+        assert!(content.text.contains("\n## Virement SEPA"));
+        assert!(content.text.contains("- L'IBAN est valide"));
+
+        let stories = content.into_stories(Language::French).unwrap();
+        assert_eq!(stories.len(), 2);
+        assert_eq!(stories[0].title, "Virement SEPA");
+        assert_eq!(
+            stories[0].acceptance_criteria,
+            vec!["Le plafond est de 10 000 EUR", "L'IBAN est valide"]
+        );
+        assert_eq!(stories[0].epic.as_deref(), Some("Paiements"));
+        assert_eq!(stories[1].acceptance_criteria.len(), 1);
+    }
+
+    #[test]
+    fn test_story_table_rows() {
+        let cell = |text: &str| format!("<w:tc>{}</w:tc>", paragraph(None, false, text));
+        let row = |cells: &[&str]| {
+            format!(
+                "<w:tr>{}</w:tr>",
+                cells.iter().map(|c| cell(c)).collect::<String>()
+            )
+        };
+        let criteria_cell = format!(
+            "<w:tc>{}{}</w:tc>",
+            paragraph(None, true, "Photo recto verso"),
+            paragraph(None, true, "Montant max 5000 EUR")
+        );
+        let body = format!(
+            "{}<w:tbl>{}{}<w:tr>{}{}</w:tr></w:tbl>",
+            paragraph(Some("Heading1"), false, "Cheques"),
+            row(&[
+                "ID",
+                "Titre",
+                "En tant que",
+                "Je veux",
+                "Afin de",
+                "Criteres"
+            ]),
+            row(&[
+                "CHQ-1",
+                "Depot photo",
+                "client",
+                "deposer un cheque",
+                "gagner du temps",
+                ""
+            ]),
+            [
+                "CHQ-2",
+                "Suivi",
+                "client",
+                "suivre mon depot",
+                "etre rassure"
+            ]
+            .iter()
+            .map(|c| cell(c))
+            .collect::<String>(),
+            criteria_cell,
+        );
+        let content = DocxReader::parse_document_xml(&document(&body)).unwrap();
+        assert_eq!(content.table_stories.len(), 2);
+
+        let stories = content.into_stories(Language::French).unwrap();
+        assert_eq!(stories[0].external_id.as_deref(), Some("CHQ-1"));
+        assert_eq!(stories[0].epic.as_deref(), Some("Cheques"));
+        assert_eq!(
+            stories[1].acceptance_criteria,
+            vec!["Photo recto verso", "Montant max 5000 EUR"]
+        );
+    }
+
+    #[test]
+    fn test_tracked_deletions_and_comments_ignored() {
+        let body = r#"<w:p>
+            <w:r><w:t>En tant que client, je veux </w:t></w:r>
+            <w:del w:id="1" w:author="A"><w:r><w:delText>supprimer</w:delText></w:r><w:r><w:t>texte efface</w:t></w:r></w:del>
+            <w:ins w:id="2" w:author="A"><w:r><w:t>consulter mon solde</w:t></w:r></w:ins>
+            <w:commentRangeStart w:id="0"/><w:r><w:t> afin de suivre mes depenses.</w:t></w:r><w:commentRangeEnd w:id="0"/>
+            <w:r><w:commentReference w:id="0"/></w:r>
+        </w:p>"#;
+        let text = DocxReader::extract_text_from_xml(&document(body)).unwrap();
+        assert!(!text.contains("efface"));
+        assert!(!text.contains("supprimer"));
+        assert!(text.contains("je veux consulter mon solde afin de suivre"));
+    }
+
+    #[test]
+    fn test_heading_level_styles() {
+        assert_eq!(heading_level("Heading1"), Some(1));
+        assert_eq!(heading_level("heading 2"), Some(2));
+        assert_eq!(heading_level("Titre2"), Some(2));
+        assert_eq!(heading_level("Normal"), None);
+        assert_eq!(heading_level("ListParagraph"), None);
+    }
+
     mod proptest_suite {
         use super::*;
         use proptest::prelude::*;
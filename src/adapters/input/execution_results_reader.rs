@@ -0,0 +1,438 @@
+//! Adapter ExecutionResultsReader - Lit les resultats d'execution Cucumber
+//!
+//! Formats acceptes (detectes d'apres le contenu) :
+//! - Cucumber JSON (`--format json` de Cucumber-JVM, cucumber-js, cucumber-rs)
+//! - JUnit XML (Cucumber-JVM, cucumber-js, cucumber-rs `writer::JUnit`)
+//! - Sortie console de cucumber-rs (`writer::Basic`, codes ANSI toleres)
+
+use std::path::Path;
+
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use serde::Deserialize;
+
+use crate::domain::errors::InputError;
+use crate::domain::i18n;
+use crate::domain::user_story::Language;
+use crate::domain::verification::{ExecutedScenario, ExecutionResults, ExecutionStatus};
+
+/// Lit un fichier de resultats d'execution
+pub struct ExecutionResultsReader;
+
+impl ExecutionResultsReader {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Lit et analyse un fichier de resultats
+    pub async fn read(&self, path: &Path) -> Result<ExecutionResults, InputError> {
+        if !path.exists() {
+            return Err(InputError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+        super::check_file_size(path)?;
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| InputError::ReadError(e.to_string()))?;
+        self.parse(&content)
+    }
+
+    /// Analyse un contenu de resultats, format detecte d'apres le premier caractere
+    pub fn parse(&self, content: &str) -> Result<ExecutionResults, InputError> {
+        let content = content.trim_start_matches('\u{feff}').trim_start();
+        let results = if content.starts_with('[') {
+            parse_cucumber_json(content)?
+        } else if content.starts_with('<') {
+            parse_junit_xml(content)?
+        } else {
+            parse_cucumber_rs_output(content)
+        };
+        if results.scenarios.is_empty() {
+            return Err(InputError::InvalidFormat {
+                details: "aucun scenario execute trouve dans les resultats".into(),
+            });
+        }
+        Ok(results)
+    }
+}
+
+impl Default for ExecutionResultsReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Cucumber JSON
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct JsonFeature {
+    #[serde(default)]
+    uri: Option<String>,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    tags: Vec<JsonTag>,
+    #[serde(default)]
+    elements: Vec<JsonElement>,
+}
+
+#[derive(Deserialize)]
+struct JsonElement {
+    #[serde(default)]
+    name: String,
+    #[serde(default, rename = "type")]
+    kind: String,
+    #[serde(default)]
+    tags: Vec<JsonTag>,
+    #[serde(default)]
+    before: Vec<JsonStep>,
+    #[serde(default)]
+    steps: Vec<JsonStep>,
+    #[serde(default)]
+    after: Vec<JsonStep>,
+}
+
+#[derive(Deserialize)]
+struct JsonTag {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct JsonStep {
+    #[serde(default)]
+    result: Option<JsonResult>,
+}
+
+#[derive(Deserialize)]
+struct JsonResult {
+    #[serde(default)]
+    status: String,
+}
+
+fn parse_cucumber_json(content: &str) -> Result<ExecutionResults, InputError> {
+    let features: Vec<JsonFeature> = serde_json::from_str(content)
+        .map_err(|e| InputError::ParseError(format!("Cucumber JSON invalide: {e}")))?;
+
+    let mut scenarios = Vec::new();
+    for feature in features {
+        let feature_tags: Vec<String> = feature.tags.into_iter().map(|t| t.name).collect();
+        // Cucumber-JVM emet le Background comme un element distinct, applique
+        // au scenario qui le suit
+        let mut background: Option<ExecutionStatus> = None;
+        for element in feature.elements {
+            let status = element
+                .before
+                .iter()
+                .chain(&element.steps)
+                .chain(&element.after)
+                .filter_map(|s| s.result.as_ref())
+                .filter_map(|r| ExecutionStatus::from_code(&r.status))
+                .reduce(ExecutionStatus::worst);
+            if element.kind == "background" {
+                background = status;
+                continue;
+            }
+            let status = match (background.take(), status) {
+                (Some(bg), Some(own)) => bg.worst(own),
+                (bg, own) => bg.or(own).unwrap_or(ExecutionStatus::Passed),
+            };
+            let mut tags = feature_tags.clone();
+            tags.extend(element.tags.into_iter().map(|t| t.name));
+            scenarios.push(ExecutedScenario {
+                feature: feature.name.clone(),
+                feature_file: feature.uri.clone(),
+                name: element.name,
+                tags,
+                status,
+            });
+        }
+    }
+    Ok(ExecutionResults { scenarios })
+}
+
+// ---------------------------------------------------------------------------
+// JUnit XML
+// ---------------------------------------------------------------------------
+
+/// Cas de test JUnit en cours de lecture
+struct JUnitCase {
+    feature: String,
+    name: String,
+    status: ExecutionStatus,
+}
+
+fn parse_junit_xml(content: &str) -> Result<ExecutionResults, InputError> {
+    let mut reader = Reader::from_str(content);
+    let mut buf = Vec::new();
+    let mut suite_name = String::new();
+    let mut current: Option<JUnitCase> = None;
+    let mut scenarios = Vec::new();
+
+    loop {
+        let event = reader
+            .read_event_into(&mut buf)
+            .map_err(|e| InputError::ParseError(format!("JUnit XML invalide: {e}")))?;
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let empty = matches!(event, Event::Empty(_));
+                match e.local_name().as_ref() {
+                    b"testsuite" => suite_name = attribute(e, b"name").unwrap_or_default(),
+                    b"testcase" => {
+                        let case = JUnitCase {
+                            feature: attribute(e, b"classname")
+                                .unwrap_or_else(|| suite_name.clone()),
+                            name: attribute(e, b"name").unwrap_or_default(),
+                            status: ExecutionStatus::Passed,
+                        };
+                        if empty {
+                            scenarios.push(junit_scenario(case));
+                        } else {
+                            current = Some(case);
+                        }
+                    }
+                    b"failure" | b"error" => {
+                        if let Some(case) = current.as_mut() {
+                            case.status = case.status.worst(ExecutionStatus::Failed);
+                        }
+                    }
+                    b"skipped" => {
+                        if let Some(case) = current.as_mut() {
+                            let message = attribute(e, b"message").unwrap_or_default();
+                            let status = if message.to_lowercase().contains("undefined")
+                                || message.to_lowercase().contains("pending")
+                            {
+                                ExecutionStatus::Undefined
+                            } else {
+                                ExecutionStatus::Skipped
+                            };
+                            case.status = case.status.worst(status);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::End(ref e) if e.local_name().as_ref() == b"testcase" => {
+                if let Some(case) = current.take() {
+                    scenarios.push(junit_scenario(case));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    Ok(ExecutionResults { scenarios })
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == name)
+        .and_then(|a| a.unescape_value().ok())
+        .map(|v| v.into_owned())
+}
+
+/// cucumber-rs prefixe les noms par le mot-cle et les suffixe par
+/// `: chemin.feature:ligne:colonne`
+fn junit_scenario(case: JUnitCase) -> ExecutedScenario {
+    let (feature, feature_file) = split_location(strip_keyword(&case.feature));
+    let (name, scenario_file) = split_location(strip_keyword(&case.name));
+    ExecutedScenario {
+        feature: feature.to_string(),
+        feature_file: feature_file.or(scenario_file),
+        name: name.to_string(),
+        tags: Vec::new(),
+        status: case.status,
+    }
+}
+
+fn split_location(text: &str) -> (&str, Option<String>) {
+    match text.rfind(": ") {
+        Some(pos) if text[pos + 2..].contains(".feature") => {
+            let location = &text[pos + 2..];
+            let end = location.find(".feature").unwrap_or(0) + ".feature".len();
+            (text[..pos].trim(), Some(location[..end].to_string()))
+        }
+        _ => (text.trim(), None),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Sortie console cucumber-rs
+// ---------------------------------------------------------------------------
+
+fn parse_cucumber_rs_output(content: &str) -> ExecutionResults {
+    let ansi = regex::Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").expect("regex ANSI valide");
+    let feature_keywords = header_keywords(|k| vec![k.feature]);
+    let scenario_keywords = header_keywords(|k| vec![k.scenario_outline, k.scenario]);
+
+    let mut feature = String::new();
+    let mut scenarios: Vec<ExecutedScenario> = Vec::new();
+    let mut in_scenario = false;
+
+    for raw in content.lines() {
+        let line = ansi.replace_all(raw, "");
+        let line = line.trim();
+        if let Some(name) = strip_header(line, &feature_keywords) {
+            feature = name.to_string();
+            in_scenario = false;
+        } else if let Some(name) = strip_header(line, &scenario_keywords) {
+            scenarios.push(ExecutedScenario {
+                feature: feature.clone(),
+                feature_file: None,
+                name: name.to_string(),
+                tags: Vec::new(),
+                status: ExecutionStatus::Passed,
+            });
+            in_scenario = true;
+        } else if in_scenario && let Some(status) = step_marker(line) {
+            if let Some(last) = scenarios.last_mut() {
+                last.status = last.status.worst(status);
+            }
+        } else if line.starts_with("[Summary]") {
+            break;
+        }
+    }
+    ExecutionResults { scenarios }
+}
+
+/// Marqueur d'etape cucumber-rs : `✔` passee, `✘` echouee, `?` sans definition
+fn step_marker(line: &str) -> Option<ExecutionStatus> {
+    let marker = line.chars().next()?;
+    match marker {
+        '✔' => Some(ExecutionStatus::Passed),
+        '✘' => Some(ExecutionStatus::Failed),
+        '?' => Some(ExecutionStatus::Undefined),
+        _ => None,
+    }
+}
+
+/// Mots-cles d'en-tete dans toutes les langues supportees, suivis de `:`
+fn header_keywords(select: impl Fn(&i18n::GherkinKeywords) -> Vec<&'static str>) -> Vec<String> {
+    let mut keywords: Vec<String> = Language::ALL
+        .iter()
+        .flat_map(|l| select(&l.keywords().gherkin))
+        .map(|k| format!("{k}:"))
+        .collect();
+    // Les formes longues (Scenario Outline) avant les courtes (Scenario)
+    keywords.sort_by_key(|k| std::cmp::Reverse(k.len()));
+    keywords.dedup();
+    keywords
+}
+
+fn strip_header<'a>(line: &'a str, keywords: &[String]) -> Option<&'a str> {
+    keywords
+        .iter()
+        .find_map(|k| line.strip_prefix(k.as_str()))
+        .map(str::trim)
+}
+
+fn strip_keyword(text: &str) -> &str {
+    let keywords = header_keywords(|k| vec![k.feature, k.scenario_outline, k.scenario]);
+    strip_header(text, &keywords).unwrap_or(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_cucumber_json_with_background() {
+        let json = r#"[{
+            "uri": "features/catalogue.feature",
+            "name": "Catalogue",
+            "tags": [{"name": "@catalogue"}],
+            "elements": [
+                {"type": "background", "name": "", "steps": [{"result": {"status": "passed"}}]},
+                {"type": "scenario", "name": "Lister", "tags": [{"name": "@FR-001"}],
+                 "steps": [{"result": {"status": "passed"}}, {"result": {"status": "undefined"}}]},
+                {"type": "background", "name": "", "steps": [{"result": {"status": "failed"}}]},
+                {"type": "scenario", "name": "Filtrer", "steps": [{"result": {"status": "skipped"}}]}
+            ]
+        }]"#;
+        let results = ExecutionResultsReader::new().parse(json).unwrap();
+        assert_eq!(results.scenarios.len(), 2);
+        assert_eq!(results.scenarios[0].status, ExecutionStatus::Undefined);
+        assert_eq!(results.scenarios[0].tags, vec!["@catalogue", "@FR-001"]);
+        assert_eq!(
+            results.scenarios[0].feature_file.as_deref(),
+            Some("features/catalogue.feature")
+        );
+        assert_eq!(results.scenarios[1].status, ExecutionStatus::Failed);
+    }
+
+    #[test]
+    fn test_parse_junit_xml() {
+        let xml = r#"<?xml version="1.0"?>
+<testsuites>
+  <testsuite name="Feature: Catalogue: tests/features/catalogue.feature">
+    <testcase name="Scenario: Lister: tests/features/catalogue.feature:4:3" />
+    <testcase name="Scenario: Filtrer: tests/features/catalogue.feature:9:3">
+      <failure message="Step failed"/>
+    </testcase>
+  </testsuite>
+  <testsuite name="cucumber-js">
+    <testcase classname="Panier" name="Vider"><skipped message="Step is undefined"/></testcase>
+  </testsuite>
+</testsuites>"#;
+        let results = ExecutionResultsReader::new().parse(xml).unwrap();
+        let summary: Vec<_> = results
+            .scenarios
+            .iter()
+            .map(|s| (s.feature.as_str(), s.name.as_str(), s.status))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Catalogue", "Lister", ExecutionStatus::Passed),
+                ("Catalogue", "Filtrer", ExecutionStatus::Failed),
+                ("Panier", "Vider", ExecutionStatus::Undefined),
+            ]
+        );
+        assert_eq!(
+            results.scenarios[0].feature_file.as_deref(),
+            Some("tests/features/catalogue.feature")
+        );
+    }
+
+    #[test]
+    fn test_parse_cucumber_rs_console_output() {
+        let output = "\u{1b}[1mFonctionnalité: Catalogue\u{1b}[0m\n\
+                      \x20 Scénario: Lister\n\
+                      \x20  ✔> Soit un catalogue\n\
+                      \x20  ✔  Quand je liste\n\
+                      \x20 Scénario: Filtrer\n\
+                      \x20  ✔  Quand je filtre\n\
+                      \x20  ✘  Alors je vois un livre\n\
+                      \x20     Step failed:\n\
+                      Feature: Cart\n\
+                      \x20 Scenario Outline: Empty\n\
+                      \x20  ?  When I empty\n\
+                      [Summary]\n\
+                      2 features\n";
+        let results = ExecutionResultsReader::new().parse(output).unwrap();
+        let summary: Vec<_> = results
+            .scenarios
+            .iter()
+            .map(|s| (s.feature.as_str(), s.name.as_str(), s.status))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Catalogue", "Lister", ExecutionStatus::Passed),
+                ("Catalogue", "Filtrer", ExecutionStatus::Failed),
+                ("Cart", "Empty", ExecutionStatus::Undefined),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_results() {
+        assert!(ExecutionResultsReader::new().parse("[]").is_err());
+    }
+}
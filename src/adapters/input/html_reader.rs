@@ -0,0 +1,472 @@
+//! Adapter HtmlReader - Extrait les User Stories depuis une page HTML
+//!
+//! Pages exportees depuis un wiki (Confluence, Notion...) : `h1` -> `#`,
+//! `h2` -> `##`, `h3`-`h6` -> `###`, `li` -> puces `- `, `strong`/`b` ->
+//! `**` (metadonnees `**ID**: ...`). Les tableaux de stories sont lus
+//! comme les tableaux Word ; `script`, `style` et `head` sont ignores.
+//!
+//! Le tokenizer est volontairement tolerant : une page mal formee ne fait
+//! jamais echouer la lecture, au pire du texte est perdu.
+
+use async_trait::async_trait;
+use std::path::Path;
+
+use crate::domain::errors::InputError;
+use crate::domain::user_story::{Language, UserStorySet};
+use crate::ports::input_reader::InputReader;
+
+use super::story_parser;
+use super::structured_document::{DocumentBuilder, StructuredDocument};
+
+/// Lit les User Stories depuis un fichier HTML
+pub struct HtmlReader;
+
+/// Elements dont le contenu est ignore
+const SKIPPED_ELEMENTS: &[&str] = &["head", "script", "style", "noscript", "template", "svg"];
+
+/// Elements dont le contenu est du texte brut (pas de balises a l'interieur)
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+/// Elements de bloc : terminent le paragraphe courant
+const BLOCK_ELEMENTS: &[&str] = &[
+    "p",
+    "div",
+    "section",
+    "article",
+    "header",
+    "footer",
+    "main",
+    "aside",
+    "nav",
+    "blockquote",
+    "dl",
+    "dt",
+    "dd",
+    "ul",
+    "ol",
+    "hr",
+    "figure",
+    "figcaption",
+    "body",
+];
+
+/// Entites nommees courantes (les autres sont conservees telles quelles)
+const NAMED_ENTITIES: &[(&str, &str)] = &[
+    ("amp", "&"),
+    ("lt", "<"),
+    ("gt", ">"),
+    ("quot", "\""),
+    ("apos", "'"),
+    ("nbsp", " "),
+    ("eacute", "é"),
+    ("egrave", "è"),
+    ("ecirc", "ê"),
+    ("euml", "ë"),
+    ("agrave", "à"),
+    ("acirc", "â"),
+    ("ccedil", "ç"),
+    ("icirc", "î"),
+    ("iuml", "ï"),
+    ("ocirc", "ô"),
+    ("ugrave", "ù"),
+    ("ucirc", "û"),
+    ("uuml", "ü"),
+    ("Eacute", "É"),
+    ("Agrave", "À"),
+    ("laquo", "«"),
+    ("raquo", "»"),
+    ("lsquo", "‘"),
+    ("rsquo", "’"),
+    ("ldquo", "“"),
+    ("rdquo", "”"),
+    ("hellip", "…"),
+    ("ndash", "–"),
+    ("mdash", "—"),
+    ("euro", "€"),
+];
+
+/// Decode les entites HTML (`&eacute;`, `&#233;`, `&#xE9;`)
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let resolved = rest[1..]
+            .char_indices()
+            .take(12)
+            .find(|(_, c)| *c == ';')
+            .map(|(end, _)| end)
+            .filter(|end| *end > 0)
+            .and_then(|end| {
+                let name = &rest[1..=end];
+                let value = match name.strip_prefix('#') {
+                    Some(code) => {
+                        let number = match code.strip_prefix(['x', 'X']) {
+                            Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                            None => code.parse::<u32>().ok(),
+                        };
+                        number.and_then(char::from_u32).map(String::from)
+                    }
+                    None => NAMED_ENTITIES
+                        .iter()
+                        .find(|(entity, _)| *entity == name)
+                        .map(|(_, value)| value.to_string()),
+                };
+                value.map(|v| (v, end + 2))
+            });
+        match resolved {
+            Some((value, consumed)) => {
+                decoded.push_str(&value);
+                rest = &rest[consumed..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Balise lue par le tokenizer
+struct Tag {
+    name: String,
+    closing: bool,
+    self_closing: bool,
+}
+
+/// Lit une balise a partir de `<` ; retourne la balise et la position apres `>`
+fn read_tag(html: &str, start: usize) -> Option<(Tag, usize)> {
+    let bytes = html.as_bytes();
+    let mut i = start + 1;
+    let closing = bytes.get(i) == Some(&b'/');
+    if closing {
+        i += 1;
+    }
+    let name_start = i;
+    while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'-') {
+        i += 1;
+    }
+    if i == name_start {
+        return None;
+    }
+    let name = html[name_start..i].to_ascii_lowercase();
+
+    // Attributs : on saute jusqu'au `>` en respectant les guillemets
+    let mut quote: Option<u8> = None;
+    while i < bytes.len() {
+        match (quote, bytes[i]) {
+            (Some(q), c) if c == q => quote = None,
+            (None, b'"' | b'\'') => quote = Some(bytes[i]),
+            (None, b'>') => {
+                let self_closing = i > start && bytes[i - 1] == b'/';
+                return Some((
+                    Tag {
+                        name,
+                        closing,
+                        self_closing,
+                    },
+                    i + 1,
+                ));
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Niveau de titre d'une balise `h1`-`h6`
+fn heading_level(name: &str) -> Option<usize> {
+    let level = name.strip_prefix('h')?.parse::<usize>().ok()?;
+    (1..=6).contains(&level).then_some(level)
+}
+
+/// Parcours des balises HTML
+#[derive(Default)]
+struct HtmlBuilder {
+    doc: DocumentBuilder,
+    skip_depth: usize,
+    pre_depth: usize,
+    in_heading: bool,
+}
+
+impl HtmlBuilder {
+    fn push_text(&mut self, raw: &str) {
+        if self.skip_depth > 0 {
+            return;
+        }
+        let text = decode_entities(raw);
+        if self.pre_depth > 0 {
+            self.doc.push_text(&text);
+            return;
+        }
+        if text.trim().is_empty() && !self.doc.has_text() {
+            return;
+        }
+        let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let leading = text.starts_with(char::is_whitespace);
+        let trailing = text.ends_with(char::is_whitespace) && !collapsed.is_empty();
+        if leading {
+            self.doc.push_text(" ");
+        }
+        self.doc.push_text(&collapsed);
+        if trailing {
+            self.doc.push_text(" ");
+        }
+    }
+
+    /// Fin de bloc : un element `li` encore vide conserve sa puce
+    fn break_block(&mut self) {
+        if self.doc.has_text() {
+            self.doc.end_paragraph();
+        }
+    }
+
+    fn start(&mut self, tag: &Tag) {
+        let name = tag.name.as_str();
+        if SKIPPED_ELEMENTS.contains(&name) {
+            // Balise auto-fermante : `end` est appele juste apres
+            self.skip_depth += 1;
+            return;
+        }
+        if self.skip_depth > 0 {
+            return;
+        }
+        if let Some(level) = heading_level(name) {
+            self.doc.start_paragraph();
+            self.doc.set_heading(level);
+            self.in_heading = true;
+            return;
+        }
+        match name {
+            "li" => {
+                self.doc.start_paragraph();
+                self.doc.set_list_item();
+            }
+            "br" => self.doc.end_paragraph(),
+            "pre" => {
+                self.break_block();
+                self.pre_depth += 1;
+            }
+            "strong" | "b" if !self.in_heading => self.doc.push_text("**"),
+            "table" => self.doc.start_table(),
+            "tr" => self.doc.start_row(),
+            "td" | "th" => self.doc.start_cell(),
+            _ if BLOCK_ELEMENTS.contains(&name) => self.break_block(),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, name: &str) {
+        if SKIPPED_ELEMENTS.contains(&name) {
+            self.skip_depth = self.skip_depth.saturating_sub(1);
+            return;
+        }
+        if self.skip_depth > 0 {
+            return;
+        }
+        if heading_level(name).is_some() {
+            self.doc.end_paragraph();
+            self.in_heading = false;
+            return;
+        }
+        match name {
+            "li" => self.doc.end_paragraph(),
+            "pre" => {
+                self.doc.end_paragraph();
+                self.pre_depth = self.pre_depth.saturating_sub(1);
+            }
+            "strong" | "b" if !self.in_heading => self.doc.push_text("**"),
+            "table" => self.doc.end_table(),
+            "tr" => self.doc.end_row(),
+            "td" | "th" => self.doc.end_cell(),
+            _ if BLOCK_ELEMENTS.contains(&name) => self.break_block(),
+            _ => {}
+        }
+    }
+}
+
+impl HtmlReader {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse une page HTML : texte Markdown + stories des tableaux
+    pub fn parse_html(html: &str) -> StructuredDocument {
+        let mut builder = HtmlBuilder::default();
+        let mut pos = 0;
+
+        while pos < html.len() {
+            let Some(offset) = html[pos..].find('<') else {
+                builder.push_text(&html[pos..]);
+                break;
+            };
+            let lt = pos + offset;
+            builder.push_text(&html[pos..lt]);
+            let rest = &html[lt..];
+
+            if rest.starts_with("<!--") {
+                pos = rest.find("-->").map_or(html.len(), |end| lt + end + 3);
+                continue;
+            }
+            if rest.starts_with("<!") || rest.starts_with("<?") {
+                pos = rest.find('>').map_or(html.len(), |end| lt + end + 1);
+                continue;
+            }
+            let Some((tag, after)) = read_tag(html, lt) else {
+                // `<` isole : conserve comme texte
+                builder.push_text("<");
+                pos = lt + 1;
+                continue;
+            };
+            pos = after;
+
+            if tag.closing {
+                builder.end(&tag.name);
+                continue;
+            }
+            builder.start(&tag);
+            if tag.self_closing {
+                builder.end(&tag.name);
+            }
+            if RAW_TEXT_ELEMENTS.contains(&tag.name.as_str()) && !tag.self_closing {
+                // Contenu brut jusqu'a la balise fermante
+                let closing = format!("</{}", tag.name);
+                let lower = html[pos..].to_ascii_lowercase();
+                match lower.find(&closing) {
+                    Some(end) => pos += end,
+                    None => break,
+                }
+            }
+        }
+
+        builder.doc.finish()
+    }
+}
+
+impl Default for HtmlReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl InputReader for HtmlReader {
+    async fn read_stories(&self, path: &Path) -> Result<UserStorySet, InputError> {
+        if !path.exists() {
+            return Err(InputError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        super::check_file_size(path)?;
+
+        let html = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| InputError::ReadError(e.to_string()))?;
+        let content = Self::parse_html(&html);
+        let language = self.detect_language(&content.language_sample());
+        let stories = content.into_stories(language)?;
+
+        Ok(UserStorySet {
+            stories,
+            source_files: vec![path.display().to_string()],
+            language,
+        })
+    }
+
+    fn detect_language(&self, content: &str) -> Language {
+        story_parser::detect_language(content)
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &["html", "htm"]
+    }
+
+    fn sniff(&self, _path: &Path, head: &[u8]) -> bool {
+        super::head_text(head).is_some_and(|text| {
+            let start = text.trim_start().to_ascii_lowercase();
+            start.starts_with("<!doctype html") || start.starts_with("<html")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_headings_lists_and_metadata() {
+        let html = r#"<!DOCTYPE html>
+<html><head><title>Export</title><style>h2 { color: red; }</style></head>
+<body>
+  <h1>Paiements</h1>
+  <!-- export Confluence -->
+  <h2>Virement SEPA</h2>
+  <p><strong>ID</strong>: PAY-42</p>
+  <p>En tant que client, je veux virer de l&#39;argent afin de payer un
+     b&eacute;n&eacute;ficiaire.</p>
+  <ul>
+    <li><p>Le plafond est de 10&nbsp;000 EUR</p></li>
+    <li>L'IBAN est <em>valide</em></li>
+  </ul>
+  <script>var x = "<h2>pas une story</h2>";</script>
+</body></html>"#;
+        let doc = HtmlReader::parse_html(html);
+        assert!(!doc.text.contains("Export"));
+        assert!(!doc.text.contains("pas une story"));
+        assert!(doc.text.contains("**ID**: PAY-42"));
+
+        let stories = doc.into_stories(Language::French).unwrap();
+        assert_eq!(stories.len(), 1);
+        assert_eq!(stories[0].title, "Virement SEPA");
+        assert_eq!(stories[0].external_id.as_deref(), Some("PAY-42"));
+        assert_eq!(stories[0].epic.as_deref(), Some("Paiements"));
+        assert_eq!(stories[0].benefit, "payer un bénéficiaire");
+        assert_eq!(
+            stories[0].acceptance_criteria,
+            vec!["Le plafond est de 10 000 EUR", "L'IBAN est valide"]
+        );
+    }
+
+    #[test]
+    fn test_story_table() {
+        let html = "<table><thead><tr><th>Key</th><th>Summary</th><th>As a</th><th>I want</th><th>So that</th></tr></thead>\
+            <tbody><tr><td>WEB-1</td><td>Login</td><td>user</td><td>log in</td><td>see my account</td></tr></tbody></table>";
+        let doc = HtmlReader::parse_html(html);
+        assert_eq!(doc.table_stories.len(), 1);
+        assert_eq!(doc.table_stories[0].external_id.as_deref(), Some("WEB-1"));
+        assert_eq!(doc.table_stories[0].title, "Login");
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(
+            decode_entities("a &amp; b &#xE9;&#233; &unknown; &"),
+            "a & b éé &unknown; &"
+        );
+    }
+
+    #[test]
+    fn test_malformed_html_is_tolerated() {
+        let doc = HtmlReader::parse_html("<p>a < b <div attr=\"x>y\">c</p></div><h2>");
+        assert!(doc.text.contains("a < b"));
+        assert!(doc.text.contains('c'));
+    }
+
+    mod proptest_suite {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn parse_html_never_panics(input in "\\PC*") {
+                let _ = HtmlReader::parse_html(&input);
+            }
+        }
+    }
+}
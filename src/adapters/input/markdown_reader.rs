@@ -1,4 +1,7 @@
 //! Adapter MarkdownReader - Parse les User Stories depuis un fichier Markdown
+//!
+//! Supporte un front matter YAML optionnel (langue, priorite, tags, stakeholder
+//! par defaut) et les metadonnees en ligne par section (`**ID**: PAY-42`).
 
 use async_trait::async_trait;
 use std::path::Path;
@@ -16,6 +19,25 @@ impl MarkdownReader {
     pub fn new() -> Self {
         Self
     }
+
+    /// Parse un contenu Markdown deja charge (fichier ou stdin)
+    pub fn parse_content(&self, content: &str) -> Result<UserStorySet, InputError> {
+        let (front_matter, body) = story_parser::split_front_matter(content)?;
+        let language = front_matter
+            .as_ref()
+            .and_then(|fm| fm.language)
+            .unwrap_or_else(|| self.detect_language(body));
+        let mut stories = story_parser::parse_stories(body, language)?;
+        if let Some(ref fm) = front_matter {
+            story_parser::apply_front_matter(&mut stories, fm);
+        }
+
+        Ok(UserStorySet {
+            stories,
+            source_files: Vec::new(),
+            language,
+        })
+    }
 }
 
 impl Default for MarkdownReader {
@@ -39,14 +61,9 @@ impl InputReader for MarkdownReader {
             .await
             .map_err(|e| InputError::ReadError(e.to_string()))?;
 
-        let language = self.detect_language(&content);
-        let stories = story_parser::parse_stories(&content, language)?;
-
-        Ok(UserStorySet {
-            stories,
-            source_files: vec![path.display().to_string()],
-            language,
-        })
+        let mut story_set = self.parse_content(&content)?;
+        story_set.source_files = vec![path.display().to_string()];
+        Ok(story_set)
     }
 
     fn detect_language(&self, content: &str) -> Language {
@@ -56,12 +73,23 @@ impl InputReader for MarkdownReader {
     fn supported_extensions(&self) -> &[&str] {
         &["md", "markdown"]
     }
+
+    /// Texte contenant au moins un titre `#` / `##`
+    fn sniff(&self, _path: &Path, head: &[u8]) -> bool {
+        super::head_text(head).is_some_and(|text| {
+            text.lines()
+                .any(|line| line.starts_with("# ") || line.starts_with("## "))
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::user_story::Priority;
     use pretty_assertions::assert_eq;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
 
     #[test]
     fn test_detect_language_french() {
@@ -76,4 +104,19 @@ mod tests {
         let content = "As a user, I want to search for a book so that I can borrow it.";
         assert_eq!(reader.detect_language(content), Language::English);
     }
+
+    #[tokio::test]
+    async fn test_read_with_front_matter() {
+        let content = "---\nlanguage: fr\npriority: P2\ntags: [paiement]\n---\n## Virement\n\n**ID**: PAY-42\n\nEn tant que client, je veux virer afin de payer.\n";
+        let mut file = NamedTempFile::with_suffix(".md").unwrap();
+        write!(file, "{}", content).unwrap();
+
+        let reader = MarkdownReader::new();
+        let set = reader.read_stories(file.path()).await.unwrap();
+        assert_eq!(set.language, Language::French);
+        assert_eq!(set.stories.len(), 1);
+        assert_eq!(set.stories[0].external_id.as_deref(), Some("PAY-42"));
+        assert_eq!(set.stories[0].priority, Some(Priority::P2));
+        assert_eq!(set.stories[0].tags, vec!["paiement"]);
+    }
 }
@@ -1,7 +1,14 @@
+pub mod asciidoc_reader;
+pub mod csv_reader;
 pub mod docx_reader;
+pub mod execution_results_reader;
+pub mod html_reader;
 pub mod markdown_reader;
+pub mod odt_reader;
 pub mod pdf_reader;
 pub mod story_parser;
+pub mod story_table;
+pub mod structured_document;
 pub mod yaml_reader;
 
 /// Taille maximale d'un fichier d'entree (10 Mo)
@@ -21,3 +28,23 @@ pub fn check_file_size(path: &std::path::Path) -> Result<(), crate::domain::erro
         _ => Ok(()),
     }
 }
+
+/// Signature d'une archive ZIP (docx, odt, xlsx)
+pub const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+/// Debut de fichier lu comme texte UTF-8 (un caractere tronque en fin est tolere)
+pub fn head_text(head: &[u8]) -> Option<&str> {
+    match std::str::from_utf8(head) {
+        Ok(text) => Some(text),
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&head[..e.valid_up_to()]).ok(),
+        Err(_) => None,
+    }
+}
+
+/// L'archive ZIP contient-elle l'entree `name` ?
+pub fn zip_has_entry(path: &std::path::Path, name: &str) -> bool {
+    std::fs::File::open(path)
+        .ok()
+        .and_then(|file| zip::ZipArchive::new(file).ok())
+        .is_some_and(|archive| archive.index_for_name(name).is_some())
+}
@@ -0,0 +1,330 @@
+//! Adapter OdtReader - Extrait les User Stories depuis un fichier OpenDocument (.odt)
+//!
+//! Un fichier .odt est une archive ZIP contenant du XML (`content.xml`).
+//! Titres `text:h` -> `#`/`##`/`###` selon `text:outline-level`, elements de
+//! liste `text:list-item` -> puces `- `, tableaux `table:table` lus comme les
+//! tableaux Word. Les modifications suivies et annotations sont ignorees.
+
+use async_trait::async_trait;
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use std::io::Read;
+use std::path::Path;
+
+use crate::domain::errors::InputError;
+use crate::domain::user_story::{Language, UserStorySet};
+use crate::ports::input_reader::InputReader;
+
+use super::story_parser;
+use super::structured_document::{DocumentBuilder, StructuredDocument, resolve_xml_ref};
+
+/// Lit les User Stories depuis un fichier OpenDocument Text (.odt)
+pub struct OdtReader;
+
+/// Type MIME d'un document texte OpenDocument (entree `mimetype` de l'archive)
+const ODT_MIMETYPE: &[u8] = b"application/vnd.oasis.opendocument.text";
+
+/// Elements dont le contenu est ignore (modifications suivies, annotations,
+/// notes de bas de page)
+const SKIPPED_ELEMENTS: &[&[u8]] = &[b"tracked-changes", b"annotation", b"note"];
+
+/// Valeur d'un attribut par nom local (`text:outline-level` -> `outline-level`)
+fn attribute(e: &BytesStart, local_name: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == local_name)
+        .and_then(|a| String::from_utf8(a.value.into_owned()).ok())
+}
+
+/// Parcours du XML OpenDocument (elements `text:*` / `table:*`)
+#[derive(Default)]
+struct OdtBuilder {
+    doc: DocumentBuilder,
+    /// Profondeur de `text:p` / `text:h` ouverts
+    paragraph_depth: usize,
+    list_item_depth: usize,
+    skip_depth: usize,
+}
+
+impl OdtBuilder {
+    /// ODF : toute suite de blancs dans le texte vaut un espace
+    fn push_text(&mut self, text: &str) {
+        if self.paragraph_depth == 0 || self.skip_depth > 0 {
+            return;
+        }
+        let mut collapsed = String::with_capacity(text.len());
+        let mut previous_space = false;
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !previous_space {
+                    collapsed.push(' ');
+                }
+                previous_space = true;
+            } else {
+                collapsed.push(c);
+                previous_space = false;
+            }
+        }
+        self.doc.push_text(&collapsed);
+    }
+
+    fn push_raw(&mut self, text: &str) {
+        if self.paragraph_depth > 0 && self.skip_depth == 0 {
+            self.doc.push_text(text);
+        }
+    }
+
+    fn start(&mut self, e: &BytesStart, is_empty: bool) {
+        let local_name = e.local_name();
+        let name = local_name.as_ref();
+        if SKIPPED_ELEMENTS.contains(&name) {
+            if !is_empty {
+                self.skip_depth += 1;
+            }
+            return;
+        }
+        if self.skip_depth > 0 {
+            return;
+        }
+        match name {
+            b"p" | b"h" => {
+                // Paragraphes imbriques (cadres) : ajoutes au paragraphe englobant
+                if self.paragraph_depth == 0 {
+                    self.doc.start_paragraph();
+                    if name == b"h" {
+                        let level = attribute(e, b"outline-level")
+                            .and_then(|v| v.parse::<usize>().ok())
+                            .unwrap_or(1);
+                        self.doc.set_heading(level);
+                    } else if self.list_item_depth > 0 {
+                        self.doc.set_list_item();
+                    }
+                }
+                if is_empty {
+                    if self.paragraph_depth == 0 {
+                        self.doc.end_paragraph();
+                    }
+                } else {
+                    self.paragraph_depth += 1;
+                }
+            }
+            b"list-item" if !is_empty => self.list_item_depth += 1,
+            b"s" => {
+                let count = attribute(e, b"c")
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or(1)
+                    .min(64);
+                self.push_raw(&" ".repeat(count));
+            }
+            b"tab" => self.push_raw("\t"),
+            b"line-break" => self.push_raw("\n"),
+            b"table" if !is_empty => self.doc.start_table(),
+            b"table-row" if !is_empty => self.doc.start_row(),
+            b"table-cell" if !is_empty => self.doc.start_cell(),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, name: &[u8]) {
+        if SKIPPED_ELEMENTS.contains(&name) {
+            self.skip_depth = self.skip_depth.saturating_sub(1);
+            return;
+        }
+        if self.skip_depth > 0 {
+            return;
+        }
+        match name {
+            b"p" | b"h" => {
+                self.paragraph_depth = self.paragraph_depth.saturating_sub(1);
+                if self.paragraph_depth == 0 {
+                    self.doc.end_paragraph();
+                }
+            }
+            b"list-item" => self.list_item_depth = self.list_item_depth.saturating_sub(1),
+            b"table-cell" => self.doc.end_cell(),
+            b"table-row" => self.doc.end_row(),
+            b"table" => self.doc.end_table(),
+            _ => {}
+        }
+    }
+}
+
+impl OdtReader {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Lit `content.xml` depuis un fichier .odt
+    fn read_content_xml(path: &Path) -> Result<String, InputError> {
+        let file = std::fs::File::open(path).map_err(|e| InputError::ReadError(e.to_string()))?;
+
+        let mut archive = zip::ZipArchive::new(file)
+            .map_err(|e| InputError::ParseError(format!("Erreur ouverture ZIP (odt): {e}")))?;
+
+        let mut content_xml = String::new();
+        let mut content_file = archive.by_name("content.xml").map_err(|e| {
+            InputError::ParseError(format!("content.xml introuvable dans le .odt: {e}"))
+        })?;
+        content_file
+            .read_to_string(&mut content_xml)
+            .map_err(|e| InputError::ReadError(e.to_string()))?;
+        Ok(content_xml)
+    }
+
+    /// Parse le `content.xml` OpenDocument : texte Markdown + stories des tableaux
+    pub fn parse_content_xml(xml: &str) -> Result<StructuredDocument, InputError> {
+        let mut reader = Reader::from_str(xml);
+        let mut builder = OdtBuilder::default();
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => builder.start(e, false),
+                Ok(Event::Empty(ref e)) => builder.start(e, true),
+                Ok(Event::Text(ref e)) => {
+                    let decoded = e.decode().map_err(|err| {
+                        InputError::ParseError(format!("Erreur decodage XML: {err}"))
+                    })?;
+                    builder.push_text(&decoded);
+                }
+                Ok(Event::GeneralRef(ref e)) => {
+                    if let Some(c) = resolve_xml_ref(e) {
+                        builder.push_raw(c.encode_utf8(&mut [0; 4]));
+                    }
+                }
+                Ok(Event::End(ref e)) => builder.end(e.local_name().as_ref()),
+                Ok(Event::Eof) => break,
+                Err(e) => {
+                    return Err(InputError::ParseError(format!(
+                        "Erreur parsing XML du .odt: {e}"
+                    )));
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(builder.doc.finish())
+    }
+}
+
+impl Default for OdtReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl InputReader for OdtReader {
+    async fn read_stories(&self, path: &Path) -> Result<UserStorySet, InputError> {
+        if !path.exists() {
+            return Err(InputError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        super::check_file_size(path)?;
+
+        let xml = Self::read_content_xml(path)?;
+        let content = Self::parse_content_xml(&xml)?;
+        let language = self.detect_language(&content.language_sample());
+        let stories = content.into_stories(language)?;
+
+        Ok(UserStorySet {
+            stories,
+            source_files: vec![path.display().to_string()],
+            language,
+        })
+    }
+
+    fn detect_language(&self, content: &str) -> Language {
+        story_parser::detect_language(content)
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &["odt"]
+    }
+
+    /// L'entree `mimetype` est la premiere de l'archive, stockee sans compression
+    fn sniff(&self, _path: &Path, head: &[u8]) -> bool {
+        head.starts_with(super::ZIP_MAGIC)
+            && head.windows(ODT_MIMETYPE.len()).any(|w| w == ODT_MIMETYPE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn content(body: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"><office:body><office:text>{body}</office:text></office:body></office:document-content>"#
+        )
+    }
+
+    #[test]
+    fn test_headings_and_lists_split_stories() {
+        let body = concat!(
+            r#"<text:h text:outline-level="1">Paiements</text:h>"#,
+            r#"<text:h text:outline-level="2">Virement SEPA</text:h>"#,
+            "<text:p>En tant que client, je veux virer de l'argent afin de payer un tiers.</text:p>",
+            "<text:list><text:list-item><text:p>Le plafond est de<text:s/>10<text:s text:c=\"1\"/>000 EUR</text:p></text:list-item>",
+            "<text:list-item><text:p>L'IBAN est <text:span>valide</text:span></text:p></text:list-item></text:list>",
+        );
+        let doc = OdtReader::parse_content_xml(&content(body)).unwrap();
+        assert!(doc.text.contains("\n# Paiements"));
+        assert!(doc.text.contains("- L'IBAN est valide"));
+
+        let stories = doc.into_stories(Language::French).unwrap();
+        assert_eq!(stories.len(), 1);
+        assert_eq!(stories[0].title, "Virement SEPA");
+        assert_eq!(stories[0].epic.as_deref(), Some("Paiements"));
+        assert_eq!(
+            stories[0].acceptance_criteria,
+            vec!["Le plafond est de 10 000 EUR", "L'IBAN est valide"]
+        );
+    }
+
+    #[test]
+    fn test_story_table_and_tracked_changes() {
+        let cell =
+            |text: &str| format!("<table:table-cell><text:p>{text}</text:p></table:table-cell>");
+        let row = |cells: &[&str]| {
+            format!(
+                "<table:table-row>{}</table:table-row>",
+                cells.iter().map(|c| cell(c)).collect::<String>()
+            )
+        };
+        let body = format!(
+            "<text:tracked-changes><text:changed-region><text:deletion><text:p>texte efface</text:p></text:deletion></text:changed-region></text:tracked-changes>\
+             <table:table>{}{}</table:table>\
+             <text:p>Note<office:annotation><text:p>commentaire</text:p></office:annotation></text:p>",
+            row(&["ID", "As a", "I want to", "So that"]),
+            row(&["ODT-1", "user", "export my data", "keep a copy"]),
+        );
+        let doc = OdtReader::parse_content_xml(&content(&body)).unwrap();
+        assert!(!doc.text.contains("efface"));
+        assert!(!doc.text.contains("commentaire"));
+        assert_eq!(doc.table_stories.len(), 1);
+        assert_eq!(doc.table_stories[0].external_id.as_deref(), Some("ODT-1"));
+        assert_eq!(doc.table_stories[0].action, "export my data");
+    }
+
+    #[test]
+    fn test_odt_reader_supported_extensions() {
+        assert_eq!(OdtReader::new().supported_extensions(), &["odt"]);
+    }
+
+    mod proptest_suite {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn parse_content_xml_never_panics(input in "\\PC*") {
+                let _ = OdtReader::parse_content_xml(&input);
+            }
+        }
+    }
+}
@@ -61,6 +61,10 @@ impl InputReader for PdfReader {
     fn supported_extensions(&self) -> &[&str] {
         &["pdf"]
     }
+
+    fn sniff(&self, _path: &Path, head: &[u8]) -> bool {
+        head.starts_with(b"%PDF")
+    }
 }
 
 #[cfg(test)]
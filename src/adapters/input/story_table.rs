@@ -0,0 +1,146 @@
+//! Tableaux de User Stories (une ligne par story)
+//!
+//! Correspondance en-tete -> champ partagee par les lecteurs tabulaires
+//! (CSV, tableaux Word). Les en-tetes sont reconnus en FR/EN, sans casse.
+
+use crate::domain::user_story::{Priority, UserStory};
+
+/// Colonne reconnue dans l'en-tete
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Id,
+    Title,
+    Actor,
+    Action,
+    Benefit,
+    Priority,
+    Criteria,
+    Tags,
+    Stakeholder,
+    Epic,
+}
+
+fn column_for_header(header: &str) -> Option<Column> {
+    match header.trim().trim_end_matches(':').to_lowercase().as_str() {
+        "id" | "identifiant" | "key" | "cle" | "clé" => Some(Column::Id),
+        "title" | "titre" | "summary" | "resume" | "résumé" => Some(Column::Title),
+        "actor" | "acteur" | "as a" | "as an" | "en tant que" => Some(Column::Actor),
+        "action" | "i want" | "i want to" | "je veux" => Some(Column::Action),
+        "benefit" | "benefice" | "bénéfice" | "so that" | "afin de" => Some(Column::Benefit),
+        "priority" | "priorite" | "priorité" => Some(Column::Priority),
+        "acceptance_criteria"
+        | "acceptance criteria"
+        | "criteria"
+        | "criteres"
+        | "critères"
+        | "criteres d'acceptation"
+        | "critères d'acceptation" => Some(Column::Criteria),
+        "tags" | "labels" | "etiquettes" | "étiquettes" => Some(Column::Tags),
+        "stakeholder" | "partie prenante" => Some(Column::Stakeholder),
+        "epic" | "feature" | "épopée" | "epopee" => Some(Column::Epic),
+        _ => None,
+    }
+}
+
+/// Decoupe une cellule multi-valeurs (retours a la ligne ou separateurs)
+fn split_cell(value: &str, separators: &[char]) -> Vec<String> {
+    value
+        .split(|c: char| c == '\n' || separators.contains(&c))
+        .map(|item| {
+            item.trim()
+                .trim_start_matches(['-', '*'])
+                .trim()
+                .to_string()
+        })
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Colonnes d'un tableau de stories, resolues depuis la ligne d'en-tete
+pub struct StoryTable {
+    columns: Vec<Option<Column>>,
+}
+
+impl StoryTable {
+    /// Reconnait un en-tete de tableau de stories (colonne titre ou action requise)
+    pub fn from_headers<S: AsRef<str>>(headers: &[S]) -> Option<Self> {
+        let columns: Vec<Option<Column>> = headers
+            .iter()
+            .map(|h| column_for_header(h.as_ref()))
+            .collect();
+        if columns.contains(&Some(Column::Action)) || columns.contains(&Some(Column::Title)) {
+            Some(Self { columns })
+        } else {
+            None
+        }
+    }
+
+    /// Construit une story depuis une ligne ; `None` si la ligne est vide.
+    /// Sans cellule ID, `external_id` reste vide (ID positionnel attribue par l'appelant).
+    pub fn story_from_row<S: AsRef<str>>(&self, row: &[S]) -> Option<UserStory> {
+        if row.iter().all(|c| c.as_ref().trim().is_empty()) {
+            return None;
+        }
+
+        let cell = |column: Column| -> &str {
+            self.columns
+                .iter()
+                .position(|c| *c == Some(column))
+                .and_then(|i| row.get(i))
+                .map_or("", |c| c.as_ref().trim())
+        };
+
+        let title = match cell(Column::Title) {
+            "" => cell(Column::Action).to_string(),
+            t => t.to_string(),
+        };
+        let mut us = UserStory::new(
+            title,
+            cell(Column::Actor).to_string(),
+            cell(Column::Action).to_string(),
+            cell(Column::Benefit).to_string(),
+        );
+        us.external_id = Some(cell(Column::Id))
+            .filter(|id| !id.is_empty())
+            .map(str::to_string);
+        us.priority = Priority::from_code(cell(Column::Priority));
+        us.acceptance_criteria = split_cell(cell(Column::Criteria), &['|', ';']);
+        us.tags = split_cell(cell(Column::Tags), &[',', ';'])
+            .into_iter()
+            .map(|t| t.trim_start_matches(['@', '#']).to_string())
+            .collect();
+        us.stakeholder = Some(cell(Column::Stakeholder))
+            .filter(|s| !s.is_empty())
+            .map(str::to_string);
+        us.epic = Some(cell(Column::Epic))
+            .filter(|s| !s.is_empty())
+            .map(str::to_string);
+        us.raw_text = row
+            .iter()
+            .map(|c| c.as_ref())
+            .collect::<Vec<_>>()
+            .join(" | ");
+
+        Some(us)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_story_table_headers() {
+        assert!(StoryTable::from_headers(&["ID", "Titre", "En tant que"]).is_some());
+        assert!(StoryTable::from_headers(&["Nom", "Valeur"]).is_none());
+
+        let table = StoryTable::from_headers(&["As a:", "I want to", "So that"]).unwrap();
+        let us = table
+            .story_from_row(&["user", "log in", "see my account"])
+            .unwrap();
+        assert_eq!(us.title, "log in");
+        assert!(us.external_id.is_none());
+        assert!(table.story_from_row(&["", " ", ""]).is_none());
+    }
+}
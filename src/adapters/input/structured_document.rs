@@ -0,0 +1,280 @@
+//! Document structure (titres, listes, tableaux) traduit en Markdown
+//!
+//! Les lecteurs de formats riches (DOCX, ODT, HTML) alimentent un
+//! `DocumentBuilder` au fil de leur parsing ; le texte produit a la forme
+//! attendue par `story_parser` (`#` epic, ou story sans `##` sous lui ;
+//! `##` story ; `- ` critere) et les tableaux de stories sont lus ligne par
+//! ligne via `StoryTable`.
+
+use quick_xml::events::BytesRef;
+
+use crate::domain::errors::InputError;
+use crate::domain::user_story::{Language, UserStory};
+
+use super::story_parser;
+use super::story_table::StoryTable;
+
+/// Contenu extrait d'un document structure
+#[derive(Debug, Default)]
+pub struct StructuredDocument {
+    /// Texte au format Markdown (titres, puces, tableaux non-stories)
+    pub text: String,
+    /// Stories lues depuis des tableaux structures
+    pub table_stories: Vec<UserStory>,
+}
+
+impl StructuredDocument {
+    /// Texte utilise pour la detection de langue (texte + lignes des tableaux)
+    pub fn language_sample(&self) -> String {
+        self.table_stories
+            .iter()
+            .fold(self.text.clone(), |acc, us| acc + "\n" + &us.raw_text)
+    }
+
+    /// Stories du document : sections Markdown puis lignes des tableaux
+    pub fn into_stories(self, language: Language) -> Result<Vec<UserStory>, InputError> {
+        let mut stories = match story_parser::parse_stories(&self.text, language) {
+            Ok(stories) => stories,
+            Err(InputError::NoStoriesFound) if !self.table_stories.is_empty() => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        for mut us in self.table_stories {
+            let position = stories.len() + 1;
+            us.external_id
+                .get_or_insert_with(|| format!("US-{:03}", position));
+            stories.push(us);
+        }
+        Ok(stories)
+    }
+}
+
+/// Resout une reference XML (`&amp;`, `&#233;`, `&#xE9;`) en caractere
+pub(crate) fn resolve_xml_ref(reference: &BytesRef) -> Option<char> {
+    let name: &[u8] = reference.as_ref();
+    match name {
+        b"amp" => Some('&'),
+        b"lt" => Some('<'),
+        b"gt" => Some('>'),
+        b"apos" => Some('\''),
+        b"quot" => Some('"'),
+        _ => reference.resolve_char_ref().ok().flatten(),
+    }
+}
+
+/// Paragraphe en cours de construction
+#[derive(Default)]
+struct Paragraph {
+    text: String,
+    heading: Option<usize>,
+    list_item: bool,
+}
+
+impl Paragraph {
+    fn render(&self) -> String {
+        let text = self.text.trim();
+        match self.heading {
+            Some(level) if !text.is_empty() => format!("{} {}", "#".repeat(level.min(3)), text),
+            _ if self.list_item && !text.is_empty() => format!("- {}", text),
+            _ => text.to_string(),
+        }
+    }
+}
+
+/// Accumulateur alimente par les parseurs de documents
+#[derive(Default)]
+pub(crate) struct DocumentBuilder {
+    lines: Vec<String>,
+    table_stories: Vec<UserStory>,
+    paragraph: Option<Paragraph>,
+    table_depth: usize,
+    rows: Vec<Vec<String>>,
+    row: Vec<String>,
+    cell: Option<String>,
+    current_epic: Option<String>,
+}
+
+impl DocumentBuilder {
+    fn paragraph_mut(&mut self) -> &mut Paragraph {
+        self.paragraph.get_or_insert_with(Paragraph::default)
+    }
+
+    /// Ouvre un paragraphe (le precedent est termine)
+    pub fn start_paragraph(&mut self) {
+        self.end_paragraph();
+        self.paragraph = Some(Paragraph::default());
+    }
+
+    /// Le paragraphe courant est un titre de niveau `level` (1 = epic)
+    pub fn set_heading(&mut self, level: usize) {
+        if level > 0 {
+            self.paragraph_mut().heading = Some(level);
+        }
+    }
+
+    /// Le paragraphe courant est un element de liste
+    pub fn set_list_item(&mut self) {
+        self.paragraph_mut().list_item = true;
+    }
+
+    pub fn push_text(&mut self, text: &str) {
+        self.paragraph_mut().text.push_str(text);
+    }
+
+    /// Le paragraphe courant contient-il du texte (hors blancs) ?
+    pub fn has_text(&self) -> bool {
+        self.paragraph
+            .as_ref()
+            .is_some_and(|p| !p.text.trim().is_empty())
+    }
+
+    pub fn end_paragraph(&mut self) {
+        let Some(paragraph) = self.paragraph.take() else {
+            return;
+        };
+        let line = paragraph.render();
+        if let Some(ref mut cell) = self.cell {
+            if !line.is_empty() {
+                if !cell.is_empty() {
+                    cell.push('\n');
+                }
+                cell.push_str(&line);
+            }
+            return;
+        }
+        if paragraph.heading == Some(1) && !paragraph.text.trim().is_empty() {
+            self.current_epic = Some(paragraph.text.trim().to_string());
+        }
+        if paragraph.heading.is_some() {
+            // Ligne vide avant un titre : `story_parser` decoupe sur "\n## "
+            self.lines.push(String::new());
+        }
+        self.lines.push(line);
+    }
+
+    pub fn start_table(&mut self) {
+        self.end_paragraph();
+        self.table_depth += 1;
+    }
+
+    /// Les tableaux imbriques sont aplatis dans la cellule englobante
+    pub fn start_row(&mut self) {
+        if self.table_depth == 1 {
+            self.row.clear();
+        }
+    }
+
+    pub fn start_cell(&mut self) {
+        if self.table_depth == 1 {
+            self.end_paragraph();
+            self.cell = Some(String::new());
+        }
+    }
+
+    pub fn end_cell(&mut self) {
+        if self.table_depth == 1 {
+            self.end_paragraph();
+            if let Some(cell) = self.cell.take() {
+                self.row.push(cell);
+            }
+        }
+    }
+
+    pub fn end_row(&mut self) {
+        if self.table_depth == 1 && !self.row.is_empty() {
+            let row = std::mem::take(&mut self.row);
+            self.rows.push(row);
+        }
+    }
+
+    /// Tableau termine : tableau de stories ou rendu texte `a | b | c`
+    pub fn end_table(&mut self) {
+        if self.table_depth == 0 {
+            return;
+        }
+        self.table_depth -= 1;
+        if self.table_depth > 0 {
+            return;
+        }
+        self.end_cell();
+        self.end_row();
+
+        let rows = std::mem::take(&mut self.rows);
+        let Some((headers, body)) = rows.split_first() else {
+            return;
+        };
+        match StoryTable::from_headers(headers) {
+            Some(table) => {
+                for row in body {
+                    if let Some(mut us) = table.story_from_row(row) {
+                        if us.epic.is_none() {
+                            us.epic = self.current_epic.clone();
+                        }
+                        self.table_stories.push(us);
+                    }
+                }
+            }
+            None => {
+                for row in &rows {
+                    let cells: Vec<String> = row.iter().map(|c| c.replace('\n', " ")).collect();
+                    self.lines.push(cells.join(" | "));
+                }
+            }
+        }
+    }
+
+    pub fn finish(mut self) -> StructuredDocument {
+        while self.table_depth > 0 {
+            self.end_table();
+        }
+        self.end_paragraph();
+        StructuredDocument {
+            text: self.lines.join("\n"),
+            table_stories: self.table_stories,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_builder_renders_markdown_sections() {
+        let mut builder = DocumentBuilder::default();
+        builder.set_heading(1);
+        builder.push_text("Paiements");
+        builder.start_paragraph();
+        builder.set_heading(2);
+        builder.push_text(" Virement ");
+        builder.start_paragraph();
+        builder.set_list_item();
+        builder.push_text("IBAN valide");
+        let doc = builder.finish();
+        assert_eq!(doc.text, "\n# Paiements\n\n## Virement\n- IBAN valide");
+    }
+
+    #[test]
+    fn test_builder_story_table_takes_current_epic() {
+        let mut builder = DocumentBuilder::default();
+        builder.set_heading(1);
+        builder.push_text("Cartes");
+        builder.start_table();
+        for row in [["Titre", "Je veux"], ["Opposition", "bloquer ma carte"]] {
+            builder.start_row();
+            for cell in row {
+                builder.start_cell();
+                builder.push_text(cell);
+                builder.end_cell();
+            }
+            builder.end_row();
+        }
+        builder.end_table();
+        let doc = builder.finish();
+        assert_eq!(doc.table_stories.len(), 1);
+        assert_eq!(doc.table_stories[0].epic.as_deref(), Some("Cartes"));
+
+        let stories = doc.into_stories(Language::French).unwrap();
+        assert_eq!(stories[0].external_id.as_deref(), Some("US-001"));
+    }
+}
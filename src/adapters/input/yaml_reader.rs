@@ -1,12 +1,38 @@
 //! Adapter YamlReader - Parse les User Stories depuis un fichier YAML
+//!
+//! Les stories peuvent etre listees a plat (`stories:`) ou regroupees par
+//! epic (`epics: [{ name, stories }]`).
 
 use async_trait::async_trait;
+use serde::Deserialize;
 use std::path::Path;
 
 use crate::domain::errors::InputError;
-use crate::domain::user_story::{Language, UserStorySet};
+use crate::domain::user_story::{Language, UserStory, UserStorySet};
 use crate::ports::input_reader::InputReader;
 
+/// Structure d'un fichier YAML de User Stories
+#[derive(Debug, Deserialize)]
+struct YamlStoryFile {
+    #[serde(default)]
+    stories: Vec<UserStory>,
+    #[serde(default)]
+    epics: Vec<YamlEpic>,
+    #[serde(default, alias = "source_file")]
+    source_files: Vec<String>,
+    #[serde(default)]
+    language: Language,
+}
+
+/// Groupe de stories sous un epic
+#[derive(Debug, Deserialize)]
+struct YamlEpic {
+    #[serde(alias = "title")]
+    name: String,
+    #[serde(default)]
+    stories: Vec<UserStory>,
+}
+
 /// Lit les User Stories depuis un fichier YAML structure
 pub struct YamlReader;
 
@@ -14,6 +40,47 @@ impl YamlReader {
     pub fn new() -> Self {
         Self
     }
+
+    /// Le contenu commence-t-il par une cle de fichier de stories
+    /// (`stories:`, `epics:`, `language:`, `source_files:`) ?
+    pub fn looks_like_yaml(content: &str) -> bool {
+        let first_line = content
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty() && !l.starts_with('#'))
+            .unwrap_or("");
+        ["stories:", "epics:", "language:", "source_files:"]
+            .iter()
+            .any(|key| first_line.starts_with(key))
+    }
+
+    /// Parse un contenu YAML deja charge (fichier ou stdin)
+    pub fn parse_content(&self, content: &str) -> Result<UserStorySet, InputError> {
+        let file: YamlStoryFile =
+            serde_yaml::from_str(content).map_err(|e| InputError::InvalidFormat {
+                details: format!("Erreur YAML: {}", e),
+            })?;
+
+        let mut stories = file.stories;
+        for epic in file.epics {
+            for mut story in epic.stories {
+                if story.epic.is_none() {
+                    story.epic = Some(epic.name.clone());
+                }
+                stories.push(story);
+            }
+        }
+
+        if stories.is_empty() {
+            return Err(InputError::NoStoriesFound);
+        }
+
+        Ok(UserStorySet {
+            stories,
+            source_files: file.source_files,
+            language: file.language,
+        })
+    }
 }
 
 impl Default for YamlReader {
@@ -37,16 +104,7 @@ impl InputReader for YamlReader {
             .await
             .map_err(|e| InputError::ReadError(e.to_string()))?;
 
-        let story_set: UserStorySet =
-            serde_yaml::from_str(&content).map_err(|e| InputError::InvalidFormat {
-                details: format!("Erreur YAML: {}", e),
-            })?;
-
-        if story_set.stories.is_empty() {
-            return Err(InputError::NoStoriesFound);
-        }
-
-        Ok(story_set)
+        self.parse_content(&content)
     }
 
     fn detect_language(&self, content: &str) -> Language {
@@ -60,6 +118,10 @@ impl InputReader for YamlReader {
     fn supported_extensions(&self) -> &[&str] {
         &["yaml", "yml"]
     }
+
+    fn sniff(&self, _path: &Path, head: &[u8]) -> bool {
+        super::head_text(head).is_some_and(Self::looks_like_yaml)
+    }
 }
 
 #[cfg(test)]
@@ -132,6 +194,43 @@ stories: []
         assert!(matches!(result.unwrap_err(), InputError::NoStoriesFound));
     }
 
+    #[tokio::test]
+    async fn test_read_yaml_grouped_by_epic() {
+        let yaml_content = r#"
+language: fr
+stories:
+  - id: "550e8400-e29b-41d4-a716-446655440000"
+    title: "Hors groupe"
+    actor: "client"
+    action: "consulter"
+    benefit: "savoir"
+    epic: "Consultation"
+epics:
+  - name: "Paiements"
+    stories:
+      - id: "550e8400-e29b-41d4-a716-446655440001"
+        title: "Virement"
+        actor: "client"
+        action: "virer"
+        benefit: "payer"
+      - id: "550e8400-e29b-41d4-a716-446655440002"
+        title: "Prelevement"
+        actor: "client"
+        action: "prelever"
+        benefit: "payer"
+"#;
+        let mut file = NamedTempFile::with_suffix(".yaml").unwrap();
+        write!(file, "{}", yaml_content).unwrap();
+
+        let reader = YamlReader::new();
+        let set = reader.read_stories(file.path()).await.unwrap();
+        let epics: Vec<Option<&str>> = set.stories.iter().map(|s| s.epic.as_deref()).collect();
+        assert_eq!(
+            epics,
+            vec![Some("Consultation"), Some("Paiements"), Some("Paiements")]
+        );
+    }
+
     #[test]
     fn test_yaml_reader_detect_language() {
         let reader = YamlReader::new();
@@ -0,0 +1,989 @@
+//! Adapter DocxSpecWriter - Ecrit la specification au format Word (.docx)
+//!
+//! Document destine a la relecture formelle (suivi des modifications Word) :
+//! styles de titres, tableau des exigences, scenarios d'acceptation numerotes
+//! et annexe de tracabilite. Les styles peuvent etre repris d'un modele
+//! d'entreprise (`.dotx`) : `word/styles.xml`, `word/numbering.xml` et le
+//! theme sont copies, les identifiants de styles resolus par leur nom
+//! (`heading 1` -> `Titre1` dans un modele francais) ; les styles absents du
+//! modele sont ajoutes a partir des definitions par defaut.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use quick_xml::Reader;
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use zip::write::SimpleFileOptions;
+
+use crate::domain::naming::ascii_slug;
+use crate::domain::output_manifest::ArtifactKind;
+use crate::domain::specification::Specification;
+use crate::domain::test_case::TestSuite;
+use crate::domain::traceability::{TraceabilityMatrix, build_traceability_matrix};
+use crate::ports::output_writer::{OutputLayout, OutputWriter, WrittenArtifact};
+
+const STYLES_PART: &str = "word/styles.xml";
+const NUMBERING_PART: &str = "word/numbering.xml";
+const THEME_PART: &str = "word/theme/theme1.xml";
+
+/// Largeur utile d'une page A4 (marges de 2,5 cm), en vingtiemes de point
+const TEXT_WIDTH: u32 = 9072;
+
+/// Identifiants des styles utilises par le document
+#[derive(Debug, Clone, PartialEq)]
+struct StyleIds {
+    title: String,
+    headings: [String; 3],
+    list_paragraph: String,
+    table: String,
+}
+
+impl Default for StyleIds {
+    fn default() -> Self {
+        Self {
+            title: "Title".into(),
+            headings: ["Heading1".into(), "Heading2".into(), "Heading3".into()],
+            list_paragraph: "ListParagraph".into(),
+            table: "TableGrid".into(),
+        }
+    }
+}
+
+/// Styles integres utilises par le document : nom (en minuscules) et
+/// identifiant dans `DEFAULT_STYLES`
+const REQUIRED_STYLES: [(&str, &str); 8] = [
+    ("normal", "Normal"),
+    ("title", "Title"),
+    ("heading 1", "Heading1"),
+    ("heading 2", "Heading2"),
+    ("heading 3", "Heading3"),
+    ("list paragraph", "ListParagraph"),
+    ("normal table", "TableNormal"),
+    ("table grid", "TableGrid"),
+];
+
+impl StyleIds {
+    /// Resout les identifiants d'un `styles.xml` par le nom des styles
+    /// integres (les identifiants sont traduits, les noms ne le sont pas) ;
+    /// a appeler sur le resultat de `complete_styles`
+    fn resolve(styles_xml: &str) -> Self {
+        let names = style_ids_by_name(styles_xml);
+        let lookup = |name: &str, default: &str| {
+            names
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| default.to_string())
+        };
+        let defaults = Self::default();
+        Self {
+            title: lookup("title", &defaults.title),
+            headings: [
+                lookup("heading 1", &defaults.headings[0]),
+                lookup("heading 2", &defaults.headings[1]),
+                lookup("heading 3", &defaults.headings[2]),
+            ],
+            list_paragraph: lookup("list paragraph", &defaults.list_paragraph),
+            table: lookup("table grid", &defaults.table),
+        }
+    }
+}
+
+/// Styles repris d'un modele Word
+struct ReferenceStyles {
+    styles_xml: String,
+    numbering_xml: Option<String>,
+    theme_xml: Option<String>,
+}
+
+/// Ecrit une specification au format DOCX
+pub struct DocxSpecWriter {
+    reference: Option<ReferenceStyles>,
+}
+
+impl DocxSpecWriter {
+    pub fn new() -> Self {
+        Self { reference: None }
+    }
+
+    /// Reprend les styles d'un modele Word (`.dotx` ou `.docx`)
+    pub fn with_reference(reference: &Path) -> Result<Self, anyhow::Error> {
+        let file = std::fs::File::open(reference).map_err(|e| {
+            anyhow::anyhow!(
+                "Document de reference illisible {}: {}",
+                reference.display(),
+                e
+            )
+        })?;
+        let mut archive = zip::ZipArchive::new(file).map_err(|e| {
+            anyhow::anyhow!(
+                "Document de reference invalide {}: {}",
+                reference.display(),
+                e
+            )
+        })?;
+        let styles_xml = read_part(&mut archive, STYLES_PART)?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "Document de reference sans {}: {}",
+                STYLES_PART,
+                reference.display()
+            )
+        })?;
+        let numbering_xml = read_part(&mut archive, NUMBERING_PART)?;
+        let theme_xml = read_part(&mut archive, THEME_PART)?;
+        Ok(Self {
+            reference: Some(ReferenceStyles {
+                styles_xml,
+                numbering_xml,
+                theme_xml,
+            }),
+        })
+    }
+
+    /// Genere le document DOCX ; l'annexe de tracabilite liste les tests
+    /// couvrants lorsque la matrice est fournie
+    pub fn render(
+        &self,
+        spec: &Specification,
+        matrix: Option<&TraceabilityMatrix>,
+    ) -> Result<Vec<u8>, anyhow::Error> {
+        let (styles_xml, numbering_reference, theme_xml) = match self.reference {
+            Some(ref reference) => (
+                complete_styles(&reference.styles_xml),
+                reference.numbering_xml.as_deref(),
+                reference.theme_xml.as_deref(),
+            ),
+            None => (DEFAULT_STYLES.to_string(), None, None),
+        };
+        let style_ids = StyleIds::resolve(&styles_xml);
+
+        let mut body = DocumentBody::new(style_ids, NumberingBase::after(numbering_reference));
+        body.specification(spec);
+        body.traceability_appendix(spec, matrix);
+        let document_xml = body.document_xml();
+        let numbering_xml = body.numbering_xml(numbering_reference);
+
+        let options = SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            // Date fixe : un contenu identique produit une archive identique
+            .last_modified_time(zip::DateTime::default());
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let mut parts: Vec<(&str, String)> = vec![
+            ("[Content_Types].xml", content_types(theme_xml.is_some())),
+            ("_rels/.rels", PACKAGE_RELS.to_string()),
+            ("docProps/core.xml", core_properties(spec)),
+            ("word/document.xml", document_xml),
+            (
+                "word/_rels/document.xml.rels",
+                document_rels(theme_xml.is_some()),
+            ),
+            (STYLES_PART, styles_xml.clone()),
+            (NUMBERING_PART, numbering_xml),
+        ];
+        if let Some(theme) = theme_xml {
+            parts.push((THEME_PART, theme.to_string()));
+        }
+        for (name, content) in parts {
+            zip.start_file(name, options)?;
+            zip.write_all(content.as_bytes())?;
+        }
+        Ok(zip.finish()?.into_inner())
+    }
+
+    /// Ecrit la specification dans `spec-<titre>.docx`
+    pub async fn write(
+        &self,
+        spec: &Specification,
+        matrix: Option<&TraceabilityMatrix>,
+        output_dir: &Path,
+    ) -> Result<PathBuf, anyhow::Error> {
+        tokio::fs::create_dir_all(output_dir).await?;
+        let path = output_dir.join(format!("spec-{}.docx", ascii_slug(&spec.title, '-')));
+        let content = self.render(spec, matrix)?;
+        tokio::fs::write(&path, &content).await?;
+        Ok(path)
+    }
+}
+
+#[async_trait]
+impl OutputWriter for DocxSpecWriter {
+    fn format(&self) -> &str {
+        "docx"
+    }
+
+    /// L'annexe reprend la matrice de tracabilite de la suite generee
+    async fn write_outputs(
+        &self,
+        spec: &Specification,
+        suite: &TestSuite,
+        layout: &OutputLayout,
+    ) -> Result<Vec<WrittenArtifact>, anyhow::Error> {
+        let matrix = build_traceability_matrix(spec, suite);
+        let path = self.write(spec, Some(&matrix), &layout.specs_dir).await?;
+        Ok(vec![WrittenArtifact::for_spec(
+            path,
+            ArtifactKind::Specification,
+            spec,
+        )])
+    }
+}
+
+impl Default for DocxSpecWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fragment de texte d'un paragraphe
+struct Run<'a> {
+    text: &'a str,
+    bold: bool,
+}
+
+fn text(text: &str) -> Run<'_> {
+    Run { text, bold: false }
+}
+
+fn bold(text: &str) -> Run<'_> {
+    Run { text, bold: true }
+}
+
+/// Premiers identifiants de numerotation libres : les definitions du modele
+/// sont conservees (ses styles peuvent y faire reference), celles du
+/// document sont numerotees a la suite
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct NumberingBase {
+    abstract_num_id: usize,
+    /// `w:numId` des listes a puces ; les listes numerotees suivent
+    bullet_num_id: usize,
+}
+
+impl NumberingBase {
+    fn after(reference: Option<&str>) -> Self {
+        let mut base = Self {
+            abstract_num_id: 0,
+            bullet_num_id: 1,
+        };
+        let Some(numbering_xml) = reference else {
+            return base;
+        };
+        let mut reader = Reader::from_str(numbering_xml);
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e) | Event::Empty(e)) => {
+                    let id =
+                        |name: &[u8]| attribute(&e, name).and_then(|v| v.parse::<usize>().ok());
+                    match e.local_name().as_ref() {
+                        b"abstractNum" => {
+                            if let Some(id) = id(b"abstractNumId") {
+                                base.abstract_num_id = base.abstract_num_id.max(id + 1);
+                            }
+                        }
+                        b"num" => {
+                            if let Some(id) = id(b"numId") {
+                                base.bullet_num_id = base.bullet_num_id.max(id + 1);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Event::Eof) | Err(_) => break,
+                _ => {}
+            }
+        }
+        base
+    }
+}
+
+/// Corps du document (`w:body`) et listes numerotees
+struct DocumentBody {
+    xml: String,
+    styles: StyleIds,
+    numbering: NumberingBase,
+    /// Listes numerotees (une instance `w:num` par liste, numerotation
+    /// reprise a 1)
+    numbered_lists: usize,
+}
+
+impl DocumentBody {
+    fn new(styles: StyleIds, numbering: NumberingBase) -> Self {
+        Self {
+            xml: String::new(),
+            styles,
+            numbering,
+            numbered_lists: 0,
+        }
+    }
+
+    fn paragraph(&mut self, style: Option<&str>, num_id: Option<usize>, runs: &[Run]) {
+        // Ecriture en memoire (String) : ne peut pas echouer
+        _ = write!(self.xml, "<w:p>");
+        if style.is_some() || num_id.is_some() {
+            _ = write!(self.xml, "<w:pPr>");
+            if let Some(style) = style {
+                _ = write!(self.xml, r#"<w:pStyle w:val="{}"/>"#, escape(style));
+            }
+            if let Some(num_id) = num_id {
+                _ = write!(
+                    self.xml,
+                    r#"<w:numPr><w:ilvl w:val="0"/><w:numId w:val="{num_id}"/></w:numPr>"#
+                );
+            }
+            _ = write!(self.xml, "</w:pPr>");
+        }
+        for run in runs {
+            write_run(&mut self.xml, run);
+        }
+        _ = write!(self.xml, "</w:p>");
+    }
+
+    fn title(&mut self, content: &str) {
+        let style = self.styles.title.clone();
+        self.paragraph(Some(&style), None, &[text(content)]);
+    }
+
+    fn heading(&mut self, level: usize, content: &str) {
+        let style = self.styles.headings[level.clamp(1, 3) - 1].clone();
+        self.paragraph(Some(&style), None, &[text(content)]);
+    }
+
+    fn line(&mut self, runs: &[Run]) {
+        self.paragraph(None, None, runs);
+    }
+
+    fn bullet(&mut self, runs: &[Run]) {
+        let style = self.styles.list_paragraph.clone();
+        self.paragraph(Some(&style), Some(self.numbering.bullet_num_id), runs);
+    }
+
+    /// Nouvelle liste numerotee ; retourne son `w:numId`
+    fn start_numbered_list(&mut self) -> usize {
+        self.numbered_lists += 1;
+        self.numbering.bullet_num_id + self.numbered_lists
+    }
+
+    fn numbered(&mut self, num_id: usize, runs: &[Run]) {
+        let style = self.styles.list_paragraph.clone();
+        self.paragraph(Some(&style), Some(num_id), runs);
+    }
+
+    fn page_break(&mut self) {
+        _ = write!(self.xml, r#"<w:p><w:r><w:br w:type="page"/></w:r></w:p>"#);
+    }
+
+    /// Tableau avec ligne d'en-tete repetee sur chaque page ; `widths` donne
+    /// la part relative de chaque colonne
+    fn table(&mut self, headers: &[&str], widths: &[u32], rows: &[Vec<String>]) {
+        let total: u32 = widths.iter().sum::<u32>().max(1);
+        let columns: Vec<u32> = widths.iter().map(|w| TEXT_WIDTH * w / total).collect();
+
+        _ = write!(
+            self.xml,
+            r#"<w:tbl><w:tblPr><w:tblStyle w:val="{}"/><w:tblW w:w="5000" w:type="pct"/><w:tblLook w:val="04A0" w:firstRow="1" w:lastRow="0" w:firstColumn="1" w:lastColumn="0" w:noHBand="0" w:noVBand="1"/></w:tblPr><w:tblGrid>"#,
+            escape(self.styles.table.as_str())
+        );
+        for width in &columns {
+            _ = write!(self.xml, r#"<w:gridCol w:w="{width}"/>"#);
+        }
+        _ = write!(self.xml, "</w:tblGrid>");
+
+        _ = write!(self.xml, "<w:tr><w:trPr><w:tblHeader/></w:trPr>");
+        for (header, width) in headers.iter().zip(&columns) {
+            self.cell(*width, &bold(header));
+        }
+        _ = write!(self.xml, "</w:tr>");
+        for row in rows {
+            _ = write!(self.xml, "<w:tr>");
+            for (value, width) in row.iter().zip(&columns) {
+                self.cell(*width, &text(value));
+            }
+            _ = write!(self.xml, "</w:tr>");
+        }
+        _ = write!(self.xml, "</w:tbl>");
+        // Un paragraphe separe deux tableaux consecutifs
+        self.line(&[]);
+    }
+
+    fn cell(&mut self, width: u32, run: &Run) {
+        _ = write!(
+            self.xml,
+            r#"<w:tc><w:tcPr><w:tcW w:w="{width}" w:type="dxa"/></w:tcPr><w:p>"#
+        );
+        write_run(&mut self.xml, run);
+        _ = write!(self.xml, "</w:p></w:tc>");
+    }
+
+    fn specification(&mut self, spec: &Specification) {
+        self.title(&format!("Specification : {}", spec.title));
+
+        let created = spec.created_at.format("%Y-%m-%d").to_string();
+        let status = spec.status.to_string();
+        let tool = format!("spec-forge v{}", spec.tool_version);
+        let mut metadata = vec![
+            ("Version", spec.version.clone()),
+            ("Statut", status),
+            ("Date", created),
+            ("Outil", tool),
+        ];
+        if let Some(ref compliance) = spec.compliance_profile {
+            metadata.push(("Conformite", format!("{:?}", compliance)));
+        }
+        for (label, value) in &metadata {
+            self.line(&[bold(&format!("{label} : ")), text(value)]);
+        }
+
+        self.heading(1, "User Scenarios & Testing");
+        for scenario in &spec.user_scenarios {
+            self.heading(
+                2,
+                &format!(
+                    "{} - {} (Priority: {})",
+                    scenario.id, scenario.title, scenario.priority
+                ),
+            );
+            self.line(&[text(&scenario.description)]);
+            self.line(&[bold("Why this priority : "), text(&scenario.why_priority)]);
+            self.line(&[
+                bold("Independent Test : "),
+                text(&scenario.independent_test),
+            ]);
+            if scenario.acceptance_scenarios.is_empty() {
+                continue;
+            }
+            self.line(&[bold("Acceptance Scenarios :")]);
+            let list = self.start_numbered_list();
+            for ac in &scenario.acceptance_scenarios {
+                self.numbered(
+                    list,
+                    &[
+                        bold("Given "),
+                        text(&ac.given),
+                        bold(", When "),
+                        text(&ac.when),
+                        bold(", Then "),
+                        text(&ac.then),
+                    ],
+                );
+            }
+        }
+
+        if !spec.edge_cases.is_empty() {
+            self.heading(2, "Edge Cases");
+            for ec in &spec.edge_cases {
+                let related = ec
+                    .related_scenario
+                    .as_ref()
+                    .map(|r| format!(" (lie a: {})", r))
+                    .unwrap_or_default();
+                self.bullet(&[text(&ec.description), text(&related)]);
+            }
+        }
+
+        self.heading(1, "Requirements");
+        self.heading(2, "Functional Requirements");
+        let rows: Vec<Vec<String>> = spec
+            .functional_requirements
+            .iter()
+            .map(|fr| {
+                vec![
+                    fr.id.clone(),
+                    fr.statement.clone(),
+                    fr.priority.to_string(),
+                    dash_or(fr.risk_level.as_ref()),
+                    fr.verification_method.to_string(),
+                    fr.rationale.clone().unwrap_or_else(|| "-".into()),
+                ]
+            })
+            .collect();
+        self.table(
+            &[
+                "ID",
+                "Enonce",
+                "Priorite",
+                "Risque",
+                "Verification",
+                "Justification",
+            ],
+            &[2, 6, 2, 2, 2, 4],
+            &rows,
+        );
+
+        if !spec.key_entities.is_empty() {
+            self.heading(2, "Key Entities");
+            for entity in &spec.key_entities {
+                let attributes = if entity.attributes.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", entity.attributes.join(", "))
+                };
+                self.bullet(&[
+                    bold(&format!("{} : ", entity.name)),
+                    text(&entity.description),
+                    text(&attributes),
+                ]);
+            }
+        }
+
+        self.heading(1, "Success Criteria");
+        for sc in &spec.success_criteria {
+            self.bullet(&[
+                bold(&format!("{} : ", sc.id)),
+                text(&sc.description),
+                text(&format!(" (Metrique: {})", sc.measurable_metric)),
+            ]);
+        }
+
+        if !spec.clarifications_needed.is_empty() {
+            self.heading(1, "Clarifications");
+            for cl in &spec.clarifications_needed {
+                if cl.resolved {
+                    self.bullet(&[
+                        bold("Q : "),
+                        text(&cl.question),
+                        bold(" R : "),
+                        text(cl.answer.as_deref().unwrap_or("N/A")),
+                    ]);
+                } else {
+                    self.bullet(&[
+                        bold("[NEEDS CLARIFICATION] "),
+                        text(&cl.question),
+                        text(&format!(" (Impact: {})", cl.impact)),
+                    ]);
+                }
+            }
+        }
+    }
+
+    /// Annexe : exigence -> stories sources et tests couvrants (ou, avant la
+    /// generation des tests, exigence -> source et methode de verification)
+    fn traceability_appendix(&mut self, spec: &Specification, matrix: Option<&TraceabilityMatrix>) {
+        self.page_break();
+        self.heading(1, "Annexe A - Tracabilite");
+        match matrix {
+            Some(matrix) => {
+                self.line(&[
+                    bold("Couverture forward : "),
+                    text(&format!("{:.0}%", matrix.summary.forward_coverage_pct)),
+                ]);
+                let rows: Vec<Vec<String>> = matrix
+                    .entries
+                    .iter()
+                    .map(|entry| {
+                        // Exigences derivees indentees sous leur parent
+                        let requirement = if entry.depth == 0 {
+                            entry.requirement_id.clone()
+                        } else {
+                            format!("{}↳ {}", "  ".repeat(entry.depth), entry.requirement_id)
+                        };
+                        vec![
+                            requirement,
+                            join_or_dash(&entry.source_stories),
+                            join_or_dash(&entry.covering_features),
+                            entry.covering_scenarios.len().to_string(),
+                            entry.status.to_string(),
+                        ]
+                    })
+                    .collect();
+                self.table(
+                    &["FR-ID", "Stories", "Features", "Scenarios", "Statut"],
+                    &[2, 2, 6, 2, 2],
+                    &rows,
+                );
+            }
+            None => {
+                self.line(&[text(
+                    "Tests non generes : tracabilite exigences -> sources uniquement.",
+                )]);
+                let rows: Vec<Vec<String>> = spec
+                    .functional_requirements
+                    .iter()
+                    .map(|fr| {
+                        vec![
+                            fr.id.clone(),
+                            fr.source.clone().unwrap_or_else(|| "-".into()),
+                            fr.parent_requirement.clone().unwrap_or_else(|| "-".into()),
+                            fr.verification_method.to_string(),
+                        ]
+                    })
+                    .collect();
+                self.table(
+                    &["FR-ID", "Source", "Exigence parente", "Verification"],
+                    &[2, 4, 3, 3],
+                    &rows,
+                );
+            }
+        }
+    }
+
+    fn document_xml(&self) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><w:body>{}<w:sectPr><w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1417" w:right="1417" w:bottom="1417" w:left="1417" w:header="708" w:footer="708" w:gutter="0"/></w:sectPr></w:body></w:document>"#,
+            self.xml
+        )
+    }
+
+    /// Puces et une instance numerotee par liste d'acceptation, ajoutees
+    /// aux definitions du modele lorsqu'il en fournit
+    fn numbering_xml(&self, reference: Option<&str>) -> String {
+        let NumberingBase {
+            abstract_num_id: bullet,
+            bullet_num_id,
+        } = self.numbering;
+        let decimal = bullet + 1;
+        let mut definitions = format!(
+            r#"<w:abstractNum w:abstractNumId="{bullet}"><w:multiLevelType w:val="singleLevel"/><w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="bullet"/><w:lvlText w:val="&#8226;"/><w:lvlJc w:val="left"/><w:pPr><w:ind w:left="720" w:hanging="360"/></w:pPr></w:lvl></w:abstractNum><w:abstractNum w:abstractNumId="{decimal}"><w:multiLevelType w:val="singleLevel"/><w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="decimal"/><w:lvlText w:val="%1."/><w:lvlJc w:val="left"/><w:pPr><w:ind w:left="720" w:hanging="360"/></w:pPr></w:lvl></w:abstractNum>"#
+        );
+        _ = write!(
+            definitions,
+            r#"<w:num w:numId="{bullet_num_id}"><w:abstractNumId w:val="{bullet}"/></w:num>"#
+        );
+        for list in 1..=self.numbered_lists {
+            _ = write!(
+                definitions,
+                r#"<w:num w:numId="{}"><w:abstractNumId w:val="{decimal}"/><w:lvlOverride w:ilvl="0"><w:startOverride w:val="1"/></w:lvlOverride></w:num>"#,
+                bullet_num_id + list
+            );
+        }
+
+        // Ordre impose par le schema : w:abstractNum*, w:num*, puis
+        // w:numIdMacAtCleanup ; les definitions du document s'inserent
+        // entre les deux premiers groupes du modele
+        if let Some(reference) = reference {
+            let insert_at = [
+                "<w:num ",
+                "<w:num>",
+                "<w:numIdMacAtCleanup",
+                "</w:numbering>",
+            ]
+            .iter()
+            .find_map(|marker| reference.find(marker));
+            if let Some(at) = insert_at {
+                let mut xml = reference.to_string();
+                xml.insert_str(at, &definitions);
+                return xml;
+            }
+        }
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">{definitions}</w:numbering>"#
+        )
+    }
+}
+
+fn write_run(xml: &mut String, run: &Run) {
+    if run.text.is_empty() {
+        return;
+    }
+    _ = write!(xml, "<w:r>");
+    if run.bold {
+        _ = write!(xml, "<w:rPr><w:b/></w:rPr>");
+    }
+    _ = write!(
+        xml,
+        r#"<w:t xml:space="preserve">{}</w:t></w:r>"#,
+        escape(run.text)
+    );
+}
+
+fn dash_or<T: ToString>(value: Option<&T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "-".into())
+}
+
+fn join_or_dash(values: &[String]) -> String {
+    if values.is_empty() {
+        "-".into()
+    } else {
+        values.join(", ")
+    }
+}
+
+/// Identifiants de styles indexes par nom de style en minuscules
+fn style_ids_by_name(styles_xml: &str) -> HashMap<String, String> {
+    let mut reader = Reader::from_str(styles_xml);
+    let mut ids = HashMap::new();
+    let mut current: Option<String> = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"style" => {
+                current = attribute(&e, b"styleId");
+            }
+            Ok(Event::Empty(e)) if e.local_name().as_ref() == b"name" => {
+                if let (Some(id), Some(name)) = (current.take(), attribute(&e, b"val")) {
+                    ids.entry(name.to_lowercase()).or_insert(id);
+                }
+            }
+            Ok(Event::End(e)) if e.local_name().as_ref() == b"style" => current = None,
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    ids
+}
+
+/// Ajoute au `styles.xml` d'un modele les styles integres qu'il ne definit
+/// pas (ni par nom, ni par identifiant), repris de `DEFAULT_STYLES` ; les
+/// styles de base (`w:basedOn`, `w:next`) pointent vers ceux du modele
+fn complete_styles(styles_xml: &str) -> String {
+    let names = style_ids_by_name(styles_xml);
+    let Some(end) = styles_xml.rfind("</w:styles>") else {
+        return styles_xml.to_string();
+    };
+    let mut added = String::new();
+    for (name, id) in REQUIRED_STYLES {
+        if names.contains_key(name) || names.values().any(|existing| existing == id) {
+            continue;
+        }
+        let Some(mut definition) = default_style_definition(id) else {
+            continue;
+        };
+        // Le style par defaut reste celui du modele
+        definition = definition.replacen(r#" w:default="1""#, "", 1);
+        for (base_name, base_id) in REQUIRED_STYLES {
+            if let Some(resolved) = names.get(base_name).filter(|r| r.as_str() != base_id) {
+                for element in ["basedOn", "next"] {
+                    definition = definition.replace(
+                        &format!(r#"<w:{element} w:val="{base_id}"/>"#),
+                        &format!(r#"<w:{element} w:val="{}"/>"#, escape(resolved.as_str())),
+                    );
+                }
+            }
+        }
+        added.push_str(&definition);
+    }
+    let mut completed = styles_xml.to_string();
+    completed.insert_str(end, &added);
+    completed
+}
+
+/// Definition `<w:style>` d'un style de `DEFAULT_STYLES`
+fn default_style_definition(id: &str) -> Option<String> {
+    let marker = format!(r#"w:styleId="{id}""#);
+    let at = DEFAULT_STYLES.find(&marker)?;
+    let start = DEFAULT_STYLES[..at].rfind("<w:style ")?;
+    let end = at + DEFAULT_STYLES[at..].find("</w:style>")? + "</w:style>".len();
+    Some(DEFAULT_STYLES[start..end].to_string())
+}
+
+fn attribute(e: &BytesStart, name: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == name)
+        .and_then(|a| String::from_utf8(a.value.into_owned()).ok())
+}
+
+fn read_part<R: std::io::Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Result<Option<String>, anyhow::Error> {
+    let mut file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+    Ok(Some(content))
+}
+
+fn content_types(with_theme: bool) -> String {
+    let theme = if with_theme {
+        r#"<Override PartName="/word/theme/theme1.xml" ContentType="application/vnd.openxmlformats-officedocument.theme+xml"/>"#
+    } else {
+        ""
+    };
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/><Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/><Override PartName="/word/numbering.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml"/><Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>{theme}</Types>"#
+    )
+}
+
+const PACKAGE_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/></Relationships>"#;
+
+fn document_rels(with_theme: bool) -> String {
+    let theme = if with_theme {
+        r#"<Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme" Target="theme/theme1.xml"/>"#
+    } else {
+        ""
+    };
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering" Target="numbering.xml"/>{theme}</Relationships>"#
+    )
+}
+
+fn core_properties(spec: &Specification) -> String {
+    let created = spec.created_at.format("%Y-%m-%dT%H:%M:%SZ");
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><dc:title>{}</dc:title><dc:creator>spec-forge</dc:creator><cp:version>{}</cp:version><dcterms:created xsi:type="dcterms:W3CDTF">{created}</dcterms:created><dcterms:modified xsi:type="dcterms:W3CDTF">{created}</dcterms:modified></cp:coreProperties>"#,
+        escape(spec.title.as_str()),
+        escape(spec.version.as_str()),
+    )
+}
+
+/// Styles par defaut (sans modele) : Normal, Title, Heading1-3,
+/// ListParagraph, TableGrid
+const DEFAULT_STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri" w:hAnsi="Calibri" w:eastAsia="Calibri" w:cs="Calibri"/><w:sz w:val="22"/><w:szCs w:val="22"/><w:lang w:val="fr-FR"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:after="120" w:line="264" w:lineRule="auto"/></w:pPr></w:pPrDefault></w:docDefaults><w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/><w:qFormat/></w:style><w:style w:type="paragraph" w:styleId="Title"><w:name w:val="Title"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:spacing w:after="240"/></w:pPr><w:rPr><w:b/><w:color w:val="1F3864"/><w:sz w:val="44"/><w:szCs w:val="44"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="360" w:after="120"/><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:b/><w:color w:val="1F3864"/><w:sz w:val="32"/><w:szCs w:val="32"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading2"><w:name w:val="heading 2"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="80"/><w:outlineLvl w:val="1"/></w:pPr><w:rPr><w:b/><w:color w:val="2E74B5"/><w:sz w:val="26"/><w:szCs w:val="26"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading3"><w:name w:val="heading 3"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="200" w:after="60"/><w:outlineLvl w:val="2"/></w:pPr><w:rPr><w:b/><w:sz w:val="24"/><w:szCs w:val="24"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="ListParagraph"><w:name w:val="List Paragraph"/><w:basedOn w:val="Normal"/><w:qFormat/><w:pPr><w:spacing w:after="60"/><w:ind w:left="720"/></w:pPr></w:style><w:style w:type="table" w:default="1" w:styleId="TableNormal"><w:name w:val="Normal Table"/><w:tblPr><w:tblInd w:w="0" w:type="dxa"/><w:tblCellMar><w:top w:w="0" w:type="dxa"/><w:left w:w="108" w:type="dxa"/><w:bottom w:w="0" w:type="dxa"/><w:right w:w="108" w:type="dxa"/></w:tblCellMar></w:tblPr></w:style><w:style w:type="table" w:styleId="TableGrid"><w:name w:val="Table Grid"/><w:basedOn w:val="TableNormal"/><w:pPr><w:spacing w:after="0" w:line="240" w:lineRule="auto"/></w:pPr><w:tblPr><w:tblBorders><w:top w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:left w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:bottom w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:right w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:insideH w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:insideV w:val="single" w:sz="4" w:space="0" w:color="auto"/></w:tblBorders></w:tblPr></w:style></w:styles>"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::specification::*;
+    use crate::domain::user_story::Priority;
+    use uuid::Uuid;
+
+    fn make_spec() -> Specification {
+        let mut spec = Specification::new("Prêts & retours".into());
+        spec.user_scenarios.push(UserScenario {
+            id: "US-001".into(),
+            title: "Emprunt".into(),
+            priority: Priority::P1,
+            description: "Emprunter un livre".into(),
+            why_priority: "Coeur de metier".into(),
+            independent_test: "Emprunter puis consulter".into(),
+            acceptance_scenarios: vec![
+                AcceptanceScenario {
+                    given: "un livre disponible".into(),
+                    when: "je l'emprunte".into(),
+                    then: "il est marque <emprunte>".into(),
+                },
+                AcceptanceScenario {
+                    given: "un livre emprunte".into(),
+                    when: "je l'emprunte".into(),
+                    then: "un refus s'affiche".into(),
+                },
+            ],
+            source_story_id: Uuid::new_v4(),
+        });
+        spec.functional_requirements.push(FunctionalRequirement {
+            id: "FR-001".into(),
+            statement: "Le systeme DOIT enregistrer l'emprunt".into(),
+            priority: Priority::P1,
+            category: RequirementCategory::Functional,
+            testable: true,
+            rationale: Some("Suivi des stocks".into()),
+            source: Some("US-001".into()),
+            verification_method: Default::default(),
+            risk_level: Some(RiskLevel::High),
+            parent_requirement: None,
+            allocated_to: Vec::new(),
+            quality_characteristic: None,
+        });
+        spec
+    }
+
+    fn read_part_of(docx: &[u8], name: &str) -> Option<String> {
+        let mut archive = zip::ZipArchive::new(Cursor::new(docx)).unwrap();
+        read_part(&mut archive, name).unwrap()
+    }
+
+    #[test]
+    fn test_render_docx_package() {
+        let spec = make_spec();
+        let docx = DocxSpecWriter::new().render(&spec, None).unwrap();
+
+        let document = read_part_of(&docx, "word/document.xml").unwrap();
+        assert!(document.contains(r#"<w:pStyle w:val="Title"/>"#));
+        assert!(document.contains("Specification : Prêts &amp; retours"));
+        assert!(document.contains(r#"<w:pStyle w:val="Heading2"/>"#));
+        assert!(document.contains("il est marque &lt;emprunte&gt;"));
+        // Tableau des exigences : en-tete repete, justification et risque
+        assert!(document.contains("<w:tblHeader/>"));
+        assert!(document.contains("Suivi des stocks"));
+        assert!(document.contains("Annexe A - Tracabilite"));
+        assert!(document.contains("Tests non generes"));
+
+        // Une instance numerotee par liste de scenarios d'acceptation
+        let numbering = read_part_of(&docx, "word/numbering.xml").unwrap();
+        assert!(numbering.contains(r#"<w:num w:numId="2">"#));
+        assert!(!numbering.contains(r#"<w:num w:numId="3">"#));
+        assert_eq!(document.matches(r#"<w:numId w:val="2"/>"#).count(), 2);
+
+        assert!(read_part_of(&docx, "[Content_Types].xml").is_some());
+        assert!(read_part_of(&docx, THEME_PART).is_none());
+
+        // Contenu identique -> archive identique (empreinte du manifeste)
+        assert_eq!(docx, DocxSpecWriter::new().render(&spec, None).unwrap());
+    }
+
+    #[test]
+    fn test_reference_styles_are_resolved_by_name() {
+        let styles = r#"<w:styles xmlns:w="w"><w:style w:type="paragraph" w:styleId="Titre1"><w:name w:val="heading 1"/></w:style><w:style w:type="paragraph" w:styleId="Titre"><w:name w:val="Title"/></w:style></w:styles>"#;
+        let dir = tempfile::TempDir::new().unwrap();
+        let reference = dir.path().join("modele.dotx");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&reference).unwrap());
+        for (name, content) in [(STYLES_PART, styles), (THEME_PART, "<a:theme/>")] {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let writer = DocxSpecWriter::with_reference(&reference).unwrap();
+        let docx = writer.render(&make_spec(), None).unwrap();
+        let document = read_part_of(&docx, "word/document.xml").unwrap();
+        assert!(document.contains(r#"<w:pStyle w:val="Titre1"/>"#));
+        assert!(document.contains(r#"<w:pStyle w:val="Titre"/>"#));
+        // Styles absents du modele : definitions par defaut ajoutees
+        assert!(document.contains(r#"<w:pStyle w:val="Heading2"/>"#));
+        assert!(document.contains(r#"<w:tblStyle w:val="TableGrid"/>"#));
+        let written = read_part_of(&docx, STYLES_PART).unwrap();
+        assert!(written.starts_with(styles.trim_end_matches("</w:styles>")));
+        for id in ["Heading2", "ListParagraph", "TableGrid", "TableNormal"] {
+            assert_eq!(written.matches(&format!(r#"w:styleId="{id}""#)).count(), 1);
+        }
+        assert!(!written.contains(r#"w:styleId="Heading1""#));
+        assert!(!written.contains(r#"w:default="1""#));
+        assert!(read_part_of(&docx, THEME_PART).is_some());
+
+        assert!(DocxSpecWriter::with_reference(&dir.path().join("absent.dotx")).is_err());
+    }
+
+    #[test]
+    fn test_complete_styles_points_to_reference_base_styles() {
+        let styles = r#"<w:styles xmlns:w="w"><w:style w:type="paragraph" w:default="1" w:styleId="Standard"><w:name w:val="Normal"/></w:style><w:style w:type="paragraph" w:styleId="Listenabsatz"><w:name w:val="List Paragraph"/></w:style></w:styles>"#;
+        let completed = complete_styles(styles);
+        assert!(!completed.contains(r#"w:styleId="Normal""#));
+        assert!(!completed.contains(r#"w:styleId="ListParagraph""#));
+        assert!(completed.contains(r#"<w:basedOn w:val="Standard"/>"#));
+        assert!(!completed.contains(r#"<w:basedOn w:val="Normal"/>"#));
+        let ids = StyleIds::resolve(&completed);
+        assert_eq!(ids.list_paragraph, "Listenabsatz");
+        assert_eq!(ids.table, "TableGrid");
+
+        // Modele complet : inchange
+        assert_eq!(complete_styles(DEFAULT_STYLES), DEFAULT_STYLES);
+    }
+
+    #[test]
+    fn test_reference_numbering_is_kept() {
+        let numbering = r#"<w:numbering xmlns:w="w"><w:abstractNum w:abstractNumId="4"><w:lvl w:ilvl="0"/></w:abstractNum><w:num w:numId="7"><w:abstractNumId w:val="4"/></w:num></w:numbering>"#;
+        let dir = tempfile::TempDir::new().unwrap();
+        let reference = dir.path().join("modele.dotx");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&reference).unwrap());
+        for (name, content) in [(STYLES_PART, DEFAULT_STYLES), (NUMBERING_PART, numbering)] {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let writer = DocxSpecWriter::with_reference(&reference).unwrap();
+        let docx = writer.render(&make_spec(), None).unwrap();
+        let written = read_part_of(&docx, NUMBERING_PART).unwrap();
+        // Definitions du modele conservees, celles du document a la suite
+        assert!(written.contains(r#"<w:num w:numId="7"><w:abstractNumId w:val="4"/></w:num>"#));
+        assert!(written.contains(r#"<w:abstractNum w:abstractNumId="5">"#));
+        assert!(written.contains(r#"<w:num w:numId="8"><w:abstractNumId w:val="5"/></w:num>"#));
+        assert!(written.contains(r#"<w:num w:numId="9"><w:abstractNumId w:val="6"/>"#));
+        assert!(written.find(r#"w:abstractNumId="6">"#) < written.find(r#"<w:num w:numId="7">"#));
+
+        let document = read_part_of(&docx, "word/document.xml").unwrap();
+        assert_eq!(document.matches(r#"<w:numId w:val="9"/>"#).count(), 2);
+        assert!(!document.contains(r#"<w:numId w:val="2"/>"#));
+    }
+}
@@ -1,23 +1,80 @@
 //! Adapter GherkinWriter - Ecrit les fichiers .feature Gherkin
-
+//!
+//! Avec la preservation des editions active, les empreintes des scenarios
+//! generes sont conservees dans `.spec-forge-gherkin.json` a cote des
+//! fichiers : un scenario retouche a la main n'est plus ecrase par la
+//! generation suivante (fusion a trois voies, voir `domain::feature_merge`).
+
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
 use std::fmt::Write;
 use std::path::{Path, PathBuf};
 
-use crate::domain::test_case::{Feature, Scenario, Step};
+use async_trait::async_trait;
+use tracing::{info, warn};
+
+use crate::domain::feature_merge::{
+    BlockHashes, FeatureBlock, FeatureMergeReport, HEADER_BLOCK, generated_hashes,
+    merge_feature_blocks,
+};
+use crate::domain::output_manifest::ArtifactKind;
+use crate::domain::specification::Specification;
+use crate::domain::test_case::{Feature, Scenario, Step, TestSuite};
 use crate::domain::user_story::Language;
+use crate::ports::output_writer::{OutputLayout, OutputWriter, WrittenArtifact};
+
+/// Empreintes des scenarios generes, par fichier .feature
+pub const GHERKIN_MANIFEST_FILE: &str = ".spec-forge-gherkin.json";
+
+/// Resultat de l'ecriture d'un fichier .feature
+#[derive(Debug, Clone)]
+pub struct FeatureWriteOutcome {
+    pub path: PathBuf,
+    /// Bilan de fusion si le fichier existait deja (preservation active)
+    pub merge: Option<FeatureMergeReport>,
+}
 
 /// Ecrit des fichiers .feature Gherkin
 pub struct GherkinWriter {
     language: Language,
+    preserve_edits: bool,
 }
 
 impl GherkinWriter {
     pub fn new(language: Language) -> Self {
-        Self { language }
+        Self {
+            language,
+            preserve_edits: false,
+        }
+    }
+
+    /// Conserve les scenarios retouches a la main lors des re-ecritures
+    pub fn with_preserve_edits(mut self, enabled: bool) -> Self {
+        self.preserve_edits = enabled;
+        self
     }
 
     /// Genere le contenu d'un fichier .feature
     pub fn render_feature(&self, feature: &Feature) -> String {
+        compose_blocks(&self.render_blocks(feature))
+    }
+
+    /// Decoupe le rendu en blocs : en-tete puis un bloc par scenario
+    fn render_blocks(&self, feature: &Feature) -> Vec<FeatureBlock> {
+        let mut blocks = vec![FeatureBlock::new(HEADER_BLOCK, self.render_header(feature))];
+        let mut keys = Vec::new();
+        for scenario in &feature.scenarios {
+            let mut content = String::new();
+            self.render_scenario(&mut content, scenario);
+            blocks.push(FeatureBlock::new(
+                unique_key(&mut keys, &scenario.name),
+                content,
+            ));
+        }
+        blocks
+    }
+
+    fn render_header(&self, feature: &Feature) -> String {
         let mut content = String::new();
 
         // Header de langue — ecriture en memoire (String) : ne peut pas echouer
@@ -30,11 +87,8 @@ impl GherkinWriter {
         }
 
         // Feature
-        let feature_keyword = match self.language {
-            Language::French => "Fonctionnalité",
-            Language::English => "Feature",
-        };
-        _ = writeln!(content, "{}: {}", feature_keyword, feature.name);
+        let keywords = &self.language.keywords().gherkin;
+        _ = writeln!(content, "{}: {}", keywords.feature, feature.name);
 
         // Description
         if !feature.description.is_empty() {
@@ -46,23 +100,13 @@ impl GherkinWriter {
 
         // Background
         if let Some(ref bg) = feature.background {
-            let bg_keyword = match self.language {
-                Language::French => "Contexte",
-                Language::English => "Background",
-            };
-            _ = writeln!(content, "  {}:", bg_keyword);
+            _ = writeln!(content, "  {}:", keywords.background);
             for step in &bg.steps {
                 _ = writeln!(content, "    {}", self.render_step(step));
             }
             _ = writeln!(content);
         }
 
-        // Scenarios
-        for scenario in &feature.scenarios {
-            self.render_scenario(&mut content, scenario);
-            _ = writeln!(content);
-        }
-
         content
     }
 
@@ -73,16 +117,11 @@ impl GherkinWriter {
         }
 
         // Scenario keyword
+        let keywords = &self.language.keywords().gherkin;
         let keyword = if scenario.examples.is_some() {
-            match self.language {
-                Language::French => "Plan du Scénario",
-                Language::English => "Scenario Outline",
-            }
+            keywords.scenario_outline
         } else {
-            match self.language {
-                Language::French => "Scénario",
-                Language::English => "Scenario",
-            }
+            keywords.scenario
         };
 
         _ = writeln!(content, "  {}: {}", keyword, scenario.name);
@@ -112,11 +151,7 @@ impl GherkinWriter {
         // Examples
         if let Some(ref examples) = scenario.examples {
             _ = writeln!(content);
-            let examples_keyword = match self.language {
-                Language::French => "Exemples",
-                Language::English => "Examples",
-            };
-            _ = writeln!(content, "    {}:", examples_keyword);
+            _ = writeln!(content, "    {}:", keywords.examples);
 
             // Headers
             let headers = examples
@@ -135,11 +170,40 @@ impl GherkinWriter {
     }
 
     fn render_step(&self, step: &Step) -> String {
-        let keyword = match self.language {
-            Language::French => step.keyword.to_french(),
-            Language::English => step.keyword.to_english(),
-        };
-        format!("{} {}", keyword, step.text)
+        format!("{} {}", step.keyword.localized(self.language), step.text)
+    }
+
+    /// Ecrit les `.feature` d'une suite (voir `write_feature_merged`)
+    pub async fn write_suite(
+        &self,
+        suite: &TestSuite,
+        output_dir: &Path,
+    ) -> Result<Vec<FeatureWriteOutcome>, anyhow::Error> {
+        let mut outcomes = Vec::with_capacity(suite.features.len());
+        for (idx, feature) in suite.features.iter().enumerate() {
+            // Meme fichier qu'une feature suivante : seule la derniere est ecrite,
+            // sinon la fusion prendrait la precedente pour une edition manuelle
+            let stem = feature.file_stem();
+            if suite.features[idx + 1..]
+                .iter()
+                .any(|f| f.file_stem() == stem)
+            {
+                continue;
+            }
+            let outcome = self.write_feature_merged(feature, output_dir).await?;
+            if let Some(report) = &outcome.merge
+                && !report.preserved.is_empty()
+            {
+                info!(
+                    path = %outcome.path.display(),
+                    preserved = report.preserved.len(),
+                    conflicts = report.conflicts.len(),
+                    "Editions manuelles conservees"
+                );
+            }
+            outcomes.push(outcome);
+        }
+        Ok(outcomes)
     }
 
     /// Ecrit un fichier .feature
@@ -148,24 +212,195 @@ impl GherkinWriter {
         feature: &Feature,
         output_dir: &Path,
     ) -> Result<PathBuf, anyhow::Error> {
+        Ok(self.write_feature_merged(feature, output_dir).await?.path)
+    }
+
+    /// Ecrit un fichier .feature en preservant les editions manuelles
+    ///
+    /// Sans preservation (ou a la premiere ecriture), le fichier est ecrit
+    /// tel que genere ; sinon les blocs existants sont fusionnes avec la
+    /// nouvelle generation d'apres les empreintes du manifeste.
+    pub async fn write_feature_merged(
+        &self,
+        feature: &Feature,
+        output_dir: &Path,
+    ) -> Result<FeatureWriteOutcome, anyhow::Error> {
         tokio::fs::create_dir_all(output_dir).await?;
 
-        let filename = format!(
-            "{}.feature",
-            feature
-                .name
-                .to_lowercase()
-                .replace(' ', "_")
-                .chars()
-                .filter(|c| c.is_alphanumeric() || *c == '_')
-                .collect::<String>()
-        );
+        let filename = format!("{}.feature", feature.file_stem());
         let path = output_dir.join(&filename);
+        let generated = self.render_blocks(feature);
+
+        if !self.preserve_edits {
+            tokio::fs::write(&path, compose_blocks(&generated)).await?;
+            return Ok(FeatureWriteOutcome { path, merge: None });
+        }
+
+        let manifest_path = output_dir.join(GHERKIN_MANIFEST_FILE);
+        let mut manifest = read_manifest(&manifest_path).await;
+        let existing = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        let (content, hashes, merge) = match existing {
+            None => (
+                compose_blocks(&generated),
+                generated_hashes(&generated),
+                None,
+            ),
+            Some(existing) => {
+                let base = manifest.get(&filename).cloned().unwrap_or_default();
+                let current = self.parse_blocks(&existing);
+                let merged = merge_feature_blocks(&base, &current, &generated);
+                for conflict in &merged.report.conflicts {
+                    warn!(
+                        file = %path.display(),
+                        scenario = %conflict.scenario,
+                        reason = %conflict.reason,
+                        "Edition manuelle conservee malgre une nouvelle generation"
+                    );
+                }
+                (
+                    compose_blocks(&merged.blocks),
+                    merged.hashes,
+                    Some(merged.report),
+                )
+            }
+        };
+
+        tokio::fs::write(&path, content).await?;
+        manifest.insert(filename, hashes);
+        tokio::fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?).await?;
+
+        Ok(FeatureWriteOutcome { path, merge })
+    }
+
+    /// Decoupe un fichier .feature existant en blocs (en-tete + scenarios)
+    ///
+    /// Un scenario commence a ses tags (lignes non vides juste au-dessus du
+    /// mot-cle, a la meme indentation) et s'etend jusqu'au scenario suivant.
+    fn parse_blocks(&self, content: &str) -> Vec<FeatureBlock> {
+        let keywords = &self.language.keywords().gherkin;
+        let scenario_name = |line: &str| {
+            let trimmed = line.trim_start();
+            [keywords.scenario_outline, keywords.scenario]
+                .iter()
+                .find_map(|k| trimmed.strip_prefix(k)?.strip_prefix(':'))
+                .map(|name| name.trim().to_string())
+        };
 
-        let content = self.render_feature(feature);
-        tokio::fs::write(&path, &content).await?;
+        let lines: Vec<&str> = content.lines().collect();
+        let mut starts = Vec::new();
+        for (idx, line) in lines.iter().enumerate() {
+            if let Some(name) = scenario_name(line) {
+                let indent = indentation(line);
+                let mut start = idx;
+                while start > 0 {
+                    let previous = lines[start - 1];
+                    if previous.trim().is_empty() || indentation(previous) != indent {
+                        break;
+                    }
+                    start -= 1;
+                }
+                starts.push((start, name));
+            }
+        }
 
-        Ok(path)
+        let header_end = starts.first().map_or(lines.len(), |(start, _)| *start);
+        let mut blocks = vec![FeatureBlock::new(
+            HEADER_BLOCK,
+            join_lines(&lines[..header_end]),
+        )];
+        let mut keys = Vec::new();
+        for (pos, (start, name)) in starts.iter().enumerate() {
+            let end = starts.get(pos + 1).map_or(lines.len(), |(next, _)| *next);
+            blocks.push(FeatureBlock::new(
+                unique_key(&mut keys, name),
+                join_lines(&lines[*start..end]),
+            ));
+        }
+        blocks
+    }
+}
+
+#[async_trait]
+impl OutputWriter for GherkinWriter {
+    fn format(&self) -> &str {
+        "feature"
+    }
+
+    async fn write_outputs(
+        &self,
+        _spec: &Specification,
+        suite: &TestSuite,
+        layout: &OutputLayout,
+    ) -> Result<Vec<WrittenArtifact>, anyhow::Error> {
+        let outcomes = self.write_suite(suite, &layout.features_dir).await?;
+        let artifacts = outcomes
+            .into_iter()
+            .map(|outcome| {
+                let source_ids = suite
+                    .features
+                    .iter()
+                    .rev()
+                    .find(|f| outcome.path.file_stem() == Some(OsStr::new(&f.file_stem())))
+                    .map(|f| f.source_scenario_ids.clone())
+                    .unwrap_or_default();
+                WrittenArtifact {
+                    merge: outcome.merge,
+                    ..WrittenArtifact::new(outcome.path, ArtifactKind::Feature, source_ids)
+                }
+            })
+            .collect();
+        Ok(artifacts)
+    }
+}
+
+/// Cle unique d'un scenario dans son fichier (les homonymes sont numerotes)
+fn unique_key(keys: &mut Vec<String>, name: &str) -> String {
+    let mut key = name.to_string();
+    let mut n = 2;
+    while keys.contains(&key) {
+        key = format!("{name} ({n})");
+        n += 1;
+    }
+    keys.push(key.clone());
+    key
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn join_lines(lines: &[&str]) -> String {
+    let mut content = String::new();
+    for line in lines {
+        content.push_str(line);
+        content.push('\n');
+    }
+    content
+}
+
+/// Assemble les blocs, separes par une ligne vide
+fn compose_blocks(blocks: &[FeatureBlock]) -> String {
+    let mut content = String::new();
+    for block in blocks {
+        content.push_str(block.content.trim_end_matches('\n'));
+        content.push_str("\n\n");
+    }
+    content
+}
+
+/// Manifeste des empreintes ; absent ou illisible, il est repart de zero
+async fn read_manifest(path: &Path) -> BTreeMap<String, BlockHashes> {
+    match tokio::fs::read_to_string(path).await {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!(path = %path.display(), error = %e, "Manifeste Gherkin illisible, ignore");
+            BTreeMap::new()
+        }),
+        Err(_) => BTreeMap::new(),
     }
 }
 
@@ -294,6 +529,24 @@ mod tests {
         assert!(content.contains("Examples:"));
     }
 
+    #[test]
+    fn test_render_feature_spanish_and_german() {
+        let feature = make_test_feature();
+        let es = GherkinWriter::new(Language::Spanish).render_feature(&feature);
+        assert!(es.contains("# language: es"));
+        assert!(es.contains("Característica: Recherche par ISBN"));
+        assert!(es.contains("Antecedentes:"));
+        assert!(es.contains("Esquema del escenario:"));
+        assert!(es.contains("Ejemplos:"));
+
+        let de = GherkinWriter::new(Language::German).render_feature(&feature);
+        assert!(de.contains("# language: de"));
+        assert!(de.contains("Funktionalität: Recherche par ISBN"));
+        assert!(de.contains("Angenommen un catalogue"));
+        assert!(de.contains("Szenariogrundriss:"));
+        assert!(de.contains("Beispiele:"));
+    }
+
     #[test]
     fn test_render_feature_french_snapshot() {
         let writer = GherkinWriter::new(Language::French);
@@ -434,4 +687,67 @@ mod tests {
         assert!(content.contains("| Alice"));
         assert!(content.contains("| Bob"));
     }
+
+    #[tokio::test]
+    async fn test_write_feature_preserves_manual_edits() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let writer = GherkinWriter::new(Language::French).with_preserve_edits(true);
+        let mut feature = make_test_feature();
+
+        let first = writer
+            .write_feature_merged(&feature, dir.path())
+            .await
+            .unwrap();
+        assert!(first.merge.is_none());
+        assert!(dir.path().join(GHERKIN_MANIFEST_FILE).exists());
+
+        // L'equipe QA retouche le premier scenario
+        let edited = std::fs::read_to_string(&first.path)
+            .unwrap()
+            .replace("est affiche", "est affiche en premier");
+        std::fs::write(&first.path, edited).unwrap();
+
+        // Nouvelle generation : le second scenario change, le premier non
+        feature.scenarios[1].steps[2].text = "l'ouvrage est trouve une seule fois".into();
+        let second = writer
+            .write_feature_merged(&feature, dir.path())
+            .await
+            .unwrap();
+        let report = second.merge.unwrap();
+        assert_eq!(report.preserved, vec!["Recherche avec ISBN-13 valide"]);
+        assert_eq!(report.updated, vec!["Recherche avec formats ISBN varies"]);
+        assert!(report.conflicts.is_empty());
+
+        let content = std::fs::read_to_string(&second.path).unwrap();
+        assert!(content.contains("est affiche en premier"));
+        assert!(content.contains("trouve une seule fois"));
+
+        // La generation change aussi le scenario retouche : conflit signale
+        feature.scenarios[0].steps[2].text = "l'ouvrage \"Le Petit Prince\" est trouve".into();
+        let third = writer
+            .write_feature_merged(&feature, dir.path())
+            .await
+            .unwrap();
+        let report = third.merge.unwrap();
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(
+            report.conflicts[0].scenario,
+            "Recherche avec ISBN-13 valide"
+        );
+        let content = std::fs::read_to_string(&third.path).unwrap();
+        assert!(content.contains("est affiche en premier"));
+    }
+
+    #[test]
+    fn test_parse_blocks_roundtrip() {
+        let writer = GherkinWriter::new(Language::French);
+        let feature = make_test_feature();
+        let blocks = writer.parse_blocks(&writer.render_feature(&feature));
+        assert_eq!(blocks.len(), 3);
+        assert!(blocks[1].content.starts_with("  @happy_path @FR-001"));
+        assert_eq!(
+            generated_hashes(&blocks),
+            generated_hashes(&writer.render_blocks(&feature))
+        );
+    }
 }
@@ -0,0 +1,739 @@
+//! Adapter HtmlReportWriter - Rapport HTML autonome
+//!
+//! Regroupe dans un seul fichier consultable hors ligne (CSS et JS integres) :
+//! la specification, les features Gherkin colorees, la matrice de tracabilite
+//! filtrable, les metriques qualite ISO 25023 et la couverture par priorite
+//! et par risque. Les identifiants d'exigence renvoient vers les scenarios
+//! qui les verifient, et inversement.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use quick_xml::escape::escape;
+
+use crate::domain::output_manifest::ArtifactKind;
+use crate::domain::specification::Specification;
+use crate::domain::test_case::{Examples, Feature, Scenario, Step, TestSuite};
+use crate::domain::traceability::{TraceabilityStatus, build_traceability_matrix};
+use crate::domain::user_story::Language;
+use crate::domain::validation::{check_bidirectional_traceability, compute_quality_metrics};
+use crate::ports::output_writer::{OutputLayout, OutputWriter, WrittenArtifact};
+
+/// Nom du fichier genere
+pub const HTML_REPORT_FILE: &str = "report.html";
+
+const STYLE: &str = r#"
+body { font-family: system-ui, sans-serif; margin: 0; color: #222; background: #fafafa; }
+header { background: #263238; color: #fff; padding: 1rem 2rem; }
+header h1 { margin: 0 0 .3rem 0; }
+nav { background: #37474f; padding: .5rem 2rem; position: sticky; top: 0; }
+nav a { color: #fff; margin-right: 1.5rem; text-decoration: none; }
+main { padding: 1rem 2rem; max-width: 1200px; }
+section { margin-bottom: 2.5rem; }
+table { border-collapse: collapse; width: 100%; margin: .5rem 0 1rem 0; background: #fff; }
+th, td { border: 1px solid #ddd; padding: .35rem .6rem; text-align: left; vertical-align: top; }
+th { background: #eceff1; }
+details { background: #fff; border: 1px solid #ddd; border-radius: 4px; margin: .4rem 0; padding: .3rem .8rem; }
+summary { cursor: pointer; font-weight: 600; }
+.req:target, details:target { outline: 3px solid #ffb300; }
+.badge { display: inline-block; padding: 0 .4rem; border-radius: 3px; font-size: .8rem; background: #cfd8dc; margin-left: .3rem; }
+.status-covered { background: #c8e6c9; }
+.status-partial { background: #fff9c4; }
+.status-gap { background: #ffcdd2; font-weight: 600; }
+.status-other { background: #bbdefb; }
+.filters label { margin-right: 1rem; }
+pre.gherkin { background: #263238; color: #eceff1; padding: .8rem; border-radius: 4px; overflow-x: auto; }
+.g-kw { color: #82b1ff; font-weight: 600; }
+.g-tag { color: #ffcc80; }
+.g-str { color: #a5d6a7; }
+.g-ph { color: #f48fb1; }
+.g-doc { color: #b0bec5; }
+.g-tbl { color: #ffe082; }
+.metric { font-weight: 600; }
+"#;
+
+const SCRIPT: &str = r##"
+function applyFilters() {
+  var p = document.getElementById('filter-priority').value;
+  var r = document.getElementById('filter-risk').value;
+  document.querySelectorAll('#matrix tbody tr').forEach(function (row) {
+    var ok = (!p || row.dataset.priority === p) && (!r || row.dataset.risk === r);
+    row.style.display = ok ? '' : 'none';
+  });
+}
+document.querySelectorAll('a[href^="#sc-"]').forEach(function (link) {
+  link.addEventListener('click', function () {
+    var target = document.getElementById(link.getAttribute('href').slice(1));
+    if (target) { target.open = true; }
+  });
+});
+"##;
+
+/// Scenario verifiant une exigence : ancre HTML et nom
+struct ScenarioLink {
+    anchor: String,
+    name: String,
+}
+
+/// Ecrit un rapport HTML autonome (spec + tests + tracabilite)
+pub struct HtmlReportWriter {
+    language: Language,
+}
+
+impl HtmlReportWriter {
+    pub fn new(language: Language) -> Self {
+        Self { language }
+    }
+
+    /// Genere le rapport HTML complet
+    pub fn render(&self, spec: &Specification, suite: &TestSuite) -> String {
+        let links = scenario_links(spec, suite);
+        let mut html = String::new();
+
+        // Ecriture en memoire (String) : ne peut pas echouer
+        _ = writeln!(html, "<!DOCTYPE html>");
+        _ = writeln!(html, "<html lang=\"{}\">", self.language.keywords().code);
+        _ = writeln!(html, "<head>");
+        _ = writeln!(html, "<meta charset=\"utf-8\">");
+        _ = writeln!(html, "<title>{}</title>", esc(&spec.title));
+        _ = writeln!(html, "<style>{}</style>", STYLE);
+        _ = writeln!(html, "</head>");
+        _ = writeln!(html, "<body>");
+        _ = writeln!(html, "<header>");
+        _ = writeln!(html, "<h1>{}</h1>", esc(&spec.title));
+        _ = writeln!(
+            html,
+            "<div>Version {} &middot; {} &middot; {} &middot; {}</div>",
+            esc(&spec.version),
+            spec.status,
+            spec.created_at.format("%Y-%m-%d"),
+            esc(&spec.tool_version)
+        );
+        _ = writeln!(html, "</header>");
+        _ = writeln!(
+            html,
+            "<nav><a href=\"#metrics\">Metriques</a><a href=\"#specification\">Specification</a>\
+             <a href=\"#features\">Features</a><a href=\"#traceability\">Tracabilite</a></nav>"
+        );
+        _ = writeln!(html, "<main>");
+
+        self.render_metrics(&mut html, spec, suite);
+        self.render_specification(&mut html, spec, &links);
+        self.render_features(&mut html, suite);
+        self.render_traceability(&mut html, spec, suite, &links);
+
+        _ = writeln!(html, "</main>");
+        _ = writeln!(html, "<script>{}</script>", SCRIPT);
+        _ = writeln!(html, "</body>");
+        _ = writeln!(html, "</html>");
+        html
+    }
+
+    fn render_metrics(&self, html: &mut String, spec: &Specification, suite: &TestSuite) {
+        let metrics = compute_quality_metrics(spec, suite);
+        let report = check_bidirectional_traceability(spec, suite);
+
+        _ = writeln!(html, "<section id=\"metrics\">");
+        _ = writeln!(html, "<h2>Metriques qualite (ISO/IEC 25023)</h2>");
+        _ = writeln!(html, "<table>");
+        _ = writeln!(html, "<tr><th>Metrique</th><th>Valeur</th></tr>");
+        for (label, value) in [
+            (
+                "Completude fonctionnelle",
+                pct(metrics.functional_completeness),
+            ),
+            (
+                "Stabilite des exigences",
+                pct(metrics.requirement_stability),
+            ),
+            (
+                "Adequation des tests (scenarios / FR)",
+                format!("{:.2}", metrics.test_adequacy_ratio),
+            ),
+            ("Couverture des P1", pct(metrics.risk_coverage)),
+            ("Couverture NFR (ISO 25010)", pct(metrics.nfr_coverage)),
+            ("Score global", pct(metrics.overall_score)),
+            (
+                "Couverture forward (FR -> tests)",
+                pct(report.forward_coverage),
+            ),
+            (
+                "Couverture backward (tests -> FR)",
+                pct(report.backward_coverage),
+            ),
+        ] {
+            _ = writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"metric\">{}</td></tr>",
+                label, value
+            );
+        }
+        _ = writeln!(html, "</table>");
+
+        for (title, header, coverage) in [
+            (
+                "Couverture par priorite",
+                "Priorite",
+                &report.coverage_by_priority,
+            ),
+            ("Couverture par risque", "Risque", &report.coverage_by_risk),
+        ] {
+            if coverage.is_empty() {
+                continue;
+            }
+            let mut rows: Vec<_> = coverage.iter().collect();
+            rows.sort_by(|a, b| a.0.cmp(b.0));
+            _ = writeln!(html, "<h3>{}</h3>", title);
+            _ = writeln!(html, "<table>");
+            _ = writeln!(html, "<tr><th>{}</th><th>Couverture</th></tr>", header);
+            for (key, value) in rows {
+                _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td class=\"{}\">{}</td></tr>",
+                    esc(key),
+                    coverage_class(*value),
+                    pct(*value)
+                );
+            }
+            _ = writeln!(html, "</table>");
+        }
+        _ = writeln!(html, "</section>");
+    }
+
+    fn render_specification(
+        &self,
+        html: &mut String,
+        spec: &Specification,
+        links: &HashMap<String, Vec<ScenarioLink>>,
+    ) {
+        _ = writeln!(html, "<section id=\"specification\">");
+        _ = writeln!(html, "<h2>Specification</h2>");
+
+        _ = writeln!(html, "<h3>Scenarios utilisateur</h3>");
+        for us in &spec.user_scenarios {
+            _ = writeln!(html, "<details id=\"us-{}\">", esc(&us.id));
+            _ = writeln!(
+                html,
+                "<summary>{} &mdash; {} <span class=\"badge\">{}</span></summary>",
+                esc(&us.id),
+                esc(&us.title),
+                us.priority
+            );
+            _ = writeln!(html, "<p>{}</p>", esc(&us.description));
+            if !us.why_priority.is_empty() {
+                _ = writeln!(html, "<p><em>{}</em></p>", esc(&us.why_priority));
+            }
+            if !us.acceptance_scenarios.is_empty() {
+                _ = writeln!(html, "<ol>");
+                for ac in &us.acceptance_scenarios {
+                    _ = writeln!(
+                        html,
+                        "<li><b>Etant donne</b> {} <b>quand</b> {} <b>alors</b> {}</li>",
+                        esc(&ac.given),
+                        esc(&ac.when),
+                        esc(&ac.then)
+                    );
+                }
+                _ = writeln!(html, "</ol>");
+            }
+            _ = writeln!(html, "</details>");
+        }
+
+        _ = writeln!(html, "<h3>Exigences</h3>");
+        _ = writeln!(html, "<table>");
+        _ = writeln!(
+            html,
+            "<tr><th>ID</th><th>Exigence</th><th>Priorite</th><th>Categorie</th><th>Verifiee par</th></tr>"
+        );
+        for fr in &spec.functional_requirements {
+            let verified_by = links
+                .get(&fr.id)
+                .map(|l| render_links(l))
+                .unwrap_or_else(|| "<span class=\"status-gap\">aucun scenario</span>".into());
+            _ = writeln!(
+                html,
+                "<tr class=\"req\" id=\"{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                req_anchor(&fr.id),
+                esc(&fr.id),
+                esc(&fr.statement),
+                fr.priority,
+                fr.category,
+                verified_by
+            );
+        }
+        _ = writeln!(html, "</table>");
+
+        if !spec.key_entities.is_empty() {
+            _ = writeln!(html, "<h3>Entites cles</h3>");
+            _ = writeln!(html, "<ul>");
+            for entity in &spec.key_entities {
+                _ = writeln!(
+                    html,
+                    "<li><b>{}</b> : {}</li>",
+                    esc(&entity.name),
+                    esc(&entity.description)
+                );
+            }
+            _ = writeln!(html, "</ul>");
+        }
+
+        if !spec.edge_cases.is_empty() {
+            _ = writeln!(html, "<h3>Cas limites</h3>");
+            _ = writeln!(html, "<ul>");
+            for edge in &spec.edge_cases {
+                _ = writeln!(html, "<li>{}</li>", esc(&edge.description));
+            }
+            _ = writeln!(html, "</ul>");
+        }
+
+        if !spec.success_criteria.is_empty() {
+            _ = writeln!(html, "<h3>Criteres de succes</h3>");
+            _ = writeln!(html, "<ul>");
+            for sc in &spec.success_criteria {
+                _ = writeln!(
+                    html,
+                    "<li>{} : {} ({})</li>",
+                    esc(&sc.id),
+                    esc(&sc.description),
+                    esc(&sc.measurable_metric)
+                );
+            }
+            _ = writeln!(html, "</ul>");
+        }
+        _ = writeln!(html, "</section>");
+    }
+
+    fn render_features(&self, html: &mut String, suite: &TestSuite) {
+        let keywords = &self.language.keywords().gherkin;
+
+        _ = writeln!(html, "<section id=\"features\">");
+        _ = writeln!(
+            html,
+            "<h2>Features ({} scenarios)</h2>",
+            suite.total_scenarios
+        );
+        for (f_idx, feature) in suite.features.iter().enumerate() {
+            _ = writeln!(html, "<h3>{}</h3>", esc(&feature.name));
+            if !feature.tags.is_empty() {
+                _ = writeln!(
+                    html,
+                    "<pre class=\"gherkin\">{}</pre>",
+                    tags_line(&feature.tags)
+                );
+            }
+            if !feature.description.is_empty() {
+                _ = writeln!(html, "<p>{}</p>", esc(&feature.description));
+            }
+            if let Some(ref bg) = feature.background {
+                _ = writeln!(html, "<pre class=\"gherkin\">");
+                _ = writeln!(html, "<span class=\"g-kw\">{}:</span>", keywords.background);
+                for step in &bg.steps {
+                    self.render_step(html, step);
+                }
+                _ = writeln!(html, "</pre>");
+            }
+            for (s_idx, scenario) in feature.scenarios.iter().enumerate() {
+                self.render_scenario(html, feature, scenario, &scenario_anchor(f_idx, s_idx));
+            }
+        }
+        _ = writeln!(html, "</section>");
+    }
+
+    fn render_scenario(
+        &self,
+        html: &mut String,
+        feature: &Feature,
+        scenario: &Scenario,
+        anchor: &str,
+    ) {
+        let keywords = &self.language.keywords().gherkin;
+        let keyword = if scenario.examples.is_some() {
+            keywords.scenario_outline
+        } else {
+            keywords.scenario
+        };
+        let requirements: Vec<String> = scenario_requirements(feature, scenario)
+            .iter()
+            .map(|id| format!("<a href=\"#{}\">{}</a>", req_anchor(id), esc(id)))
+            .collect();
+
+        _ = writeln!(html, "<details id=\"{}\">", anchor);
+        _ = writeln!(
+            html,
+            "<summary>{} <span class=\"badge\">{}</span> {}</summary>",
+            esc(&scenario.name),
+            scenario.scenario_type,
+            requirements.join(" ")
+        );
+        _ = writeln!(html, "<pre class=\"gherkin\">");
+        if !scenario.tags.is_empty() {
+            _ = writeln!(html, "{}", tags_line(&scenario.tags));
+        }
+        _ = writeln!(
+            html,
+            "<span class=\"g-kw\">{}:</span> {}",
+            keyword,
+            esc(&scenario.name)
+        );
+        for step in &scenario.steps {
+            self.render_step(html, step);
+        }
+        if let Some(ref examples) = scenario.examples {
+            _ = writeln!(html);
+            _ = writeln!(html, "  <span class=\"g-kw\">{}:</span>", keywords.examples);
+            render_examples(html, examples);
+        }
+        _ = writeln!(html, "</pre>");
+        _ = writeln!(html, "</details>");
+    }
+
+    fn render_step(&self, html: &mut String, step: &Step) {
+        _ = writeln!(
+            html,
+            "  <span class=\"g-kw\">{}</span> {}",
+            step.keyword.localized(self.language),
+            highlight_text(&step.text)
+        );
+        if let Some(ref doc) = step.doc_string {
+            _ = writeln!(html, "<span class=\"g-doc\">    \"\"\"");
+            for line in doc.lines() {
+                _ = writeln!(html, "    {}", esc(line));
+            }
+            _ = writeln!(html, "    \"\"\"</span>");
+        }
+        if let Some(ref table) = step.data_table {
+            for row in table {
+                _ = writeln!(html, "    {}", table_row(row));
+            }
+        }
+    }
+
+    fn render_traceability(
+        &self,
+        html: &mut String,
+        spec: &Specification,
+        suite: &TestSuite,
+        links: &HashMap<String, Vec<ScenarioLink>>,
+    ) {
+        let matrix = build_traceability_matrix(spec, suite);
+
+        _ = writeln!(html, "<section id=\"traceability\">");
+        _ = writeln!(html, "<h2>Matrice de tracabilite</h2>");
+        _ = writeln!(
+            html,
+            "<p>Couvertes: {} | Partielles: {} | GAP: {} | Autre verification: {} | Couverture forward: <b>{:.0}%</b></p>",
+            matrix.summary.covered,
+            matrix.summary.partially_covered,
+            matrix.summary.not_covered,
+            matrix.summary.verified_other,
+            matrix.summary.forward_coverage_pct
+        );
+        _ = writeln!(html, "<div class=\"filters\">");
+        _ = writeln!(
+            html,
+            "<label>Priorite <select id=\"filter-priority\" onchange=\"applyFilters()\">\
+             <option value=\"\">Toutes</option><option>P1</option><option>P2</option><option>P3</option></select></label>"
+        );
+        _ = writeln!(
+            html,
+            "<label>Risque <select id=\"filter-risk\" onchange=\"applyFilters()\">\
+             <option value=\"\">Tous</option><option>High</option><option>Medium</option><option>Low</option></select></label>"
+        );
+        _ = writeln!(html, "</div>");
+
+        _ = writeln!(html, "<table id=\"matrix\">");
+        _ = writeln!(
+            html,
+            "<thead><tr><th>FR-ID</th><th>Priorite</th><th>Risque</th><th>Verification</th>\
+             <th>Scenarios</th><th>Statut</th></tr></thead>"
+        );
+        _ = writeln!(html, "<tbody>");
+        for entry in &matrix.entries {
+            let risk = entry
+                .risk_level
+                .map(|r| r.to_string())
+                .unwrap_or_else(|| "-".into());
+            let scenarios = links
+                .get(&entry.requirement_id)
+                .map(|l| render_links(l))
+                .unwrap_or_else(|| "-".into());
+            _ = writeln!(
+                html,
+                "<tr data-priority=\"{:?}\" data-risk=\"{}\"><td>{}<a href=\"#{}\">{}</a></td>\
+                 <td>{}</td><td>{}</td><td>{}</td><td>{}</td><td class=\"{}\">{}</td></tr>",
+                entry.priority,
+                risk,
+                tree_indent(entry.depth),
+                req_anchor(&entry.requirement_id),
+                esc(&entry.requirement_id),
+                entry.priority,
+                risk,
+                entry.verification_method,
+                scenarios,
+                status_class(entry.status),
+                entry.status
+            );
+        }
+        _ = writeln!(html, "</tbody>");
+        _ = writeln!(html, "</table>");
+
+        if !matrix.summary.orphan_tests.is_empty() {
+            _ = writeln!(html, "<h3>Tests orphelins (sans exigence associee)</h3>");
+            _ = writeln!(html, "<ul>");
+            for name in &matrix.summary.orphan_tests {
+                _ = writeln!(html, "<li>{}</li>", esc(name));
+            }
+            _ = writeln!(html, "</ul>");
+        }
+        _ = writeln!(html, "</section>");
+    }
+
+    /// Ecrit le rapport dans `output_dir/report.html`
+    pub async fn write(
+        &self,
+        spec: &Specification,
+        suite: &TestSuite,
+        output_dir: &Path,
+    ) -> Result<PathBuf, anyhow::Error> {
+        tokio::fs::create_dir_all(output_dir).await?;
+        let path = output_dir.join(HTML_REPORT_FILE);
+        tokio::fs::write(&path, self.render(spec, suite)).await?;
+        Ok(path)
+    }
+}
+
+#[async_trait]
+impl OutputWriter for HtmlReportWriter {
+    fn format(&self) -> &str {
+        "html"
+    }
+
+    async fn write_outputs(
+        &self,
+        spec: &Specification,
+        suite: &TestSuite,
+        layout: &OutputLayout,
+    ) -> Result<Vec<WrittenArtifact>, anyhow::Error> {
+        let path = self.write(spec, suite, &layout.report_dir).await?;
+        Ok(vec![WrittenArtifact::for_spec(
+            path,
+            ArtifactKind::HtmlReport,
+            spec,
+        )])
+    }
+}
+
+fn esc(text: &str) -> String {
+    escape(text).into_owned()
+}
+
+fn pct(ratio: f64) -> String {
+    format!("{:.0}%", ratio * 100.0)
+}
+
+fn req_anchor(id: &str) -> String {
+    format!("req-{}", esc(id))
+}
+
+/// Indentation d'une exigence derivee dans la matrice (arbre parent/enfants)
+fn tree_indent(depth: usize) -> String {
+    if depth == 0 {
+        String::new()
+    } else {
+        format!("{}&#8627; ", "&nbsp;&nbsp;".repeat(depth))
+    }
+}
+
+fn scenario_anchor(feature_idx: usize, scenario_idx: usize) -> String {
+    format!("sc-{}-{}", feature_idx + 1, scenario_idx + 1)
+}
+
+/// Exigences verifiees par un scenario : `verification_of` puis tags `@FR-`
+/// (a defaut, celles de la feature)
+fn scenario_requirements(feature: &Feature, scenario: &Scenario) -> Vec<String> {
+    let mut ids = scenario.linked_requirements();
+    if ids.is_empty() {
+        ids = feature.covered_requirements.clone();
+    }
+    ids
+}
+
+/// Index exigence -> scenarios qui la verifient (lien direct,
+/// `Scenario::linked_requirements`)
+fn scenario_links(spec: &Specification, suite: &TestSuite) -> HashMap<String, Vec<ScenarioLink>> {
+    let mut links: HashMap<String, Vec<ScenarioLink>> = HashMap::new();
+    for fr in &spec.functional_requirements {
+        for (f_idx, feature) in suite.features.iter().enumerate() {
+            for (s_idx, scenario) in feature.scenarios.iter().enumerate() {
+                let covers = scenario.linked_requirements().contains(&fr.id);
+                if covers {
+                    links.entry(fr.id.clone()).or_default().push(ScenarioLink {
+                        anchor: scenario_anchor(f_idx, s_idx),
+                        name: scenario.name.clone(),
+                    });
+                }
+            }
+        }
+    }
+    links
+}
+
+fn render_links(links: &[ScenarioLink]) -> String {
+    links
+        .iter()
+        .map(|l| format!("<a href=\"#{}\">{}</a>", l.anchor, esc(&l.name)))
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+fn status_class(status: TraceabilityStatus) -> &'static str {
+    match status {
+        TraceabilityStatus::FullyCovered => "status-covered",
+        TraceabilityStatus::PartiallyCovered => "status-partial",
+        TraceabilityStatus::NotCovered => "status-gap",
+        TraceabilityStatus::VerifiedByAnalysis
+        | TraceabilityStatus::VerifiedByInspection
+        | TraceabilityStatus::VerifiedByDemo => "status-other",
+    }
+}
+
+fn coverage_class(ratio: f64) -> &'static str {
+    if ratio >= 1.0 {
+        "status-covered"
+    } else if ratio > 0.0 {
+        "status-partial"
+    } else {
+        "status-gap"
+    }
+}
+
+fn tags_line(tags: &[String]) -> String {
+    tags.iter()
+        .map(|t| format!("<span class=\"g-tag\">{}</span>", esc(t)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn table_row(cells: &[String]) -> String {
+    let cells: Vec<String> = cells.iter().map(|c| format!(" {} ", esc(c))).collect();
+    format!("<span class=\"g-tbl\">|{}|</span>", cells.join("|"))
+}
+
+fn render_examples(html: &mut String, examples: &Examples) {
+    _ = writeln!(html, "    {}", table_row(&examples.headers));
+    for row in &examples.rows {
+        _ = writeln!(html, "    {}", table_row(row));
+    }
+}
+
+/// Colore les litteraux `"..."` et les placeholders `<...>` d'une etape
+fn highlight_text(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(pos) = rest.find(['"', '<']) {
+        out.push_str(&esc(&rest[..pos]));
+        let (open, close, class) = if rest[pos..].starts_with('"') {
+            ('"', '"', "g-str")
+        } else {
+            ('<', '>', "g-ph")
+        };
+        match rest[pos + 1..].find(close) {
+            Some(len) => {
+                let token = &rest[pos..pos + len + 2];
+                _ = write!(out, "<span class=\"{}\">{}</span>", class, esc(token));
+                rest = &rest[pos + len + 2..];
+            }
+            None => {
+                out.push_str(&esc(&open.to_string()));
+                rest = &rest[pos + 1..];
+            }
+        }
+    }
+    out.push_str(&esc(rest));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::specification::{
+        FunctionalRequirement, RequirementCategory, VerificationMethod,
+    };
+    use crate::domain::test_case::{ScenarioType, StepKeyword, TestCoverage};
+    use crate::domain::user_story::Priority;
+
+    fn sample() -> (Specification, TestSuite) {
+        let mut spec = Specification::new("Catalogue <v2>".into());
+        spec.functional_requirements.push(FunctionalRequirement {
+            id: "FR-001".into(),
+            statement: "Le systeme DOIT lister les livres".into(),
+            priority: Priority::P1,
+            category: RequirementCategory::Functional,
+            testable: true,
+            rationale: None,
+            source: None,
+            verification_method: VerificationMethod::Test,
+            risk_level: None,
+            parent_requirement: None,
+            allocated_to: vec![],
+            quality_characteristic: None,
+        });
+        let mut feature = Feature::new("Catalogue".into(), String::new());
+        feature.scenarios.push(Scenario {
+            name: "Lister les livres".into(),
+            tags: vec!["@FR-001".into()],
+            scenario_type: ScenarioType::HappyPath,
+            steps: vec![Step {
+                keyword: StepKeyword::When,
+                text: "je cherche \"Dune\" en <format>".into(),
+                doc_string: None,
+                data_table: None,
+            }],
+            examples: None,
+            test_data_suggestions: vec![],
+            verification_of: vec!["FR-001".into()],
+            coverage_technique: None,
+        });
+        let suite = TestSuite {
+            features: vec![feature],
+            source_spec_id: spec.id,
+            total_scenarios: 1,
+            coverage: TestCoverage {
+                requirements_covered: vec!["FR-001".into()],
+                requirements_total: 1,
+                coverage_percentage: 100.0,
+                scenarios_by_type: Default::default(),
+            },
+        };
+        (spec, suite)
+    }
+
+    #[test]
+    fn test_render_cross_links_requirements_and_scenarios() {
+        let (spec, suite) = sample();
+        let html = HtmlReportWriter::new(Language::French).render(&spec, &suite);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Catalogue &lt;v2&gt;</title>"));
+        // Exigence -> scenario et scenario -> exigence
+        assert!(html.contains("id=\"req-FR-001\""));
+        assert!(html.contains("<a href=\"#sc-1-1\">Lister les livres</a>"));
+        assert!(html.contains("<details id=\"sc-1-1\">"));
+        assert!(html.contains("<a href=\"#req-FR-001\">FR-001</a>"));
+        assert!(html.contains("data-priority=\"P1\""));
+        assert!(html.contains("class=\"status-partial\""));
+        // Aucune ressource externe
+        assert!(!html.contains("http://") && !html.contains("https://"));
+    }
+
+    #[test]
+    fn test_highlight_text() {
+        assert_eq!(
+            highlight_text("je cherche \"Dune\" en <format>"),
+            "je cherche <span class=\"g-str\">&quot;Dune&quot;</span> en \
+             <span class=\"g-ph\">&lt;format&gt;</span>"
+        );
+        assert_eq!(highlight_text("a < b"), "a &lt; b");
+    }
+}
@@ -0,0 +1,147 @@
+//! Adapter ImpactWriter - Ecrit le rapport d'analyse d'impact
+//!
+//! Liste les exigences modifiees entre deux versions de la spec et les
+//! scenarios existants a revoir, regenerer ou supprimer.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use crate::domain::impact::{ImpactAction, ImpactReport};
+
+/// Ecrit le rapport d'impact en Markdown
+pub struct ImpactWriter;
+
+impl ImpactWriter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Genere le rapport d'impact en Markdown
+    pub fn render(&self, report: &ImpactReport) -> String {
+        let mut md = String::new();
+
+        // Ecriture en memoire (String) : ne peut pas echouer
+        _ = writeln!(md, "# Analyse d'impact");
+        _ = writeln!(md);
+        _ = writeln!(md, "- Exigences modifiees: {}", report.changes.len());
+        _ = writeln!(
+            md,
+            "- Scenarios a revoir: {} | a regenerer: {} | a supprimer: {}",
+            report.count(ImpactAction::Review),
+            report.count(ImpactAction::Regenerate),
+            report.count(ImpactAction::Delete),
+        );
+        if !report.requirements_to_generate.is_empty() {
+            _ = writeln!(
+                md,
+                "- Exigences a (re)generer: {}",
+                report.requirements_to_generate.join(", ")
+            );
+        }
+        _ = writeln!(md);
+
+        _ = writeln!(md, "## Exigences modifiees");
+        _ = writeln!(md);
+        if report.changes.is_empty() {
+            _ = writeln!(md, "Aucun changement detecte.");
+            _ = writeln!(md);
+        } else {
+            _ = writeln!(md, "| FR-ID | Changement | Champs |");
+            _ = writeln!(md, "|-------|------------|--------|");
+            for change in &report.changes {
+                let fields = if change.fields.is_empty() {
+                    "-".to_string()
+                } else {
+                    change.fields.join(", ")
+                };
+                _ = writeln!(
+                    md,
+                    "| {} | {} | {} |",
+                    change.requirement_id, change.kind, fields
+                );
+            }
+            _ = writeln!(md);
+        }
+
+        for action in [
+            ImpactAction::Delete,
+            ImpactAction::Regenerate,
+            ImpactAction::Review,
+        ] {
+            let scenarios: Vec<_> = report
+                .scenarios
+                .iter()
+                .filter(|s| s.action == action)
+                .collect();
+            if scenarios.is_empty() {
+                continue;
+            }
+            _ = writeln!(md, "## Scenarios {}", action.to_string().to_lowercase());
+            _ = writeln!(md);
+            _ = writeln!(md, "| Feature | Scenario | Exigences | Motif |");
+            _ = writeln!(md, "|---------|----------|-----------|-------|");
+            for scenario in scenarios {
+                _ = writeln!(
+                    md,
+                    "| {} | {} | {} | {} |",
+                    scenario.feature,
+                    scenario.scenario.replace('|', "\\|"),
+                    scenario.requirements.join(", "),
+                    scenario.reason
+                );
+            }
+            _ = writeln!(md);
+        }
+
+        md
+    }
+
+    /// Ecrit le rapport dans `impact.md`
+    pub async fn write(
+        &self,
+        report: &ImpactReport,
+        output_dir: &Path,
+    ) -> Result<PathBuf, anyhow::Error> {
+        tokio::fs::create_dir_all(output_dir).await?;
+        let path = output_dir.join("impact.md");
+        tokio::fs::write(&path, self.render(report)).await?;
+        Ok(path)
+    }
+}
+
+impl Default for ImpactWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::impact::{ChangeKind, ImpactedScenario, RequirementChange};
+
+    #[test]
+    fn test_render_impact_report() {
+        let report = ImpactReport {
+            changes: vec![RequirementChange {
+                requirement_id: "FR-002".into(),
+                kind: ChangeKind::Modified,
+                fields: vec!["statement".into()],
+            }],
+            scenarios: vec![ImpactedScenario {
+                feature: "Catalogue".into(),
+                scenario: "Filtrer".into(),
+                action: ImpactAction::Regenerate,
+                requirements: vec!["FR-002".into()],
+                reason: "enonce modifie".into(),
+            }],
+            requirements_to_generate: vec!["FR-002".into()],
+        };
+        let md = ImpactWriter::new().render(&report);
+        assert!(md.contains("- Scenarios a revoir: 0 | a regenerer: 1 | a supprimer: 0"));
+        assert!(md.contains("| FR-002 | Modifiee | statement |"));
+        assert!(md.contains("## Scenarios a regenerer"));
+        assert!(md.contains("| Catalogue | Filtrer | FR-002 | enonce modifie |"));
+        assert!(!md.contains("## Scenarios a supprimer"));
+    }
+}
@@ -0,0 +1,81 @@
+//! Adapter JsonWriter - Ecrit la specification et la suite en JSON
+//!
+//! `specification.json` et `test_suite.json` sont les entrees des commandes
+//! travaillant sur un run existant (`generate-tests`, `impact`, `report`...).
+
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+
+use crate::domain::output_manifest::ArtifactKind;
+use crate::domain::specification::Specification;
+use crate::domain::test_case::TestSuite;
+use crate::ports::output_writer::{OutputLayout, OutputWriter, WrittenArtifact};
+
+/// Nom du fichier de la specification serialisee
+pub const SPECIFICATION_JSON_FILE: &str = "specification.json";
+
+/// Nom du fichier de la suite de tests serialisee
+pub const TEST_SUITE_JSON_FILE: &str = "test_suite.json";
+
+/// Ecrit la specification et la suite de tests en JSON indente
+pub struct JsonWriter;
+
+impl JsonWriter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Ecrit `specification.json`
+    pub async fn write_specification(
+        &self,
+        spec: &Specification,
+        output_dir: &Path,
+    ) -> Result<PathBuf, anyhow::Error> {
+        write_json(spec, &output_dir.join(SPECIFICATION_JSON_FILE)).await
+    }
+
+    /// Ecrit `test_suite.json`
+    pub async fn write_suite(
+        &self,
+        suite: &TestSuite,
+        output_dir: &Path,
+    ) -> Result<PathBuf, anyhow::Error> {
+        write_json(suite, &output_dir.join(TEST_SUITE_JSON_FILE)).await
+    }
+}
+
+async fn write_json<T: serde::Serialize>(value: &T, path: &Path) -> Result<PathBuf, anyhow::Error> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, serde_json::to_string_pretty(value)?).await?;
+    Ok(path.to_path_buf())
+}
+
+#[async_trait]
+impl OutputWriter for JsonWriter {
+    fn format(&self) -> &str {
+        "json"
+    }
+
+    async fn write_outputs(
+        &self,
+        spec: &Specification,
+        suite: &TestSuite,
+        layout: &OutputLayout,
+    ) -> Result<Vec<WrittenArtifact>, anyhow::Error> {
+        let spec_path = self.write_specification(spec, &layout.data_dir).await?;
+        let suite_path = self.write_suite(suite, &layout.data_dir).await?;
+        Ok(vec![
+            WrittenArtifact::for_spec(spec_path, ArtifactKind::Json, spec),
+            WrittenArtifact::for_spec(suite_path, ArtifactKind::Json, spec),
+        ])
+    }
+}
+
+impl Default for JsonWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,50 @@
+//! Adapter ManifestWriter - Lit et ecrit `manifest.json`
+//!
+//! Le manifeste du run precedent est relu avant d'etre remplace pour
+//! identifier les artefacts qui ne sont plus produits.
+
+use std::path::{Path, PathBuf};
+
+use tracing::warn;
+
+use crate::domain::output_manifest::{OUTPUT_MANIFEST_FILE, OutputManifest};
+
+/// Lit et ecrit le manifeste des artefacts d'un dossier de sortie
+pub struct ManifestWriter;
+
+impl ManifestWriter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Manifeste du run precedent ; absent ou illisible, il est ignore
+    pub async fn read(&self, output_dir: &Path) -> Option<OutputManifest> {
+        let path = output_dir.join(OUTPUT_MANIFEST_FILE);
+        let content = tokio::fs::read_to_string(&path).await.ok()?;
+        match serde_json::from_str(&content) {
+            Ok(manifest) => Some(manifest),
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Manifeste illisible, ignore");
+                None
+            }
+        }
+    }
+
+    /// Ecrit le manifeste dans `manifest.json`
+    pub async fn write(
+        &self,
+        manifest: &OutputManifest,
+        output_dir: &Path,
+    ) -> Result<PathBuf, anyhow::Error> {
+        tokio::fs::create_dir_all(output_dir).await?;
+        let path = output_dir.join(OUTPUT_MANIFEST_FILE);
+        tokio::fs::write(&path, serde_json::to_string_pretty(manifest)?).await?;
+        Ok(path)
+    }
+}
+
+impl Default for ManifestWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -2,211 +2,147 @@
 
 use std::fmt::Write;
 use std::path::{Path, PathBuf};
-
-use crate::domain::specification::Specification;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::json;
+
+use crate::adapters::output::mermaid::{domain_model_diagram, requirement_hierarchy_diagram};
+use crate::adapters::output::template_context::{label_or_dash, labelled_list, replace_field};
+use crate::adapters::templates::file_template_engine::FileTemplateEngine;
+use crate::domain::epic::CrossEpicReference;
+use crate::domain::naming::ascii_slug;
+use crate::domain::output_manifest::ArtifactKind;
+use crate::domain::specification::{FunctionalRequirement, Specification};
+use crate::domain::test_case::TestSuite;
+use crate::ports::output_writer::{OutputLayout, OutputWriter, WrittenArtifact};
+use crate::ports::template_engine::TemplateEngine;
 
 /// Ecrit une specification raffinee au format Markdown spec-kit
-pub struct MarkdownWriter;
+pub struct MarkdownWriter {
+    templates: Arc<dyn TemplateEngine>,
+}
+
+/// Template de document de la specification
+pub const SPEC_TEMPLATE: &str = "output_spec";
+
+/// Ligne de l'index des specifications par epic
+#[derive(Debug, Clone)]
+pub struct EpicIndexEntry {
+    /// Nom de l'epic
+    pub epic: String,
+    /// Nombre de user stories
+    pub story_count: usize,
+    /// IDs des exigences de l'epic
+    pub requirement_ids: Vec<String>,
+    /// Nombre de scenarios Gherkin generes
+    pub scenario_count: usize,
+    /// Lien relatif (depuis l'index) vers la specification
+    pub spec_link: String,
+    /// Lien relatif (depuis l'index) vers le dossier des features (absent
+    /// si le format `feature` n'est pas produit)
+    pub features_link: Option<String>,
+}
 
 impl MarkdownWriter {
     pub fn new() -> Self {
-        Self
+        Self::with_templates(Arc::new(FileTemplateEngine::builtin()))
     }
 
-    /// Genere le contenu Markdown de la specification
-    pub fn render_specification(&self, spec: &Specification) -> String {
-        let mut md = String::new();
-
-        // Header — ecriture en memoire (String) : ne peut pas echouer
-        _ = writeln!(md, "# Feature Specification: {}", spec.title);
-        _ = writeln!(md);
-        _ = writeln!(md, "**Created**: {}", spec.created_at.format("%Y-%m-%d"));
-        _ = writeln!(md, "**Status**: {}", spec.status);
-        _ = writeln!(md, "**Version**: {}", spec.version);
-        _ = writeln!(md, "**Tool**: spec-forge v{}", spec.tool_version);
-        if let Some(ref compliance) = spec.compliance_profile {
-            _ = writeln!(md, "**Compliance**: {:?}", compliance);
-        }
-        _ = writeln!(md);
-
-        // User Scenarios
-        _ = writeln!(md, "## User Scenarios & Testing");
-        _ = writeln!(md);
-
-        for scenario in &spec.user_scenarios {
-            _ = writeln!(
-                md,
-                "### {} - {} (Priority: {})",
-                scenario.id, scenario.title, scenario.priority
-            );
-            _ = writeln!(md);
-            _ = writeln!(md, "{}", scenario.description);
-            _ = writeln!(md);
-            _ = writeln!(md, "**Why this priority**: {}", scenario.why_priority);
-            _ = writeln!(md);
-            _ = writeln!(md, "**Independent Test**: {}", scenario.independent_test);
-            _ = writeln!(md);
-            _ = writeln!(md, "**Acceptance Scenarios**:");
-            _ = writeln!(md);
+    /// Moteur de templates configure (documents surcharges par projet)
+    pub fn with_templates(templates: Arc<dyn TemplateEngine>) -> Self {
+        Self { templates }
+    }
 
-            for (i, ac) in scenario.acceptance_scenarios.iter().enumerate() {
-                _ = writeln!(
-                    md,
-                    "{}. **Given** {}, **When** {}, **Then** {}",
-                    i + 1,
-                    ac.given,
-                    ac.when,
-                    ac.then
-                );
-            }
-            _ = writeln!(md);
-            _ = writeln!(md, "---");
-            _ = writeln!(md);
-        }
+    /// Genere le contenu Markdown de la specification (template `output_spec`)
+    pub fn render_specification(&self, spec: &Specification) -> Result<String, anyhow::Error> {
+        self.templates
+            .render(SPEC_TEMPLATE, &specification_context(spec)?)
+    }
 
-        // Edge Cases
-        if !spec.edge_cases.is_empty() {
-            _ = writeln!(md, "### Edge Cases");
-            _ = writeln!(md);
-            for ec in &spec.edge_cases {
-                _ = write!(md, "- {}", ec.description);
-                if let Some(ref related) = ec.related_scenario {
-                    _ = write!(md, " (lie a: {})", related);
-                }
-                _ = writeln!(md);
-            }
-            _ = writeln!(md);
-        }
+    /// Genere l'index Markdown des specifications par epic
+    pub fn render_epic_index(
+        &self,
+        entries: &[EpicIndexEntry],
+        cross_references: &[CrossEpicReference],
+    ) -> String {
+        let mut md = String::new();
 
-        // Functional Requirements
-        _ = writeln!(md, "## Requirements");
-        _ = writeln!(md);
-        _ = writeln!(md, "### Functional Requirements");
+        _ = writeln!(md, "# Specifications par epic");
         _ = writeln!(md);
-
         _ = writeln!(
             md,
-            "| ID | Enonce | Priorite | Categorie | Verification | Risque |"
+            "| Epic | Stories | Exigences | Scenarios | Specification | Features |"
         );
         _ = writeln!(
             md,
-            "|---|--------|----------|-----------|-------------|--------|"
+            "|------|---------|-----------|-----------|---------------|----------|"
         );
-        for fr in &spec.functional_requirements {
-            _ = writeln!(
-                md,
-                "| {} | {} | {} | {} | {} | {} |",
-                fr.id,
-                fr.statement,
-                fr.priority,
-                fr.category,
-                fr.verification_method,
-                fr.risk_level
-                    .as_ref()
-                    .map(|r| r.to_string())
-                    .unwrap_or_else(|| "-".into()),
-            );
-        }
-        _ = writeln!(md);
-
-        // FR details (rationale, source) if present
-        let has_details = spec.functional_requirements.iter().any(|fr| {
-            fr.rationale.is_some() || fr.source.is_some() || fr.quality_characteristic.is_some()
-        });
-        if has_details {
-            _ = writeln!(md, "#### Details des exigences");
-            _ = writeln!(md);
-            for fr in &spec.functional_requirements {
-                let mut details = Vec::new();
-                if let Some(ref r) = fr.rationale {
-                    details.push(format!("Justification: {}", r));
-                }
-                if let Some(ref s) = fr.source {
-                    details.push(format!("Source: {}", s));
-                }
-                if let Some(ref qc) = fr.quality_characteristic {
-                    details.push(format!("Qualite ISO 25010: {}", qc));
-                }
-                if !details.is_empty() {
-                    _ = writeln!(md, "- **{}**: {}", fr.id, details.join(" | "));
-                }
-            }
-            _ = writeln!(md);
-        }
-
-        // Key Entities
-        if !spec.key_entities.is_empty() {
-            _ = writeln!(md, "### Key Entities");
-            _ = writeln!(md);
-            for entity in &spec.key_entities {
-                _ = writeln!(md, "- **{}**: {}", entity.name, entity.description);
-                for attr in &entity.attributes {
-                    _ = writeln!(md, "  - {}", attr);
+        for entry in entries {
+            let requirements = match (entry.requirement_ids.first(), entry.requirement_ids.last()) {
+                (Some(first), Some(last)) if first != last => {
+                    format!("{}..{} ({})", first, last, entry.requirement_ids.len())
                 }
-            }
-            _ = writeln!(md);
-        }
-
-        // Success Criteria
-        _ = writeln!(md, "## Success Criteria");
-        _ = writeln!(md);
-        _ = writeln!(md, "### Measurable Outcomes");
-        _ = writeln!(md);
-
-        for sc in &spec.success_criteria {
+                (Some(first), _) => format!("{} (1)", first),
+                _ => "-".to_string(),
+            };
+            let features = entry
+                .features_link
+                .as_ref()
+                .map(|link| format!("[{}]({})", link, link));
             _ = writeln!(
                 md,
-                "- **{}**: {} (Metrique: {})",
-                sc.id, sc.description, sc.measurable_metric
+                "| {} | {} | {} | {} | [{}]({}) | {} |",
+                entry.epic,
+                entry.story_count,
+                requirements,
+                entry.scenario_count,
+                entry.spec_link,
+                entry.spec_link,
+                label_or_dash(features.as_ref()),
             );
         }
         _ = writeln!(md);
 
-        // Clarifications
-        if !spec.clarifications_needed.is_empty() {
-            _ = writeln!(md, "## Clarifications");
+        if !cross_references.is_empty() {
+            _ = writeln!(md, "## References inter-epics");
             _ = writeln!(md);
-            for cl in &spec.clarifications_needed {
-                if cl.resolved {
-                    _ = writeln!(
-                        md,
-                        "- **Q**: {} -> **A**: {}",
-                        cl.question,
-                        cl.answer.as_deref().unwrap_or("N/A")
-                    );
-                } else {
-                    _ = writeln!(md, "- **[NEEDS CLARIFICATION]**: {}", cl.question);
-                    _ = writeln!(md, "  - Contexte: {}", cl.context);
-                    _ = writeln!(md, "  - Impact: {}", cl.impact);
-                    if !cl.suggested_options.is_empty() {
-                        _ = writeln!(md, "  - Options: {}", cl.suggested_options.join(", "));
-                    }
-                }
+            for reference in cross_references {
+                let target_link = entries
+                    .iter()
+                    .find(|e| e.epic == reference.to_epic)
+                    .map(|e| e.spec_link.as_str())
+                    .unwrap_or("");
+                _ = writeln!(
+                    md,
+                    "- **{}** ({}) -> [{}]({}) ({})",
+                    reference.requirement_id,
+                    reference.from_epic,
+                    reference.target_id,
+                    target_link,
+                    reference.to_epic,
+                );
             }
             _ = writeln!(md);
         }
 
-        // Validation
-        if let Some(ref validation) = spec.validation {
-            _ = writeln!(md, "## Validation");
-            _ = writeln!(md);
-            _ = writeln!(
-                md,
-                "- Completude: {:.0}%",
-                validation.completeness_score * 100.0
-            );
-            _ = writeln!(md, "- Clarte: {:.0}%", validation.clarity_score * 100.0);
-            _ = writeln!(
-                md,
-                "- Testabilite: {:.0}%",
-                validation.testability_score * 100.0
-            );
-            _ = writeln!(md);
-        }
-
         md
     }
 
+    /// Ecrit l'index des epics (`index.md`) dans le dossier des specifications
+    pub async fn write_epic_index(
+        &self,
+        entries: &[EpicIndexEntry],
+        cross_references: &[CrossEpicReference],
+        output_dir: &Path,
+    ) -> Result<PathBuf, anyhow::Error> {
+        tokio::fs::create_dir_all(output_dir).await?;
+        let path = output_dir.join("index.md");
+        let content = self.render_epic_index(entries, cross_references);
+        tokio::fs::write(&path, &content).await?;
+        Ok(path)
+    }
+
     /// Ecrit la specification dans un fichier
     pub async fn write(
         &self,
@@ -215,24 +151,96 @@ impl MarkdownWriter {
     ) -> Result<PathBuf, anyhow::Error> {
         tokio::fs::create_dir_all(output_dir).await?;
 
-        let filename = format!(
-            "spec-{}.md",
-            spec.title
-                .to_lowercase()
-                .replace(' ', "-")
-                .chars()
-                .filter(|c| c.is_alphanumeric() || *c == '-')
-                .collect::<String>()
-        );
+        let filename = format!("spec-{}.md", ascii_slug(&spec.title, '-'));
         let path = output_dir.join(&filename);
 
-        let content = self.render_specification(spec);
+        let content = self.render_specification(spec)?;
         tokio::fs::write(&path, &content).await?;
 
         Ok(path)
     }
 }
 
+#[async_trait]
+impl OutputWriter for MarkdownWriter {
+    fn format(&self) -> &str {
+        "markdown"
+    }
+
+    async fn write_outputs(
+        &self,
+        spec: &Specification,
+        _suite: &TestSuite,
+        layout: &OutputLayout,
+    ) -> Result<Vec<WrittenArtifact>, anyhow::Error> {
+        let path = self.write(spec, &layout.specs_dir).await?;
+        Ok(vec![WrittenArtifact::for_spec(
+            path,
+            ArtifactKind::Specification,
+            spec,
+        )])
+    }
+}
+
+/// Contexte du template de specification : la spec serialisee (`spec`),
+/// enrichie des libelles d'affichage de chaque element (`labels`) et des
+/// diagrammes Mermaid (`diagrams`)
+fn specification_context(spec: &Specification) -> Result<serde_json::Value, anyhow::Error> {
+    let mut value = serde_json::to_value(spec)?;
+    replace_field(
+        &mut value,
+        "user_scenarios",
+        labelled_list(
+            &spec.user_scenarios,
+            |s| json!({ "priority": s.priority.to_string() }),
+        )?,
+    );
+    replace_field(
+        &mut value,
+        "functional_requirements",
+        labelled_list(&spec.functional_requirements, |fr| {
+            json!({
+                "priority": fr.priority.to_string(),
+                "category": fr.category.to_string(),
+                "verification_method": fr.verification_method.to_string(),
+                "risk_level": label_or_dash(fr.risk_level.as_ref()),
+                "details": requirement_details(fr),
+            })
+        })?,
+    );
+    let has_requirement_details = spec.functional_requirements.iter().any(|fr| {
+        fr.rationale.is_some() || fr.source.is_some() || fr.quality_characteristic.is_some()
+    });
+
+    Ok(json!({
+        "spec": value,
+        "labels": {
+            "status": spec.status.to_string(),
+            "compliance": spec.compliance_profile.as_ref().map(|c| format!("{:?}", c)),
+        },
+        "has_requirement_details": has_requirement_details,
+        "diagrams": {
+            "domain_model": domain_model_diagram(&spec.key_entities),
+            "requirement_hierarchy": requirement_hierarchy_diagram(spec),
+        },
+    }))
+}
+
+/// Details d'une exigence (justification, source, qualite) sur une ligne
+fn requirement_details(fr: &FunctionalRequirement) -> Option<String> {
+    let mut details = Vec::new();
+    if let Some(ref r) = fr.rationale {
+        details.push(format!("Justification: {}", r));
+    }
+    if let Some(ref s) = fr.source {
+        details.push(format!("Source: {}", s));
+    }
+    if let Some(ref qc) = fr.quality_characteristic {
+        details.push(format!("Qualite ISO 25010: {}", qc));
+    }
+    (!details.is_empty()).then(|| details.join(" | "))
+}
+
 impl Default for MarkdownWriter {
     fn default() -> Self {
         Self::new()
@@ -284,7 +292,7 @@ mod tests {
         });
 
         let writer = MarkdownWriter::new();
-        let md = writer.render_specification(&spec);
+        let md = writer.render_specification(&spec).unwrap();
 
         assert!(md.contains("# Feature Specification: Recherche Bibliotheque"));
         assert!(md.contains("US-001"));
@@ -293,6 +301,78 @@ mod tests {
         assert!(md.contains("Given"));
     }
 
+    #[test]
+    fn test_render_specification_embeds_diagrams() {
+        let mut spec = Specification::new("Comptes".into());
+        spec.key_entities.push(KeyEntity {
+            name: "Client".into(),
+            description: "Titulaire".into(),
+            attributes: vec!["nom".into()],
+            relationships: vec!["Client 1..* Compte".into()],
+        });
+        for (id, parent) in [("FR-001", None), ("FR-002", Some("FR-001"))] {
+            spec.functional_requirements.push(FunctionalRequirement {
+                id: id.into(),
+                statement: "Le systeme DOIT ouvrir un compte".into(),
+                priority: Priority::P1,
+                category: RequirementCategory::Functional,
+                testable: true,
+                rationale: None,
+                source: None,
+                verification_method: Default::default(),
+                risk_level: None,
+                parent_requirement: parent.map(String::from),
+                allocated_to: Vec::new(),
+                quality_characteristic: None,
+            });
+        }
+
+        let md = MarkdownWriter::new().render_specification(&spec).unwrap();
+        assert!(md.contains("```mermaid\nclassDiagram\n"));
+        assert!(md.contains("    client --> \"1..*\" compte\n```\n"));
+        assert!(md.contains("#### Hierarchie des exigences\n\n```mermaid\ngraph TD\n"));
+        assert!(md.contains("    fr_001 --> fr_002\n```\n"));
+    }
+
+    #[test]
+    fn test_render_epic_index() {
+        let entries = vec![
+            EpicIndexEntry {
+                epic: "Paiements".into(),
+                story_count: 3,
+                requirement_ids: vec!["FR-001".into(), "FR-002".into(), "FR-003".into()],
+                scenario_count: 7,
+                spec_link: "spec-paiements.md".into(),
+                features_link: Some("../features/paiements".into()),
+            },
+            EpicIndexEntry {
+                epic: "Cartes".into(),
+                story_count: 1,
+                requirement_ids: vec!["FR-004".into()],
+                scenario_count: 2,
+                spec_link: "spec-cartes.md".into(),
+                features_link: None,
+            },
+        ];
+        let refs = vec![CrossEpicReference {
+            from_epic: "Cartes".into(),
+            requirement_id: "FR-004".into(),
+            to_epic: "Paiements".into(),
+            target_id: "FR-001".into(),
+        }];
+
+        let md = MarkdownWriter::new().render_epic_index(&entries, &refs);
+        assert!(md.contains(
+            "| Paiements | 3 | FR-001..FR-003 (3) | 7 | [spec-paiements.md](spec-paiements.md)"
+        ));
+        assert!(md.contains("| [../features/paiements](../features/paiements) |"));
+        assert!(
+            md.contains("| Cartes | 1 | FR-004 (1) | 2 | [spec-cartes.md](spec-cartes.md) | - |")
+        );
+        assert!(md.contains("## References inter-epics"));
+        assert!(md.contains("- **FR-004** (Cartes) -> [FR-001](spec-paiements.md) (Paiements)"));
+    }
+
     #[test]
     fn test_render_specification_snapshot() {
         let mut spec = Specification::new("Recherche Bibliotheque".into());
@@ -338,7 +418,7 @@ mod tests {
         });
 
         let writer = MarkdownWriter::new();
-        let md = writer.render_specification(&spec);
+        let md = writer.render_specification(&spec).unwrap();
         insta::assert_snapshot!(md);
     }
 
@@ -369,7 +449,7 @@ mod tests {
         });
 
         let writer = MarkdownWriter::new();
-        let md = writer.render_specification(&spec);
+        let md = writer.render_specification(&spec).unwrap();
         insta::assert_snapshot!(md);
     }
 }
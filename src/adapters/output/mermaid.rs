@@ -0,0 +1,438 @@
+//! Diagrammes Mermaid des documents de sortie
+//!
+//! Modele du domaine (`classDiagram`) depuis les entites cles, arbre des
+//! exigences depuis `parent_requirement` et repartition de la couverture
+//! (`pie`) depuis le resume de tracabilite. Les diagrammes sont embarques
+//! dans les documents Markdown sous forme de blocs ```` ```mermaid ````.
+
+use std::fmt::Write;
+
+use crate::domain::naming::{ascii_slug, transliterate};
+use crate::domain::specification::{KeyEntity, Specification};
+use crate::domain::traceability::TraceabilitySummary;
+
+/// Longueur maximale de l'enonce affiche dans un noeud d'exigence
+const NODE_STATEMENT_MAX_CHARS: usize = 48;
+
+/// Relation entre deux entites, extraite d'un texte libre
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityRelation {
+    pub from: String,
+    pub to: String,
+    pub from_cardinality: Option<String>,
+    pub to_cardinality: Option<String>,
+    /// Mots restants (verbe) : `possede`
+    pub label: Option<String>,
+}
+
+/// Extrait une relation d'un texte tel que `Client 1..* Compte`,
+/// `1 -- 0..* Commande` ou `possede des Comptes`
+///
+/// L'entite source est `owner` si le texte ne nomme qu'une entite ; la
+/// cible est une entite connue (pluriel accepte) ou, a defaut, le mot en
+/// majuscule qui suit une cardinalite. Sans cible, `None`.
+pub fn parse_relationship(owner: &str, text: &str, entities: &[&str]) -> Option<EntityRelation> {
+    let tokens: Vec<&str> = text
+        .split_whitespace()
+        .map(|t| t.trim_matches(|c: char| matches!(c, ',' | ';' | ':' | '.' | '(' | ')')))
+        .filter(|t| !t.is_empty() && !matches!(*t, "--" | "-" | "->" | "-->"))
+        .collect();
+
+    // Mentions d'entites : (position, nombre de mots, nom)
+    let mut mentions: Vec<(usize, usize, &str)> = Vec::new();
+    let mut idx = 0;
+    while idx < tokens.len() {
+        match entity_at(&tokens[idx..], entities) {
+            Some((words, name)) => {
+                mentions.push((idx, words, name));
+                idx += words;
+            }
+            None => idx += 1,
+        }
+    }
+
+    let cardinalities: Vec<usize> = (0..tokens.len())
+        .filter(|&i| is_cardinality(tokens[i]) && !mentions.iter().any(|m| covers(m, i)))
+        .collect();
+
+    let explicit_source = mentions.len() >= 2 || mentions.first().is_some_and(|m| m.2 == owner);
+    let (from, target) = if mentions.len() >= 2 {
+        (mentions[0].2.to_string(), Some(mentions[1]))
+    } else if explicit_source {
+        (owner.to_string(), None)
+    } else {
+        (owner.to_string(), mentions.first().copied())
+    };
+
+    let target = match target {
+        Some((pos, words, name)) => (pos, words, name.to_string()),
+        None => {
+            // Entite inconnue : mot en majuscule apres la derniere cardinalite
+            let pos = cardinalities.last()? + 1;
+            let token = tokens.get(pos)?;
+            if !token.chars().next().is_some_and(char::is_uppercase) {
+                return None;
+            }
+            (pos, 1, (*token).to_string())
+        }
+    };
+    if target.2 == from {
+        return None;
+    }
+
+    let before_target: Vec<&str> = cardinalities
+        .iter()
+        .filter(|&&i| i < target.0)
+        .map(|&i| tokens[i])
+        .collect();
+    let (from_cardinality, to_cardinality) = match before_target.as_slice() {
+        [] => (None, None),
+        [to] => (None, Some(to.to_string())),
+        [from, .., to] => (Some(from.to_string()), Some(to.to_string())),
+    };
+
+    let label_words: Vec<&str> = tokens
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| {
+            !cardinalities.contains(i)
+                && !mentions.iter().any(|m| covers(m, *i))
+                && !(target.0..target.0 + target.1).contains(i)
+        })
+        .map(|(_, t)| *t)
+        .collect();
+
+    Some(EntityRelation {
+        from,
+        to: target.2,
+        from_cardinality,
+        to_cardinality,
+        label: (!label_words.is_empty()).then(|| label_words.join(" ")),
+    })
+}
+
+/// Entite nommee au debut de `tokens` (nom le plus long d'abord)
+fn entity_at<'a>(tokens: &[&str], entities: &[&'a str]) -> Option<(usize, &'a str)> {
+    let mut candidates: Vec<(usize, &'a str)> = entities
+        .iter()
+        .map(|name| (name.split_whitespace().count(), *name))
+        .filter(|(words, _)| *words > 0 && *words <= tokens.len())
+        .collect();
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.0));
+    candidates.into_iter().find(|(words, name)| {
+        name.split_whitespace()
+            .zip(&tokens[..*words])
+            .all(|(expected, found)| same_word(expected, found))
+    })
+}
+
+/// Mots egaux aux accents, a la casse et au pluriel (`s`, `x`) pres
+fn same_word(expected: &str, found: &str) -> bool {
+    let expected = transliterate(expected);
+    let found = transliterate(found);
+    found == expected
+        || found
+            .strip_suffix(['s', 'x'])
+            .is_some_and(|singular| singular == expected)
+}
+
+fn covers(mention: &(usize, usize, &str), idx: usize) -> bool {
+    (mention.0..mention.0 + mention.1).contains(&idx)
+}
+
+/// `1`, `*`, `n`, `0..1`, `1..*`, `0..n`
+fn is_cardinality(token: &str) -> bool {
+    let bound = |b: &str| b == "*" || b == "n" || b == "N" || b.parse::<u32>().is_ok();
+    match token.split_once("..") {
+        Some((low, high)) => bound(low) && bound(high),
+        None => bound(token),
+    }
+}
+
+/// Identifiant Mermaid d'un nom (le libelle d'origine est conserve a part)
+fn node_id(name: &str, prefix: &str) -> String {
+    let slug = ascii_slug(name, '_');
+    if slug.is_empty() || slug.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("{prefix}_{slug}")
+    } else {
+        slug
+    }
+}
+
+/// Texte sans les caracteres structurants de la syntaxe Mermaid
+fn mermaid_text(text: &str) -> String {
+    text.chars()
+        .filter(|c| !matches!(c, '"' | '{' | '}' | '<' | '>' | '#' | ';' | '`'))
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Modele du domaine : une classe par entite (attributs en membres) et
+/// une association par relation reconnue
+pub fn domain_model_diagram(entities: &[KeyEntity]) -> Option<String> {
+    if entities.is_empty() {
+        return None;
+    }
+    let names: Vec<&str> = entities.iter().map(|e| e.name.as_str()).collect();
+    let mut declared: Vec<String> = Vec::new();
+    let mut diagram = String::from("classDiagram\n");
+
+    // Ecriture en memoire (String) : ne peut pas echouer
+    let mut declare = |diagram: &mut String, name: &str| -> String {
+        let id = node_id(name, "entity");
+        if !declared.contains(&id) {
+            _ = writeln!(diagram, "    class {}[\"{}\"]", id, mermaid_text(name));
+            declared.push(id.clone());
+        }
+        id
+    };
+
+    for entity in entities {
+        let id = declare(&mut diagram, &entity.name);
+        for attribute in &entity.attributes {
+            let attribute = mermaid_text(attribute);
+            if !attribute.is_empty() {
+                _ = writeln!(diagram, "    {} : +{}", id, attribute);
+            }
+        }
+    }
+    for entity in entities {
+        for text in &entity.relationships {
+            let Some(relation) = parse_relationship(&entity.name, text, &names) else {
+                continue;
+            };
+            let from = declare(&mut diagram, &relation.from);
+            let to = declare(&mut diagram, &relation.to);
+            let cardinality = |c: &Option<String>| {
+                c.as_ref()
+                    .map(|c| format!(" \"{}\"", mermaid_text(c)))
+                    .unwrap_or_default()
+            };
+            _ = write!(
+                diagram,
+                "    {}{} -->{} {}",
+                from,
+                cardinality(&relation.from_cardinality),
+                cardinality(&relation.to_cardinality),
+                to
+            );
+            match relation.label {
+                Some(label) => _ = writeln!(diagram, " : {}", mermaid_text(&label)),
+                None => _ = writeln!(diagram),
+            }
+        }
+    }
+    Some(diagram)
+}
+
+/// Arbre des exigences derivees (`parent_requirement`) ; `None` si aucune
+/// exigence n'a de parent
+pub fn requirement_hierarchy_diagram(spec: &Specification) -> Option<String> {
+    let links: Vec<(&str, &str)> = spec
+        .functional_requirements
+        .iter()
+        .filter_map(|fr| Some((fr.parent_requirement.as_deref()?, fr.id.as_str())))
+        .collect();
+    if links.is_empty() {
+        return None;
+    }
+
+    let mut diagram = String::from("graph TD\n");
+    // Noeuds dans l'ordre de la spec (parents inconnus en fin de liste)
+    let mut nodes: Vec<&str> = spec
+        .functional_requirements
+        .iter()
+        .map(|fr| fr.id.as_str())
+        .filter(|id| {
+            links
+                .iter()
+                .any(|(parent, child)| parent == id || child == id)
+        })
+        .collect();
+    for (parent, _) in &links {
+        if !nodes.contains(parent) {
+            nodes.push(parent);
+        }
+    }
+    // Ecriture en memoire (String) : ne peut pas echouer
+    for id in &nodes {
+        let label = match spec.functional_requirements.iter().find(|fr| fr.id == *id) {
+            Some(fr) => format!("{}: {}", id, truncate(&mermaid_text(&fr.statement))),
+            None => format!("{} (inconnue)", id),
+        };
+        _ = writeln!(
+            diagram,
+            "    {}[\"{}\"]",
+            node_id(id, "req"),
+            mermaid_text(&label)
+        );
+    }
+    for (parent, child) in &links {
+        _ = writeln!(
+            diagram,
+            "    {} --> {}",
+            node_id(parent, "req"),
+            node_id(child, "req")
+        );
+    }
+    Some(diagram)
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= NODE_STATEMENT_MAX_CHARS {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(NODE_STATEMENT_MAX_CHARS).collect();
+    format!("{}...", cut.trim_end())
+}
+
+/// Repartition des exigences par statut de couverture (`pie`)
+pub fn coverage_pie_diagram(summary: &TraceabilitySummary) -> Option<String> {
+    if summary.total_requirements == 0 {
+        return None;
+    }
+    let mut diagram = String::from("pie title Couverture des exigences\n");
+    for (label, count) in [
+        ("Couvertes", summary.covered),
+        ("Partielles", summary.partially_covered),
+        ("Non couvertes", summary.not_covered),
+        ("Autre verification", summary.verified_other),
+    ] {
+        if count > 0 {
+            // Ecriture en memoire (String) : ne peut pas echouer
+            _ = writeln!(diagram, "    \"{}\" : {}", label, count);
+        }
+    }
+    Some(diagram)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::specification::{FunctionalRequirement, RequirementCategory};
+    use crate::domain::user_story::Priority;
+    use pretty_assertions::assert_eq;
+
+    fn entity(name: &str, attributes: &[&str], relationships: &[&str]) -> KeyEntity {
+        KeyEntity {
+            name: name.into(),
+            description: String::new(),
+            attributes: attributes.iter().map(|a| a.to_string()).collect(),
+            relationships: relationships.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_relationship_forms() {
+        let entities = ["Client", "Compte", "Carte bancaire"];
+        assert_eq!(
+            parse_relationship("Client", "Client 1..* Compte", &entities),
+            Some(EntityRelation {
+                from: "Client".into(),
+                to: "Compte".into(),
+                from_cardinality: None,
+                to_cardinality: Some("1..*".into()),
+                label: None,
+            })
+        );
+
+        let relation =
+            parse_relationship("Compte", "1 -- 0..* Cartes bancaires (emises)", &entities).unwrap();
+        assert_eq!(relation.from, "Compte");
+        assert_eq!(relation.to, "Carte bancaire");
+        assert_eq!(relation.from_cardinality.as_deref(), Some("1"));
+        assert_eq!(relation.to_cardinality.as_deref(), Some("0..*"));
+        assert_eq!(relation.label.as_deref(), Some("emises"));
+
+        let relation = parse_relationship("Client", "possede des Comptes", &entities).unwrap();
+        assert_eq!(relation.to, "Compte");
+        assert_eq!(relation.label.as_deref(), Some("possede des"));
+
+        // Entite non declaree : mot en majuscule apres la cardinalite
+        let relation = parse_relationship("Compte", "0..* Operation", &entities).unwrap();
+        assert_eq!(relation.to, "Operation");
+
+        assert_eq!(
+            parse_relationship("Client", "est identifie par son email", &entities),
+            None
+        );
+    }
+
+    #[test]
+    fn test_domain_model_diagram() {
+        let entities = vec![
+            entity(
+                "Client",
+                &["nom", "email {unique}"],
+                &["Client 1..* Compte"],
+            ),
+            entity("Compte", &["solde"], &["appartient a un Client"]),
+        ];
+        assert_eq!(
+            domain_model_diagram(&entities).unwrap(),
+            "classDiagram\n\
+             \x20   class client[\"Client\"]\n\
+             \x20   client : +nom\n\
+             \x20   client : +email unique\n\
+             \x20   class compte[\"Compte\"]\n\
+             \x20   compte : +solde\n\
+             \x20   client --> \"1..*\" compte\n\
+             \x20   compte --> client : appartient a un\n"
+        );
+        assert_eq!(domain_model_diagram(&[]), None);
+    }
+
+    #[test]
+    fn test_requirement_hierarchy_diagram() {
+        let mut spec = Specification::new("Paiements".into());
+        for (id, parent) in [
+            ("FR-001", None),
+            ("FR-002", Some("FR-001")),
+            ("FR-003", None),
+        ] {
+            spec.functional_requirements.push(FunctionalRequirement {
+                id: id.into(),
+                statement: format!("Le systeme DOIT traiter le cas {id}"),
+                priority: Priority::P1,
+                category: RequirementCategory::Functional,
+                testable: true,
+                rationale: None,
+                source: None,
+                verification_method: Default::default(),
+                risk_level: None,
+                parent_requirement: parent.map(String::from),
+                allocated_to: Vec::new(),
+                quality_characteristic: None,
+            });
+        }
+        assert_eq!(
+            requirement_hierarchy_diagram(&spec).unwrap(),
+            "graph TD\n\
+             \x20   fr_001[\"FR-001: Le systeme DOIT traiter le cas FR-001\"]\n\
+             \x20   fr_002[\"FR-002: Le systeme DOIT traiter le cas FR-002\"]\n\
+             \x20   fr_001 --> fr_002\n"
+        );
+
+        spec.functional_requirements[1].parent_requirement = None;
+        assert_eq!(requirement_hierarchy_diagram(&spec), None);
+    }
+
+    #[test]
+    fn test_coverage_pie_skips_empty_slices() {
+        let summary = TraceabilitySummary {
+            total_requirements: 4,
+            covered: 3,
+            partially_covered: 0,
+            not_covered: 1,
+            verified_other: 0,
+            forward_coverage_pct: 75.0,
+            orphan_tests: Vec::new(),
+        };
+        assert_eq!(
+            coverage_pie_diagram(&summary).unwrap(),
+            "pie title Couverture des exigences\n    \"Couvertes\" : 3\n    \"Non couvertes\" : 1\n"
+        );
+    }
+}
@@ -1,3 +1,13 @@
+pub mod docx_spec_writer;
 pub mod gherkin_writer;
+pub mod html_report_writer;
+pub mod impact_writer;
+pub mod json_writer;
+pub mod manifest_writer;
 pub mod markdown_writer;
+pub mod mermaid;
+pub mod step_definitions_writer;
+pub mod step_glossary_writer;
+pub mod template_context;
+pub mod test_export_writer;
 pub mod traceability_writer;
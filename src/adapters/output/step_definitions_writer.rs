@@ -0,0 +1,568 @@
+//! Adapter StepDefinitionsWriter - Ecrit les squelettes de step definitions
+//!
+//! Un fichier par groupe (feature ou entite) pour cucumber-rs, Cucumber-JVM,
+//! cucumber-js ou behave. La regeneration est idempotente : un fichier
+//! existant est conserve tel quel et seuls les steps absents de tous les
+//! fichiers de steps du dossier y sont ajoutes.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use crate::domain::step_definitions::{
+    ExpressionPart, ParameterType, StepDefinition, StepTarget, argument_name,
+};
+use crate::domain::test_case::StepKeyword;
+
+/// Nombre maximal de mots repris du texte dans un nom de fonction
+const MAX_NAME_WORDS: usize = 8;
+
+/// Bilan d'ecriture d'un fichier de steps
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepFileOutcome {
+    pub path: PathBuf,
+    pub group: String,
+    /// Stubs ajoutes lors de cette generation
+    pub added: usize,
+    /// Steps deja presents dans le fichier, laisses intacts
+    pub preserved: usize,
+}
+
+/// Ecrit des fichiers de step definitions
+pub struct StepDefinitionsWriter {
+    target: StepTarget,
+}
+
+impl StepDefinitionsWriter {
+    pub fn new(target: StepTarget) -> Self {
+        Self { target }
+    }
+
+    /// Nom du fichier de steps d'un groupe
+    pub fn file_name(&self, group: &str) -> String {
+        let slug = argument_name(group);
+        match self.target {
+            StepTarget::Rust => format!("{}_steps.rs", slug),
+            StepTarget::Java => format!("{}.java", self.class_name(group)),
+            StepTarget::TypeScript => format!("{}.steps.ts", slug),
+            StepTarget::Python => format!("{}_steps.py", slug),
+        }
+    }
+
+    /// Fin de nom commune aux fichiers de steps de la cible
+    fn file_suffix(&self) -> &'static str {
+        match self.target {
+            StepTarget::Rust => "_steps.rs",
+            StepTarget::Java => "Steps.java",
+            StepTarget::TypeScript => ".steps.ts",
+            StepTarget::Python => "_steps.py",
+        }
+    }
+
+    fn class_name(&self, group: &str) -> String {
+        let mut name = pascal_case(&argument_name(group));
+        if name.starts_with(|c: char| c.is_ascii_digit()) {
+            name.insert(0, 'S');
+        }
+        format!("{}Steps", name)
+    }
+
+    /// Motif d'appariement : Cucumber Expression, ou motif `parse` pour behave
+    pub fn pattern(&self, definition: &StepDefinition) -> String {
+        if self.target != StepTarget::Python {
+            return definition.cucumber_expression();
+        }
+        definition
+            .parts
+            .iter()
+            .map(|part| match part {
+                ExpressionPart::Text(text) => text.replace('{', "{{").replace('}', "}}"),
+                ExpressionPart::Parameter(i) => {
+                    let parameter = &definition.parameters[*i];
+                    match parameter.kind {
+                        ParameterType::Int => format!("{{{}:d}}", parameter.name),
+                        ParameterType::Float => format!("{{{}:f}}", parameter.name),
+                        ParameterType::String => format!("\"{{{}}}\"", parameter.name),
+                        ParameterType::Word => format!("{{{}:w}}", parameter.name),
+                        ParameterType::Anonymous => format!("{{{}}}", parameter.name),
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Step deja defini dans un contenu, sous n'importe quel mot-cle
+    /// (Cucumber apparie l'expression seule)
+    fn is_defined_in(&self, definition: &StepDefinition, content: &str) -> bool {
+        [StepKeyword::Given, StepKeyword::When, StepKeyword::Then]
+            .into_iter()
+            .any(|keyword| content.contains(&self.signature(keyword, definition)))
+    }
+
+    /// Debut d'annotation identifiant un step, recherche dans les fichiers existants
+    fn signature(&self, keyword: StepKeyword, definition: &StepDefinition) -> String {
+        let pattern = string_literal(&self.pattern(definition));
+        let keyword = keyword_name(keyword);
+        match self.target {
+            StepTarget::Rust => format!("{}(expr = {})", keyword.to_lowercase(), pattern),
+            StepTarget::Java => format!("@{}({})", keyword, pattern),
+            StepTarget::TypeScript => format!("{}({},", keyword, pattern),
+            StepTarget::Python => format!("@{}({})", keyword.to_lowercase(), pattern),
+        }
+    }
+
+    /// Genere le contenu d'un fichier de steps. Avec `existing`, le contenu
+    /// existant est conserve et seuls les steps absents sont ajoutes.
+    /// Retourne le contenu et le nombre de stubs ajoutes.
+    pub fn render_file(
+        &self,
+        group: &str,
+        definitions: &[&StepDefinition],
+        existing: Option<&str>,
+    ) -> (String, usize) {
+        let current = existing.unwrap_or_default();
+        let mut used_names: Vec<String> = Vec::new();
+        let mut stubs = Vec::new();
+        for definition in definitions {
+            if self.is_defined_in(definition, current) {
+                continue;
+            }
+            let mut name = self.function_name(definition);
+            let base = name.clone();
+            let mut n = 2;
+            while used_names.contains(&name) || current.contains(&format!("{}(", name)) {
+                name = format!("{}_{}", base, n);
+                n += 1;
+            }
+            stubs.push(self.render_stub(definition, &name));
+            used_names.push(name);
+        }
+        let added = stubs.len();
+
+        let content = match existing {
+            None => {
+                let mut content = self.render_header(group);
+                for stub in &stubs {
+                    content.push('\n');
+                    content.push_str(stub);
+                }
+                if self.target == StepTarget::Java {
+                    content.push_str("}\n");
+                }
+                content
+            }
+            Some(existing) if stubs.is_empty() => existing.to_string(),
+            Some(existing) => {
+                let appended: String = stubs.iter().map(|stub| format!("\n{}", stub)).collect();
+                match (self.target, existing.rfind('}')) {
+                    // Java : les methodes restent dans la classe
+                    (StepTarget::Java, Some(end)) => {
+                        format!("{}{}{}", &existing[..end], appended, &existing[end..])
+                    }
+                    _ => {
+                        let mut content = existing.to_string();
+                        if !content.is_empty() && !content.ends_with('\n') {
+                            content.push('\n');
+                        }
+                        content.push_str(&appended);
+                        content
+                    }
+                }
+            }
+        };
+        (content, added)
+    }
+
+    fn render_header(&self, group: &str) -> String {
+        let mut content = String::new();
+        // Ecriture en memoire (String) : ne peut pas echouer
+        match self.target {
+            StepTarget::Rust => {
+                _ = writeln!(content, "//! Step definitions : {}", group);
+                _ = writeln!(content, "//!");
+                _ = writeln!(
+                    content,
+                    "//! Squelettes generes par spec-forge (cucumber-rs). Les steps deja"
+                );
+                _ = writeln!(content, "//! presents sont conserves a la regeneration.");
+                _ = writeln!(content);
+                _ = writeln!(content, "use super::World;");
+            }
+            StepTarget::Java => {
+                _ = writeln!(content, "// Step definitions : {}", group);
+                _ = writeln!(
+                    content,
+                    "// Squelettes generes par spec-forge (Cucumber-JVM). Les steps deja"
+                );
+                _ = writeln!(content, "// presents sont conserves a la regeneration.");
+                _ = writeln!(content);
+                _ = writeln!(content, "import io.cucumber.java.PendingException;");
+                _ = writeln!(content, "import io.cucumber.java.en.Given;");
+                _ = writeln!(content, "import io.cucumber.java.en.Then;");
+                _ = writeln!(content, "import io.cucumber.java.en.When;");
+                _ = writeln!(content);
+                _ = writeln!(content, "public class {} {{", self.class_name(group));
+            }
+            StepTarget::TypeScript => {
+                _ = writeln!(content, "// Step definitions : {}", group);
+                _ = writeln!(
+                    content,
+                    "// Squelettes generes par spec-forge (cucumber-js). Les steps deja"
+                );
+                _ = writeln!(content, "// presents sont conserves a la regeneration.");
+                _ = writeln!(content);
+                _ = writeln!(
+                    content,
+                    "import {{ Given, When, Then }} from '@cucumber/cucumber';"
+                );
+            }
+            StepTarget::Python => {
+                _ = writeln!(content, "# Step definitions : {}", group);
+                _ = writeln!(
+                    content,
+                    "# Squelettes generes par spec-forge (behave). Les steps deja"
+                );
+                _ = writeln!(content, "# presents sont conserves a la regeneration.");
+                _ = writeln!(content);
+                _ = writeln!(content, "from behave import given, when, then");
+            }
+        }
+        content
+    }
+
+    fn render_stub(&self, definition: &StepDefinition, name: &str) -> String {
+        let mut stub = String::new();
+        let pattern = string_literal(&self.pattern(definition));
+        let keyword = keyword_name(definition.keyword);
+        let sample = definition.sample.replace('\n', " ");
+        let parameters = &definition.parameters;
+        match self.target {
+            StepTarget::Rust => {
+                let mut args = vec!["world: &mut World".to_string()];
+                args.extend(parameters.iter().map(|p| {
+                    let kind = match p.kind {
+                        ParameterType::Int => "i64",
+                        ParameterType::Float => "f64",
+                        _ => "String",
+                    };
+                    format!("{}: {}", p.name, kind)
+                }));
+                _ = writeln!(
+                    stub,
+                    "#[cucumber::{}(expr = {})]",
+                    keyword.to_lowercase(),
+                    pattern
+                );
+                _ = writeln!(stub, "async fn {}({}) {{", name, args.join(", "));
+                _ = writeln!(stub, "    // Etape : {}", sample);
+                _ = writeln!(stub, "    let _ = world;");
+                _ = writeln!(stub, "    todo!(\"step non implemente\")");
+                _ = writeln!(stub, "}}");
+            }
+            StepTarget::Java => {
+                let args: Vec<String> = parameters
+                    .iter()
+                    .map(|p| {
+                        let kind = match p.kind {
+                            ParameterType::Int => "int",
+                            ParameterType::Float => "float",
+                            _ => "String",
+                        };
+                        format!("{} {}", kind, camel_case(&p.name))
+                    })
+                    .collect();
+                _ = writeln!(stub, "    @{}({})", keyword, pattern);
+                _ = writeln!(stub, "    public void {}({}) {{", name, args.join(", "));
+                _ = writeln!(stub, "        // Etape : {}", sample);
+                _ = writeln!(stub, "        throw new PendingException();");
+                _ = writeln!(stub, "    }}");
+            }
+            StepTarget::TypeScript => {
+                let args: Vec<String> = parameters
+                    .iter()
+                    .map(|p| {
+                        let kind = match p.kind {
+                            ParameterType::Int | ParameterType::Float => "number",
+                            _ => "string",
+                        };
+                        format!("{}: {}", camel_case(&p.name), kind)
+                    })
+                    .collect();
+                _ = writeln!(
+                    stub,
+                    "{}({}, async function {}({}) {{",
+                    keyword,
+                    pattern,
+                    name,
+                    args.join(", ")
+                );
+                _ = writeln!(stub, "  // Etape : {}", sample);
+                _ = writeln!(stub, "  return 'pending';");
+                _ = writeln!(stub, "}});");
+            }
+            StepTarget::Python => {
+                let mut args = vec!["context".to_string()];
+                args.extend(parameters.iter().map(|p| p.name.clone()));
+                _ = writeln!(stub);
+                _ = writeln!(stub, "@{}({})", keyword.to_lowercase(), pattern);
+                _ = writeln!(stub, "def {}({}):", name, args.join(", "));
+                _ = writeln!(stub, "    # Etape : {}", sample);
+                _ = writeln!(
+                    stub,
+                    "    raise NotImplementedError({})",
+                    string_literal(&format!("STEP: {} {}", keyword, sample))
+                );
+            }
+        }
+        stub
+    }
+
+    /// Nom de fonction derive du mot-cle et du texte litteral du step
+    fn function_name(&self, definition: &StepDefinition) -> String {
+        let text: String = definition
+            .parts
+            .iter()
+            .filter_map(|part| match part {
+                ExpressionPart::Text(text) => Some(text.as_str()),
+                ExpressionPart::Parameter(_) => None,
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let words: Vec<String> = argument_name(&text)
+            .split('_')
+            .filter(|w| !w.is_empty() && *w != "arg")
+            .take(MAX_NAME_WORDS)
+            .map(str::to_string)
+            .collect();
+        let snake = std::iter::once(keyword_name(definition.keyword).to_lowercase())
+            .chain(words)
+            .collect::<Vec<_>>()
+            .join("_");
+        match self.target {
+            StepTarget::Rust | StepTarget::Python => snake,
+            StepTarget::Java | StepTarget::TypeScript => camel_case(&snake),
+        }
+    }
+
+    /// Ecrit un fichier de steps par groupe, en conservant les steps deja
+    /// presents dans les fichiers existants
+    pub async fn write(
+        &self,
+        definitions: &[StepDefinition],
+        output_dir: &Path,
+    ) -> Result<Vec<StepFileOutcome>, anyhow::Error> {
+        tokio::fs::create_dir_all(output_dir).await?;
+
+        let mut groups: Vec<(&str, Vec<&StepDefinition>)> = Vec::new();
+        for definition in definitions {
+            match groups
+                .iter_mut()
+                .find(|(group, _)| *group == definition.group)
+            {
+                Some((_, members)) => members.push(definition),
+                None => groups.push((&definition.group, vec![definition])),
+            }
+        }
+
+        // Fichiers de steps deja presents : un step defini dans l'un d'eux
+        // (groupe renomme, step deplace a la main) n'est pas regenere ailleurs
+        let mut existing_files: Vec<(PathBuf, String)> = Vec::new();
+        let mut entries = tokio::fs::read_dir(output_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let is_step_file = path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().ends_with(self.file_suffix()));
+            if is_step_file && entry.file_type().await?.is_file() {
+                let content = tokio::fs::read_to_string(&path).await?;
+                existing_files.push((path, content));
+            }
+        }
+
+        let mut outcomes = Vec::new();
+        for (group, members) in groups {
+            let path = output_dir.join(self.file_name(group));
+            let existing = existing_files
+                .iter()
+                .find(|(file, _)| *file == path)
+                .map(|(_, content)| content.clone());
+            let total = members.len();
+            let members: Vec<&StepDefinition> = members
+                .into_iter()
+                .filter(|definition| {
+                    !existing_files.iter().any(|(file, content)| {
+                        *file != path && self.is_defined_in(definition, content)
+                    })
+                })
+                .collect();
+            if existing.is_none() && members.is_empty() {
+                continue;
+            }
+            let (content, added) = self.render_file(group, &members, existing.as_deref());
+            if existing.as_deref() != Some(content.as_str()) {
+                tokio::fs::write(&path, &content).await?;
+            }
+            outcomes.push(StepFileOutcome {
+                path,
+                group: group.to_string(),
+                added,
+                preserved: total - added,
+            });
+        }
+        Ok(outcomes)
+    }
+}
+
+fn keyword_name(keyword: StepKeyword) -> &'static str {
+    match keyword {
+        StepKeyword::Given => "Given",
+        StepKeyword::When => "When",
+        StepKeyword::Then => "Then",
+        // Resolus sur l'etape precedente lors de la collecte
+        StepKeyword::And | StepKeyword::But => "Given",
+    }
+}
+
+/// Litteral chaine entre guillemets doubles (syntaxe commune aux 4 langages)
+fn string_literal(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn pascal_case(snake: &str) -> String {
+    snake
+        .split('_')
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn camel_case(snake: &str) -> String {
+    let pascal = pascal_case(snake);
+    let mut chars = pascal.chars();
+    chars
+        .next()
+        .map(|c| c.to_ascii_lowercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::step_definitions::{COMMON_GROUP, parse_step_text};
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    fn make_definition(keyword: StepKeyword, text: &str) -> StepDefinition {
+        let types: HashMap<String, ParameterType> = [("devise".to_string(), ParameterType::Word)]
+            .into_iter()
+            .collect();
+        let (parts, parameters) = parse_step_text(text, &types);
+        StepDefinition {
+            keyword,
+            parts,
+            parameters,
+            sample: text.into(),
+            group: COMMON_GROUP.into(),
+        }
+    }
+
+    fn make_definitions() -> Vec<StepDefinition> {
+        vec![
+            make_definition(StepKeyword::Given, "l'ouvrage \"Le Petit Prince\" existe"),
+            make_definition(StepKeyword::When, "je vire 10 <devise>"),
+        ]
+    }
+
+    #[test]
+    fn test_render_rust_stubs() {
+        let definitions = make_definitions();
+        let refs: Vec<&StepDefinition> = definitions.iter().collect();
+        let (content, added) =
+            StepDefinitionsWriter::new(StepTarget::Rust).render_file("Virement", &refs, None);
+        assert_eq!(added, 2);
+        assert!(content.contains("use super::World;"));
+        assert!(content.contains("#[cucumber::given(expr = \"l'ouvrage {string} existe\")]"));
+        assert!(
+            content.contains("async fn when_je_vire(world: &mut World, arg1: i64, devise: String)")
+        );
+    }
+
+    #[test]
+    fn test_render_java_and_typescript_stubs() {
+        let definitions = make_definitions();
+        let refs: Vec<&StepDefinition> = definitions.iter().collect();
+
+        let java = StepDefinitionsWriter::new(StepTarget::Java);
+        assert_eq!(
+            java.file_name("Gestion du compte"),
+            "GestionDuCompteSteps.java"
+        );
+        let (content, _) = java.render_file("Gestion du compte", &refs, None);
+        assert!(content.contains("public class GestionDuCompteSteps {"));
+        assert!(content.contains("    @When(\"je vire {int} {word}\")"));
+        assert!(content.contains("public void whenJeVire(int arg1, String devise)"));
+        assert!(content.trim_end().ends_with('}'));
+
+        let (content, _) =
+            StepDefinitionsWriter::new(StepTarget::TypeScript).render_file("x", &refs, None);
+        assert!(content.contains(
+            "When(\"je vire {int} {word}\", async function whenJeVire(arg1: number, devise: string) {"
+        ));
+    }
+
+    #[test]
+    fn test_render_python_parse_pattern() {
+        let definition = make_definition(StepKeyword::Then, "le solde vaut 1,5 <devise> (\"net\")");
+        let writer = StepDefinitionsWriter::new(StepTarget::Python);
+        assert_eq!(
+            writer.pattern(&definition),
+            "le solde vaut {arg1:f} {devise:w} (\"{arg3}\")"
+        );
+        let (content, _) = writer.render_file("x", &[&definition], None);
+        assert!(content.contains("@then(\"le solde vaut {arg1:f} {devise:w} (\\\"{arg3}\\\")\")"));
+        assert!(content.contains("def then_le_solde_vaut(context, arg1, devise, arg3):"));
+    }
+
+    #[test]
+    fn test_regeneration_preserves_implemented_steps() {
+        let writer = StepDefinitionsWriter::new(StepTarget::Java);
+        let definitions = make_definitions();
+        let first: Vec<&StepDefinition> = definitions.iter().take(1).collect();
+        let (content, _) = writer.render_file("Virement", &first, None);
+        let implemented = content.replace("throw new PendingException();", "catalog.open();");
+
+        let all: Vec<&StepDefinition> = definitions.iter().collect();
+        let (merged, added) = writer.render_file("Virement", &all, Some(&implemented));
+        assert_eq!(added, 1);
+        assert!(merged.contains("catalog.open();"));
+        assert!(merged.contains("public void whenJeVire("));
+        assert!(merged.trim_end().ends_with('}'));
+        assert_eq!(merged.matches("public class").count(), 1);
+
+        let (again, added) = writer.render_file("Virement", &all, Some(&merged));
+        assert_eq!(added, 0);
+        assert_eq!(again, merged);
+    }
+
+    #[tokio::test]
+    async fn test_regeneration_checks_all_step_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let writer = StepDefinitionsWriter::new(StepTarget::Rust);
+        let mut definitions = make_definitions();
+        writer.write(&definitions, dir.path()).await.unwrap();
+
+        // Meme step regroupe ailleurs, et sous un autre mot-cle
+        definitions[0].group = "Catalogue".into();
+        definitions[1].keyword = StepKeyword::Given;
+        let outcomes = writer.write(&definitions, dir.path()).await.unwrap();
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].added, 0);
+        assert_eq!(outcomes[0].preserved, 1);
+        assert!(!dir.path().join(writer.file_name("Catalogue")).exists());
+    }
+}
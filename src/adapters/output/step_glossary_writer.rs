@@ -0,0 +1,126 @@
+//! Adapter StepGlossaryWriter - Ecrit le glossaire des etapes Gherkin
+//!
+//! Liste les formulations canoniques retenues par la normalisation du
+//! vocabulaire et les variantes qu'elles remplacent.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use crate::domain::step_vocabulary::StepGlossary;
+
+/// Ecrit le glossaire des etapes en Markdown
+pub struct StepGlossaryWriter;
+
+impl StepGlossaryWriter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Genere le glossaire en Markdown
+    pub fn render(&self, glossary: &StepGlossary) -> String {
+        let mut md = String::new();
+
+        // Ecriture en memoire (String) : ne peut pas echouer
+        _ = writeln!(md, "# Glossaire des etapes");
+        _ = writeln!(md);
+        _ = writeln!(
+            md,
+            "- Formulations distinctes: {} -> {}",
+            glossary.distinct_before,
+            glossary.entries.len()
+        );
+        _ = writeln!(
+            md,
+            "- Etapes reecrites vers leur formulation canonique: {}",
+            glossary.rewritten_steps
+        );
+        _ = writeln!(md);
+
+        let merged: Vec<_> = glossary.merged_entries().collect();
+        if !merged.is_empty() {
+            _ = writeln!(md, "## Formulations fusionnees");
+            _ = writeln!(md);
+            for entry in merged {
+                _ = writeln!(md, "### {:?} {}", entry.keyword, entry.canonical);
+                _ = writeln!(md);
+                _ = writeln!(md, "| Variante | Occurrences | Similarite |");
+                _ = writeln!(md, "|----------|-------------|------------|");
+                for variant in &entry.variants {
+                    _ = writeln!(
+                        md,
+                        "| {} | {} | {:.0}% |",
+                        variant.text.replace('|', "\\|"),
+                        variant.occurrences,
+                        variant.similarity * 100.0
+                    );
+                }
+                _ = writeln!(md);
+            }
+        }
+
+        _ = writeln!(md, "## Vocabulaire canonique");
+        _ = writeln!(md);
+        _ = writeln!(md, "| Mot-cle | Expression | Occurrences |");
+        _ = writeln!(md, "|---------|------------|-------------|");
+        for entry in &glossary.entries {
+            _ = writeln!(
+                md,
+                "| {:?} | `{}` | {} |",
+                entry.keyword,
+                entry.expression.replace('|', "\\|"),
+                entry.total_occurrences()
+            );
+        }
+
+        md
+    }
+
+    /// Ecrit le glossaire dans un fichier
+    pub async fn write(
+        &self,
+        glossary: &StepGlossary,
+        output_dir: &Path,
+    ) -> Result<PathBuf, anyhow::Error> {
+        tokio::fs::create_dir_all(output_dir).await?;
+        let path = output_dir.join("step_glossary.md");
+        tokio::fs::write(&path, self.render(glossary)).await?;
+        Ok(path)
+    }
+}
+
+impl Default for StepGlossaryWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::step_vocabulary::{GlossaryEntry, StepVariant};
+    use crate::domain::test_case::StepKeyword;
+
+    #[test]
+    fn test_render_glossary() {
+        let glossary = StepGlossary {
+            entries: vec![GlossaryEntry {
+                keyword: StepKeyword::Given,
+                canonical: "l'utilisateur est connecte".into(),
+                expression: "l'utilisateur est connecte".into(),
+                occurrences: 3,
+                variants: vec![StepVariant {
+                    text: "l'utilisateur s'est connecte".into(),
+                    occurrences: 1,
+                    similarity: 0.85,
+                }],
+            }],
+            rewritten_steps: 1,
+            distinct_before: 2,
+        };
+        let md = StepGlossaryWriter::new().render(&glossary);
+        assert!(md.contains("- Formulations distinctes: 2 -> 1"));
+        assert!(md.contains("### Given l'utilisateur est connecte"));
+        assert!(md.contains("| l'utilisateur s'est connecte | 1 | 85% |"));
+        assert!(md.contains("| Given | `l'utilisateur est connecte` | 4 |"));
+    }
+}
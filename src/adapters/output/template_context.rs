@@ -0,0 +1,42 @@
+//! Contexte des templates de documents de sortie
+//!
+//! Les types du domaine sont exposes tels que serialises ; chaque element
+//! recoit en plus un objet `labels` portant ses libelles d'affichage (les
+//! formes serde des enums, `not_covered`, `must`..., ne sont pas celles des
+//! rapports).
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Serialise `item` et lui ajoute ses libelles sous la cle `labels`
+pub fn labelled<T: Serialize>(item: &T, labels: Value) -> Result<Value, anyhow::Error> {
+    let mut value = serde_json::to_value(item)?;
+    if let Value::Object(ref mut map) = value {
+        map.insert("labels".into(), labels);
+    }
+    Ok(value)
+}
+
+/// Serialise une liste en ajoutant les libelles de chaque element
+pub fn labelled_list<T: Serialize>(
+    items: &[T],
+    labels: impl Fn(&T) -> Value,
+) -> Result<Value, anyhow::Error> {
+    items
+        .iter()
+        .map(|item| labelled(item, labels(item)))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Value::Array)
+}
+
+/// Remplace le champ `key` d'un objet serialise
+pub fn replace_field(value: &mut Value, key: &str, field: Value) {
+    if let Value::Object(map) = value {
+        map.insert(key.into(), field);
+    }
+}
+
+/// Libelle optionnel : `-` en l'absence de valeur
+pub fn label_or_dash<T: ToString>(value: Option<&T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "-".into())
+}
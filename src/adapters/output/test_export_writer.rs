@@ -0,0 +1,402 @@
+//! Adapter TestExportWriter - Exporte les cas de test vers les outils de gestion
+//!
+//! - Xray : JSON d'import en masse (tests Cucumber), cles d'exigence `FR-xxx`,
+//!   projet Jira cible (`output.xray_project_key`)
+//! - TestRail : CSV, une section par feature, preconditions / etapes / resultats
+//! - JUnit : squelette XML (un testcase par scenario, non execute) pour les tableaux de bord
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use quick_xml::escape::escape;
+
+use crate::domain::output_manifest::ArtifactKind;
+use crate::domain::specification::Specification;
+use crate::domain::test_case::{Feature, Scenario, Step, StepKeyword, TestExportFormat, TestSuite};
+use crate::domain::user_story::Language;
+use crate::ports::output_writer::{OutputLayout, OutputWriter, WrittenArtifact};
+
+/// Exporte une TestSuite dans un format d'outil de gestion de tests
+pub struct TestExportWriter {
+    format: TestExportFormat,
+    language: Language,
+    /// Cle du projet Jira des tests Xray (`fields.project.key`)
+    project_key: Option<String>,
+}
+
+impl TestExportWriter {
+    pub fn new(format: TestExportFormat, language: Language) -> Self {
+        Self {
+            format,
+            language,
+            project_key: None,
+        }
+    }
+
+    /// Projet Jira dans lequel Xray cree les tests importes
+    pub fn with_project_key(mut self, project_key: Option<String>) -> Self {
+        self.project_key = project_key;
+        self
+    }
+
+    /// Genere le contenu de l'export
+    pub fn render(&self, suite: &TestSuite) -> Result<String, anyhow::Error> {
+        match self.format {
+            TestExportFormat::Xray => self.render_xray(suite),
+            TestExportFormat::TestRail => self.render_testrail(suite),
+            TestExportFormat::JUnit => Ok(self.render_junit(suite)),
+        }
+    }
+
+    /// Tests Cucumber au format d'import en masse Xray
+    fn render_xray(&self, suite: &TestSuite) -> Result<String, anyhow::Error> {
+        let tests: Vec<serde_json::Value> = suite
+            .features
+            .iter()
+            .flat_map(|feature| {
+                feature.scenarios.iter().map(move |scenario| {
+                    let mut labels: Vec<String> = Vec::new();
+                    let tags = scenario.tags.iter().chain(&feature.tags);
+                    for label in tags
+                        .map(|t| t.trim_start_matches('@').to_string())
+                        .chain([scenario.scenario_type.to_string()])
+                    {
+                        if !labels.contains(&label) {
+                            labels.push(label);
+                        }
+                    }
+                    let mut fields = serde_json::json!({
+                        "summary": scenario.name,
+                        "description": feature.name,
+                        "labels": labels,
+                    });
+                    if let Some(key) = &self.project_key {
+                        fields["project"] = serde_json::json!({ "key": key });
+                    }
+                    serde_json::json!({
+                        "testtype": "Cucumber",
+                        "fields": fields,
+                        "xray_test_repository_folder": format!("/{}", feature.name),
+                        "requirement_keys": requirement_keys(feature, scenario),
+                        "gherkin_def": self.gherkin_definition(feature, scenario),
+                    })
+                })
+            })
+            .collect();
+        Ok(serde_json::to_string_pretty(&tests)?)
+    }
+
+    /// Corps Gherkin d'un test Xray (Background inclus, Examples pour les outlines)
+    fn gherkin_definition(&self, feature: &Feature, scenario: &Scenario) -> String {
+        let background = feature.background.iter().flat_map(|b| &b.steps);
+        let mut lines: Vec<String> = background
+            .chain(&scenario.steps)
+            .map(|step| self.step_line(step))
+            .collect();
+        if let Some(examples) = &scenario.examples {
+            lines.push(String::new());
+            lines.push(format!("{}:", self.language.keywords().gherkin.examples));
+            lines.push(format!("  | {} |", examples.headers.join(" | ")));
+            for row in &examples.rows {
+                lines.push(format!("  | {} |", row.join(" | ")));
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Cas TestRail : une ligne par scenario, sections par feature
+    fn render_testrail(&self, suite: &TestSuite) -> Result<String, anyhow::Error> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record([
+            "Section",
+            "Title",
+            "Priority",
+            "References",
+            "Preconditions",
+            "Steps",
+            "Expected Result",
+        ])?;
+        for feature in &suite.features {
+            let background = feature.background.iter().flat_map(|b| &b.steps);
+            for scenario in &feature.scenarios {
+                let (preconditions, mut actions, expected) =
+                    split_steps(background.clone().chain(&scenario.steps));
+                if let Some(examples) = &scenario.examples {
+                    actions.push(String::new());
+                    actions.push(format!(
+                        "{}: {}",
+                        self.language.keywords().gherkin.examples,
+                        examples.headers.join(" | ")
+                    ));
+                    actions.extend(examples.rows.iter().map(|row| row.join(" | ")));
+                }
+                writer.write_record([
+                    feature.name.as_str(),
+                    scenario.name.as_str(),
+                    priority(feature, scenario),
+                    requirement_keys(feature, scenario).join(", ").as_str(),
+                    preconditions.join("\n").as_str(),
+                    actions.join("\n").as_str(),
+                    expected.join("\n").as_str(),
+                ])?;
+            }
+        }
+        Ok(String::from_utf8(writer.into_inner()?)?)
+    }
+
+    /// Squelette JUnit : chaque scenario est un testcase `skipped`
+    fn render_junit(&self, suite: &TestSuite) -> String {
+        let mut xml = String::new();
+        let total: usize = suite.features.iter().map(|f| f.scenarios.len()).sum();
+        _ = writeln!(xml, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+        _ = writeln!(
+            xml,
+            "<testsuites name=\"spec-forge\" tests=\"{}\" skipped=\"{}\">",
+            total, total
+        );
+        for feature in &suite.features {
+            _ = writeln!(
+                xml,
+                "  <testsuite name=\"{}\" tests=\"{}\" skipped=\"{}\">",
+                escape(feature.name.as_str()),
+                feature.scenarios.len(),
+                feature.scenarios.len()
+            );
+            for scenario in &feature.scenarios {
+                _ = writeln!(
+                    xml,
+                    "    <testcase classname=\"{}\" name=\"{}\">",
+                    escape(feature.name.as_str()),
+                    escape(scenario.name.as_str())
+                );
+                _ = writeln!(xml, "      <properties>");
+                let requirements = requirement_keys(feature, scenario);
+                if !requirements.is_empty() {
+                    _ = writeln!(
+                        xml,
+                        "        <property name=\"requirements\" value=\"{}\"/>",
+                        escape(requirements.join(",").as_str())
+                    );
+                }
+                _ = writeln!(
+                    xml,
+                    "        <property name=\"scenario_type\" value=\"{}\"/>",
+                    scenario.scenario_type
+                );
+                if !scenario.tags.is_empty() {
+                    _ = writeln!(
+                        xml,
+                        "        <property name=\"tags\" value=\"{}\"/>",
+                        escape(scenario.tags.join(" ").as_str())
+                    );
+                }
+                _ = writeln!(xml, "      </properties>");
+                _ = writeln!(xml, "      <skipped message=\"non execute\"/>");
+                _ = writeln!(xml, "    </testcase>");
+            }
+            _ = writeln!(xml, "  </testsuite>");
+        }
+        _ = writeln!(xml, "</testsuites>");
+        xml
+    }
+
+    fn step_line(&self, step: &Step) -> String {
+        format!("{} {}", step.keyword.localized(self.language), step.text)
+    }
+
+    /// Ecrit l'export dans `output_dir`
+    pub async fn write(
+        &self,
+        suite: &TestSuite,
+        output_dir: &Path,
+    ) -> Result<PathBuf, anyhow::Error> {
+        tokio::fs::create_dir_all(output_dir).await?;
+        let path = output_dir.join(self.format.file_name());
+        let content = self.render(suite)?;
+        tokio::fs::write(&path, &content).await?;
+        Ok(path)
+    }
+}
+
+#[async_trait]
+impl OutputWriter for TestExportWriter {
+    fn format(&self) -> &str {
+        self.format.code()
+    }
+
+    async fn write_outputs(
+        &self,
+        spec: &Specification,
+        suite: &TestSuite,
+        layout: &OutputLayout,
+    ) -> Result<Vec<WrittenArtifact>, anyhow::Error> {
+        let path = self.write(suite, &layout.exports_dir).await?;
+        Ok(vec![WrittenArtifact::for_spec(
+            path,
+            ArtifactKind::TestExport,
+            spec,
+        )])
+    }
+}
+
+/// Cles d'exigence du scenario : `verification_of` et tags `@FR-xxx`,
+/// a defaut les exigences couvertes par la feature
+fn requirement_keys(feature: &Feature, scenario: &Scenario) -> Vec<String> {
+    let mut keys = scenario.linked_requirements();
+    if keys.is_empty() {
+        keys = feature.covered_requirements.clone();
+    }
+    keys
+}
+
+/// Priorite TestRail d'apres les tags `@P1` / `@P2` / `@P3`
+fn priority(feature: &Feature, scenario: &Scenario) -> &'static str {
+    let tags: Vec<&str> = scenario
+        .tags
+        .iter()
+        .chain(&feature.tags)
+        .map(|t| t.trim_start_matches('@'))
+        .collect();
+    if tags.contains(&"P1") {
+        "High"
+    } else if tags.contains(&"P3") {
+        "Low"
+    } else {
+        "Medium"
+    }
+}
+
+/// Repartit les etapes en preconditions (Given), actions (When) et
+/// resultats attendus (Then), And/But suivant l'etape precedente
+fn split_steps<'a>(
+    steps: impl Iterator<Item = &'a Step>,
+) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let mut sections: [Vec<String>; 3] = Default::default();
+    let mut current = 0;
+    for step in steps {
+        current = match step.keyword {
+            StepKeyword::Given => 0,
+            StepKeyword::When => 1,
+            StepKeyword::Then => 2,
+            StepKeyword::And | StepKeyword::But => current,
+        };
+        sections[current].push(step.text.clone());
+    }
+    let [given, when, then] = sections;
+    (given, when, then)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_case::{Background, Examples, ScenarioType, TestCoverage};
+    use pretty_assertions::assert_eq;
+
+    fn step(keyword: StepKeyword, text: &str) -> Step {
+        Step {
+            keyword,
+            text: text.into(),
+            doc_string: None,
+            data_table: None,
+        }
+    }
+
+    fn make_suite() -> TestSuite {
+        let mut feature = Feature::new("Recherche & ISBN".into(), String::new());
+        feature.background = Some(Background {
+            steps: vec![step(StepKeyword::Given, "un catalogue")],
+        });
+        feature.scenarios.push(Scenario {
+            name: "Recherche \"valide\"".into(),
+            tags: vec!["@P1".into(), "@FR-002".into()],
+            scenario_type: ScenarioType::HappyPath,
+            steps: vec![
+                step(StepKeyword::Given, "un ISBN <isbn>"),
+                step(StepKeyword::When, "il recherche"),
+                step(StepKeyword::Then, "le livre est affiche"),
+                step(StepKeyword::And, "le temps est < 2 s"),
+            ],
+            examples: Some(Examples {
+                headers: vec!["isbn".into()],
+                rows: vec![vec!["978-2".into()], vec!["979-1".into()]],
+            }),
+            test_data_suggestions: Vec::new(),
+            verification_of: vec!["FR-001".into()],
+            coverage_technique: None,
+        });
+        TestSuite {
+            features: vec![feature],
+            source_spec_id: uuid::Uuid::nil(),
+            total_scenarios: 1,
+            coverage: TestCoverage {
+                requirements_covered: Vec::new(),
+                requirements_total: 0,
+                coverage_percentage: 0.0,
+                scenarios_by_type: Default::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_render_xray_keeps_requirement_keys() {
+        let json = TestExportWriter::new(TestExportFormat::Xray, Language::English)
+            .render(&make_suite())
+            .unwrap();
+        let tests: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let test = &tests[0];
+        assert_eq!(test["testtype"], "Cucumber");
+        assert_eq!(
+            test["requirement_keys"],
+            serde_json::json!(["FR-001", "FR-002"])
+        );
+        assert_eq!(
+            test["fields"]["labels"],
+            serde_json::json!(["P1", "FR-002", "happy_path"])
+        );
+        let gherkin = test["gherkin_def"].as_str().unwrap();
+        assert!(gherkin.starts_with("Given un catalogue\nGiven un ISBN <isbn>"));
+        assert!(gherkin.ends_with("Examples:\n  | isbn |\n  | 978-2 |\n  | 979-1 |"));
+        assert!(test["fields"].get("project").is_none());
+    }
+
+    #[test]
+    fn test_render_xray_project_key() {
+        let json = TestExportWriter::new(TestExportFormat::Xray, Language::English)
+            .with_project_key(Some("PAY".into()))
+            .render(&make_suite())
+            .unwrap();
+        let tests: serde_json::Value = serde_json::from_str(&json).unwrap();
+        for test in tests.as_array().unwrap() {
+            assert_eq!(test["fields"]["project"]["key"], "PAY");
+        }
+    }
+
+    #[test]
+    fn test_render_testrail_csv() {
+        let csv = TestExportWriter::new(TestExportFormat::TestRail, Language::French)
+            .render(&make_suite())
+            .unwrap();
+        let mut reader = csv::Reader::from_reader(csv.as_bytes());
+        let record = reader.records().next().unwrap().unwrap();
+        assert_eq!(&record[0], "Recherche & ISBN");
+        assert_eq!(&record[2], "High");
+        assert_eq!(&record[3], "FR-001, FR-002");
+        assert_eq!(&record[4], "un catalogue\nun ISBN <isbn>");
+        assert_eq!(&record[5], "il recherche\n\nExemples: isbn\n978-2\n979-1");
+        assert_eq!(&record[6], "le livre est affiche\nle temps est < 2 s");
+    }
+
+    #[test]
+    fn test_render_junit_escapes_and_lists_requirements() {
+        let xml = TestExportWriter::new(TestExportFormat::JUnit, Language::French)
+            .render(&make_suite())
+            .unwrap();
+        assert!(
+            xml.contains("<testsuite name=\"Recherche &amp; ISBN\" tests=\"1\" skipped=\"1\">")
+        );
+        assert!(xml.contains("name=\"Recherche &quot;valide&quot;\">"));
+        assert!(xml.contains("<property name=\"requirements\" value=\"FR-001,FR-002\"/>"));
+        assert!(xml.contains("<skipped message=\"non execute\"/>"));
+    }
+}
@@ -2,156 +2,75 @@
 //!
 //! Genere une matrice de tracabilite enrichie (ISO 29148 section 6.6)
 
-use std::fmt::Write;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
+use async_trait::async_trait;
+use serde_json::json;
+
+use crate::adapters::output::mermaid::coverage_pie_diagram;
+use crate::adapters::output::template_context::{
+    label_or_dash, labelled, labelled_list, replace_field,
+};
+use crate::adapters::templates::file_template_engine::FileTemplateEngine;
+use crate::domain::output_manifest::ArtifactKind;
 use crate::domain::specification::Specification;
 use crate::domain::test_case::TestSuite;
-use crate::domain::traceability::{TraceabilityStatus, build_traceability_matrix};
+use crate::domain::traceability::{
+    TraceabilityEntry, TraceabilityMatrix, TraceabilityStatus, build_traceability_matrix,
+};
+use crate::domain::validation::compute_quality_metrics;
+use crate::domain::verification::{ExecutionResults, apply_execution_results};
+use crate::ports::output_writer::{OutputLayout, OutputWriter, WrittenArtifact};
+use crate::ports::template_engine::TemplateEngine;
 
 /// Ecrit un rapport de tracabilite Markdown
-pub struct TraceabilityWriter;
+pub struct TraceabilityWriter {
+    templates: Arc<dyn TemplateEngine>,
+}
+
+/// Template de document du rapport de tracabilite
+pub const TRACEABILITY_TEMPLATE: &str = "output_traceability";
 
 impl TraceabilityWriter {
     pub fn new() -> Self {
-        Self
+        Self::with_templates(Arc::new(FileTemplateEngine::builtin()))
+    }
+
+    /// Moteur de templates configure (documents surcharges par projet)
+    pub fn with_templates(templates: Arc<dyn TemplateEngine>) -> Self {
+        Self { templates }
     }
 
     /// Genere le rapport de tracabilite en Markdown
-    pub fn render(&self, spec: &Specification, suite: &TestSuite) -> String {
+    pub fn render(&self, spec: &Specification, suite: &TestSuite) -> Result<String, anyhow::Error> {
         let matrix = build_traceability_matrix(spec, suite);
-        let mut md = String::new();
-
-        // Ecriture en memoire (String) : ne peut pas echouer
-        _ = writeln!(md, "# Matrice de Tracabilite");
-        _ = writeln!(md);
-        _ = writeln!(md, "**Specification**: {}", spec.title);
-        _ = writeln!(md, "**Date**: {}", spec.created_at.format("%Y-%m-%d"));
-        _ = writeln!(md, "**Version**: {}", spec.version);
-        _ = writeln!(md, "**Conformite**: ISO/IEC/IEEE 29148:2018 section 6.6");
-        _ = writeln!(md);
-
-        // Enriched traceability table
-        _ = writeln!(md, "## Matrice de tracabilite exigences-tests");
-        _ = writeln!(md);
-        _ = writeln!(
-            md,
-            "| FR-ID | Priorite | Risque | Verification | Feature | Scenarios | Technique | Statut |"
-        );
-        _ = writeln!(
-            md,
-            "|-------|----------|--------|-------------|---------|-----------|-----------|--------|"
-        );
-
-        for entry in &matrix.entries {
-            let risk = entry
-                .risk_level
-                .as_ref()
-                .map(|r| r.to_string())
-                .unwrap_or_else(|| "-".into());
-            let features = if entry.covering_features.is_empty() {
-                "-".into()
-            } else {
-                entry.covering_features.join(", ")
-            };
-            let scenario_count = entry.covering_scenarios.len();
-            let techniques: String = if entry.coverage_techniques.is_empty() {
-                "-".into()
-            } else {
-                entry
-                    .coverage_techniques
-                    .iter()
-                    .map(|t| t.to_string())
-                    .collect::<Vec<_>>()
-                    .join("+")
-            };
-            let status = match entry.status {
-                TraceabilityStatus::NotCovered => format!("**{}**", entry.status),
-                _ => entry.status.to_string(),
-            };
-
-            _ = writeln!(
-                md,
-                "| {} | {} | {} | {} | {} | {} | {} | {} |",
-                entry.requirement_id,
-                entry.priority,
-                risk,
-                entry.verification_method,
-                features,
-                scenario_count,
-                techniques,
-                status,
-            );
-        }
-
-        _ = writeln!(md);
-
-        // Resume de couverture
-        _ = writeln!(md, "## Resume de couverture");
-        _ = writeln!(md);
-        _ = writeln!(
-            md,
-            "- Exigences totales: {}",
-            matrix.summary.total_requirements,
-        );
-        _ = writeln!(
-            md,
-            "- Couvertes: {} | Partielles: {} | GAP: {} | Autre verification: {}",
-            matrix.summary.covered,
-            matrix.summary.partially_covered,
-            matrix.summary.not_covered,
-            matrix.summary.verified_other,
-        );
-        _ = writeln!(
-            md,
-            "- Couverture forward: **{:.0}%**",
-            matrix.summary.forward_coverage_pct,
-        );
-        _ = writeln!(
-            md,
-            "- Scenarios happy path: {}",
-            suite.coverage.scenarios_by_type.happy_path
-        );
-        _ = writeln!(
-            md,
-            "- Scenarios edge case: {}",
-            suite.coverage.scenarios_by_type.edge_case
-        );
-        _ = writeln!(
-            md,
-            "- Scenarios erreur: {}",
-            suite.coverage.scenarios_by_type.error_scenario
-        );
-        _ = writeln!(md, "- Total scenarios: {}", suite.total_scenarios);
-        _ = writeln!(md);
-
-        // Orphan tests
-        if !matrix.summary.orphan_tests.is_empty() {
-            _ = writeln!(md, "### Tests orphelins (sans exigence associee)");
-            _ = writeln!(md);
-            for name in &matrix.summary.orphan_tests {
-                _ = writeln!(md, "- {}", name);
-            }
-            _ = writeln!(md);
-        }
+        self.render_matrix(spec, suite, &matrix)
+    }
 
-        // Compliance notes
-        if !matrix.compliance_notes.is_empty() {
-            _ = writeln!(md, "## Notes de conformite");
-            _ = writeln!(md);
-            _ = writeln!(md, "| Norme | Section | Statut | Details |");
-            _ = writeln!(md, "|-------|---------|--------|---------|");
-            for note in &matrix.compliance_notes {
-                _ = writeln!(
-                    md,
-                    "| {} | {} | {} | {} |",
-                    note.standard, note.section, note.status, note.details,
-                );
-            }
-            _ = writeln!(md);
-        }
+    /// Genere le rapport de verification : matrice etendue par les resultats
+    /// d'execution (statut par scenario, verdict par exigence)
+    pub fn render_verification(
+        &self,
+        spec: &Specification,
+        suite: &TestSuite,
+        results: &ExecutionResults,
+    ) -> Result<String, anyhow::Error> {
+        let mut matrix = build_traceability_matrix(spec, suite);
+        apply_execution_results(&mut matrix, suite, results);
+        self.render_matrix(spec, suite, &matrix)
+    }
 
-        md
+    fn render_matrix(
+        &self,
+        spec: &Specification,
+        suite: &TestSuite,
+        matrix: &TraceabilityMatrix,
+    ) -> Result<String, anyhow::Error> {
+        self.templates.render(
+            TRACEABILITY_TEMPLATE,
+            &traceability_context(spec, suite, matrix)?,
+        )
     }
 
     /// Ecrit le rapport dans un fichier
@@ -163,12 +82,167 @@ impl TraceabilityWriter {
     ) -> Result<PathBuf, anyhow::Error> {
         tokio::fs::create_dir_all(output_dir).await?;
         let path = output_dir.join("traceability.md");
-        let content = self.render(spec, suite);
+        let content = self.render(spec, suite)?;
+        tokio::fs::write(&path, &content).await?;
+        Ok(path)
+    }
+
+    /// Ecrit le rapport de verification dans `verification.md`
+    pub async fn write_verification(
+        &self,
+        spec: &Specification,
+        suite: &TestSuite,
+        results: &ExecutionResults,
+        output_dir: &Path,
+    ) -> Result<PathBuf, anyhow::Error> {
+        tokio::fs::create_dir_all(output_dir).await?;
+        let path = output_dir.join("verification.md");
+        let content = self.render_verification(spec, suite, results)?;
         tokio::fs::write(&path, &content).await?;
         Ok(path)
     }
 }
 
+/// Contexte du template de tracabilite : spec, suite, matrice et metriques
+/// ISO 25023 serialisees, les lignes de la matrice portant leurs libelles
+fn traceability_context(
+    spec: &Specification,
+    suite: &TestSuite,
+    matrix: &TraceabilityMatrix,
+) -> Result<serde_json::Value, anyhow::Error> {
+    let verified = matrix.verification.is_some();
+    let entries = matrix
+        .entries
+        .iter()
+        .map(|entry| {
+            let mut value = labelled(entry, entry_labels(entry, verified))?;
+            replace_field(
+                &mut value,
+                "scenario_results",
+                labelled_list(
+                    &entry.scenario_results,
+                    |result| json!({ "status": label_or(result.status.as_ref(), "non execute") }),
+                )?,
+            );
+            Ok(value)
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+    let mut matrix_value = serde_json::to_value(matrix)?;
+    replace_field(
+        &mut matrix_value,
+        "entries",
+        serde_json::Value::Array(entries),
+    );
+    replace_field(
+        &mut matrix_value,
+        "compliance_notes",
+        labelled_list(
+            &matrix.compliance_notes,
+            |note| json!({ "status": note.status.to_string() }),
+        )?,
+    );
+
+    replace_field(
+        &mut matrix_value,
+        "allocations",
+        labelled_list(&matrix.allocations, |allocation| {
+            json!({
+                "requirements": allocation.requirements.join(", "),
+                "scenario_count": allocation.scenarios.len(),
+                "not_covered": if allocation.not_covered.is_empty() {
+                    "-".to_string()
+                } else {
+                    format!("**{}**", allocation.not_covered.join(", "))
+                },
+            })
+        })?,
+    );
+
+    Ok(json!({
+        "spec": spec,
+        "suite": suite,
+        "matrix": matrix_value,
+        "metrics": compute_quality_metrics(spec, suite),
+        "diagrams": {
+            "coverage": coverage_pie_diagram(&matrix.summary),
+        },
+    }))
+}
+
+/// Libelles d'une ligne de la matrice (le verdict n'est affiche qu'apres
+/// rapprochement des resultats d'execution)
+fn entry_labels(entry: &TraceabilityEntry, verified: bool) -> serde_json::Value {
+    let features = if entry.covering_features.is_empty() {
+        "-".into()
+    } else {
+        entry.covering_features.join(", ")
+    };
+    let techniques = if entry.coverage_techniques.is_empty() {
+        "-".into()
+    } else {
+        entry
+            .coverage_techniques
+            .iter()
+            .map(|t| t.to_string())
+            .collect::<Vec<_>>()
+            .join("+")
+    };
+    let status = match entry.status {
+        TraceabilityStatus::NotCovered => format!("**{}**", entry.status),
+        _ if entry.rolled_up => format!("{} (derivees)", entry.status),
+        _ => entry.status.to_string(),
+    };
+    // Arbre des exigences : les derivees sont indentees sous leur parent
+    let requirement = if entry.depth == 0 {
+        entry.requirement_id.clone()
+    } else {
+        format!(
+            "{}↳ {}",
+            "&nbsp;&nbsp;".repeat(entry.depth),
+            entry.requirement_id
+        )
+    };
+    json!({
+        "requirement": requirement,
+        "priority": entry.priority.to_string(),
+        "risk_level": label_or_dash(entry.risk_level.as_ref()),
+        "verification_method": entry.verification_method.to_string(),
+        "features": features,
+        "scenario_count": entry.covering_scenarios.len(),
+        "techniques": techniques,
+        "status": status,
+        "verdict": entry.verdict.filter(|_| verified).map(|v| v.to_string()),
+    })
+}
+
+fn label_or<T: ToString>(value: Option<&T>, default: &str) -> String {
+    value
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| default.into())
+}
+
+#[async_trait]
+impl OutputWriter for TraceabilityWriter {
+    fn format(&self) -> &str {
+        "traceability"
+    }
+
+    async fn write_outputs(
+        &self,
+        spec: &Specification,
+        suite: &TestSuite,
+        layout: &OutputLayout,
+    ) -> Result<Vec<WrittenArtifact>, anyhow::Error> {
+        let path = self.write(spec, suite, &layout.traceability_dir).await?;
+        Ok(vec![WrittenArtifact::for_spec(
+            path,
+            ArtifactKind::Traceability,
+            spec,
+        )])
+    }
+}
+
 impl Default for TraceabilityWriter {
     fn default() -> Self {
         Self::new()
@@ -255,7 +329,7 @@ mod tests {
         let spec = make_spec_with_requirements(&["FR-001", "FR-002"]);
         let suite = make_suite_covering(&["FR-001", "FR-002"], 2);
 
-        let output = writer.render(&spec, &suite);
+        let output = writer.render(&spec, &suite).unwrap();
         assert!(output.contains("# Matrice de Tracabilite"));
         assert!(output.contains("FR-001"));
         assert!(output.contains("FR-002"));
@@ -270,7 +344,7 @@ mod tests {
         let spec = make_spec_with_requirements(&["FR-001", "FR-002", "FR-003"]);
         let suite = make_suite_covering(&["FR-001"], 3);
 
-        let output = writer.render(&spec, &suite);
+        let output = writer.render(&spec, &suite).unwrap();
         assert!(output.contains("FR-001"));
         assert!(output.contains("Couvert"));
         assert!(output.contains("FR-002"));
@@ -294,12 +368,67 @@ mod tests {
             },
         };
 
-        let output = writer.render(&spec, &suite);
+        let output = writer.render(&spec, &suite).unwrap();
         assert!(output.contains("**GAP**"));
         assert!(output.contains("0%"));
         assert!(output.contains("Total scenarios: 0"));
     }
 
+    #[test]
+    fn test_render_requirement_tree_and_allocations() {
+        let writer = TraceabilityWriter::new();
+        let mut spec = make_spec_with_requirements(&["FR-001", "FR-002", "FR-003"]);
+        spec.functional_requirements[1].parent_requirement = Some("FR-001".into());
+        spec.functional_requirements[2].parent_requirement = Some("FR-002".into());
+        spec.functional_requirements[0].allocated_to = vec!["Auth".into()];
+        spec.functional_requirements[2].allocated_to = vec!["Auth".into()];
+        let suite = make_suite_covering(&["FR-001", "FR-002"], 3);
+
+        let output = writer.render(&spec, &suite).unwrap();
+        assert!(output.contains("| FR-001 |"));
+        assert!(output.contains("| &nbsp;&nbsp;↳ FR-002 |"));
+        assert!(output.contains("| &nbsp;&nbsp;&nbsp;&nbsp;↳ FR-003 |"));
+        assert!(output.contains("Partiel (derivees)"));
+        assert!(output.contains("## Allocation par composant"));
+        assert!(output.contains("| Auth | FR-001, FR-003 | 2 | **FR-003** |"));
+    }
+
+    #[test]
+    fn test_render_verification_adds_verdicts() {
+        use crate::domain::verification::{ExecutedScenario, ExecutionStatus};
+
+        let writer = TraceabilityWriter::new();
+        let spec = make_spec_with_requirements(&["FR-001", "FR-002"]);
+        let suite = make_suite_covering(&["FR-001"], 2);
+        let results = ExecutionResults {
+            scenarios: vec![
+                ExecutedScenario {
+                    feature: "Test Feature".into(),
+                    feature_file: None,
+                    name: "Test Scenario".into(),
+                    tags: vec![],
+                    status: ExecutionStatus::Passed,
+                },
+                ExecutedScenario {
+                    feature: "Test Feature".into(),
+                    feature_file: None,
+                    name: "Test Scenario 2".into(),
+                    tags: vec![],
+                    status: ExecutionStatus::Failed,
+                },
+            ],
+        };
+
+        let output = writer.render_verification(&spec, &suite, &results).unwrap();
+        assert!(output.contains("| Statut | Verdict |"));
+        assert!(output.contains("| Couvert | Echec |"));
+        assert!(output.contains("| **GAP** | Non execute |"));
+        assert!(output.contains("- Verifiees: 0 | Echec: 1 | Incompletes: 0 | Non executees: 1"));
+        assert!(output.contains("| FR-001 | Test Feature | Test Scenario 2 | failed |"));
+        // Sans resultats, le rapport reste inchange
+        assert!(!writer.render(&spec, &suite).unwrap().contains("Verdict"));
+    }
+
     #[tokio::test]
     async fn test_write_to_disk() {
         let writer = TraceabilityWriter::new();
@@ -325,7 +454,7 @@ mod tests {
             .unwrap()
             .and_utc();
         let suite = make_suite_covering(&["FR-001", "FR-002"], 2);
-        let output = writer.render(&spec, &suite);
+        let output = writer.render(&spec, &suite).unwrap();
         insta::assert_snapshot!(output);
     }
 
@@ -339,7 +468,7 @@ mod tests {
             .unwrap()
             .and_utc();
         let suite = make_suite_covering(&["FR-001"], 3);
-        let output = writer.render(&spec, &suite);
+        let output = writer.render(&spec, &suite).unwrap();
         insta::assert_snapshot!(output);
     }
 }
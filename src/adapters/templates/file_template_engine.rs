@@ -1,50 +1,183 @@
 //! Adapter FileTemplateEngine - Charge et rend les templates Handlebars
+//!
+//! Deux familles de templates : les prompts LLM (`*.md`) et les documents de
+//! sortie (`*.md.hbs`, rendus sans echappement HTML, avec des helpers de
+//! mise en forme). Les documents par defaut sont embarques dans le binaire
+//! et peuvent etre remplaces par projet (`templates.overrides`).
 
-use handlebars::Handlebars;
+use chrono::{DateTime, Utc};
+use handlebars::{
+    Context, Handlebars, Helper, HelperResult, Output, RenderContext, handlebars_helper,
+};
 use std::path::{Path, PathBuf};
 use tracing::debug;
 
+use crate::infrastructure::config::TemplatesConfig;
 use crate::ports::template_engine::TemplateEngine;
 
+/// Templates de documents par defaut (nom, contenu)
+const DEFAULT_DOCUMENT_TEMPLATES: [(&str, &str); 2] = [
+    (
+        "output_spec",
+        include_str!("../../../templates/output_spec.md.hbs"),
+    ),
+    (
+        "output_traceability",
+        include_str!("../../../templates/output_traceability.md.hbs"),
+    ),
+];
+
 /// Moteur de templates base sur les fichiers Handlebars
 pub struct FileTemplateEngine {
     handlebars: Handlebars<'static>,
+    documents: Handlebars<'static>,
     template_dir: PathBuf,
 }
 
 impl FileTemplateEngine {
     /// Cree un moteur de templates depuis un repertoire
     pub fn new(template_dir: &Path) -> Result<Self, anyhow::Error> {
+        let mut engine = Self::builtin();
+        engine.template_dir = template_dir.to_path_buf();
+        engine.register_dir(template_dir)?;
+        Ok(engine)
+    }
+
+    /// Moteur configure : repertoire des templates puis surcharges du projet
+    pub fn from_config(config: &TemplatesConfig) -> Result<Self, anyhow::Error> {
+        let engine = Self::new(&config.directory)?;
+        match config.overrides {
+            Some(ref overrides) => engine.with_overrides(overrides),
+            None => Ok(engine),
+        }
+    }
+
+    /// Moteur limite aux documents de sortie embarques
+    pub fn builtin() -> Self {
         let mut handlebars = Handlebars::new();
         handlebars.set_strict_mode(false);
 
-        // Charger tous les templates .md du repertoire
-        if template_dir.exists() {
-            for entry in std::fs::read_dir(template_dir)? {
-                let entry = entry?;
-                let path = entry.path();
-                if path.extension().is_some_and(|ext| ext == "md")
-                    && path
-                        .file_stem()
-                        .is_some_and(|name| !name.eq_ignore_ascii_case("readme"))
-                {
-                    let name = path
-                        .file_stem()
-                        .unwrap_or_default()
-                        .to_string_lossy()
-                        .to_string();
-                    let content = std::fs::read_to_string(&path)?;
-                    handlebars.register_template_string(&name, &content)?;
-                    debug!(template = %name, "Template charge");
-                }
+        let mut documents = Handlebars::new();
+        documents.set_strict_mode(false);
+        documents.register_escape_fn(handlebars::no_escape);
+        register_document_helpers(&mut documents);
+        for (name, content) in DEFAULT_DOCUMENT_TEMPLATES {
+            // Templates embarques, verifies par les tests
+            if let Err(e) = documents.register_template_string(name, content) {
+                tracing::error!(template = %name, error = %e, "Template embarque invalide");
             }
         }
 
-        Ok(Self {
+        Self {
             handlebars,
-            template_dir: template_dir.to_path_buf(),
+            documents,
+            template_dir: PathBuf::new(),
+        }
+    }
+
+    /// Remplace les templates par ceux d'un repertoire propre au projet
+    pub fn with_overrides(mut self, overrides_dir: &Path) -> Result<Self, anyhow::Error> {
+        if !overrides_dir.is_dir() {
+            anyhow::bail!(
+                "Repertoire de templates introuvable: {}",
+                overrides_dir.display()
+            );
+        }
+        self.register_dir(overrides_dir)?;
+        Ok(self)
+    }
+
+    /// Charge les prompts `.md` et les documents `.md.hbs` d'un repertoire
+    fn register_dir(&mut self, dir: &Path) -> Result<(), anyhow::Error> {
+        if !dir.exists() {
+            return Ok(());
+        }
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let file_name = path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            if let Some(name) = file_name.strip_suffix(".hbs") {
+                let name = name.strip_suffix(".md").unwrap_or(name);
+                let content = std::fs::read_to_string(&path)?;
+                self.documents
+                    .register_template_string(name, &content)
+                    .map_err(|e| anyhow::anyhow!("Template '{}' invalide: {}", file_name, e))?;
+                debug!(template = %name, "Template de document charge");
+            } else if path.extension().is_some_and(|ext| ext == "md")
+                && path
+                    .file_stem()
+                    .is_some_and(|name| !name.eq_ignore_ascii_case("readme"))
+            {
+                let name = path
+                    .file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string();
+                let content = std::fs::read_to_string(&path)?;
+                self.handlebars.register_template_string(&name, &content)?;
+                debug!(template = %name, "Template charge");
+            }
+        }
+        Ok(())
+    }
+}
+
+handlebars_helper!(inc: |value: u64| value + 1);
+handlebars_helper!(percent: |ratio: f64| format!("{:.0}%", ratio * 100.0));
+handlebars_helper!(round: |value: f64| format!("{value:.0}"));
+handlebars_helper!(join: |items: array, separator: str| {
+    items
+        .iter()
+        .map(|item| match item {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
         })
+        .collect::<Vec<_>>()
+        .join(separator)
+});
+handlebars_helper!(date: |value: str, format: str| {
+    match value.parse::<DateTime<Utc>>() {
+        Ok(parsed) => parsed.format(format).to_string(),
+        Err(_) => value.to_string(),
     }
+});
+
+/// `{{row a b c}}` : ligne de tableau Markdown `| a | b | c |` (les `|` des
+/// cellules sont echappes, les retours a la ligne remplaces par des espaces)
+fn row_helper(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let cells: Vec<String> = h
+        .params()
+        .iter()
+        .map(|param| {
+            let text = match param.value() {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Null => String::new(),
+                other => other.to_string(),
+            };
+            text.replace('|', "\\|").replace('\n', " ")
+        })
+        .collect();
+    out.write(&format!("| {} |", cells.join(" | ")))?;
+    Ok(())
+}
+
+fn register_document_helpers(documents: &mut Handlebars<'static>) {
+    documents.register_helper("inc", Box::new(inc));
+    documents.register_helper("percent", Box::new(percent));
+    documents.register_helper("round", Box::new(round));
+    documents.register_helper("join", Box::new(join));
+    documents.register_helper("date", Box::new(date));
+    documents.register_helper("row", Box::new(row_helper));
 }
 
 impl TemplateEngine for FileTemplateEngine {
@@ -76,13 +209,23 @@ impl TemplateEngine for FileTemplateEngine {
         template_name: &str,
         context: &serde_json::Value,
     ) -> Result<String, anyhow::Error> {
-        self.handlebars
+        let registry = if self.documents.has_template(template_name) {
+            &self.documents
+        } else {
+            &self.handlebars
+        };
+        registry
             .render(template_name, context)
             .map_err(|e| anyhow::anyhow!("Erreur de rendu du template '{}': {}", template_name, e))
     }
 
     fn list_templates(&self) -> Vec<String> {
-        self.handlebars.get_templates().keys().cloned().collect()
+        self.handlebars
+            .get_templates()
+            .keys()
+            .chain(self.documents.get_templates().keys())
+            .cloned()
+            .collect()
     }
 }
 
@@ -155,4 +298,58 @@ mod tests {
         assert!(result.contains("- un"));
         assert!(result.contains("- trois"));
     }
+
+    #[test]
+    fn test_builtin_documents_can_be_overridden() {
+        let engine = FileTemplateEngine::builtin();
+        assert!(engine.list_templates().contains(&"output_spec".to_string()));
+
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("output_spec.md.hbs"),
+            "# {{spec.title}} <{{labels.status}}>",
+        )
+        .unwrap();
+        let engine = engine.with_overrides(dir.path()).unwrap();
+        let context = serde_json::json!({
+            "spec": { "title": "Prets & retours" },
+            "labels": { "status": "Draft" }
+        });
+        // Documents Markdown : pas d'echappement HTML
+        assert_eq!(
+            engine.render("output_spec", &context).unwrap(),
+            "# Prets & retours <Draft>"
+        );
+
+        assert!(
+            FileTemplateEngine::builtin()
+                .with_overrides(&dir.path().join("absent"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_document_helpers() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("helpers.md.hbs"),
+            "{{row id text}}\n{{date created \"%d/%m/%Y\"}} {{percent score}} {{round pct}} \
+             {{#each items}}{{inc @index}}{{/each}} {{join items \", \"}}",
+        )
+        .unwrap();
+
+        let engine = FileTemplateEngine::new(dir.path()).unwrap();
+        let context = serde_json::json!({
+            "id": "FR-001",
+            "text": "a | b\nc",
+            "created": "2026-03-05T10:00:00Z",
+            "score": 0.854,
+            "pct": 66.6,
+            "items": ["x", "y"]
+        });
+        assert_eq!(
+            engine.render("helpers", &context).unwrap(),
+            "| FR-001 | a \\| b c |\n05/03/2026 85% 67 12 x, y"
+        );
+    }
 }
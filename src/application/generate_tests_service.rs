@@ -4,7 +4,9 @@ use std::sync::Arc;
 use tracing::{debug, info, warn};
 
 use crate::domain::errors::GenerationError;
-use crate::domain::specification::Specification;
+use crate::domain::regeneration::retain_selected_scenarios;
+use crate::domain::specification::{FunctionalRequirement, Specification};
+use crate::domain::step_vocabulary::{DEFAULT_STEP_SIMILARITY, build_step_glossary};
 use crate::domain::test_case::*;
 use crate::domain::user_story::Language;
 use crate::ports::llm_service::LlmService;
@@ -17,8 +19,12 @@ pub struct GenerateTestsService {
     language: Language,
     max_retries: usize,
     token_budget: usize,
+    step_vocabulary_prompt: bool,
 }
 
+/// Nombre max de formulations canoniques rappelees au LLM
+const MAX_VOCABULARY_PHRASES: usize = 30;
+
 /// Schema JSON de sortie du LLM pour la generation de tests
 #[derive(Debug, serde::Deserialize)]
 struct LlmTestOutput {
@@ -107,6 +113,17 @@ fn estimate_spec_tokens(spec: &Specification) -> usize {
     len / 4
 }
 
+/// Nombre maximal de FR par appel LLM lors d'une generation ciblee (gap-fill)
+const MAX_GAP_FRS_PER_CHUNK: usize = 20;
+
+/// Sous-spec limitee a un ensemble de FR (scenarios utilisateur conserves
+/// pour le contexte)
+fn requirement_subspec(spec: &Specification, frs: &[FunctionalRequirement]) -> Specification {
+    let mut sub = spec.clone();
+    sub.functional_requirements = frs.to_vec();
+    sub
+}
+
 /// Construit des sous-specs pour le batching de generation de tests.
 ///
 /// Chaque batch contient un sous-ensemble de scenarios utilisateur, les edge cases
@@ -194,6 +211,7 @@ impl GenerateTestsService {
             language,
             max_retries,
             token_budget: 6000,
+            step_vocabulary_prompt: false,
         }
     }
 
@@ -202,6 +220,21 @@ impl GenerateTestsService {
         self
     }
 
+    /// Rappelle aux batches suivants les formulations d'etapes deja employees
+    pub fn with_step_vocabulary_prompt(mut self, enabled: bool) -> Self {
+        self.step_vocabulary_prompt = enabled;
+        self
+    }
+
+    /// Formulations canoniques des etapes deja generees (vide si desactive)
+    fn known_vocabulary(&self, suite: &TestSuite) -> Vec<String> {
+        if !self.step_vocabulary_prompt {
+            return Vec::new();
+        }
+        build_step_glossary(suite, DEFAULT_STEP_SIMILARITY)
+            .canonical_phrases(self.language, MAX_VOCABULARY_PHRASES)
+    }
+
     /// Genere les tests Gherkin depuis une specification
     pub async fn generate(&self, spec: &Specification) -> Result<TestSuite, GenerationError> {
         let batches = build_spec_batches(spec, self.token_budget);
@@ -232,7 +265,12 @@ impl GenerateTestsService {
                 "Batch de generation"
             );
 
-            match self.generate_single(&batch_spec).await {
+            let vocabulary = if self.step_vocabulary_prompt && !suites.is_empty() {
+                self.known_vocabulary(&Self::merge_test_suites(suites.clone()))
+            } else {
+                Vec::new()
+            };
+            match self.generate_single(&batch_spec, &vocabulary).await {
                 Ok(suite) => suites.push(suite),
                 Err(GenerationError::OutputTruncated { .. })
                     if batch_spec.user_scenarios.len() > 1 =>
@@ -284,7 +322,6 @@ impl GenerateTestsService {
         merged.compute_coverage(spec.functional_requirements.len());
 
         // --- Passe supplementaire : combler les gaps par chunks de FR ---
-        const MAX_GAP_FRS_PER_CHUNK: usize = 20;
         let max_gap_passes = 2;
 
         for pass in 0..max_gap_passes {
@@ -324,10 +361,10 @@ impl GenerateTestsService {
                     "Gap-fill chunk"
                 );
 
-                let mut gap_spec = spec.clone();
-                gap_spec.functional_requirements = chunk.to_vec();
+                let gap_spec = requirement_subspec(spec, chunk);
 
-                match self.generate_single(&gap_spec).await {
+                let vocabulary = self.known_vocabulary(&merged);
+                match self.generate_single(&gap_spec, &vocabulary).await {
                     Ok(gap_suite) => {
                         merged.features.extend(gap_suite.features);
                     }
@@ -385,14 +422,81 @@ impl GenerateTestsService {
         Ok(merged)
     }
 
+    /// Genere les tests des seules exigences selectionnees
+    ///
+    /// Les FR sont envoyees par chunks avec le reste de la spec comme contexte ;
+    /// le vocabulaire d'etapes de la suite existante est rappele au LLM si
+    /// `step_vocabulary_prompt` est actif. Seuls les scenarios verifiant une
+    /// exigence selectionnee sont conserves.
+    pub async fn generate_for_requirements(
+        &self,
+        spec: &Specification,
+        requirement_ids: &[String],
+        existing: &TestSuite,
+    ) -> Result<TestSuite, GenerationError> {
+        let selected: Vec<FunctionalRequirement> = spec
+            .functional_requirements
+            .iter()
+            .filter(|fr| requirement_ids.contains(&fr.id))
+            .cloned()
+            .collect();
+
+        let mut suites = Vec::new();
+        for (chunk_idx, chunk) in selected.chunks(MAX_GAP_FRS_PER_CHUNK).enumerate() {
+            info!(
+                chunk = chunk_idx + 1,
+                chunk_frs = chunk.len(),
+                "Generation ciblee"
+            );
+            let mut context = existing.clone();
+            context.features.extend(
+                suites
+                    .iter()
+                    .flat_map(|s: &TestSuite| s.features.iter().cloned()),
+            );
+            let vocabulary = self.known_vocabulary(&context);
+            suites.push(
+                self.generate_single(&requirement_subspec(spec, chunk), &vocabulary)
+                    .await?,
+            );
+        }
+
+        let mut merged = Self::merge_test_suites(suites);
+        merged.source_spec_id = spec.id;
+        let dropped = retain_selected_scenarios(&mut merged, requirement_ids);
+        if dropped > 0 {
+            warn!(dropped, "Scenarios generes hors selection ignores");
+        }
+        merged.compute_coverage(spec.functional_requirements.len());
+        Ok(merged)
+    }
+
     /// Genere les tests pour un batch unique
-    async fn generate_single(&self, spec: &Specification) -> Result<TestSuite, GenerationError> {
+    async fn generate_single(
+        &self,
+        spec: &Specification,
+        vocabulary: &[String],
+    ) -> Result<TestSuite, GenerationError> {
         let is_french = self.language == Language::French;
+        let keywords = &self.language.keywords().gherkin;
 
         // System prompt
         let system_context = serde_json::json!({
             "gherkin_language": self.language.gherkin_code(),
             "french": is_french,
+            "localized": self.language != Language::English,
+            "keywords": {
+                "feature": keywords.feature,
+                "background": keywords.background,
+                "scenario": keywords.scenario,
+                "scenario_outline": keywords.scenario_outline,
+                "examples": keywords.examples,
+                "given": keywords.given,
+                "when": keywords.when,
+                "then": keywords.then,
+                "and": keywords.and,
+                "but": keywords.but,
+            },
         });
         let system_prompt = self
             .templates
@@ -401,7 +505,7 @@ impl GenerateTestsService {
                 details: format!("Erreur template: {}", e),
             })?;
 
-        let user_prompt = self.build_user_prompt(spec);
+        let user_prompt = self.build_user_prompt(spec, vocabulary);
 
         debug!(
             system_prompt_len = system_prompt.len(),
@@ -451,7 +555,7 @@ impl GenerateTestsService {
         }
     }
 
-    fn build_user_prompt(&self, spec: &Specification) -> String {
+    fn build_user_prompt(&self, spec: &Specification, vocabulary: &[String]) -> String {
         let mut prompt = String::from(
             "Genere des scenarios de test Gherkin pour la specification suivante:\n\n",
         );
@@ -499,6 +603,18 @@ impl GenerateTestsService {
             prompt.push('\n');
         }
 
+        // Vocabulaire des batches precedents : reutiliser les memes formulations
+        if !vocabulary.is_empty() {
+            prompt.push_str("## Vocabulaire d'etapes existant\n\n");
+            prompt.push_str(
+                "Reutilise ces formulations a l'identique lorsqu'une etape exprime la meme action:\n",
+            );
+            for phrase in vocabulary {
+                prompt.push_str(&format!("- {}\n", phrase));
+            }
+            prompt.push('\n');
+        }
+
         prompt
     }
 
@@ -609,15 +725,23 @@ fn parse_step(ls: &LlmStep) -> Step {
     }
 }
 
+/// Mot-cle d'etape dans n'importe quelle langue supportee (defaut: And)
 fn parse_keyword(s: &str) -> StepKeyword {
-    match s.to_lowercase().as_str() {
-        "given" | "soit" => StepKeyword::Given,
-        "when" | "quand" => StepKeyword::When,
-        "then" | "alors" => StepKeyword::Then,
-        "and" | "et" => StepKeyword::And,
-        "but" | "mais" => StepKeyword::But,
-        _ => StepKeyword::And,
-    }
+    let lower = s.trim().to_lowercase();
+    [
+        StepKeyword::Given,
+        StepKeyword::When,
+        StepKeyword::Then,
+        StepKeyword::And,
+        StepKeyword::But,
+    ]
+    .into_iter()
+    .find(|keyword| {
+        Language::ALL
+            .iter()
+            .any(|language| keyword.localized(*language).to_lowercase() == lower)
+    })
+    .unwrap_or(StepKeyword::And)
 }
 
 fn parse_scenario_type(s: &str) -> ScenarioType {
@@ -779,6 +903,16 @@ mod tests {
         assert_eq!(parse_keyword("Et"), StepKeyword::And);
         assert_eq!(parse_keyword("But"), StepKeyword::But);
         assert_eq!(parse_keyword("Mais"), StepKeyword::But);
+        assert_eq!(parse_keyword("Pero"), StepKeyword::But);
+        assert_eq!(parse_keyword("Und"), StepKeyword::And);
+    }
+
+    #[test]
+    fn test_parse_keyword_spanish_german() {
+        assert_eq!(parse_keyword("Dado"), StepKeyword::Given);
+        assert_eq!(parse_keyword("Cuando"), StepKeyword::When);
+        assert_eq!(parse_keyword("angenommen"), StepKeyword::Given);
+        assert_eq!(parse_keyword("Dann"), StepKeyword::Then);
     }
 
     #[test]
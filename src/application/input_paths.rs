@@ -0,0 +1,520 @@
+//! Expansion des chemins d'entree
+//!
+//! Dossiers parcourus recursivement, motifs glob (`docs/**/us_*.md`),
+//! exclusions (`--exclude`, `.specforgeignore`) et entree standard (`-`).
+//! L'ordre du resultat est stable : chemins tries, sans doublons.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use tracing::{debug, warn};
+
+use crate::domain::errors::InputError;
+
+/// Chemin designant l'entree standard
+pub const STDIN_PATH: &str = "-";
+
+/// Fichier d'exclusions lu a la racine de chaque dossier parcouru
+pub const IGNORE_FILE: &str = ".specforgeignore";
+
+/// Options d'expansion des chemins d'entree
+#[derive(Debug, Clone)]
+pub struct ExpandOptions {
+    /// Parcourir les sous-dossiers
+    pub recursive: bool,
+    /// Inclure les fichiers et dossiers caches (`.xxx`) rencontres lors du parcours
+    pub include_hidden: bool,
+    /// Suivre les liens symboliques (les boucles sont detectees et ignorees)
+    pub follow_symlinks: bool,
+    /// Motifs d'exclusion (syntaxe `.specforgeignore`)
+    pub excludes: Vec<String>,
+}
+
+impl Default for ExpandOptions {
+    fn default() -> Self {
+        Self {
+            recursive: true,
+            include_hidden: false,
+            follow_symlinks: false,
+            excludes: Vec::new(),
+        }
+    }
+}
+
+/// Motif d'exclusion compile (sous-ensemble de la syntaxe gitignore)
+#[derive(Debug)]
+struct IgnorePattern {
+    regex: Regex,
+    negated: bool,
+    dir_only: bool,
+}
+
+impl IgnorePattern {
+    /// Compile une ligne : `#` commentaire, `!` negation, `/` final = dossier,
+    /// motif sans `/` = nom a n'importe quelle profondeur.
+    fn parse(line: &str) -> Result<Option<Self>, InputError> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let line = line.trim_start_matches('/');
+        let prefix = if anchored { "" } else { "(?:.*/)?" };
+        let regex = Regex::new(&format!("^{}{}$", prefix, glob_to_regex(line))).map_err(|e| {
+            InputError::InvalidFormat {
+                details: format!("Motif d'exclusion invalide '{}': {}", line, e),
+            }
+        })?;
+        Ok(Some(Self {
+            regex,
+            negated,
+            dir_only,
+        }))
+    }
+}
+
+/// Ensemble ordonne de motifs d'exclusion (le dernier motif qui correspond l'emporte)
+#[derive(Debug, Default)]
+struct IgnoreMatcher {
+    patterns: Vec<IgnorePattern>,
+}
+
+impl IgnoreMatcher {
+    fn add_lines<'a>(&mut self, lines: impl Iterator<Item = &'a str>) -> Result<(), InputError> {
+        for line in lines {
+            if let Some(pattern) = IgnorePattern::parse(line)? {
+                self.patterns.push(pattern);
+            }
+        }
+        Ok(())
+    }
+
+    /// `relative` est le chemin relatif a la racine, separe par `/`
+    fn is_ignored(&self, relative: &str, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for pattern in &self.patterns {
+            if pattern.dir_only && !is_dir {
+                continue;
+            }
+            if pattern.regex.is_match(relative) {
+                ignored = !pattern.negated;
+            }
+        }
+        ignored
+    }
+}
+
+/// Traduit un glob (`*`, `**`, `?`, `[...]`) en expression reguliere non ancree
+fn glob_to_regex(glob: &str) -> String {
+    let chars: Vec<char> = glob.chars().collect();
+    let mut regex = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if chars.get(i + 2) == Some(&'/') {
+                    regex.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    regex.push_str(".*");
+                    i += 2;
+                }
+                continue;
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => match chars[i..].iter().position(|&c| c == ']') {
+                Some(end) => {
+                    let class: String = chars[i + 1..i + end].iter().collect();
+                    let class = class
+                        .strip_prefix('!')
+                        .map_or(class.clone(), |rest| format!("^{}", rest));
+                    regex.push('[');
+                    regex.push_str(&class.replace('\\', "\\\\"));
+                    regex.push(']');
+                    i += end + 1;
+                    continue;
+                }
+                None => regex.push_str("\\["),
+            },
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    regex
+}
+
+fn is_glob(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
+fn is_hidden(name: &str) -> bool {
+    name.starts_with('.') && name != "." && name != ".."
+}
+
+fn has_supported_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| extensions.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Chemin relatif en notation `/` (independant de la plateforme)
+fn to_slash(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Parcours d'une racine (dossier ou base d'un glob)
+struct Walk<'a> {
+    root: &'a Path,
+    extensions: &'a [&'a str],
+    options: &'a ExpandOptions,
+    ignore: IgnoreMatcher,
+    /// Motif glob du chemin relatif (None = tous les fichiers supportes)
+    glob: Option<Regex>,
+    visited: HashSet<PathBuf>,
+}
+
+impl Walk<'_> {
+    fn run(mut self, out: &mut Vec<PathBuf>) -> Result<(), InputError> {
+        if let Ok(canonical) = self.root.canonicalize() {
+            self.visited.insert(canonical);
+        }
+        self.walk_dir(self.root, out)
+    }
+
+    fn walk_dir(&mut self, dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), InputError> {
+        let read = std::fs::read_dir(dir).map_err(|e| {
+            InputError::ReadError(format!(
+                "Impossible de lire le dossier {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+        let mut entries = Vec::new();
+        for entry in read {
+            entries.push(entry.map_err(|e| InputError::ReadError(e.to_string()))?);
+        }
+        entries.sort_by_key(|e| e.file_name());
+
+        for entry in entries {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if is_hidden(&name) && !self.options.include_hidden {
+                continue;
+            }
+
+            let file_type = entry
+                .file_type()
+                .map_err(|e| InputError::ReadError(e.to_string()))?;
+            let is_dir = if file_type.is_symlink() {
+                if !self.options.follow_symlinks {
+                    debug!(path = %path.display(), "Lien symbolique ignore (follow_symlinks = false)");
+                    continue;
+                }
+                match std::fs::metadata(&path) {
+                    Ok(meta) => meta.is_dir(),
+                    Err(e) => {
+                        warn!(path = %path.display(), error = %e, "Lien symbolique casse ignore");
+                        continue;
+                    }
+                }
+            } else {
+                file_type.is_dir()
+            };
+
+            let relative = to_slash(path.strip_prefix(self.root).unwrap_or(&path));
+            if self.ignore.is_ignored(&relative, is_dir) {
+                debug!(path = %path.display(), "Chemin exclu");
+                continue;
+            }
+
+            if is_dir {
+                if !self.options.recursive && self.glob.is_none() {
+                    continue;
+                }
+                if let Ok(canonical) = path.canonicalize()
+                    && !self.visited.insert(canonical)
+                {
+                    warn!(path = %path.display(), "Boucle de liens symboliques ignoree");
+                    continue;
+                }
+                self.walk_dir(&path, out)?;
+            } else if has_supported_extension(&path, self.extensions)
+                && self.glob.as_ref().is_none_or(|g| g.is_match(&relative))
+            {
+                out.push(path);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Expande les chemins d'entree en fichiers a lire.
+///
+/// - dossier : fichiers dont l'extension est dans `extensions` (recursif selon options)
+/// - glob : fichiers correspondant au motif, relativement a sa partie fixe
+/// - fichier explicite ou `-` (stdin) : conserve tel quel, sauf s'il est exclu
+pub fn expand_input_paths(
+    paths: &[PathBuf],
+    extensions: &[&str],
+    options: &ExpandOptions,
+) -> Result<Vec<PathBuf>, InputError> {
+    let mut excludes = IgnoreMatcher::default();
+    excludes.add_lines(options.excludes.iter().map(String::as_str))?;
+
+    let mut result = Vec::new();
+    for path in paths {
+        let raw = path.to_string_lossy();
+        if raw == STDIN_PATH {
+            result.push(path.clone());
+        } else if is_glob(&raw) {
+            expand_glob(&raw, extensions, options, &mut result)?;
+        } else if path.is_dir() {
+            walk_root(path, None, extensions, options, &mut result)?;
+        } else if !excludes.is_ignored(&to_slash(path), false) {
+            result.push(path.clone());
+        }
+    }
+
+    result.sort();
+    result.dedup();
+    Ok(result)
+}
+
+/// Decoupe un glob en racine fixe + motif (`docs/**/us_*.md` -> `docs`, `**/us_*.md`)
+fn expand_glob(
+    pattern: &str,
+    extensions: &[&str],
+    options: &ExpandOptions,
+    out: &mut Vec<PathBuf>,
+) -> Result<(), InputError> {
+    let normalized = pattern.replace('\\', "/");
+    let segments: Vec<&str> = normalized.split('/').collect();
+    let fixed = segments.iter().take_while(|s| !is_glob(s)).count();
+
+    let root = match segments[..fixed].join("/") {
+        r if r.is_empty() && normalized.starts_with('/') => PathBuf::from("/"),
+        r if r.is_empty() => PathBuf::from("."),
+        r => PathBuf::from(r),
+    };
+    let relative_glob = segments[fixed..].join("/");
+    let glob = Regex::new(&format!("^{}$", glob_to_regex(&relative_glob))).map_err(|e| {
+        InputError::InvalidFormat {
+            details: format!("Motif glob invalide '{}': {}", pattern, e),
+        }
+    })?;
+
+    let before = out.len();
+    if root.is_dir() {
+        walk_root(&root, Some(glob), extensions, options, out)?;
+    }
+    if out.len() == before {
+        return Err(InputError::FileNotFound {
+            path: format!("{} (aucun fichier ne correspond au motif)", pattern),
+        });
+    }
+    Ok(())
+}
+
+fn walk_root(
+    root: &Path,
+    glob: Option<Regex>,
+    extensions: &[&str],
+    options: &ExpandOptions,
+    out: &mut Vec<PathBuf>,
+) -> Result<(), InputError> {
+    let mut ignore = IgnoreMatcher::default();
+    let ignore_file = root.join(IGNORE_FILE);
+    if ignore_file.is_file() {
+        let content = std::fs::read_to_string(&ignore_file)
+            .map_err(|e| InputError::ReadError(e.to_string()))?;
+        ignore.add_lines(content.lines())?;
+    }
+    ignore.add_lines(options.excludes.iter().map(String::as_str))?;
+
+    let with_root = out.len();
+    Walk {
+        root,
+        extensions,
+        options,
+        ignore,
+        glob,
+        visited: HashSet::new(),
+    }
+    .run(out)?;
+
+    // Chemins "./x" -> "x" pour des racines implicites
+    if root == Path::new(".") {
+        for path in &mut out[with_root..] {
+            if let Ok(stripped) = path.strip_prefix(".") {
+                *path = stripped.to_path_buf();
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const EXTENSIONS: &[&str] = &["md", "yaml"];
+
+    fn names(root: &Path, paths: &[PathBuf]) -> Vec<String> {
+        paths
+            .iter()
+            .map(|p| to_slash(p.strip_prefix(root).unwrap_or(p)))
+            .collect()
+    }
+
+    fn tree() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("docs/paiements")).unwrap();
+        fs::create_dir_all(root.join("docs/drafts")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join("top.md"), "# US").unwrap();
+        fs::write(root.join("docs/us_cartes.md"), "# US").unwrap();
+        fs::write(root.join("docs/notes.txt"), "x").unwrap();
+        fs::write(root.join("docs/paiements/us_virement.md"), "# US").unwrap();
+        fs::write(root.join("docs/paiements/backlog.yaml"), "stories: []").unwrap();
+        fs::write(root.join("docs/drafts/us_brouillon.md"), "# US").unwrap();
+        fs::write(root.join(".git/HEAD.md"), "x").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_glob_to_regex() {
+        let re = Regex::new(&format!("^{}$", glob_to_regex("**/us_*.md"))).unwrap();
+        assert!(re.is_match("us_a.md"));
+        assert!(re.is_match("a/b/us_a.md"));
+        assert!(!re.is_match("a/us_a.mdx"));
+        let re = Regex::new(&format!("^{}$", glob_to_regex("us_[!x]?.md"))).unwrap();
+        assert!(re.is_match("us_ab.md"));
+        assert!(!re.is_match("us_xb.md"));
+    }
+
+    #[test]
+    fn test_recursive_skips_hidden_and_is_sorted() {
+        let dir = tree();
+        let result = expand_input_paths(
+            &[dir.path().to_path_buf()],
+            EXTENSIONS,
+            &ExpandOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            names(dir.path(), &result),
+            vec![
+                "docs/drafts/us_brouillon.md",
+                "docs/paiements/backlog.yaml",
+                "docs/paiements/us_virement.md",
+                "docs/us_cartes.md",
+                "top.md",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_glob_pattern_and_excludes() {
+        let dir = tree();
+        let pattern = dir.path().join("docs/**/us_*.md");
+        let options = ExpandOptions {
+            excludes: vec!["drafts/".into()],
+            ..ExpandOptions::default()
+        };
+        let result = expand_input_paths(&[pattern], EXTENSIONS, &options).unwrap();
+        assert_eq!(
+            names(dir.path(), &result),
+            vec!["docs/paiements/us_virement.md", "docs/us_cartes.md"]
+        );
+    }
+
+    #[test]
+    fn test_glob_without_match_is_an_error() {
+        let dir = tree();
+        let pattern = dir.path().join("docs/**/absent_*.md");
+        let err =
+            expand_input_paths(&[pattern], EXTENSIONS, &ExpandOptions::default()).unwrap_err();
+        assert!(matches!(err, InputError::FileNotFound { .. }));
+        assert!(err.to_string().contains("absent_*.md"));
+
+        let missing_root = dir.path().join("inexistant/*.md");
+        assert!(
+            expand_input_paths(&[missing_root], EXTENSIONS, &ExpandOptions::default()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_specforgeignore_with_negation() {
+        let dir = tree();
+        fs::write(
+            dir.path().join(IGNORE_FILE),
+            "# brouillons\ndocs/drafts/\n*.yaml\n!docs/paiements/backlog.yaml\ntop.md\n",
+        )
+        .unwrap();
+        let result = expand_input_paths(
+            &[dir.path().to_path_buf()],
+            EXTENSIONS,
+            &ExpandOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            names(dir.path(), &result),
+            vec![
+                "docs/paiements/backlog.yaml",
+                "docs/paiements/us_virement.md",
+                "docs/us_cartes.md",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stdin_and_explicit_files_kept() {
+        let dir = tree();
+        let hidden = dir.path().join(".git/HEAD.md");
+        let result = expand_input_paths(
+            &[PathBuf::from(STDIN_PATH), hidden.clone(), hidden.clone()],
+            EXTENSIONS,
+            &ExpandOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(result, vec![hidden, PathBuf::from(STDIN_PATH)]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_loop_is_not_followed_forever() {
+        let dir = tree();
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("docs/loop")).unwrap();
+
+        let default = expand_input_paths(
+            &[dir.path().to_path_buf()],
+            EXTENSIONS,
+            &ExpandOptions::default(),
+        )
+        .unwrap();
+        let options = ExpandOptions {
+            follow_symlinks: true,
+            ..ExpandOptions::default()
+        };
+        let followed =
+            expand_input_paths(&[dir.path().to_path_buf()], EXTENSIONS, &options).unwrap();
+        assert_eq!(default, followed);
+    }
+}
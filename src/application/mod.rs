@@ -1,6 +1,9 @@
 pub mod generate_tests_service;
+pub mod input_paths;
 pub mod json_utils;
 pub mod llm_retry;
 pub mod pipeline;
 pub mod pipeline_events;
+pub mod reader_registry;
 pub mod refine_service;
+pub mod translate_service;
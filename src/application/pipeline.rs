@@ -2,39 +2,270 @@
 
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tracing::info;
+use tokio::io::AsyncReadExt;
+use tracing::{info, warn};
 
-use crate::adapters::input::docx_reader::DocxReader;
+use crate::adapters::input::MAX_INPUT_FILE_SIZE;
+use crate::adapters::input::execution_results_reader::ExecutionResultsReader;
 use crate::adapters::input::markdown_reader::MarkdownReader;
-use crate::adapters::input::pdf_reader::PdfReader;
 use crate::adapters::input::yaml_reader::YamlReader;
-use crate::adapters::output::gherkin_writer::GherkinWriter;
-use crate::adapters::output::markdown_writer::MarkdownWriter;
+use crate::adapters::output::docx_spec_writer::DocxSpecWriter;
+use crate::adapters::output::gherkin_writer::{FeatureWriteOutcome, GherkinWriter};
+use crate::adapters::output::html_report_writer::HtmlReportWriter;
+use crate::adapters::output::impact_writer::ImpactWriter;
+use crate::adapters::output::json_writer::JsonWriter;
+use crate::adapters::output::manifest_writer::ManifestWriter;
+use crate::adapters::output::markdown_writer::{EpicIndexEntry, MarkdownWriter};
+use crate::adapters::output::step_definitions_writer::{StepDefinitionsWriter, StepFileOutcome};
+use crate::adapters::output::step_glossary_writer::StepGlossaryWriter;
+use crate::adapters::output::test_export_writer::TestExportWriter;
 use crate::adapters::output::traceability_writer::TraceabilityWriter;
 use crate::application::generate_tests_service::GenerateTestsService;
+use crate::application::input_paths::{ExpandOptions, STDIN_PATH, expand_input_paths};
+use crate::application::reader_registry::ReaderRegistry;
 use crate::application::refine_service::RefineService;
-use crate::domain::errors::DomainError;
+use crate::application::translate_service::TranslateService;
+use crate::domain::dedupe::{
+    DEFAULT_SIMILARITY_THRESHOLD, DedupeStrategy, DuplicatePair, apply_dedupe, find_near_duplicates,
+};
+use crate::domain::epic::{
+    CrossEpicReference, EpicSpecification, UNASSIGNED_EPIC, cited_requirement_ids,
+    find_cross_epic_references, renumber_across_epics,
+};
+use crate::domain::errors::{DomainError, InputError, ValidationError};
+use crate::domain::gherkin_refactor::refactor_test_suite;
+use crate::domain::impact::{ImpactReport, analyze_impact, impacted_subspec, splice_regenerated};
+use crate::domain::naming::ascii_slug;
+use crate::domain::output_format::OutputFormat;
+use crate::domain::output_manifest::{ArtifactKind, OutputManifest, content_hash};
+use crate::domain::regeneration::{
+    MergeOutcome, RequirementSelection, merge_regenerated_requirements,
+};
 use crate::domain::specification::Specification;
-use crate::domain::test_case::TestSuite;
+use crate::domain::step_definitions::{StepGrouping, StepTarget, collect_step_definitions};
+use crate::domain::step_vocabulary::{
+    DEFAULT_STEP_SIMILARITY, StepVocabularyMode, build_step_glossary, normalize_step_vocabulary,
+};
+use crate::domain::story_lint::{StoryLintReport, lint_story_sets};
+use crate::domain::test_case::{TestExportFormat, TestSuite};
+use crate::domain::traceability::build_traceability_matrix;
 use crate::domain::user_story::{Language, UserStorySet};
+use crate::domain::verification::{
+    VerificationSummary, VerificationVerdict, apply_execution_results,
+};
 use crate::infrastructure::config::Config;
 use crate::ports::input_reader::InputReader;
 use crate::ports::llm_service::LlmService;
+use crate::ports::output_writer::{OutputLayout, OutputWriter, WrittenArtifact};
 use crate::ports::template_engine::TemplateEngine;
 
 /// Resultat du pipeline complet
 pub struct PipelineResult {
     pub specification: Specification,
     pub test_suite: TestSuite,
+    /// Specification Markdown, ou DOCX si `output.spec_format` vaut `docx`
     pub spec_path: std::path::PathBuf,
+    /// Specification DOCX (`output.spec_format` docx ou both)
+    pub spec_docx_path: Option<std::path::PathBuf>,
     pub feature_paths: Vec<std::path::PathBuf>,
     pub traceability_path: Option<std::path::PathBuf>,
+    /// Glossaire des etapes (`pipeline.step_vocabulary` different de `off`)
+    pub glossary_path: Option<std::path::PathBuf>,
+    /// Exports `output.test_exports` (Xray, TestRail, JUnit)
+    pub export_paths: Vec<std::path::PathBuf>,
+    /// Rapport HTML autonome (`output.html_report`)
+    pub report_path: Option<std::path::PathBuf>,
+    /// `.feature` dont des scenarios retouches a la main ont ete conserves
+    pub manual_edits: Vec<FeatureWriteOutcome>,
+    /// Toutes les sorties ecrites, y compris par les writers injectes
+    pub artifacts: Vec<WrittenArtifact>,
+    pub manifest: ManifestOutcome,
+}
+
+/// Manifeste des artefacts et nettoyage des artefacts obsoletes
+pub struct ManifestOutcome {
+    pub manifest_path: PathBuf,
+    /// Artefacts d'un run precedent qui ne sont plus produits, laisses en place
+    pub stale_paths: Vec<PathBuf>,
+    /// Artefacts obsoletes supprimes (`output.clean_stale` / `--clean`)
+    pub removed_paths: Vec<PathBuf>,
+}
+
+/// Resultat d'une traduction (spec + tests re-rendus dans la langue cible)
+pub struct TranslationResult {
+    pub specification: Specification,
+    pub test_suite: TestSuite,
+    pub language: Language,
+    pub spec_path: PathBuf,
+    pub feature_paths: Vec<PathBuf>,
+    /// `specification.json` et `test_suite.json` traduits
+    pub json_paths: Vec<PathBuf>,
+}
+
+/// Resultat de la generation des squelettes de step definitions
+pub struct StepScaffoldResult {
+    pub target: StepTarget,
+    /// Step definitions distinctes de la suite
+    pub definitions: usize,
+    pub files: Vec<StepFileOutcome>,
+}
+
+/// Resultat de l'analyse d'impact
+pub struct ImpactResult {
+    pub report: ImpactReport,
+    pub report_path: PathBuf,
+    /// Suite mise a jour si la regeneration a ete demandee
+    pub test_suite: Option<TestSuite>,
+    /// `.feature` et `test_suite.json` re-ecrits apres regeneration
+    pub written_paths: Vec<PathBuf>,
+}
+
+/// Resultat d'une regeneration ciblee des tests
+pub struct RegenerationResult {
+    pub test_suite: TestSuite,
+    /// Exigences effectivement regenerees
+    pub requirements: Vec<String>,
+    pub outcome: MergeOutcome,
+    /// `.feature` et `test_suite.json` re-ecrits
+    pub written_paths: Vec<PathBuf>,
+}
+
+/// Resultat de la verification des exigences par les resultats d'execution
+pub struct VerificationResult {
+    pub summary: VerificationSummary,
+    /// Exigences dont au moins un scenario couvrant a echoue
+    pub failed_requirements: Vec<String>,
+    pub report_path: PathBuf,
+}
+
+/// Resultat du pipeline pour un epic
+pub struct EpicPipelineOutput {
+    pub epic: String,
+    pub specification: Specification,
+    pub test_suite: TestSuite,
+    pub spec_path: PathBuf,
+    pub spec_docx_path: Option<PathBuf>,
+    pub feature_paths: Vec<PathBuf>,
+    pub traceability_path: Option<PathBuf>,
+    pub glossary_path: Option<PathBuf>,
+    pub export_paths: Vec<PathBuf>,
+    pub report_path: Option<PathBuf>,
+    pub manual_edits: Vec<FeatureWriteOutcome>,
+    pub artifacts: Vec<WrittenArtifact>,
+}
+
+/// Resultat du pipeline par epic (une spec et un dossier de features par epic)
+pub struct EpicPipelineResult {
+    pub epics: Vec<EpicPipelineOutput>,
+    pub cross_references: Vec<CrossEpicReference>,
+    pub index_path: PathBuf,
+    pub manifest: ManifestOutcome,
+}
+
+/// Artefact a inscrire au manifeste : chemin, nature, IDs source
+type Artifact = (PathBuf, ArtifactKind, Vec<String>);
+
+/// Glossaire des etapes d'une spec (hors writers : ecrit avant les sorties)
+fn glossary_artifact(path: &Path, spec: &Specification) -> Artifact {
+    let story_ids = spec.user_scenarios.iter().map(|s| s.id.clone()).collect();
+    (path.to_path_buf(), ArtifactKind::StepGlossary, story_ids)
+}
+
+/// Chemins des sorties ecrites, par nature d'artefact
+struct OutputPaths {
+    spec_path: PathBuf,
+    spec_docx_path: Option<PathBuf>,
+    feature_paths: Vec<PathBuf>,
+    traceability_path: Option<PathBuf>,
+    export_paths: Vec<PathBuf>,
+    report_path: Option<PathBuf>,
+    manual_edits: Vec<FeatureWriteOutcome>,
+}
+
+impl OutputPaths {
+    fn from_artifacts(artifacts: &[WrittenArtifact]) -> Self {
+        let is_docx = |a: &&WrittenArtifact| a.path.extension().is_some_and(|e| e == "docx");
+        let of_kind = |kind: ArtifactKind| artifacts.iter().filter(move |a| a.kind == kind);
+        let spec_docx_path = of_kind(ArtifactKind::Specification)
+            .find(is_docx)
+            .map(|a| a.path.clone());
+        // Markdown de preference ; `Config::validate` garantit une specification
+        let spec_path = of_kind(ArtifactKind::Specification)
+            .find(|a| !is_docx(a))
+            .map(|a| a.path.clone())
+            .or_else(|| spec_docx_path.clone())
+            .unwrap_or_default();
+
+        Self {
+            spec_path,
+            spec_docx_path,
+            feature_paths: of_kind(ArtifactKind::Feature)
+                .map(|a| a.path.clone())
+                .collect(),
+            traceability_path: of_kind(ArtifactKind::Traceability)
+                .next()
+                .map(|a| a.path.clone()),
+            export_paths: of_kind(ArtifactKind::TestExport)
+                .map(|a| a.path.clone())
+                .collect(),
+            report_path: of_kind(ArtifactKind::HtmlReport)
+                .next()
+                .map(|a| a.path.clone()),
+            // Ecritures ayant conserve des editions manuelles
+            manual_edits: of_kind(ArtifactKind::Feature)
+                .filter(|a| {
+                    a.merge
+                        .as_ref()
+                        .is_some_and(|m| !m.preserved.is_empty() || !m.conflicts.is_empty())
+                })
+                .map(|a| FeatureWriteOutcome {
+                    path: a.path.clone(),
+                    merge: a.merge.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Lit un flux texte en appliquant la limite des fichiers d'entree
+/// (`MAX_INPUT_FILE_SIZE`)
+async fn read_limited(
+    reader: impl tokio::io::AsyncRead + Unpin,
+    label: &str,
+) -> Result<String, InputError> {
+    // Lecture en octets : la limite peut tomber au milieu d'un caractere UTF-8,
+    // le decodage n'a lieu qu'une fois la taille verifiee
+    let mut bytes = Vec::new();
+    reader
+        .take(MAX_INPUT_FILE_SIZE + 1)
+        .read_to_end(&mut bytes)
+        .await
+        .map_err(|e| InputError::ReadError(format!("Lecture de {}: {}", label, e)))?;
+    if bytes.len() as u64 > MAX_INPUT_FILE_SIZE {
+        return Err(InputError::ReadError(format!(
+            "Fichier trop volumineux: {} (plus de {} octets, limite: {} octets)",
+            label, MAX_INPUT_FILE_SIZE, MAX_INPUT_FILE_SIZE,
+        )));
+    }
+    String::from_utf8(bytes)
+        .map_err(|e| InputError::ReadError(format!("Lecture de {}: UTF-8 invalide ({})", label, e)))
+}
+
+/// Nom de dossier derive d'un nom d'epic (slug ASCII, comme les .feature)
+fn epic_dir_name(epic: &str) -> String {
+    ascii_slug(epic, '_')
 }
 
 /// Orchestrateur du pipeline
 pub struct Pipeline {
     refine_service: RefineService,
     generate_service: GenerateTestsService,
+    translate_service: TranslateService,
+    readers: ReaderRegistry,
+    /// Writers injectes, appeles apres ceux de `output.formats`
+    writers: Vec<Arc<dyn OutputWriter>>,
+    /// Templates des prompts et des documents de sortie
+    templates: Arc<dyn TemplateEngine>,
     config: Config,
 }
 
@@ -59,15 +290,45 @@ impl Pipeline {
             language,
             config.pipeline.max_retries,
         )
+        .with_token_budget(config.pipeline.token_budget)
+        .with_step_vocabulary_prompt(config.pipeline.step_vocabulary_prompt);
+
+        let translate_service = TranslateService::new(
+            Arc::clone(&llm),
+            Arc::clone(&templates),
+            config.pipeline.max_retries,
+        )
         .with_token_budget(config.pipeline.token_budget);
 
         Self {
             refine_service,
             generate_service,
+            translate_service,
+            readers: ReaderRegistry::default(),
+            writers: Vec::new(),
+            templates,
             config,
         }
     }
 
+    /// Ajoute un lecteur d'entree (prioritaire sur les lecteurs par defaut)
+    pub fn with_reader(mut self, reader: Arc<dyn InputReader>) -> Self {
+        self.readers.register(reader);
+        self
+    }
+
+    /// Ajoute un writer de sortie, appele apres ceux de `output.formats`
+    /// (ses fichiers sous le dossier de sortie sont inscrits au manifeste)
+    pub fn with_writer(mut self, writer: Arc<dyn OutputWriter>) -> Self {
+        self.writers.push(writer);
+        self
+    }
+
+    /// Lecteurs d'entree enregistres
+    pub fn readers(&self) -> &ReaderRegistry {
+        &self.readers
+    }
+
     /// Retourne la configuration du pipeline
     pub fn config(&self) -> &Config {
         &self.config
@@ -75,29 +336,7 @@ impl Pipeline {
 
     /// Lit les user stories depuis un fichier (detecte le format)
     pub async fn read_stories(&self, input_path: &Path) -> Result<UserStorySet, DomainError> {
-        let extension = input_path
-            .extension()
-            .and_then(|e| e.to_str())
-            .unwrap_or("");
-
-        let story_set = match extension {
-            "yaml" | "yml" => {
-                let reader = YamlReader::new();
-                reader.read_stories(input_path).await?
-            }
-            "pdf" => {
-                let reader = PdfReader::new();
-                reader.read_stories(input_path).await?
-            }
-            "docx" => {
-                let reader = DocxReader::new();
-                reader.read_stories(input_path).await?
-            }
-            _ => {
-                let reader = MarkdownReader::new();
-                reader.read_stories(input_path).await?
-            }
-        };
+        let story_set = self.readers.read_stories(input_path).await?;
 
         info!(
             count = story_set.stories.len(),
@@ -108,47 +347,65 @@ impl Pipeline {
         Ok(story_set)
     }
 
-    /// Extensions de fichiers supportees
-    const SUPPORTED_EXTENSIONS: &'static [&'static str] =
-        &["md", "markdown", "yaml", "yml", "pdf", "docx"];
-
-    /// Expande les chemins : dossiers → fichiers supportes, fichiers → tels quels
-    pub fn expand_paths(paths: &[PathBuf]) -> Result<Vec<PathBuf>, DomainError> {
-        let mut result = Vec::new();
-        for path in paths {
-            if path.is_dir() {
-                let entries = std::fs::read_dir(path).map_err(|e| {
-                    DomainError::Input(crate::domain::errors::InputError::ReadError(format!(
-                        "Impossible de lire le dossier {}: {}",
-                        path.display(),
-                        e
-                    )))
-                })?;
-                for entry in entries {
-                    let entry = entry.map_err(|e| {
-                        DomainError::Input(crate::domain::errors::InputError::ReadError(
-                            e.to_string(),
-                        ))
-                    })?;
-                    let entry_path = entry.path();
-                    if entry_path.is_file()
-                        && let Some(ext) = entry_path.extension().and_then(|e| e.to_str())
-                        && Self::SUPPORTED_EXTENSIONS.contains(&ext)
-                    {
-                        result.push(entry_path);
-                    }
-                }
-            } else {
-                result.push(path.clone());
-            }
+    /// Expande les chemins : dossiers (recursif), globs et exclusions → fichiers
+    /// des formats supportes par les lecteurs par defaut
+    pub fn expand_paths(
+        paths: &[PathBuf],
+        options: &ExpandOptions,
+    ) -> Result<Vec<PathBuf>, DomainError> {
+        let readers = ReaderRegistry::default();
+        Ok(expand_input_paths(paths, &readers.extensions(), options)?)
+    }
+
+    /// Expande les chemins avec les formats des lecteurs de ce pipeline
+    /// (lecteurs par defaut et lecteurs enregistres)
+    pub fn expand_with_readers(
+        &self,
+        paths: &[PathBuf],
+        options: &ExpandOptions,
+    ) -> Result<Vec<PathBuf>, DomainError> {
+        Ok(expand_input_paths(
+            paths,
+            &self.readers.extensions(),
+            options,
+        )?)
+    }
+
+    /// Options d'expansion issues de la section `paths` de la configuration
+    pub fn expand_options(&self) -> ExpandOptions {
+        let paths = &self.config.paths;
+        ExpandOptions {
+            recursive: paths.recursive,
+            include_hidden: paths.include_hidden,
+            follow_symlinks: paths.follow_symlinks,
+            excludes: paths.exclude.clone(),
         }
-        result.sort();
-        Ok(result)
     }
 
-    /// Lit les user stories depuis plusieurs fichiers et les fusionne
-    pub async fn read_stories_multi(&self, paths: &[PathBuf]) -> Result<UserStorySet, DomainError> {
-        let expanded = Self::expand_paths(paths)?;
+    /// Lit des user stories depuis l'entree standard (Markdown, ou YAML si le
+    /// contenu commence par une cle `stories:`/`epics:`)
+    async fn read_stdin_stories(&self) -> Result<UserStorySet, DomainError> {
+        let content = read_limited(tokio::io::stdin(), STDIN_PATH).await?;
+
+        let mut story_set = if YamlReader::looks_like_yaml(&content) {
+            YamlReader::new().parse_content(&content)?
+        } else {
+            MarkdownReader::new().parse_content(&content)?
+        };
+        story_set.source_files = vec![STDIN_PATH.to_string()];
+        info!(
+            count = story_set.stories.len(),
+            "User stories lues depuis stdin"
+        );
+        Ok(story_set)
+    }
+
+    /// Lit les user stories de plusieurs fichiers (un `UserStorySet` par fichier)
+    pub async fn read_story_sets(
+        &self,
+        paths: &[PathBuf],
+    ) -> Result<Vec<UserStorySet>, DomainError> {
+        let expanded = self.expand_with_readers(paths, &self.expand_options())?;
         if expanded.is_empty() {
             return Err(DomainError::Input(
                 crate::domain::errors::InputError::NoStoriesFound,
@@ -157,16 +414,86 @@ impl Pipeline {
 
         let mut sets = Vec::new();
         for path in &expanded {
-            let set = self.read_stories(path).await?;
+            let set = if path.as_os_str() == STDIN_PATH {
+                self.read_stdin_stories().await?
+            } else {
+                self.read_stories(path).await?
+            };
             sets.push(set);
         }
+        Ok(sets)
+    }
+
+    /// Lint INVEST des user stories (sans appel LLM)
+    pub async fn lint_stories(&self, paths: &[PathBuf]) -> Result<StoryLintReport, DomainError> {
+        let sets = self.read_story_sets(paths).await?;
+        Ok(lint_story_sets(&sets, self.config.pipeline.token_budget))
+    }
+
+    /// Pre-etape du pipeline : lint des stories selon `pipeline.story_lint`
+    fn preflight_lint(&self, sets: &[UserStorySet]) -> Result<(), DomainError> {
+        let strict = match self.config.pipeline.story_lint.as_str() {
+            "off" => return Ok(()),
+            "strict" => true,
+            _ => false,
+        };
+
+        let report = lint_story_sets(sets, self.config.pipeline.token_budget);
+        for issue in &report.issues {
+            warn!(
+                story = %issue.story,
+                rule = issue.rule.code(),
+                severity = %issue.severity,
+                "{}",
+                issue.message
+            );
+        }
 
-        let merged = UserStorySet::merge(sets);
+        if strict && report.is_failure(true) {
+            return Err(DomainError::Validation(ValidationError::StoryLintFailed {
+                errors: report.error_count(),
+                warnings: report.warning_count(),
+            }));
+        }
+        Ok(())
+    }
+
+    /// Signale les quasi-doublons et applique `pipeline.dedupe` au set fusionne
+    fn dedupe_stories(&self, merged: &mut UserStorySet, duplicates: &[DuplicatePair]) {
+        for pair in duplicates {
+            warn!(
+                first = %pair.first_label,
+                first_file = pair.first_source.as_deref().unwrap_or("-"),
+                duplicate = %pair.second_label,
+                duplicate_file = pair.second_source.as_deref().unwrap_or("-"),
+                similarity = format!("{:.2}", pair.similarity),
+                conflicting = pair.is_conflicting(),
+                "Story probablement dupliquee"
+            );
+        }
+
+        let strategy = DedupeStrategy::from_code(&self.config.pipeline.dedupe)
+            .unwrap_or(DedupeStrategy::Report);
+        let removed = apply_dedupe(merged, duplicates, strategy);
+        if removed > 0 {
+            info!(removed, strategy = %self.config.pipeline.dedupe, "Doublons retires");
+        }
+    }
+
+    /// Lit les user stories depuis plusieurs fichiers et les fusionne
+    pub async fn read_stories_multi(&self, paths: &[PathBuf]) -> Result<UserStorySet, DomainError> {
+        let sets = self.read_story_sets(paths).await?;
+        let files = sets.len();
+        self.preflight_lint(&sets)?;
+
+        let duplicates = find_near_duplicates(&sets, DEFAULT_SIMILARITY_THRESHOLD);
+        let mut merged = UserStorySet::merge(sets);
+        self.dedupe_stories(&mut merged, &duplicates);
         info!(
             count = merged.stories.len(),
-            files = expanded.len(),
+            files = files,
             "User stories fusionnees depuis {} fichier(s)",
-            expanded.len()
+            files
         );
         Ok(merged)
     }
@@ -192,15 +519,7 @@ impl Pipeline {
     ) -> Result<Specification, DomainError> {
         let spec = self.refine_service.refine(story_set, constitution).await?;
 
-        // Ecrire la spec
-        let writer = MarkdownWriter::new();
-        let spec_path = writer.write(&spec, output_dir).await.map_err(|e| {
-            DomainError::Refinement(crate::domain::errors::RefinementError::LlmFailed {
-                details: e.to_string(),
-            })
-        })?;
-
-        info!(path = %spec_path.display(), "Specification ecrite");
+        self.write_specification(&spec, output_dir).await?;
 
         Ok(spec)
     }
@@ -211,91 +530,599 @@ impl Pipeline {
         spec: &Specification,
         output_dir: &Path,
     ) -> Result<TestSuite, DomainError> {
-        let suite = self.generate_service.generate(spec).await?;
+        let mut suite = self.generate_service.generate(spec).await?;
+        self.normalize_steps(&mut suite, output_dir).await?;
+        self.refactor_gherkin(spec, &mut suite);
 
         // Ecrire les fichiers .feature
-        let language = match self.config.output.gherkin_language.as_str() {
-            "en" => Language::English,
-            _ => Language::French,
+        let language = Language::from_code(&self.config.output.gherkin_language);
+        let outcomes = self
+            .write_features(language, &suite, output_dir)
+            .await
+            .map_err(|e| {
+                DomainError::Generation(crate::domain::errors::GenerationError::GherkinFailed {
+                    details: e.to_string(),
+                })
+            })?;
+        for outcome in &outcomes {
+            info!(path = %outcome.path.display(), "Feature ecrite");
+        }
+
+        Ok(suite)
+    }
+
+    /// Traduit une specification et ses tests, puis les re-ecrit dans la
+    /// langue cible (spec Markdown, .feature, JSON)
+    pub async fn translate(
+        &self,
+        spec: &Specification,
+        suite: &TestSuite,
+        target: Language,
+        output_dir: &Path,
+    ) -> Result<TranslationResult, DomainError> {
+        let translated = self
+            .translate_service
+            .translate(spec, suite, target)
+            .await?;
+        let write_error = |e: anyhow::Error| {
+            DomainError::Translation(crate::domain::errors::TranslationError::OutputFailed {
+                details: e.to_string(),
+            })
         };
-        let writer = GherkinWriter::new(language);
 
-        for feature in &suite.features {
-            let path = writer
-                .write_feature(feature, output_dir)
+        let spec_path = MarkdownWriter::with_templates(Arc::clone(&self.templates))
+            .write(&translated.specification, &output_dir.join("specs"))
+            .await
+            .map_err(write_error)?;
+
+        let feature_paths: Vec<PathBuf> = self
+            .write_features(target, &translated.test_suite, &output_dir.join("features"))
+            .await
+            .map_err(write_error)?
+            .into_iter()
+            .map(|outcome| outcome.path)
+            .collect();
+
+        let json_writer = JsonWriter::new();
+        let json_paths = vec![
+            json_writer
+                .write_specification(&translated.specification, output_dir)
                 .await
-                .map_err(|e| {
-                    DomainError::Generation(crate::domain::errors::GenerationError::GherkinFailed {
+                .map_err(write_error)?,
+            json_writer
+                .write_suite(&translated.test_suite, output_dir)
+                .await
+                .map_err(write_error)?,
+        ];
+
+        info!(
+            language = %target,
+            segments = translated.segments,
+            features = feature_paths.len(),
+            "Traduction ecrite"
+        );
+
+        Ok(TranslationResult {
+            specification: translated.specification,
+            test_suite: translated.test_suite,
+            language: target,
+            spec_path,
+            feature_paths,
+            json_paths,
+        })
+    }
+
+    /// Genere les squelettes de step definitions d'une suite de tests. Les
+    /// entites de la specification servent au regroupement par entite ; les
+    /// steps deja presents dans `output_dir` sont conserves.
+    pub async fn scaffold_steps(
+        &self,
+        spec: Option<&Specification>,
+        suite: &TestSuite,
+        target: StepTarget,
+        grouping: StepGrouping,
+        output_dir: &Path,
+    ) -> Result<StepScaffoldResult, DomainError> {
+        let entities = spec.map(|s| s.key_entities.as_slice()).unwrap_or_default();
+        let definitions = collect_step_definitions(suite, entities, grouping);
+
+        let files = StepDefinitionsWriter::new(target)
+            .write(&definitions, output_dir)
+            .await
+            .map_err(|e| {
+                DomainError::Generation(
+                    crate::domain::errors::GenerationError::StepScaffoldFailed {
                         details: e.to_string(),
-                    })
-                })?;
-            info!(path = %path.display(), "Feature ecrite");
+                    },
+                )
+            })?;
+
+        info!(
+            target = %target,
+            definitions = definitions.len(),
+            files = files.len(),
+            added = files.iter().map(|f| f.added).sum::<usize>(),
+            "Step definitions ecrites"
+        );
+
+        Ok(StepScaffoldResult {
+            target,
+            definitions: definitions.len(),
+            files,
+        })
+    }
+
+    /// Analyse l'impact d'un changement de spec sur la suite existante et,
+    /// si demande, regenere uniquement les exigences impactees
+    pub async fn impact(
+        &self,
+        old: &Specification,
+        new: &Specification,
+        suite: &TestSuite,
+        regenerate: bool,
+        output_dir: &Path,
+    ) -> Result<ImpactResult, DomainError> {
+        let report = analyze_impact(old, new, suite);
+        let write_error = |e: anyhow::Error| {
+            DomainError::Generation(crate::domain::errors::GenerationError::GherkinFailed {
+                details: e.to_string(),
+            })
+        };
+
+        let report_path = ImpactWriter::new()
+            .write(&report, output_dir)
+            .await
+            .map_err(write_error)?;
+        info!(
+            changes = report.changes.len(),
+            scenarios = report.scenarios.len(),
+            path = %report_path.display(),
+            "Rapport d'impact ecrit"
+        );
+
+        if !regenerate || report.is_empty() {
+            return Ok(ImpactResult {
+                report,
+                report_path,
+                test_suite: None,
+                written_paths: Vec::new(),
+            });
         }
 
-        Ok(suite)
+        let mut updated = suite.clone();
+        let regenerated = if report.requirements_to_generate.is_empty() {
+            TestSuite {
+                features: Vec::new(),
+                ..suite.clone()
+            }
+        } else {
+            let sub = impacted_subspec(new, &report.requirements_to_generate);
+            self.generate_service.generate(&sub).await?
+        };
+        info!(
+            requirements = report.requirements_to_generate.len(),
+            scenarios = regenerated.total_scenarios,
+            "Scenarios regeneres pour les exigences impactees"
+        );
+        splice_regenerated(
+            &mut updated,
+            &report,
+            regenerated,
+            new.functional_requirements.len(),
+        );
+        updated.source_spec_id = new.id;
+        let written_paths = self.write_suite_files(&updated, output_dir).await?;
+
+        Ok(ImpactResult {
+            report,
+            report_path,
+            test_suite: Some(updated),
+            written_paths,
+        })
     }
 
-    /// Pipeline complet : US -> Spec -> Tests
-    pub async fn run_full(
+    /// Regenere les tests des seules exigences selectionnees et les fusionne
+    /// dans la suite existante
+    ///
+    /// Les scenarios ne verifiant que des exigences selectionnees sont
+    /// remplaces ; les autres sont conserves tels quels.
+    pub async fn regenerate_requirements(
         &self,
-        input_paths: &[PathBuf],
+        spec: &Specification,
+        suite: &TestSuite,
+        selection: &RequirementSelection,
         output_dir: &Path,
-        constitution: Option<&str>,
-    ) -> Result<PipelineResult, DomainError> {
-        info!("Demarrage du pipeline complet");
+    ) -> Result<RegenerationResult, DomainError> {
+        let requirements = selection.select(spec, suite);
+        let mut updated = suite.clone();
+        updated.source_spec_id = spec.id;
 
-        let specs_dir = output_dir.join("specs");
-        let features_dir = output_dir.join("features");
+        let outcome = if requirements.is_empty() {
+            warn!("Aucune exigence ne correspond a la selection");
+            updated.compute_coverage(spec.functional_requirements.len());
+            MergeOutcome::default()
+        } else {
+            let regenerated = self
+                .generate_service
+                .generate_for_requirements(spec, &requirements, suite)
+                .await?;
+            merge_regenerated_requirements(
+                &mut updated,
+                &requirements,
+                regenerated,
+                spec.functional_requirements.len(),
+            )
+        };
+        info!(
+            requirements = requirements.len(),
+            replaced = outcome.replaced,
+            added = outcome.added,
+            shared = outcome.shared.len(),
+            "Regeneration ciblee terminee"
+        );
 
-        // Etape 1: Raffinement
-        let story_set = self.read_stories_multi(input_paths).await?;
-        let spec = self.refine_service.refine(&story_set, constitution).await?;
+        let written_paths = self.write_suite_files(&updated, output_dir).await?;
+        Ok(RegenerationResult {
+            test_suite: updated,
+            requirements,
+            outcome,
+            written_paths,
+        })
+    }
 
-        let md_writer = MarkdownWriter::new();
-        let spec_path = md_writer.write(&spec, &specs_dir).await.map_err(|e| {
-            DomainError::Refinement(crate::domain::errors::RefinementError::LlmFailed {
+    /// Ecrit `manifest.json` et traite les artefacts du run precedent qui ne
+    /// sont plus produits
+    ///
+    /// Avec `output.clean_stale`, un artefact obsolete est supprime s'il n'a
+    /// pas ete modifie depuis son ecriture ; sinon il est seulement signale.
+    async fn write_manifest(
+        &self,
+        output_dir: &Path,
+        artifacts: Vec<Artifact>,
+    ) -> Result<ManifestOutcome, DomainError> {
+        let write_error = |e: anyhow::Error| {
+            DomainError::Generation(crate::domain::errors::GenerationError::GherkinFailed {
                 details: e.to_string(),
             })
-        })?;
-        info!(path = %spec_path.display(), "Specification ecrite");
+        };
+
+        let mut manifest = OutputManifest::new();
+        for (path, kind, source_ids) in artifacts {
+            let Ok(relative) = path.strip_prefix(output_dir) else {
+                warn!(path = %path.display(), "Artefact hors du dossier de sortie, non inscrit");
+                continue;
+            };
+            let content = tokio::fs::read(&path)
+                .await
+                .map_err(|e| write_error(e.into()))?;
+            let relative = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            manifest.record(relative, &content, kind, source_ids);
+        }
 
-        // Etape 2: Generation de tests
-        let suite = self.generate_service.generate(&spec).await?;
+        let writer = ManifestWriter::new();
+        let mut stale_paths = Vec::new();
+        let mut removed_paths = Vec::new();
+        if let Some(previous) = writer.read(output_dir).await {
+            for entry in manifest.stale_since(&previous) {
+                let path = output_dir.join(&entry.path);
+                let Ok(content) = tokio::fs::read(&path).await else {
+                    continue;
+                };
+                let untouched = content_hash(&content) == entry.hash;
+                if self.config.output.clean_stale && untouched {
+                    tokio::fs::remove_file(&path)
+                        .await
+                        .map_err(|e| write_error(e.into()))?;
+                    // Dossier d'epic devenu vide : best effort
+                    if let Some(parent) = path.parent() {
+                        _ = tokio::fs::remove_dir(parent).await;
+                    }
+                    info!(path = %path.display(), "Artefact obsolete supprime");
+                    removed_paths.push(path);
+                } else {
+                    warn!(
+                        path = %path.display(),
+                        modified = !untouched,
+                        "Artefact obsolete conserve"
+                    );
+                    stale_paths.push(path);
+                }
+            }
+        }
 
-        let language = match self.config.output.gherkin_language.as_str() {
-            "en" => Language::English,
-            _ => Language::French,
+        let manifest_path = writer
+            .write(&manifest, output_dir)
+            .await
+            .map_err(write_error)?;
+        info!(
+            path = %manifest_path.display(),
+            artifacts = manifest.artifacts.len(),
+            stale = stale_paths.len(),
+            removed = removed_paths.len(),
+            "Manifeste ecrit"
+        );
+        Ok(ManifestOutcome {
+            manifest_path,
+            stale_paths,
+            removed_paths,
+        })
+    }
+
+    /// Ecrit les `.feature` d'une suite ; les scenarios retouches a la main
+    /// sont conserves si `output.preserve_manual_edits` est actif
+    async fn write_features(
+        &self,
+        language: Language,
+        suite: &TestSuite,
+        features_dir: &Path,
+    ) -> Result<Vec<FeatureWriteOutcome>, anyhow::Error> {
+        GherkinWriter::new(language)
+            .with_preserve_edits(self.config.output.preserve_manual_edits)
+            .write_suite(suite, features_dir)
+            .await
+    }
+
+    /// Re-ecrit les `.feature` (sous `features/`) et `test_suite.json`
+    async fn write_suite_files(
+        &self,
+        suite: &TestSuite,
+        output_dir: &Path,
+    ) -> Result<Vec<PathBuf>, DomainError> {
+        let write_error = |e: anyhow::Error| {
+            DomainError::Generation(crate::domain::errors::GenerationError::GherkinFailed {
+                details: e.to_string(),
+            })
         };
-        let gherkin_writer = GherkinWriter::new(language);
-        let mut feature_paths = Vec::new();
-        for feature in &suite.features {
-            let path = gherkin_writer
-                .write_feature(feature, &features_dir)
+        let language = Language::from_code(&self.config.output.gherkin_language);
+        let mut written_paths: Vec<PathBuf> = self
+            .write_features(language, suite, &output_dir.join("features"))
+            .await
+            .map_err(write_error)?
+            .into_iter()
+            .map(|outcome| outcome.path)
+            .collect();
+        let suite_path = JsonWriter::new()
+            .write_suite(suite, output_dir)
+            .await
+            .map_err(write_error)?;
+        written_paths.push(suite_path);
+        Ok(written_paths)
+    }
+
+    /// Rapproche des resultats d'execution (Cucumber JSON, JUnit XML, sortie
+    /// cucumber-rs) de la suite et ecrit le rapport de verification
+    pub async fn verify(
+        &self,
+        spec: &Specification,
+        suite: &TestSuite,
+        results_path: &Path,
+        output_dir: &Path,
+    ) -> Result<VerificationResult, DomainError> {
+        let results = ExecutionResultsReader::new().read(results_path).await?;
+
+        let mut matrix = build_traceability_matrix(spec, suite);
+        apply_execution_results(&mut matrix, suite, &results);
+        let failed_requirements = matrix
+            .entries
+            .iter()
+            .filter(|e| e.verdict == Some(VerificationVerdict::Failed))
+            .map(|e| e.requirement_id.clone())
+            .collect();
+        let summary = matrix.verification.unwrap_or_default();
+
+        let report_path = TraceabilityWriter::with_templates(Arc::clone(&self.templates))
+            .write_verification(spec, suite, &results, output_dir)
+            .await
+            .map_err(|e| {
+                DomainError::Generation(crate::domain::errors::GenerationError::GherkinFailed {
+                    details: e.to_string(),
+                })
+            })?;
+
+        info!(
+            executed = results.scenarios.len(),
+            verified = summary.verified,
+            failed = summary.failed,
+            unmatched = summary.unmatched_results.len(),
+            path = %report_path.display(),
+            "Rapport de verification ecrit"
+        );
+
+        Ok(VerificationResult {
+            summary,
+            failed_requirements,
+            report_path,
+        })
+    }
+
+    /// Exporte une suite de tests vers des outils de gestion de tests
+    /// (Xray JSON, TestRail CSV, squelette JUnit)
+    pub async fn export_tests(
+        &self,
+        suite: &TestSuite,
+        formats: &[TestExportFormat],
+        output_dir: &Path,
+    ) -> Result<Vec<PathBuf>, DomainError> {
+        let language = Language::from_code(&self.config.output.gherkin_language);
+        let mut paths = Vec::with_capacity(formats.len());
+        for &format in formats {
+            let path = TestExportWriter::new(format, language)
+                .with_project_key(self.config.output.xray_project_key.clone())
+                .write(suite, output_dir)
                 .await
                 .map_err(|e| {
                     DomainError::Generation(crate::domain::errors::GenerationError::GherkinFailed {
                         details: e.to_string(),
                     })
                 })?;
-            feature_paths.push(path);
+            info!(format = %format, path = %path.display(), "Export des tests ecrit");
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// Ecrit le rapport HTML autonome (spec, features, tracabilite, metriques)
+    pub async fn write_html_report(
+        &self,
+        spec: &Specification,
+        suite: &TestSuite,
+        output_dir: &Path,
+    ) -> Result<PathBuf, DomainError> {
+        let language = Language::from_code(&self.config.output.gherkin_language);
+        let path = HtmlReportWriter::new(language)
+            .write(spec, suite, output_dir)
+            .await
+            .map_err(|e| {
+                DomainError::Generation(crate::domain::errors::GenerationError::GherkinFailed {
+                    details: e.to_string(),
+                })
+            })?;
+        info!(path = %path.display(), "Rapport HTML ecrit");
+        Ok(path)
+    }
+
+    /// Writer DOCX, avec les styles de `output.docx_reference` si renseigne
+    fn docx_writer(&self) -> Result<DocxSpecWriter, DomainError> {
+        match self.config.output.docx_reference {
+            Some(ref reference) => DocxSpecWriter::with_reference(reference).map_err(|e| {
+                DomainError::Refinement(crate::domain::errors::RefinementError::LlmFailed {
+                    details: e.to_string(),
+                })
+            }),
+            None => Ok(DocxSpecWriter::new()),
+        }
+    }
+
+    /// Ecrit la specification seule dans ses formats configures (markdown,
+    /// docx) ; sans suite, l'annexe DOCX se limite aux sources
+    async fn write_specification(
+        &self,
+        spec: &Specification,
+        specs_dir: &Path,
+    ) -> Result<(), DomainError> {
+        let write_error = |e: anyhow::Error| {
+            DomainError::Refinement(crate::domain::errors::RefinementError::LlmFailed {
+                details: e.to_string(),
+            })
+        };
+        let formats = self.config.output.resolved_formats();
+        if formats.contains(&OutputFormat::Markdown) {
+            let path = MarkdownWriter::with_templates(Arc::clone(&self.templates))
+                .write(spec, specs_dir)
+                .await
+                .map_err(write_error)?;
+            info!(path = %path.display(), "Specification ecrite");
         }
+        if formats.contains(&OutputFormat::Docx) {
+            let path = self
+                .docx_writer()?
+                .write(spec, None, specs_dir)
+                .await
+                .map_err(write_error)?;
+            info!(path = %path.display(), "Specification DOCX ecrite");
+        }
+        Ok(())
+    }
 
-        // Tracabilite
-        let traceability_path = if self.config.output.traceability {
-            let trace_writer = TraceabilityWriter::new();
-            let path = trace_writer
-                .write(&spec, &suite, output_dir)
+    /// Writers des formats de `output.formats`, suivis des writers injectes
+    fn output_writers(&self) -> Result<Vec<Arc<dyn OutputWriter>>, DomainError> {
+        let language = Language::from_code(&self.config.output.gherkin_language);
+        let mut writers: Vec<Arc<dyn OutputWriter>> = Vec::new();
+        for format in self.config.output.resolved_formats() {
+            let writer: Arc<dyn OutputWriter> = match format {
+                OutputFormat::Markdown => {
+                    Arc::new(MarkdownWriter::with_templates(Arc::clone(&self.templates)))
+                }
+                OutputFormat::Docx => Arc::new(self.docx_writer()?),
+                OutputFormat::Feature => Arc::new(
+                    GherkinWriter::new(language)
+                        .with_preserve_edits(self.config.output.preserve_manual_edits),
+                ),
+                OutputFormat::Traceability => Arc::new(TraceabilityWriter::with_templates(
+                    Arc::clone(&self.templates),
+                )),
+                OutputFormat::Json => Arc::new(JsonWriter::new()),
+                OutputFormat::Html => Arc::new(HtmlReportWriter::new(language)),
+                OutputFormat::TestExport(format) => Arc::new(
+                    TestExportWriter::new(format, language)
+                        .with_project_key(self.config.output.xray_project_key.clone()),
+                ),
+            };
+            writers.push(writer);
+        }
+        writers.extend(self.writers.iter().cloned());
+        Ok(writers)
+    }
+
+    /// Ecrit les sorties d'une specification et de sa suite avec chaque writer
+    async fn write_outputs(
+        &self,
+        spec: &Specification,
+        suite: &TestSuite,
+        layout: &OutputLayout,
+    ) -> Result<Vec<WrittenArtifact>, DomainError> {
+        let mut artifacts = Vec::new();
+        for writer in self.output_writers()? {
+            let written = writer
+                .write_outputs(spec, suite, layout)
                 .await
                 .map_err(|e| {
                     DomainError::Generation(crate::domain::errors::GenerationError::GherkinFailed {
-                        details: e.to_string(),
+                        details: format!("{}: {}", writer.format(), e),
                     })
                 })?;
-            info!(path = %path.display(), "Matrice de tracabilite ecrite");
-            Some(path)
-        } else {
-            None
+            info!(
+                format = writer.format(),
+                files = written.len(),
+                "Sorties ecrites"
+            );
+            artifacts.extend(written);
+        }
+        Ok(artifacts)
+    }
+
+    /// Pipeline complet : US -> Spec -> Tests
+    pub async fn run_full(
+        &self,
+        input_paths: &[PathBuf],
+        output_dir: &Path,
+        constitution: Option<&str>,
+    ) -> Result<PipelineResult, DomainError> {
+        info!("Demarrage du pipeline complet");
+
+        // Etape 1: Raffinement
+        let story_set = self.read_stories_multi(input_paths).await?;
+        let spec = self.refine_service.refine(&story_set, constitution).await?;
+
+        // Etape 2: Generation de tests ; en cas d'echec, la specification
+        // raffinee est tout de meme ecrite
+        let layout = OutputLayout::new(output_dir);
+        let mut suite = match self.generate_service.generate(&spec).await {
+            Ok(suite) => suite,
+            Err(e) => {
+                self.keep_specification(&spec, &layout).await;
+                return Err(e.into());
+            }
         };
+        let glossary_path = self.normalize_steps(&mut suite, output_dir).await?;
+        self.refactor_gherkin(&spec, &mut suite);
+
+        // Etape 3: Sorties (`output.formats` puis writers injectes)
+        let artifacts = self.write_outputs(&spec, &suite, &layout).await?;
+        let outputs = OutputPaths::from_artifacts(&artifacts);
+
+        let mut entries: Vec<Artifact> = artifacts
+            .iter()
+            .map(|a| (a.path.clone(), a.kind, a.source_ids.clone()))
+            .collect();
+        if let Some(ref path) = glossary_path {
+            entries.push(glossary_artifact(path, &spec));
+        }
+        let manifest = self.write_manifest(output_dir, entries).await?;
 
         info!(
             specs = spec.user_scenarios.len(),
@@ -308,9 +1135,235 @@ impl Pipeline {
         Ok(PipelineResult {
             specification: spec,
             test_suite: suite,
-            spec_path,
-            feature_paths,
-            traceability_path,
+            spec_path: outputs.spec_path,
+            spec_docx_path: outputs.spec_docx_path,
+            feature_paths: outputs.feature_paths,
+            traceability_path: outputs.traceability_path,
+            glossary_path,
+            export_paths: outputs.export_paths,
+            report_path: outputs.report_path,
+            manual_edits: outputs.manual_edits,
+            artifacts,
+            manifest,
+        })
+    }
+
+    /// Ecrit la specification seule (Markdown, DOCX, JSON selon
+    /// `output.formats`) apres un echec de generation ; une erreur d'ecriture
+    /// est journalisee sans masquer celle de la generation
+    async fn keep_specification(&self, spec: &Specification, layout: &OutputLayout) {
+        if let Err(e) = self.write_specification(spec, &layout.specs_dir).await {
+            warn!(error = %e, "Specification non ecrite apres l'echec de generation");
+        }
+        if self
+            .config
+            .output
+            .resolved_formats()
+            .contains(&OutputFormat::Json)
+        {
+            match JsonWriter::new()
+                .write_specification(spec, &layout.data_dir)
+                .await
+            {
+                Ok(path) => info!(path = %path.display(), "Specification JSON ecrite"),
+                Err(e) => {
+                    warn!(error = %e, "Specification JSON non ecrite apres l'echec de generation")
+                }
+            }
+        }
+    }
+
+    /// Background commun et Scenario Outline (`pipeline.gherkin_refactor`)
+    fn refactor_gherkin(&self, spec: &Specification, suite: &mut TestSuite) {
+        if !self.config.pipeline.gherkin_refactor {
+            return;
+        }
+        let report = refactor_test_suite(suite);
+        if !report.is_empty() {
+            suite.compute_coverage(spec.functional_requirements.len());
+            info!(
+                backgrounds = report.backgrounds_extracted,
+                hoisted_steps = report.hoisted_steps,
+                outlines = report.outlines_created,
+                folded = report.scenarios_folded,
+                "Refactoring Gherkin applique"
+            );
+        }
+    }
+
+    /// Applique `pipeline.step_vocabulary` a une suite generee et ecrit le
+    /// glossaire des etapes dans `output_dir`
+    async fn normalize_steps(
+        &self,
+        suite: &mut TestSuite,
+        output_dir: &Path,
+    ) -> Result<Option<PathBuf>, DomainError> {
+        let mode = StepVocabularyMode::from_code(&self.config.pipeline.step_vocabulary)
+            .unwrap_or(StepVocabularyMode::Report);
+        let glossary = match mode {
+            StepVocabularyMode::Off => return Ok(None),
+            StepVocabularyMode::Report => build_step_glossary(suite, DEFAULT_STEP_SIMILARITY),
+            StepVocabularyMode::Rewrite => {
+                normalize_step_vocabulary(suite, DEFAULT_STEP_SIMILARITY)
+            }
+        };
+
+        let path = StepGlossaryWriter::new()
+            .write(&glossary, output_dir)
+            .await
+            .map_err(|e| {
+                DomainError::Generation(crate::domain::errors::GenerationError::GherkinFailed {
+                    details: e.to_string(),
+                })
+            })?;
+        info!(
+            path = %path.display(),
+            distinct = glossary.distinct_before,
+            canonical = glossary.entries.len(),
+            rewritten = glossary.rewritten_steps,
+            "Glossaire des etapes ecrit"
+        );
+        Ok(Some(path))
+    }
+}
+
+impl Pipeline {
+    /// Pipeline par epic : une specification et un dossier de features par epic,
+    /// avec des IDs FR/US uniques sur tout le backlog et un index `specs/index.md`.
+    pub async fn run_by_epic(
+        &self,
+        input_paths: &[PathBuf],
+        output_dir: &Path,
+        constitution: Option<&str>,
+    ) -> Result<EpicPipelineResult, DomainError> {
+        let story_set = self.read_stories_multi(input_paths).await?;
+        let groups = story_set.group_by_epic();
+        info!(epics = groups.len(), "Demarrage du pipeline par epic");
+
+        // Etape 1: Raffinement par epic
+        let mut epic_specs = Vec::with_capacity(groups.len());
+        for (name, set) in &groups {
+            let name = name.clone().unwrap_or_else(|| UNASSIGNED_EPIC.to_string());
+            info!(epic = %name, stories = set.stories.len(), "Raffinement de l'epic");
+            let mut specification = self.refine_service.refine(set, constitution).await?;
+            specification.title = name.clone();
+            epic_specs.push(EpicSpecification {
+                name,
+                story_count: set.stories.len(),
+                specification,
+                cited_ids: cited_requirement_ids(set),
+            });
+        }
+
+        for reference in renumber_across_epics(&mut epic_specs) {
+            warn!(
+                epic = %reference.epic,
+                requirement = %reference.requirement_id,
+                target = %reference.target_id,
+                "Reference d'exigence inconnue de l'epic et absente des stories : ignoree"
+            );
+        }
+        let cross_references = find_cross_epic_references(&epic_specs);
+
+        // Etape 2: Generation des tests et ecriture des sorties par epic
+        let md_writer = MarkdownWriter::with_templates(Arc::clone(&self.templates));
+
+        let mut epics = Vec::with_capacity(epic_specs.len());
+        let mut index_entries = Vec::with_capacity(epic_specs.len());
+        // Lien "Features" de l'index seulement si les .feature sont ecrits
+        let writes_features = self
+            .config
+            .output
+            .resolved_formats()
+            .contains(&OutputFormat::Feature);
+        let mut entries: Vec<Artifact> = Vec::new();
+        let mut all_story_ids = Vec::new();
+        for epic in epic_specs {
+            let dir_name = epic_dir_name(&epic.name);
+            let spec = epic.specification;
+
+            let mut suite = self.generate_service.generate(&spec).await?;
+            let glossary_path = self
+                .normalize_steps(&mut suite, &output_dir.join("glossary").join(&dir_name))
+                .await?;
+            self.refactor_gherkin(&spec, &mut suite);
+
+            let artifacts = self
+                .write_outputs(
+                    &spec,
+                    &suite,
+                    &OutputLayout::for_epic(output_dir, &dir_name),
+                )
+                .await?;
+            let outputs = OutputPaths::from_artifacts(&artifacts);
+            info!(epic = %epic.name, path = %outputs.spec_path.display(), "Specification ecrite");
+
+            entries.extend(
+                artifacts
+                    .iter()
+                    .map(|a| (a.path.clone(), a.kind, a.source_ids.clone())),
+            );
+            if let Some(ref path) = glossary_path {
+                entries.push(glossary_artifact(path, &spec));
+            }
+            all_story_ids.extend(spec.user_scenarios.iter().map(|s| s.id.clone()));
+
+            index_entries.push(EpicIndexEntry {
+                epic: epic.name.clone(),
+                story_count: epic.story_count,
+                requirement_ids: spec
+                    .functional_requirements
+                    .iter()
+                    .map(|fr| fr.id.clone())
+                    .collect(),
+                scenario_count: suite.total_scenarios,
+                spec_link: outputs
+                    .spec_path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                features_link: writes_features.then(|| format!("../features/{}", dir_name)),
+            });
+
+            epics.push(EpicPipelineOutput {
+                epic: epic.name,
+                specification: spec,
+                test_suite: suite,
+                spec_path: outputs.spec_path,
+                spec_docx_path: outputs.spec_docx_path,
+                feature_paths: outputs.feature_paths,
+                traceability_path: outputs.traceability_path,
+                glossary_path,
+                export_paths: outputs.export_paths,
+                report_path: outputs.report_path,
+                manual_edits: outputs.manual_edits,
+                artifacts,
+            });
+        }
+
+        let index_path = md_writer
+            .write_epic_index(&index_entries, &cross_references, &output_dir.join("specs"))
+            .await
+            .map_err(|e| {
+                DomainError::Refinement(crate::domain::errors::RefinementError::LlmFailed {
+                    details: e.to_string(),
+                })
+            })?;
+        info!(
+            path = %index_path.display(),
+            epics = epics.len(),
+            cross_references = cross_references.len(),
+            "Index des epics ecrit"
+        );
+
+        entries.push((index_path.clone(), ArtifactKind::EpicIndex, all_story_ids));
+        let manifest = self.write_manifest(output_dir, entries).await?;
+
+        Ok(EpicPipelineResult {
+            epics,
+            cross_references,
+            index_path,
+            manifest,
         })
     }
 }
@@ -322,13 +1375,35 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[tokio::test]
+    async fn test_read_limited_rejects_oversized_input() {
+        let small = read_limited(&b"# US-001"[..], STDIN_PATH).await.unwrap();
+        assert_eq!(small, "# US-001");
+
+        let big = vec![b'a'; MAX_INPUT_FILE_SIZE as usize + 1];
+        let err = read_limited(&big[..], STDIN_PATH).await.unwrap_err();
+        assert!(err.to_string().contains("trop volumineux"));
+
+        // La limite tombe au milieu d'un caractere multi-octets
+        let accents = "é".repeat(MAX_INPUT_FILE_SIZE as usize / 2 + 1);
+        let err = read_limited(accents.as_bytes(), STDIN_PATH)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("trop volumineux"));
+
+        let exact = "é".repeat(MAX_INPUT_FILE_SIZE as usize / 2);
+        let content = read_limited(exact.as_bytes(), STDIN_PATH).await.unwrap();
+        assert_eq!(content.len() as u64, MAX_INPUT_FILE_SIZE);
+    }
+
     #[test]
     fn test_expand_paths_single_file() {
         let dir = TempDir::new().unwrap();
         let file = dir.path().join("story.md");
         fs::write(&file, "# US").unwrap();
 
-        let result = Pipeline::expand_paths(std::slice::from_ref(&file)).unwrap();
+        let result =
+            Pipeline::expand_paths(std::slice::from_ref(&file), &ExpandOptions::default()).unwrap();
         assert_eq!(result, vec![file]);
     }
 
@@ -340,7 +1415,8 @@ mod tests {
         fs::write(dir.path().join("c.txt"), "pas supporte").unwrap(); // ignore
         fs::write(dir.path().join("d.pdf"), "fake pdf").unwrap();
 
-        let result = Pipeline::expand_paths(&[dir.path().to_path_buf()]).unwrap();
+        let result =
+            Pipeline::expand_paths(&[dir.path().to_path_buf()], &ExpandOptions::default()).unwrap();
         // .md, .yaml, .pdf sont supportes, .txt non. Resultat trie.
         assert_eq!(result.len(), 3);
         let names: Vec<String> = result
@@ -353,20 +1429,38 @@ mod tests {
     #[test]
     fn test_expand_paths_empty_directory() {
         let dir = TempDir::new().unwrap();
-        let result = Pipeline::expand_paths(&[dir.path().to_path_buf()]).unwrap();
+        let result =
+            Pipeline::expand_paths(&[dir.path().to_path_buf()], &ExpandOptions::default()).unwrap();
         assert!(result.is_empty());
     }
 
     #[test]
-    fn test_expand_paths_nested_not_recursive() {
+    fn test_epic_dir_name() {
+        assert_eq!(epic_dir_name("Paiements SEPA"), "paiements_sepa");
+        assert_eq!(epic_dir_name("Cartes & Comptes"), "cartes_comptes");
+    }
+
+    #[test]
+    fn test_expand_paths_nested_recursive() {
         let dir = TempDir::new().unwrap();
         let sub = dir.path().join("subdir");
         fs::create_dir(&sub).unwrap();
         fs::write(sub.join("nested.md"), "# US").unwrap();
         fs::write(dir.path().join("top.md"), "# US").unwrap();
 
-        let result = Pipeline::expand_paths(&[dir.path().to_path_buf()]).unwrap();
-        // Seulement top.md, pas nested.md (pas de recursion)
+        let result =
+            Pipeline::expand_paths(&[dir.path().to_path_buf()], &ExpandOptions::default()).unwrap();
+        assert_eq!(
+            result,
+            vec![sub.join("nested.md"), dir.path().join("top.md")]
+        );
+
+        // recursive = false : seulement top.md
+        let options = ExpandOptions {
+            recursive: false,
+            ..ExpandOptions::default()
+        };
+        let result = Pipeline::expand_paths(&[dir.path().to_path_buf()], &options).unwrap();
         assert_eq!(result.len(), 1);
         assert!(result[0].file_name().unwrap() == "top.md");
     }
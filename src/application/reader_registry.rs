@@ -0,0 +1,243 @@
+//! Registre des lecteurs d'entree (InputReader)
+//!
+//! Resolution du lecteur d'un fichier : par extension d'abord, puis par
+//! reconnaissance du contenu (`InputReader::sniff`) pour les extensions
+//! inconnues. Un lecteur enregistre apres les lecteurs par defaut est
+//! prioritaire : une bibliotheque cliente peut ainsi ajouter un format ou
+//! remplacer un lecteur existant.
+
+use std::io::Read;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::adapters::input::ZIP_MAGIC;
+use crate::adapters::input::asciidoc_reader::AsciiDocReader;
+use crate::adapters::input::csv_reader::CsvReader;
+use crate::adapters::input::docx_reader::DocxReader;
+use crate::adapters::input::html_reader::HtmlReader;
+use crate::adapters::input::markdown_reader::MarkdownReader;
+use crate::adapters::input::odt_reader::OdtReader;
+use crate::adapters::input::pdf_reader::PdfReader;
+use crate::adapters::input::yaml_reader::YamlReader;
+use crate::adapters::input::zip_has_entry;
+use crate::domain::errors::InputError;
+use crate::domain::user_story::UserStorySet;
+use crate::ports::input_reader::InputReader;
+
+/// Nombre d'octets lus pour reconnaitre le contenu d'un fichier
+const SNIFF_BYTES: usize = 8 * 1024;
+
+/// Ensemble ordonne de lecteurs d'entree
+#[derive(Clone)]
+pub struct ReaderRegistry {
+    readers: Vec<Arc<dyn InputReader>>,
+}
+
+impl ReaderRegistry {
+    /// Registre vide (aucun format reconnu)
+    pub fn new() -> Self {
+        Self {
+            readers: Vec::new(),
+        }
+    }
+
+    /// Registre des formats fournis par spec-forge
+    pub fn with_default_readers() -> Self {
+        let mut registry = Self::new();
+        registry.register(Arc::new(MarkdownReader::new()));
+        registry.register(Arc::new(YamlReader::new()));
+        registry.register(Arc::new(PdfReader::new()));
+        registry.register(Arc::new(DocxReader::new()));
+        registry.register(Arc::new(OdtReader::new()));
+        registry.register(Arc::new(HtmlReader::new()));
+        registry.register(Arc::new(AsciiDocReader::new()));
+        registry.register(Arc::new(CsvReader::new()));
+        registry
+    }
+
+    /// Enregistre un lecteur (prioritaire sur ceux deja enregistres)
+    pub fn register(&mut self, reader: Arc<dyn InputReader>) {
+        self.readers.push(reader);
+    }
+
+    /// Extensions reconnues, sans doublon, dans l'ordre d'enregistrement
+    pub fn extensions(&self) -> Vec<&str> {
+        let mut extensions: Vec<&str> = Vec::new();
+        for ext in self.readers.iter().flat_map(|r| r.supported_extensions()) {
+            if !extensions.contains(ext) {
+                extensions.push(ext);
+            }
+        }
+        extensions
+    }
+
+    /// Lecteur associe a une extension (sans casse)
+    pub fn reader_for_extension(&self, extension: &str) -> Option<&Arc<dyn InputReader>> {
+        let extension = extension.to_lowercase();
+        self.readers
+            .iter()
+            .rev()
+            .find(|r| r.supported_extensions().contains(&extension.as_str()))
+    }
+
+    /// Lecteur d'un fichier : extension, puis contenu
+    pub fn resolve(&self, path: &Path) -> Result<&Arc<dyn InputReader>, InputError> {
+        if !path.exists() {
+            return Err(InputError::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if let Some(reader) = self.reader_for_extension(extension) {
+            return Ok(reader);
+        }
+
+        let head = read_head(path)?;
+        self.readers
+            .iter()
+            .rev()
+            .find(|r| r.sniff(path, &head))
+            .ok_or_else(|| InputError::UnsupportedFormat {
+                path: path.display().to_string(),
+                details: self.unsupported_details(path, extension, &head),
+            })
+    }
+
+    /// Lit les user stories d'un fichier avec le lecteur resolu
+    pub async fn read_stories(&self, path: &Path) -> Result<UserStorySet, InputError> {
+        self.resolve(path)?.read_stories(path).await
+    }
+
+    fn unsupported_details(&self, path: &Path, extension: &str, head: &[u8]) -> String {
+        if head.starts_with(ZIP_MAGIC) && zip_has_entry(path, "xl/workbook.xml") {
+            return "classeur Excel non supporte, exporter la feuille en CSV".to_string();
+        }
+        let kind = if head.starts_with(ZIP_MAGIC) {
+            "archive ZIP non reconnue".to_string()
+        } else if extension.is_empty() {
+            "contenu non reconnu".to_string()
+        } else {
+            format!("extension .{} inconnue", extension)
+        };
+        format!(
+            "{}; formats supportes: {}",
+            kind,
+            self.extensions().join(", ")
+        )
+    }
+}
+
+impl Default for ReaderRegistry {
+    fn default() -> Self {
+        Self::with_default_readers()
+    }
+}
+
+/// Premiers octets d'un fichier
+fn read_head(path: &Path) -> Result<Vec<u8>, InputError> {
+    let file = std::fs::File::open(path).map_err(|e| InputError::ReadError(e.to_string()))?;
+    let mut head = Vec::with_capacity(SNIFF_BYTES);
+    file.take(SNIFF_BYTES as u64)
+        .read_to_end(&mut head)
+        .map_err(|e| InputError::ReadError(e.to_string()))?;
+    Ok(head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::user_story::Language;
+    use async_trait::async_trait;
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+
+    fn write_file(dir: &Path, name: &str, content: &[u8]) -> std::path::PathBuf {
+        let path = dir.join(name);
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(content)
+            .unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_by_extension_case_insensitive() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_file(
+            dir.path(),
+            "stories.YML",
+            b"stories:\n  - id: 550e8400-e29b-41d4-a716-446655440000\n    title: Login\n    actor: user\n    action: log in\n    benefit: work\n",
+        );
+        let set = ReaderRegistry::default().read_stories(&path).await.unwrap();
+        assert_eq!(set.stories[0].title, "Login");
+    }
+
+    #[test]
+    fn test_sniff_unknown_extensions() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = ReaderRegistry::default();
+        let cases = [
+            ("export.txt", b"stories:\n  - title: A\n".as_slice(), "yaml"),
+            ("notes.txt", b"# Epic\n\n## Story\n".as_slice(), "md"),
+            ("scan.bin", b"%PDF-1.7\n".as_slice(), "pdf"),
+            ("page.txt", b"  <!DOCTYPE html><html>".as_slice(), "html"),
+        ];
+        for (name, content, expected) in cases {
+            let path = write_file(dir.path(), name, content);
+            let reader = registry.resolve(&path).unwrap();
+            assert!(
+                reader.supported_extensions().contains(&expected),
+                "{name} devrait etre lu comme {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_unknown_format_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_file(dir.path(), "data.bin", &[0u8, 159, 146, 150]);
+        let err = ReaderRegistry::default().resolve(&path).err().unwrap();
+        match err {
+            InputError::UnsupportedFormat { details, .. } => {
+                assert!(details.starts_with("extension .bin inconnue"));
+                assert!(details.contains("md, markdown, yaml"));
+            }
+            other => panic!("UnsupportedFormat attendu, obtenu {other:?}"),
+        }
+    }
+
+    struct TxtReader;
+
+    #[async_trait]
+    impl InputReader for TxtReader {
+        async fn read_stories(&self, path: &Path) -> Result<UserStorySet, InputError> {
+            Ok(UserStorySet {
+                stories: Vec::new(),
+                source_files: vec![path.display().to_string()],
+                language: Language::English,
+            })
+        }
+
+        fn detect_language(&self, _content: &str) -> Language {
+            Language::English
+        }
+
+        fn supported_extensions(&self) -> &[&str] {
+            &["txt", "md"]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_reader_takes_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_file(dir.path(), "stories.md", b"# Rien\n");
+        let mut registry = ReaderRegistry::default();
+        registry.register(Arc::new(TxtReader));
+
+        assert_eq!(registry.extensions().last(), Some(&"txt"));
+        let set = registry.read_stories(&path).await.unwrap();
+        assert!(set.stories.is_empty());
+        assert_eq!(set.language, Language::English);
+    }
+}
@@ -8,8 +8,10 @@ use uuid::Uuid;
 
 use crate::domain::errors::RefinementError;
 use crate::domain::specification::*;
-use crate::domain::user_story::{Language, UserStorySet};
-use crate::domain::validation::validate_specification;
+use crate::domain::user_story::UserStorySet;
+use crate::domain::validation::{
+    HierarchyIssue, check_requirement_hierarchy, validate_specification,
+};
 use crate::ports::llm_service::LlmService;
 use crate::ports::template_engine::TemplateEngine;
 
@@ -150,19 +152,6 @@ struct LlmClarification {
     impact: String,
 }
 
-/// Estime le nombre de tokens d'une UserStory (heuristique: chars / 4)
-fn estimate_story_tokens(story: &crate::domain::user_story::UserStory) -> usize {
-    let mut len = story.title.len()
-        + story.actor.len()
-        + story.action.len()
-        + story.benefit.len()
-        + story.raw_text.len();
-    for ac in &story.acceptance_criteria {
-        len += ac.len();
-    }
-    len / 4
-}
-
 /// Construit des lots adaptatifs par budget de tokens
 fn build_batches(
     stories: &[crate::domain::user_story::UserStory],
@@ -177,7 +166,7 @@ fn build_batches(
     let mut current_tokens: usize = 0;
 
     for (i, story) in stories.iter().enumerate() {
-        let tokens = estimate_story_tokens(story);
+        let tokens = story.estimated_tokens();
 
         // Si le lot courant est non-vide et ajouterrait depasse le budget → nouveau lot
         if !current_batch.is_empty() && current_tokens + tokens > token_budget {
@@ -311,10 +300,7 @@ impl RefineService {
         story_set: &UserStorySet,
         constitution: Option<&str>,
     ) -> Result<Specification, RefinementError> {
-        let language_str = match story_set.language {
-            Language::French => "francais",
-            Language::English => "english",
-        };
+        let language_str = story_set.language.keywords().name;
 
         // Construire le system prompt
         let system_context = serde_json::json!({ "language": language_str });
@@ -409,6 +395,7 @@ impl RefineService {
         let total_stories: usize = specs.iter().map(|s| s.source_stories.len()).sum();
         let title = format!("Specification ({} User Stories)", total_stories);
         let mut merged = Specification::new(title);
+        merged.language = specs[0].language;
 
         let mut fr_counter = 1usize;
         let mut us_counter = 1usize;
@@ -555,6 +542,7 @@ impl RefineService {
         };
 
         let mut spec = Specification::new(title);
+        spec.language = Some(story_set.language);
         spec.source_stories = story_set.stories.iter().map(|s| s.id).collect();
 
         // User scenarios — correler par ID/titre plutot que par index
@@ -778,13 +766,8 @@ pub enum WarningSeverity {
     Info,
 }
 
-// Reutiliser la liste canonique depuis le module de validation
-use crate::domain::validation::AMBIGUOUS_WORDS;
-
-/// Mots normatifs attendus dans les enonces (ISO 29148)
-const NORMATIVE_KEYWORDS: &[&str] = &[
-    "MUST", "SHOULD", "COULD", "SHALL", "WILL", "DOIT", "DEVRAIT", "POURRAIT",
-];
+// Listes de la langue des stories (toutes les langues si elle est inconnue)
+use crate::domain::i18n::{ambiguous_words_for, normative_keywords_for};
 
 /// Verifie si un mot est present en tant que mot entier (word boundary)
 fn contains_word(text: &str, word: &str) -> bool {
@@ -805,6 +788,8 @@ fn contains_word(text: &str, word: &str) -> bool {
 /// Valide la sortie LLM selon les criteres ISO 29148
 fn validate_llm_spec_output(spec: &Specification) -> Vec<LlmValidationWarning> {
     let mut warnings = Vec::new();
+    let normative_keywords = normative_keywords_for(spec.language);
+    let ambiguous_words = ambiguous_words_for(spec.language);
 
     // 1. Detection de doublons d'ID
     let mut seen_ids = std::collections::HashSet::new();
@@ -822,7 +807,7 @@ fn validate_llm_spec_output(spec: &Specification) -> Vec<LlmValidationWarning> {
     for fr in &spec.functional_requirements {
         // 2. Verification syntaxe normative (MUST/SHOULD/COULD)
         let upper = fr.statement.to_uppercase();
-        if !NORMATIVE_KEYWORDS.iter().any(|kw| upper.contains(kw)) {
+        if !normative_keywords.iter().any(|kw| upper.contains(kw)) {
             warnings.push(LlmValidationWarning {
                 rule: "ISO-29148-NORMATIVE".into(),
                 element_id: fr.id.clone(),
@@ -833,7 +818,7 @@ fn validate_llm_spec_output(spec: &Specification) -> Vec<LlmValidationWarning> {
 
         // 3. Detection de mots ambigus (word boundary matching)
         let lower = fr.statement.to_lowercase();
-        for word in AMBIGUOUS_WORDS {
+        for word in &ambiguous_words {
             if contains_word(&lower, word) {
                 warnings.push(LlmValidationWarning {
                     rule: "ISO-29148-UNAMBIGUOUS".into(),
@@ -889,7 +874,21 @@ fn validate_llm_spec_output(spec: &Specification) -> Vec<LlmValidationWarning> {
         }
     }
 
-    // 8. Verification acceptance scenarios complets (Given+When+Then)
+    // 8. Hierarchie des exigences derivees (parents, cycles, priorites)
+    for issue in check_requirement_hierarchy(spec) {
+        let severity = match issue {
+            HierarchyIssue::Cycle { .. } => WarningSeverity::Error,
+            _ => WarningSeverity::Warning,
+        };
+        warnings.push(LlmValidationWarning {
+            rule: "ISO-29148-HIERARCHY".into(),
+            element_id: issue.requirement_id().to_string(),
+            severity,
+            message: issue.to_string(),
+        });
+    }
+
+    // 9. Verification acceptance scenarios complets (Given+When+Then)
     for us in &spec.user_scenarios {
         for (i, ac) in us.acceptance_scenarios.iter().enumerate() {
             if ac.given.trim().is_empty() || ac.when.trim().is_empty() || ac.then.trim().is_empty()
@@ -1402,6 +1401,23 @@ That's all."#;
         assert!(warnings.iter().any(|w| w.rule == "ISO-29148-TESTABLE"));
     }
 
+    #[test]
+    fn test_validate_requirement_hierarchy() {
+        let mut spec = Specification::new("Test".into());
+        spec.functional_requirements
+            .push(make_fr("FR-001", "Le systeme DOIT A", Priority::P3));
+        let mut child = make_fr("FR-002", "Le systeme DOIT B", Priority::P1);
+        child.parent_requirement = Some("FR-001".into());
+        spec.functional_requirements.push(child);
+        let warnings = validate_llm_spec_output(&spec);
+        let hierarchy: Vec<_> = warnings
+            .iter()
+            .filter(|w| w.rule == "ISO-29148-HIERARCHY")
+            .collect();
+        assert_eq!(hierarchy.len(), 1);
+        assert_eq!(hierarchy[0].element_id, "FR-002");
+    }
+
     // -----------------------------------------------------------------------
     // Tests parse helpers
     // -----------------------------------------------------------------------
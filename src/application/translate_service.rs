@@ -0,0 +1,379 @@
+//! Service de traduction d'une specification et de ses tests Gherkin
+//!
+//! Seuls les segments de texte (`domain::translation`) sont envoyes au LLM,
+//! par lots. Un segment dont les jetons proteges (IDs, tags, placeholders)
+//! changent provoque un retry ; le resultat final est controle par
+//! `structure_differences`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::domain::errors::TranslationError;
+use crate::domain::specification::Specification;
+use crate::domain::test_case::TestSuite;
+use crate::domain::translation::{
+    TranslationSegment, apply_translations, segment_mismatch, structure_differences,
+    translatable_segments,
+};
+use crate::domain::user_story::Language;
+use crate::ports::llm_service::LlmService;
+use crate::ports::template_engine::TemplateEngine;
+
+/// Service de traduction Spec + Tests -> autre langue
+pub struct TranslateService {
+    llm: Arc<dyn LlmService>,
+    templates: Arc<dyn TemplateEngine>,
+    max_retries: usize,
+    token_budget: usize,
+}
+
+/// Schema JSON d'entree/sortie du LLM
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct LlmSegments {
+    segments: Vec<TranslationSegment>,
+}
+
+/// Specification et tests traduits
+#[derive(Debug, Clone)]
+pub struct TranslatedArtifacts {
+    pub specification: Specification,
+    pub test_suite: TestSuite,
+    /// Nombre de segments traduits
+    pub segments: usize,
+}
+
+/// Decoupe les segments en lots d'environ `token_budget` tokens
+fn build_segment_batches(
+    segments: Vec<TranslationSegment>,
+    token_budget: usize,
+) -> Vec<Vec<TranslationSegment>> {
+    let mut batches = Vec::new();
+    let mut current = Vec::new();
+    let mut current_tokens = 0usize;
+    for segment in segments {
+        let tokens = (segment.key.len() + segment.text.len()) / 4 + 1;
+        if !current.is_empty() && current_tokens + tokens > token_budget {
+            batches.push(std::mem::take(&mut current));
+            current_tokens = 0;
+        }
+        current_tokens += tokens;
+        current.push(segment);
+    }
+    if !current.is_empty() {
+        batches.push(current);
+    }
+    batches
+}
+
+/// Raison de rejeter la traduction d'un lot (segment manquant ou jetons modifies)
+fn batch_mismatch(batch: &[TranslationSegment], output: &LlmSegments) -> Option<String> {
+    let translated: HashMap<&str, &str> = output
+        .segments
+        .iter()
+        .map(|s| (s.key.as_str(), s.text.as_str()))
+        .collect();
+    batch
+        .iter()
+        .find_map(|segment| match translated.get(segment.key.as_str()) {
+            None => Some(format!("segment {} manquant", segment.key)),
+            Some(text) => segment_mismatch(&segment.text, text)
+                .map(|reason| format!("segment {}: {}", segment.key, reason)),
+        })
+}
+
+impl TranslateService {
+    pub fn new(
+        llm: Arc<dyn LlmService>,
+        templates: Arc<dyn TemplateEngine>,
+        max_retries: usize,
+    ) -> Self {
+        Self {
+            llm,
+            templates,
+            max_retries,
+            token_budget: 2000,
+        }
+    }
+
+    pub fn with_token_budget(mut self, token_budget: usize) -> Self {
+        self.token_budget = token_budget;
+        self
+    }
+
+    /// Traduit tous les textes de la specification et des tests dans `target`
+    pub async fn translate(
+        &self,
+        spec: &Specification,
+        suite: &TestSuite,
+        target: Language,
+    ) -> Result<TranslatedArtifacts, TranslationError> {
+        let segments = translatable_segments(spec, suite);
+        let batches = build_segment_batches(segments, self.token_budget);
+        info!(
+            language = %target,
+            batches = batches.len(),
+            "Debut de la traduction"
+        );
+
+        let keywords = target.keywords();
+        let system_context = serde_json::json!({
+            "language": keywords.name,
+            "code": keywords.code,
+        });
+        let system_prompt = self
+            .templates
+            .render("translate_system", &system_context)
+            .map_err(|e| TranslationError::LlmFailed {
+                details: format!("Erreur template systeme: {}", e),
+            })?;
+
+        let mut translations = HashMap::new();
+        for (idx, batch) in batches.iter().enumerate() {
+            debug!(
+                batch = idx + 1,
+                segments = batch.len(),
+                "Traduction d'un lot"
+            );
+            let output = self.call_llm_with_retry(&system_prompt, batch).await?;
+            for segment in output.segments {
+                translations.insert(segment.key, segment.text);
+            }
+        }
+
+        let mut specification = spec.clone();
+        specification.language = Some(target);
+        let mut test_suite = suite.clone();
+        let applied = apply_translations(&mut specification, &mut test_suite, &translations);
+
+        let differences = structure_differences(spec, suite, &specification, &test_suite);
+        if !differences.is_empty() {
+            for difference in &differences {
+                warn!("{}", difference);
+            }
+            return Err(TranslationError::StructureMismatch { differences });
+        }
+
+        info!(segments = applied, "Traduction terminee");
+        Ok(TranslatedArtifacts {
+            specification,
+            test_suite,
+            segments: applied,
+        })
+    }
+
+    async fn call_llm_with_retry(
+        &self,
+        system_prompt: &str,
+        batch: &[TranslationSegment],
+    ) -> Result<LlmSegments, TranslationError> {
+        use crate::application::llm_retry::{self, LlmRetryError};
+
+        let input = LlmSegments {
+            segments: batch.to_vec(),
+        };
+        let user_prompt = format!(
+            "Traduis les segments suivants:\n\n```json\n{}\n```\n",
+            serde_json::to_string_pretty(&input).unwrap_or_default()
+        );
+
+        // Dernier essai accepte tel quel : le controle de structure final
+        // produit alors une erreur detaillee
+        let expected = batch.to_vec();
+        let validate = move |output: &LlmSegments, attempt: usize, max_retries: usize| {
+            batch_mismatch(&expected, output).filter(|_| attempt < max_retries)
+        };
+
+        match llm_retry::call_with_retry(
+            self.llm.as_ref(),
+            system_prompt,
+            &user_prompt,
+            self.max_retries,
+            Some(&validate),
+        )
+        .await
+        {
+            Ok(output) => Ok(output),
+            Err(LlmRetryError::Truncated { details }) => {
+                Err(TranslationError::OutputTruncated { details })
+            }
+            Err(LlmRetryError::Failed { details }) => Err(TranslationError::LlmFailed { details }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::llm::mock_adapter::MockLlmAdapter;
+    use crate::adapters::templates::file_template_engine::FileTemplateEngine;
+    use crate::domain::test_case::{Feature, Scenario, ScenarioType, Step, StepKeyword};
+    use pretty_assertions::assert_eq;
+
+    fn sample() -> (Specification, TestSuite) {
+        let spec = Specification::new("Paiements".into());
+        let mut feature = Feature::new("Virement".into(), String::new());
+        feature.tags = vec!["@US-001".into()];
+        feature.scenarios.push(Scenario {
+            name: "Virement de <montant>".into(),
+            tags: vec!["@FR-001".into()],
+            scenario_type: ScenarioType::HappyPath,
+            steps: vec![Step {
+                keyword: StepKeyword::When,
+                text: "je vire <montant> EUR".into(),
+                doc_string: None,
+                data_table: None,
+            }],
+            examples: None,
+            test_data_suggestions: Vec::new(),
+            verification_of: vec!["FR-001".into()],
+            coverage_technique: None,
+        });
+        let mut suite = crate::domain::test_case::TestSuite {
+            features: vec![feature],
+            source_spec_id: spec.id,
+            total_scenarios: 1,
+            coverage: crate::domain::test_case::TestCoverage {
+                requirements_covered: Vec::new(),
+                requirements_total: 0,
+                coverage_percentage: 0.0,
+                scenarios_by_type: Default::default(),
+            },
+        };
+        suite.compute_coverage(1);
+        (spec, suite)
+    }
+
+    fn service(responses: Vec<serde_json::Value>, max_retries: usize) -> TranslateService {
+        let llm = Arc::new(MockLlmAdapter::new(
+            responses.iter().map(|r| r.to_string()).collect(),
+        ));
+        let templates =
+            Arc::new(FileTemplateEngine::new(std::path::Path::new("templates")).unwrap());
+        TranslateService::new(llm, templates, max_retries)
+    }
+
+    #[test]
+    fn test_build_segment_batches() {
+        let segments: Vec<TranslationSegment> = (0..5)
+            .map(|i| TranslationSegment {
+                key: format!("k{i}"),
+                text: "x".repeat(36),
+            })
+            .collect();
+        let batches = build_segment_batches(segments, 20);
+        assert_eq!(
+            batches.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_translate_preserves_structure() {
+        let (spec, suite) = sample();
+        let response = serde_json::json!({ "segments": [
+            { "key": "spec.title", "text": "Payments" },
+            { "key": "feature.0.name", "text": "Transfer" },
+            { "key": "feature.0.sc.0.name", "text": "Transfer of <montant>" },
+            { "key": "feature.0.sc.0.step.0", "text": "I transfer <montant> EUR" },
+        ]});
+        let result = service(vec![response], 0)
+            .translate(&spec, &suite, Language::English)
+            .await
+            .unwrap();
+        assert_eq!(result.segments, 4);
+        assert_eq!(result.specification.title, "Payments");
+        let scenario = &result.test_suite.features[0].scenarios[0];
+        assert_eq!(scenario.steps[0].text, "I transfer <montant> EUR");
+        assert_eq!(scenario.verification_of, vec!["FR-001"]);
+    }
+
+    #[tokio::test]
+    async fn test_translated_german_spec_passes_normative_validation() {
+        use crate::domain::specification::{FunctionalRequirement, RequirementCategory};
+        use crate::domain::user_story::Priority;
+        use crate::domain::validation::{
+            check_requirement_wellformedness_for, validate_specification,
+        };
+
+        let (mut spec, suite) = sample();
+        for (id, statement) in [
+            ("FR-001", "Le systeme DOIT valider l'IBAN"),
+            ("FR-002", "Le systeme POURRAIT proposer un favori"),
+            ("FR-003", "Le systeme POURRAIT memoriser le montant"),
+        ] {
+            spec.functional_requirements.push(FunctionalRequirement {
+                id: id.into(),
+                statement: statement.into(),
+                priority: Priority::P1,
+                category: RequirementCategory::Functional,
+                testable: true,
+                rationale: None,
+                source: None,
+                verification_method: Default::default(),
+                risk_level: None,
+                parent_requirement: None,
+                allocated_to: Vec::new(),
+                quality_characteristic: None,
+            });
+        }
+        // Mots normatifs indiques par le prompt, avec et sans Umlaut
+        let response = serde_json::json!({ "segments": [
+            { "key": "fr.0.statement", "text": "Das System MUSS die IBAN prüfen" },
+            { "key": "fr.1.statement", "text": "Das System KÖNNTE einen Favoriten vorschlagen" },
+            { "key": "fr.2.statement", "text": "Das System KOENNTE den Betrag speichern" },
+        ]});
+        let result = service(vec![response], 0)
+            .translate(&spec, &suite, Language::German)
+            .await
+            .unwrap();
+        let translated = &result.specification;
+        assert_eq!(translated.language, Some(Language::German));
+
+        let ids = translated
+            .functional_requirements
+            .iter()
+            .map(|fr| fr.id.as_str())
+            .collect();
+        for fr in &translated.functional_requirements {
+            let warnings = check_requirement_wellformedness_for(fr, &ids, translated.language);
+            assert!(
+                warnings
+                    .iter()
+                    .all(|w| w.criterion
+                        != crate::domain::validation::WellFormednessCriterion::Correct),
+                "{}: {:?}",
+                fr.statement,
+                warnings
+            );
+        }
+        let validation = validate_specification(translated);
+        assert!(
+            validation
+                .checklist_items
+                .iter()
+                .filter(|c| c.description.contains("normative"))
+                .all(|c| c.passed)
+        );
+        let prompt = std::fs::read_to_string("templates/translate_system.md").unwrap();
+        assert!(prompt.contains("KÖNNTE"));
+    }
+
+    #[tokio::test]
+    async fn test_translate_rejects_renamed_placeholder() {
+        let (spec, suite) = sample();
+        let response = serde_json::json!({ "segments": [
+            { "key": "feature.0.sc.0.step.0", "text": "I transfer <amount> EUR" },
+        ]});
+        let err = service(vec![response], 0)
+            .translate(&spec, &suite, Language::English)
+            .await
+            .unwrap_err();
+        match err {
+            TranslationError::StructureMismatch { differences } => {
+                assert!(differences[0].contains("<amount>"));
+            }
+            other => panic!("StructureMismatch attendu, obtenu {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,371 @@
+//! Detection des User Stories quasi-dupliquees entre fichiers
+//!
+//! Similarite de Jaccard deterministe sur des shingles de 3 caracteres
+//! (texte normalise : minuscules, sans accents ni ponctuation) calcules sur
+//! titre / acteur / action / benefice.
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::user_story::{UserStory, UserStorySet};
+
+/// Seuil de similarite au-dela duquel deux stories sont considerees dupliquees
+pub const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.8;
+
+/// Taille des shingles (en caracteres)
+const SHINGLE_SIZE: usize = 3;
+
+/// Nombre minimal de shingles pour comparer une story (texte trop court :
+/// jamais consideree comme doublon)
+const MIN_SHINGLES: usize = 4;
+
+/// Strategie de dedoublonnage avant raffinement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DedupeStrategy {
+    /// Signaler les doublons sans modifier les stories
+    Report,
+    /// Conserver la premiere occurrence
+    KeepFirst,
+    /// Conserver la premiere occurrence en y ajoutant les criteres des doublons
+    MergeCriteria,
+}
+
+impl DedupeStrategy {
+    /// Construit une strategie a partir d'un code ("report", "keep-first", "merge-criteria")
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_lowercase().replace('_', "-").as_str() {
+            "report" | "off" => Some(DedupeStrategy::Report),
+            "keep-first" => Some(DedupeStrategy::KeepFirst),
+            "merge-criteria" => Some(DedupeStrategy::MergeCriteria),
+            _ => None,
+        }
+    }
+}
+
+/// Paire de stories probablement dupliquees
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicatePair {
+    /// Position de la premiere story dans l'ordre de fusion
+    pub first: usize,
+    /// Position du doublon (toujours > `first`)
+    pub second: usize,
+    pub first_label: String,
+    pub second_label: String,
+    pub first_source: Option<String>,
+    pub second_source: Option<String>,
+    /// Similarite de Jaccard (0.0 - 1.0)
+    pub similarity: f64,
+    /// Criteres presents dans le doublon mais pas dans la premiere story
+    pub diverging_criteria: Vec<String>,
+}
+
+impl DuplicatePair {
+    /// Les deux stories ont-elles des criteres d'acceptation divergents ?
+    pub fn is_conflicting(&self) -> bool {
+        !self.diverging_criteria.is_empty()
+    }
+}
+
+/// Detecte les stories quasi-dupliquees (positions dans l'ordre de `UserStorySet::merge`)
+pub fn find_near_duplicates(sets: &[UserStorySet], threshold: f64) -> Vec<DuplicatePair> {
+    let entries: Vec<(&UserStory, Option<&String>)> = sets
+        .iter()
+        .flat_map(|set| {
+            let source = set.source_files.first();
+            set.stories.iter().map(move |story| (story, source))
+        })
+        .collect();
+    let shingles: Vec<BTreeSet<String>> = entries
+        .iter()
+        .map(|(story, _)| story_shingles(story))
+        .collect();
+
+    let mut pairs = Vec::new();
+    for i in 0..entries.len() {
+        if shingles[i].len() < MIN_SHINGLES {
+            continue;
+        }
+        for j in (i + 1)..entries.len() {
+            if shingles[j].len() < MIN_SHINGLES {
+                continue;
+            }
+            let similarity = jaccard(&shingles[i], &shingles[j]);
+            if similarity < threshold {
+                continue;
+            }
+            let (first, first_source) = entries[i];
+            let (second, second_source) = entries[j];
+            pairs.push(DuplicatePair {
+                first: i,
+                second: j,
+                first_label: story_label(first),
+                second_label: story_label(second),
+                first_source: first_source.cloned(),
+                second_source: second_source.cloned(),
+                similarity,
+                diverging_criteria: missing_criteria(first, second),
+            });
+        }
+    }
+    pairs
+}
+
+/// Applique la strategie sur le set fusionne ; retourne le nombre de stories retirees
+pub fn apply_dedupe(
+    merged: &mut UserStorySet,
+    pairs: &[DuplicatePair],
+    strategy: DedupeStrategy,
+) -> usize {
+    if strategy == DedupeStrategy::Report || pairs.is_empty() {
+        return 0;
+    }
+
+    // Doublon -> story conservee (une chaine a->b->c se resout vers a)
+    let mut keeper: HashMap<usize, usize> = HashMap::new();
+    let mut sorted: Vec<&DuplicatePair> = pairs.iter().collect();
+    sorted.sort_by_key(|p| (p.second, p.first));
+    for pair in sorted {
+        if keeper.contains_key(&pair.second) {
+            continue;
+        }
+        let mut target = pair.first;
+        while let Some(&next) = keeper.get(&target) {
+            target = next;
+        }
+        keeper.insert(pair.second, target);
+    }
+
+    if strategy == DedupeStrategy::MergeCriteria {
+        let mut removed: Vec<(&usize, &usize)> = keeper.iter().collect();
+        removed.sort();
+        for (&dup, &kept) in removed {
+            let extra = missing_criteria(&merged.stories[kept], &merged.stories[dup]);
+            let tags: Vec<String> = merged.stories[dup]
+                .tags
+                .iter()
+                .filter(|t| !merged.stories[kept].tags.contains(t))
+                .cloned()
+                .collect();
+            let target = &mut merged.stories[kept];
+            target.acceptance_criteria.extend(extra);
+            target.tags.extend(tags);
+        }
+    }
+
+    let before = merged.stories.len();
+    let mut index = 0;
+    merged.stories.retain(|_| {
+        let keep = !keeper.contains_key(&index);
+        index += 1;
+        keep
+    });
+    before - merged.stories.len()
+}
+
+/// Criteres de `other` absents de `story` (comparaison normalisee)
+fn missing_criteria(story: &UserStory, other: &UserStory) -> Vec<String> {
+    let known: BTreeSet<String> = story
+        .acceptance_criteria
+        .iter()
+        .map(|c| normalize(c))
+        .collect();
+    other
+        .acceptance_criteria
+        .iter()
+        .filter(|c| !known.contains(&normalize(c)))
+        .cloned()
+        .collect()
+}
+
+fn story_shingles(story: &UserStory) -> BTreeSet<String> {
+    let text = normalize(&format!(
+        "{} {} {} {}",
+        story.title, story.actor, story.action, story.benefit
+    ));
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() < SHINGLE_SIZE {
+        return BTreeSet::new();
+    }
+    chars
+        .windows(SHINGLE_SIZE)
+        .map(|w| w.iter().collect())
+        .collect()
+}
+
+fn jaccard(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count();
+    let union = a.len() + b.len() - intersection;
+    intersection as f64 / union as f64
+}
+
+/// Minuscules, accents retires, ponctuation remplacee par des espaces simples
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(fold_accent)
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Lettre accentuee -> lettre ASCII de base (autres caracteres inchanges)
+pub(crate) fn fold_accent(c: char) -> char {
+    match c {
+        'à' | 'â' | 'ä' | 'á' | 'ã' => 'a',
+        'ç' => 'c',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'î' | 'ï' | 'í' | 'ì' => 'i',
+        'ô' | 'ö' | 'ó' | 'ò' | 'õ' => 'o',
+        'ù' | 'û' | 'ü' | 'ú' => 'u',
+        'ÿ' => 'y',
+        'ñ' => 'n',
+        _ => c,
+    }
+}
+
+fn story_label(story: &UserStory) -> String {
+    story
+        .external_id
+        .clone()
+        .unwrap_or_else(|| story.title.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::user_story::Language;
+    use pretty_assertions::assert_eq;
+
+    fn make_story(id: &str, action: &str, criteria: &[&str]) -> UserStory {
+        let mut us = UserStory::new(
+            "Depot de cheque".into(),
+            "client".into(),
+            action.into(),
+            "encaisser sans me deplacer".into(),
+        );
+        us.external_id = Some(id.into());
+        us.acceptance_criteria = criteria.iter().map(|c| c.to_string()).collect();
+        us
+    }
+
+    fn make_set(file: &str, stories: Vec<UserStory>) -> UserStorySet {
+        UserStorySet {
+            stories,
+            source_files: vec![file.into()],
+            language: Language::French,
+        }
+    }
+
+    fn sample_sets() -> Vec<UserStorySet> {
+        vec![
+            make_set(
+                "a.md",
+                vec![make_story(
+                    "US-001",
+                    "déposer un chèque par photo",
+                    &["Montant limite a 5000 EUR"],
+                )],
+            ),
+            make_set(
+                "b.md",
+                vec![
+                    make_story(
+                        "US-101",
+                        "deposer un cheque par photo",
+                        &["Montant limite a 5000 eur", "Photo recto et verso"],
+                    ),
+                    make_story("US-102", "consulter mes plafonds", &[]),
+                ],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_find_near_duplicates_ignores_accents_and_case() {
+        let pairs = find_near_duplicates(&sample_sets(), DEFAULT_SIMILARITY_THRESHOLD);
+        assert_eq!(pairs.len(), 1);
+        let pair = &pairs[0];
+        assert_eq!((pair.first, pair.second), (0, 1));
+        assert_eq!(pair.first_source.as_deref(), Some("a.md"));
+        assert_eq!(pair.second_label, "US-101");
+        assert!(pair.similarity > 0.99);
+        assert_eq!(pair.diverging_criteria, vec!["Photo recto et verso"]);
+        assert!(pair.is_conflicting());
+    }
+
+    #[test]
+    fn test_apply_dedupe_strategies() {
+        let sets = sample_sets();
+        let pairs = find_near_duplicates(&sets, DEFAULT_SIMILARITY_THRESHOLD);
+
+        let mut report = UserStorySet::merge(sets.clone());
+        assert_eq!(apply_dedupe(&mut report, &pairs, DedupeStrategy::Report), 0);
+        assert_eq!(report.stories.len(), 3);
+
+        let mut keep_first = UserStorySet::merge(sets.clone());
+        assert_eq!(
+            apply_dedupe(&mut keep_first, &pairs, DedupeStrategy::KeepFirst),
+            1
+        );
+        assert_eq!(keep_first.stories[0].acceptance_criteria.len(), 1);
+        assert_eq!(keep_first.stories[1].external_id.as_deref(), Some("US-102"));
+
+        let mut merged = UserStorySet::merge(sets);
+        apply_dedupe(&mut merged, &pairs, DedupeStrategy::MergeCriteria);
+        assert_eq!(merged.stories.len(), 2);
+        assert_eq!(
+            merged.stories[0].acceptance_criteria,
+            vec!["Montant limite a 5000 EUR", "Photo recto et verso"]
+        );
+    }
+
+    #[test]
+    fn test_apply_dedupe_chain_resolves_to_first() {
+        let set = make_set(
+            "a.md",
+            vec![
+                make_story("US-001", "deposer un cheque par photo", &["A"]),
+                make_story("US-002", "deposer un cheque par photo", &["B"]),
+                make_story("US-003", "deposer un cheque par photo", &["C"]),
+            ],
+        );
+        let pairs = find_near_duplicates(std::slice::from_ref(&set), 0.8);
+        assert_eq!(pairs.len(), 3);
+        let mut merged = set;
+        assert_eq!(
+            apply_dedupe(&mut merged, &pairs, DedupeStrategy::MergeCriteria),
+            2
+        );
+        assert_eq!(merged.stories[0].acceptance_criteria, vec!["A", "B", "C"]);
+    }
+
+    #[test]
+    fn test_sparse_stories_are_not_duplicates() {
+        // Lignes CSV sans action ni benefice : seul le titre les distingue
+        let sparse = |title: &str| UserStory::new(title.into(), "PO".into(), "".into(), "".into());
+        let set = make_set("a.csv", vec![sparse("Export PDF"), sparse("Connexion SSO")]);
+        assert!(find_near_duplicates(std::slice::from_ref(&set), 0.8).is_empty());
+
+        let empty = make_set("b.csv", vec![sparse(""), sparse("")]);
+        assert!(find_near_duplicates(std::slice::from_ref(&empty), 0.8).is_empty());
+    }
+
+    #[test]
+    fn test_dedupe_strategy_from_code() {
+        assert_eq!(
+            DedupeStrategy::from_code("keep-first"),
+            Some(DedupeStrategy::KeepFirst)
+        );
+        assert_eq!(
+            DedupeStrategy::from_code("merge_criteria"),
+            Some(DedupeStrategy::MergeCriteria)
+        );
+        assert_eq!(DedupeStrategy::from_code("drop"), None);
+    }
+}
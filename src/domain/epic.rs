@@ -0,0 +1,458 @@
+//! Modele de domaine - Epics
+//!
+//! Une specification par epic, avec des identifiants FR/US/SC uniques sur
+//! l'ensemble du backlog pour que les references inter-epics restent valides.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::specification::Specification;
+use super::user_story::UserStorySet;
+
+/// Libelle du groupe des stories sans epic
+pub const UNASSIGNED_EPIC: &str = "Hors epic";
+
+/// Specification raffinee pour un epic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpicSpecification {
+    /// Nom de l'epic
+    pub name: String,
+
+    /// Nombre de user stories de l'epic
+    pub story_count: usize,
+
+    /// Specification de l'epic
+    pub specification: Specification,
+
+    /// IDs d'exigences cites explicitement dans les stories sources : seules
+    /// references inconnues de l'epic conservees (inter-epics)
+    #[serde(default)]
+    pub cited_ids: Vec<String>,
+}
+
+/// Reference d'exigence inconnue de l'epic et absente de ses stories sources
+/// (ID invente par le LLM)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnresolvedReference {
+    pub epic: String,
+    pub requirement_id: String,
+    pub target_id: String,
+}
+
+/// Reference d'une exigence vers une exigence d'un autre epic
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossEpicReference {
+    pub from_epic: String,
+    pub requirement_id: String,
+    pub to_epic: String,
+    pub target_id: String,
+}
+
+/// IDs d'exigences (`FR-xxx`) cites dans les stories d'un epic
+pub fn cited_requirement_ids(set: &UserStorySet) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for story in &set.stories {
+        let texts = std::iter::once(story.raw_text.as_str())
+            .chain(story.acceptance_criteria.iter().map(String::as_str));
+        for id in texts.flat_map(id_tokens).filter(|t| t.starts_with("FR-")) {
+            if !ids.iter().any(|known| known == id) {
+                ids.push(id.to_string());
+            }
+        }
+    }
+    ids
+}
+
+/// Renumerote les FR/US/SC de tous les epics en sequence globale.
+///
+/// Les references internes a un epic (`parent_requirement`, `source`,
+/// mentions dans l'enonce et la justification, `related_scenario`) suivent
+/// la renumerotation. Une reference FR inconnue localement n'est conservee
+/// que si elle est citee dans les stories sources (`cited_ids`) ; sinon elle
+/// est retournee, et retiree de `parent_requirement`.
+pub fn renumber_across_epics(epics: &mut [EpicSpecification]) -> Vec<UnresolvedReference> {
+    let mut unresolved = Vec::new();
+    let mut fr_counter = 1usize;
+    let mut us_counter = 1usize;
+    let mut sc_counter = 1usize;
+
+    for epic in epics.iter_mut() {
+        let spec = &mut epic.specification;
+
+        // Mapping local (old ID -> new ID), premiere occurrence retenue
+        let mut id_map: HashMap<String, String> = HashMap::new();
+        let mut fr_ids = Vec::with_capacity(spec.functional_requirements.len());
+        for fr in &spec.functional_requirements {
+            let new_id = format!("FR-{:03}", fr_counter);
+            fr_counter += 1;
+            id_map
+                .entry(fr.id.clone())
+                .or_insert_with(|| new_id.clone());
+            fr_ids.push(new_id);
+        }
+        let mut us_ids = Vec::with_capacity(spec.user_scenarios.len());
+        for us in &spec.user_scenarios {
+            let new_id = format!("US-{:03}", us_counter);
+            us_counter += 1;
+            id_map
+                .entry(us.id.clone())
+                .or_insert_with(|| new_id.clone());
+            us_ids.push(new_id);
+        }
+
+        for (fr, new_id) in spec.functional_requirements.iter_mut().zip(fr_ids) {
+            let texts = [
+                fr.parent_requirement.as_deref(),
+                Some(fr.statement.as_str()),
+                fr.rationale.as_deref(),
+                fr.source.as_deref(),
+            ];
+            let mut invented: Vec<&str> = Vec::new();
+            for target in texts.into_iter().flatten().flat_map(id_tokens) {
+                if target.starts_with("FR-")
+                    && !id_map.contains_key(target)
+                    && !epic.cited_ids.iter().any(|c| c == target)
+                    && !invented.contains(&target)
+                {
+                    invented.push(target);
+                }
+            }
+            unresolved.extend(invented.iter().map(|target| UnresolvedReference {
+                epic: epic.name.clone(),
+                requirement_id: new_id.clone(),
+                target_id: target.to_string(),
+            }));
+            if fr
+                .parent_requirement
+                .as_deref()
+                .is_some_and(|p| invented.contains(&p))
+            {
+                fr.parent_requirement = None;
+            }
+
+            fr.id = new_id;
+            if let Some(ref parent) = fr.parent_requirement
+                && let Some(mapped) = id_map.get(parent)
+            {
+                fr.parent_requirement = Some(mapped.clone());
+            }
+            if let Some(ref source) = fr.source {
+                fr.source = Some(remap_ids(source, &id_map));
+            }
+            fr.statement = remap_ids(&fr.statement, &id_map);
+            if let Some(ref rationale) = fr.rationale {
+                fr.rationale = Some(remap_ids(rationale, &id_map));
+            }
+        }
+        for (us, new_id) in spec.user_scenarios.iter_mut().zip(us_ids) {
+            us.id = new_id;
+        }
+        for ec in &mut spec.edge_cases {
+            if let Some(ref related) = ec.related_scenario
+                && let Some(mapped) = id_map.get(related)
+            {
+                ec.related_scenario = Some(mapped.clone());
+            }
+        }
+        for sc in &mut spec.success_criteria {
+            sc.id = format!("SC-{:03}", sc_counter);
+            sc_counter += 1;
+        }
+    }
+    unresolved
+}
+
+/// Liste les references d'exigences pointant vers un autre epic
+/// (`parent_requirement` ou mention `FR-xxx` dans l'enonce/justification/source).
+///
+/// Seuls les IDs cites dans les stories sources de l'epic (`cited_ids`) sont
+/// retenus : un ID invente par le LLM ne cree pas de reference.
+pub fn find_cross_epic_references(epics: &[EpicSpecification]) -> Vec<CrossEpicReference> {
+    let owner: HashMap<&str, &str> = epics
+        .iter()
+        .flat_map(|e| {
+            e.specification
+                .functional_requirements
+                .iter()
+                .map(move |fr| (fr.id.as_str(), e.name.as_str()))
+        })
+        .collect();
+
+    let mut references = Vec::new();
+    for epic in epics {
+        for fr in &epic.specification.functional_requirements {
+            let texts = [
+                fr.parent_requirement.as_deref(),
+                Some(fr.statement.as_str()),
+                fr.rationale.as_deref(),
+                fr.source.as_deref(),
+            ];
+            for target in texts.into_iter().flatten().flat_map(id_tokens) {
+                let Some(&to_epic) = owner.get(target) else {
+                    continue;
+                };
+                if !epic.cited_ids.iter().any(|c| c == target) {
+                    continue;
+                }
+                let reference = CrossEpicReference {
+                    from_epic: epic.name.clone(),
+                    requirement_id: fr.id.clone(),
+                    to_epic: to_epic.to_string(),
+                    target_id: target.to_string(),
+                };
+                if to_epic != epic.name && !references.contains(&reference) {
+                    references.push(reference);
+                }
+            }
+        }
+    }
+    references
+}
+
+/// Decoupe un texte en jetons de type identifiant (`FR-001`, `US-12`, ...)
+fn id_tokens(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+        .filter(|token| {
+            token.split_once('-').is_some_and(|(prefix, digits)| {
+                !prefix.is_empty()
+                    && prefix.chars().all(|c| c.is_ascii_uppercase())
+                    && !digits.is_empty()
+                    && digits.chars().all(|c| c.is_ascii_digit())
+            })
+        })
+}
+
+/// Remplace les identifiants connus d'un texte selon le mapping
+fn remap_ids(text: &str, id_map: &HashMap<String, String>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut token = String::new();
+    for c in text.chars().chain(std::iter::once('\0')) {
+        if c.is_ascii_alphanumeric() || c == '-' {
+            token.push(c);
+            continue;
+        }
+        match id_map.get(&token) {
+            Some(mapped) => result.push_str(mapped),
+            None => result.push_str(&token),
+        }
+        token.clear();
+        if c != '\0' {
+            result.push(c);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::specification::*;
+    use crate::domain::user_story::{Priority, UserStory};
+    use pretty_assertions::assert_eq;
+    use uuid::Uuid;
+
+    fn make_fr(id: &str, parent: Option<&str>, source: Option<&str>) -> FunctionalRequirement {
+        FunctionalRequirement {
+            id: id.into(),
+            statement: format!("Le systeme DOIT {}", id),
+            priority: Priority::P2,
+            category: RequirementCategory::Functional,
+            testable: true,
+            rationale: None,
+            source: source.map(Into::into),
+            verification_method: VerificationMethod::Test,
+            risk_level: None,
+            parent_requirement: parent.map(Into::into),
+            allocated_to: Vec::new(),
+            quality_characteristic: None,
+        }
+    }
+
+    fn make_epic(
+        name: &str,
+        frs: Vec<FunctionalRequirement>,
+        us_ids: &[&str],
+    ) -> EpicSpecification {
+        let mut spec = Specification::new(name.into());
+        spec.functional_requirements = frs;
+        for id in us_ids {
+            spec.user_scenarios.push(UserScenario {
+                id: (*id).into(),
+                title: format!("Scenario {}", id),
+                priority: Priority::P2,
+                description: String::new(),
+                why_priority: String::new(),
+                independent_test: String::new(),
+                acceptance_scenarios: Vec::new(),
+                source_story_id: Uuid::new_v4(),
+            });
+        }
+        EpicSpecification {
+            name: name.into(),
+            story_count: us_ids.len(),
+            specification: spec,
+            cited_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_renumber_across_epics_unique_ids() {
+        let mut epics = vec![
+            make_epic(
+                "Paiements",
+                vec![
+                    make_fr("FR-001", None, Some("US-001")),
+                    make_fr("FR-002", Some("FR-001"), Some("US-001")),
+                ],
+                &["US-001"],
+            ),
+            make_epic(
+                "Cartes",
+                vec![
+                    make_fr("FR-001", None, Some("US-001, US-002")),
+                    make_fr("FR-002", Some("FR-001"), None),
+                ],
+                &["US-001", "US-002"],
+            ),
+        ];
+        renumber_across_epics(&mut epics);
+
+        let ids: Vec<&str> = epics
+            .iter()
+            .flat_map(|e| e.specification.functional_requirements.iter())
+            .map(|fr| fr.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["FR-001", "FR-002", "FR-003", "FR-004"]);
+
+        let cards = &epics[1].specification;
+        assert_eq!(
+            cards.functional_requirements[1]
+                .parent_requirement
+                .as_deref(),
+            Some("FR-003")
+        );
+        assert_eq!(
+            cards.functional_requirements[0].source.as_deref(),
+            Some("US-002, US-003")
+        );
+        assert_eq!(cards.user_scenarios[0].id, "US-002");
+    }
+
+    #[test]
+    fn test_find_cross_epic_references() {
+        let mut epics = vec![
+            make_epic(
+                "Paiements",
+                vec![make_fr("FR-001", None, None)],
+                &["US-001"],
+            ),
+            make_epic(
+                "Cartes",
+                vec![make_fr("FR-002", Some("FR-001"), None)],
+                &["US-002"],
+            ),
+        ];
+        epics[1].specification.functional_requirements[0].statement =
+            "Le systeme DOIT respecter FR-001 et FR-002".into();
+        // Reference citee dans les stories de l'epic Cartes
+        epics[1].cited_ids = vec!["FR-001".into()];
+
+        let refs = find_cross_epic_references(&epics);
+        assert_eq!(
+            refs,
+            vec![CrossEpicReference {
+                from_epic: "Cartes".into(),
+                requirement_id: "FR-002".into(),
+                to_epic: "Paiements".into(),
+                target_id: "FR-001".into(),
+            }]
+        );
+        // La mention de FR-002 (exigence du meme epic) n'est pas inter-epics
+        assert!(refs.iter().all(|r| r.target_id != "FR-002"));
+    }
+
+    #[test]
+    fn test_local_mentions_follow_renumbering() {
+        // Chaque epic numerote localement a partir de FR-001 : les mentions
+        // locales ne doivent pas devenir des references vers le premier epic
+        let mut epics = vec![
+            make_epic(
+                "Paiements",
+                vec![make_fr("FR-001", None, None)],
+                &["US-001"],
+            ),
+            make_epic(
+                "Cartes",
+                vec![
+                    make_fr("FR-001", None, None),
+                    make_fr("FR-002", Some("FR-001"), None),
+                ],
+                &["US-001"],
+            ),
+        ];
+        let cards = &mut epics[1].specification.functional_requirements[1];
+        cards.statement = "Le systeme DOIT completer FR-001".into();
+        cards.rationale = Some("Detaille FR-001".into());
+
+        renumber_across_epics(&mut epics);
+
+        let cards = &epics[1].specification.functional_requirements[1];
+        assert_eq!(cards.statement, "Le systeme DOIT completer FR-002");
+        assert_eq!(cards.rationale.as_deref(), Some("Detaille FR-002"));
+        assert!(find_cross_epic_references(&epics).is_empty());
+    }
+
+    #[test]
+    fn test_invented_references_are_dropped() {
+        // Epic raffine seul : le LLM invente FR-009 comme parent et mentionne
+        // FR-001 (renumerote globalement dans l'epic Paiements)
+        let mut epics = vec![
+            make_epic(
+                "Paiements",
+                (1..=9)
+                    .map(|i| make_fr(&format!("FR-{:03}", i), None, None))
+                    .collect(),
+                &["US-001"],
+            ),
+            make_epic(
+                "Cartes",
+                vec![make_fr("FR-001", Some("FR-009"), None)],
+                &["US-001"],
+            ),
+        ];
+        epics[1].specification.functional_requirements[0].rationale =
+            Some("Complete FR-012".into());
+
+        let unresolved = renumber_across_epics(&mut epics);
+        let targets: Vec<&str> = unresolved.iter().map(|u| u.target_id.as_str()).collect();
+        assert_eq!(targets, vec!["FR-009", "FR-012"]);
+        assert_eq!(unresolved[0].requirement_id, "FR-010");
+        let cards = &epics[1].specification.functional_requirements[0];
+        assert_eq!(cards.parent_requirement, None);
+        assert!(find_cross_epic_references(&epics).is_empty());
+    }
+
+    #[test]
+    fn test_cited_requirement_ids() {
+        let mut story =
+            UserStory::new("Opposition".into(), "client".into(), "x".into(), "y".into());
+        story.raw_text = "En tant que client (voir FR-003 et US-002)".into();
+        story.acceptance_criteria = vec!["Conforme a FR-003 et FR-007".into()];
+        let set = UserStorySet {
+            stories: vec![story],
+            source_files: Vec::new(),
+            language: Default::default(),
+        };
+        assert_eq!(cited_requirement_ids(&set), vec!["FR-003", "FR-007"]);
+    }
+
+    #[test]
+    fn test_remap_ids_whole_tokens_only() {
+        let map = HashMap::from([("US-001".to_string(), "US-010".to_string())]);
+        assert_eq!(
+            remap_ids("US-001, US-0011 (US-001)", &map),
+            "US-010, US-0011 (US-010)"
+        );
+    }
+}
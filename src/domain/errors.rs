@@ -18,6 +18,9 @@ pub enum DomainError {
 
     #[error("Erreur de validation: {0}")]
     Validation(#[from] ValidationError),
+
+    #[error("Erreur de traduction: {0}")]
+    Translation(#[from] TranslationError),
 }
 
 /// Erreurs de lecture des entrees
@@ -37,6 +40,9 @@ pub enum InputError {
 
     #[error("Erreur de parsing: {0}")]
     ParseError(String),
+
+    #[error("Format non supporte: {path} ({details})")]
+    UnsupportedFormat { path: String, details: String },
 }
 
 /// Erreurs de raffinement US -> Spec
@@ -64,6 +70,9 @@ pub enum GenerationError {
     #[error("Syntaxe Gherkin invalide a la ligne {line}: {message}")]
     InvalidGherkinSyntax { line: usize, message: String },
 
+    #[error("Echec de l'ecriture des step definitions: {details}")]
+    StepScaffoldFailed { details: String },
+
     #[error("La specification n'a pas ete raffinee")]
     SpecNotRefined,
 
@@ -71,6 +80,22 @@ pub enum GenerationError {
     OutputTruncated { details: String },
 }
 
+/// Erreurs de traduction d'une specification et de ses tests
+#[derive(Error, Debug)]
+pub enum TranslationError {
+    #[error("Echec de la traduction LLM: {details}")]
+    LlmFailed { details: String },
+
+    #[error("Sortie LLM tronquee (max_tokens atteint): {details}")]
+    OutputTruncated { details: String },
+
+    #[error("Structure modifiee par la traduction: {}", differences.join("; "))]
+    StructureMismatch { differences: Vec<String> },
+
+    #[error("Echec de l'ecriture de la traduction: {details}")]
+    OutputFailed { details: String },
+}
+
 /// Erreurs de validation
 #[derive(Error, Debug)]
 pub enum ValidationError {
@@ -82,6 +107,9 @@ pub enum ValidationError {
 
     #[error("Completude insuffisante: {score:.1}% (minimum: {threshold:.1}%)")]
     CompletenessBelow { score: f32, threshold: f32 },
+
+    #[error("Lint des user stories en echec: {errors} erreur(s), {warnings} avertissement(s)")]
+    StoryLintFailed { errors: usize, warnings: usize },
 }
 
 #[cfg(test)]
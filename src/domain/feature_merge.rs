@@ -0,0 +1,279 @@
+//! Fusion a trois voies des fichiers .feature regeneres
+//!
+//! Chaque bloc d'un fichier (en-tete ou scenario) est compare a l'empreinte
+//! de sa derniere version generee : un bloc intact est remplace par la
+//! nouvelle generation, un bloc retouche a la main est conserve. Quand la
+//! generation et l'edition manuelle divergent, le bloc manuel est garde et
+//! un conflit est signale au lieu d'ecraser le travail de l'equipe QA.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::output_manifest::content_hash;
+
+/// Cle du bloc d'en-tete (langue, tags, Feature, description, Background)
+pub const HEADER_BLOCK: &str = "";
+
+/// Bloc de texte nomme d'un fichier .feature
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureBlock {
+    /// Nom du scenario (unique dans le fichier), `HEADER_BLOCK` pour l'en-tete
+    pub key: String,
+    pub content: String,
+}
+
+impl FeatureBlock {
+    pub fn new(key: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            content: content.into(),
+        }
+    }
+}
+
+/// Empreintes des blocs generes, par cle de bloc
+pub type BlockHashes = BTreeMap<String, String>;
+
+/// Conflit entre une edition manuelle et la nouvelle generation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergeConflict {
+    /// Scenario concerne (`HEADER_BLOCK` pour l'en-tete)
+    pub scenario: String,
+    pub reason: String,
+}
+
+/// Bilan de la fusion d'un fichier .feature
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeatureMergeReport {
+    /// Blocs retouches a la main et conserves
+    pub preserved: Vec<String>,
+    /// Blocs intacts remplaces par une generation differente
+    pub updated: Vec<String>,
+    /// Scenarios nouvellement generes
+    pub added: Vec<String>,
+    /// Scenarios intacts qui ne sont plus generes
+    pub removed: Vec<String>,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// Resultat de la fusion : blocs a ecrire et nouvelles empreintes de base
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureMerge {
+    pub blocks: Vec<FeatureBlock>,
+    pub hashes: BlockHashes,
+    pub report: FeatureMergeReport,
+}
+
+/// Empreinte FNV-1a 64 bits d'un bloc, insensible aux espaces de fin de
+/// ligne et aux lignes vides de bordure
+pub fn block_hash(content: &str) -> String {
+    let normalized = content
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    content_hash(normalized.trim_matches('\n').as_bytes())
+}
+
+/// Empreintes d'une generation complete (premiere ecriture d'un fichier)
+pub fn generated_hashes(blocks: &[FeatureBlock]) -> BlockHashes {
+    blocks
+        .iter()
+        .map(|b| (b.key.clone(), block_hash(&b.content)))
+        .collect()
+}
+
+/// Fusionne le fichier existant (`current`) avec la nouvelle generation
+///
+/// `base` contient les empreintes des blocs tels qu'ecrits lors de la
+/// generation precedente. L'ordre du fichier existant est conserve ; les
+/// nouveaux scenarios sont ajoutes a la fin.
+pub fn merge_feature_blocks(
+    base: &BlockHashes,
+    current: &[FeatureBlock],
+    generated: &[FeatureBlock],
+) -> FeatureMerge {
+    let mut report = FeatureMergeReport::default();
+    let mut hashes = BlockHashes::new();
+    let mut blocks = Vec::new();
+
+    for block in current {
+        let key = &block.key;
+        let current_hash = block_hash(&block.content);
+        let base_hash = base.get(key);
+        let untouched = base_hash == Some(&current_hash);
+        let generated_block = generated
+            .iter()
+            .find(|g| &g.key == key)
+            .map(|g| (g, block_hash(&g.content)));
+
+        match (untouched, generated_block) {
+            // Bloc intact : la generation fait foi
+            (true, Some((generated_block, generated_hash))) => {
+                if generated_hash != current_hash {
+                    report.updated.push(key.clone());
+                }
+                hashes.insert(key.clone(), generated_hash);
+                blocks.push(generated_block.clone());
+            }
+            (true, None) => report.removed.push(key.clone()),
+            // Bloc retouche (ou inconnu) que la generation rejoint
+            (false, Some((_, generated_hash))) if generated_hash == current_hash => {
+                hashes.insert(key.clone(), generated_hash);
+                blocks.push(block.clone());
+            }
+            // Generation inchangee depuis la base : l'edition manuelle gagne
+            (false, Some((_, generated_hash))) if base_hash == Some(&generated_hash) => {
+                report.preserved.push(key.clone());
+                hashes.insert(key.clone(), generated_hash);
+                blocks.push(block.clone());
+            }
+            (false, Some((_, generated_hash))) => {
+                report.preserved.push(key.clone());
+                report.conflicts.push(MergeConflict {
+                    scenario: key.clone(),
+                    reason: if base_hash.is_some() {
+                        "modifie localement et par la generation".into()
+                    } else {
+                        "present localement sans empreinte de generation".into()
+                    },
+                });
+                hashes.insert(key.clone(), generated_hash);
+                blocks.push(block.clone());
+            }
+            (false, None) => {
+                // Scenario ajoute a la main : rien a signaler
+                if base_hash.is_some() {
+                    report.preserved.push(key.clone());
+                    report.conflicts.push(MergeConflict {
+                        scenario: key.clone(),
+                        reason: "modifie localement mais plus genere".into(),
+                    });
+                }
+                blocks.push(block.clone());
+            }
+        }
+    }
+
+    for generated_block in generated {
+        let key = &generated_block.key;
+        if current.iter().any(|b| &b.key == key) {
+            continue;
+        }
+        let generated_hash = block_hash(&generated_block.content);
+        match base.get(key) {
+            // Supprime a la main, generation inchangee : la suppression est respectee
+            Some(base_hash) if base_hash == &generated_hash => {
+                hashes.insert(key.clone(), generated_hash);
+            }
+            Some(_) => {
+                report.conflicts.push(MergeConflict {
+                    scenario: key.clone(),
+                    reason: "supprime localement mais modifie par la generation".into(),
+                });
+                report.added.push(key.clone());
+                hashes.insert(key.clone(), generated_hash);
+                blocks.push(generated_block.clone());
+            }
+            None => {
+                report.added.push(key.clone());
+                hashes.insert(key.clone(), generated_hash);
+                blocks.push(generated_block.clone());
+            }
+        }
+    }
+
+    FeatureMerge {
+        blocks,
+        hashes,
+        report,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn blocks(items: &[(&str, &str)]) -> Vec<FeatureBlock> {
+        items
+            .iter()
+            .map(|(key, content)| FeatureBlock::new(*key, *content))
+            .collect()
+    }
+
+    fn contents(merge: &FeatureMerge) -> Vec<&str> {
+        merge.blocks.iter().map(|b| b.content.as_str()).collect()
+    }
+
+    #[test]
+    fn test_block_hash_ignores_trailing_whitespace() {
+        assert_eq!(
+            block_hash("  Scenario: A\n    Given x  \n\n"),
+            block_hash("  Scenario: A\n    Given x")
+        );
+        assert_ne!(block_hash("Given x"), block_hash("Given y"));
+    }
+
+    #[test]
+    fn test_untouched_blocks_follow_generation() {
+        let previous = blocks(&[("", "Feature: F"), ("A", "A v1"), ("B", "B v1")]);
+        let base = generated_hashes(&previous);
+        let generated = blocks(&[("", "Feature: F"), ("A", "A v2"), ("C", "C v1")]);
+
+        let merge = merge_feature_blocks(&base, &previous, &generated);
+        assert_eq!(contents(&merge), vec!["Feature: F", "A v2", "C v1"]);
+        assert_eq!(merge.report.updated, vec!["A"]);
+        assert_eq!(merge.report.removed, vec!["B"]);
+        assert_eq!(merge.report.added, vec!["C"]);
+        assert!(merge.report.conflicts.is_empty());
+        assert_eq!(merge.hashes, generated_hashes(&generated));
+    }
+
+    #[test]
+    fn test_manual_edits_are_preserved() {
+        let previous = blocks(&[("", "Feature: F"), ("A", "A v1"), ("B", "B v1")]);
+        let base = generated_hashes(&previous);
+        let current = blocks(&[
+            ("", "Feature: F"),
+            ("A", "A edite"),
+            ("B", "B edite"),
+            ("Manuel", "ajoute a la main"),
+        ]);
+        let generated = blocks(&[("", "Feature: F"), ("A", "A v1"), ("B", "B v2")]);
+
+        let merge = merge_feature_blocks(&base, &current, &generated);
+        assert_eq!(
+            contents(&merge),
+            vec!["Feature: F", "A edite", "B edite", "ajoute a la main"]
+        );
+        assert_eq!(merge.report.preserved, vec!["A", "B"]);
+        assert_eq!(
+            merge.report.conflicts,
+            vec![MergeConflict {
+                scenario: "B".into(),
+                reason: "modifie localement et par la generation".into(),
+            }]
+        );
+        // La base suit la generation : le conflit n'est signale qu'une fois
+        assert_eq!(merge.hashes.get("B"), Some(&block_hash("B v2")));
+        assert!(!merge.hashes.contains_key("Manuel"));
+    }
+
+    #[test]
+    fn test_manual_deletion_is_respected_until_generation_changes() {
+        let previous = blocks(&[("", "Feature: F"), ("A", "A v1")]);
+        let base = generated_hashes(&previous);
+        let current = blocks(&[("", "Feature: F")]);
+
+        let unchanged = merge_feature_blocks(&base, &current, &previous);
+        assert_eq!(contents(&unchanged), vec!["Feature: F"]);
+        assert!(unchanged.report.conflicts.is_empty());
+
+        let changed = blocks(&[("", "Feature: F"), ("A", "A v2")]);
+        let merge = merge_feature_blocks(&base, &current, &changed);
+        assert_eq!(contents(&merge), vec!["Feature: F", "A v2"]);
+        assert_eq!(merge.report.conflicts.len(), 1);
+    }
+}
@@ -0,0 +1,542 @@
+//! Refactoring deterministe des features Gherkin generees
+//!
+//! - Les etapes Given communes en tete de tous les scenarios d'une feature
+//!   sont remontees dans le `Background`.
+//! - Les scenarios freres qui ne different que par des valeurs litterales
+//!   (nombres, chaines entre guillemets) sont fusionnes en `Scenario Outline`
+//!   avec une table `Examples` ; tags, `verification_of` et suggestions de
+//!   donnees sont conserves par union.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::step_definitions::{ExpressionPart, ParameterType, parse_step_with_values};
+use super::test_case::{Background, Examples, Feature, Scenario, Step, StepKeyword, TestSuite};
+
+/// Prefixe des colonnes Examples creees par la fusion
+const EXAMPLES_COLUMN: &str = "valeur";
+
+/// Bilan du refactoring d'une suite
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RefactorReport {
+    /// Features dont le Background a ete cree ou complete
+    pub backgrounds_extracted: usize,
+    /// Etapes retirees des scenarios (toutes occurrences confondues)
+    pub hoisted_steps: usize,
+    pub outlines_created: usize,
+    /// Scenarios absorbes dans un Scenario Outline
+    pub scenarios_folded: usize,
+}
+
+impl RefactorReport {
+    pub fn is_empty(&self) -> bool {
+        self.backgrounds_extracted == 0 && self.outlines_created == 0
+    }
+}
+
+/// Applique les deux passes a chaque feature de la suite. La couverture doit
+/// etre recalculee ensuite (le nombre de scenarios peut baisser).
+pub fn refactor_test_suite(suite: &mut TestSuite) -> RefactorReport {
+    let mut report = RefactorReport::default();
+    for feature in &mut suite.features {
+        let (outlines, folded) = fold_into_outlines(feature);
+        report.outlines_created += outlines;
+        report.scenarios_folded += folded;
+
+        let hoisted = extract_background(feature);
+        if hoisted > 0 {
+            report.backgrounds_extracted += 1;
+            report.hoisted_steps += hoisted * feature.scenarios.len();
+        }
+    }
+    report
+}
+
+/// Remonte dans le Background le prefixe Given commun a tous les scenarios.
+/// Retourne le nombre d'etapes remontees.
+pub fn extract_background(feature: &mut Feature) -> usize {
+    if feature.scenarios.len() < 2 {
+        return 0;
+    }
+
+    let given_runs: Vec<usize> = feature
+        .scenarios
+        .iter()
+        .map(|s| leading_given_len(&s.steps))
+        .collect();
+    // Chaque scenario garde au moins une etape
+    let mut shared = feature
+        .scenarios
+        .iter()
+        .zip(&given_runs)
+        .map(|(s, run)| (*run).min(s.steps.len().saturating_sub(1)))
+        .min()
+        .unwrap_or(0);
+
+    let first = &feature.scenarios[0];
+    for (i, step) in first.steps.iter().take(shared).enumerate() {
+        let common = feature.scenarios[1..]
+            .iter()
+            .all(|s| same_step(&s.steps[i], step));
+        // Un placeholder d'Examples ne peut pas figurer dans le Background
+        let has_placeholder = feature
+            .scenarios
+            .iter()
+            .any(|s| uses_placeholder(s, &step.text));
+        if !common || has_placeholder {
+            shared = i;
+            break;
+        }
+    }
+    if shared == 0 {
+        return 0;
+    }
+
+    let mut hoisted: Vec<Step> = feature.scenarios[0].steps[..shared].to_vec();
+    for scenario in &mut feature.scenarios {
+        scenario.steps.drain(..shared);
+        if let Some(step) = scenario.steps.first_mut()
+            && matches!(step.keyword, StepKeyword::And | StepKeyword::But)
+        {
+            step.keyword = StepKeyword::Given;
+        }
+    }
+
+    match &mut feature.background {
+        Some(background) => {
+            if background.steps.is_empty() {
+                hoisted[0].keyword = StepKeyword::Given;
+            } else {
+                hoisted[0].keyword = StepKeyword::And;
+            }
+            background.steps.append(&mut hoisted);
+        }
+        None => {
+            hoisted[0].keyword = StepKeyword::Given;
+            feature.background = Some(Background { steps: hoisted });
+        }
+    }
+    shared
+}
+
+/// Fusionne les scenarios ne differant que par des valeurs litterales.
+/// Retourne (outlines crees, scenarios absorbes).
+pub fn fold_into_outlines(feature: &mut Feature) -> (usize, usize) {
+    let shapes: Vec<Option<Shape>> = feature.scenarios.iter().map(shape).collect();
+
+    // Groupes de scenarios de meme forme, dans l'ordre de premiere apparition
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for (i, current) in shapes.iter().enumerate() {
+        let Some(current) = current else { continue };
+        match groups
+            .iter_mut()
+            .find(|g| shapes[g[0]].as_ref().is_some_and(|s| s.key == current.key))
+        {
+            Some(group) => group.push(i),
+            None => groups.push(vec![i]),
+        }
+    }
+
+    let mut outlines = Vec::new();
+    for group in groups.into_iter().filter(|g| g.len() > 1) {
+        let members: Vec<&Shape> = group.iter().filter_map(|&i| shapes[i].as_ref()).collect();
+        let varying: Vec<usize> = (0..members[0].values.len())
+            .filter(|&v| members.iter().any(|m| m.values[v] != members[0].values[v]))
+            .collect();
+        // Scenarios identiques au texte pres : rien a parametrer
+        if varying.is_empty() {
+            continue;
+        }
+        let scenarios: Vec<&Scenario> = group.iter().map(|&i| &feature.scenarios[i]).collect();
+        let outline = build_outline(&scenarios, &members, &varying);
+        outlines.push((group, outline));
+    }
+
+    let created = outlines.len();
+    let mut folded = 0;
+    let mut remove = vec![false; feature.scenarios.len()];
+    for (group, outline) in outlines {
+        folded += group.len();
+        feature.scenarios[group[0]] = outline;
+        for &i in &group[1..] {
+            remove[i] = true;
+        }
+    }
+    let mut index = 0;
+    feature.scenarios.retain(|_| {
+        index += 1;
+        !remove[index - 1]
+    });
+    (created, folded)
+}
+
+/// Forme d'un scenario : gabarit de chaque etape, et valeurs de ses parametres
+struct Shape {
+    key: String,
+    /// Valeurs brutes de tous les parametres, etape par etape
+    values: Vec<String>,
+    kinds: Vec<ParameterType>,
+    /// (etape, position dans l'etape) de chaque valeur
+    positions: Vec<(usize, usize)>,
+}
+
+/// Forme d'un scenario simple (les Scenario Outline ne sont pas refusionnes)
+fn shape(scenario: &Scenario) -> Option<Shape> {
+    if scenario.examples.is_some() {
+        return None;
+    }
+    let mut key = format!("{}|", scenario.scenario_type);
+    let mut values = Vec::new();
+    let mut kinds = Vec::new();
+    let mut positions = Vec::new();
+    for (s, step) in scenario.steps.iter().enumerate() {
+        let (parts, parameters, step_values) =
+            parse_step_with_values(step.text.trim(), &HashMap::new());
+        if parameters
+            .iter()
+            .any(|p| p.kind == ParameterType::Anonymous)
+        {
+            return None;
+        }
+        key.push_str(&format!("{:?}:", step.keyword));
+        for part in &parts {
+            match part {
+                ExpressionPart::Text(text) => key.push_str(text),
+                ExpressionPart::Parameter(i) => {
+                    key.push_str(parameters[*i].kind.expression());
+                }
+            }
+        }
+        key.push_str(&format!("|{:?}|{:?}\n", step.doc_string, step.data_table));
+        for (p, value) in step_values.into_iter().enumerate() {
+            kinds.push(parameters[p].kind);
+            positions.push((s, p));
+            values.push(value);
+        }
+    }
+    Some(Shape {
+        key,
+        values,
+        kinds,
+        positions,
+    })
+}
+
+fn build_outline(scenarios: &[&Scenario], members: &[&Shape], varying: &[usize]) -> Scenario {
+    let first = scenarios[0];
+    let headers: Vec<String> = (1..=varying.len())
+        .map(|n| format!("{}{}", EXAMPLES_COLUMN, n))
+        .collect();
+
+    let mut outline = first.clone();
+    outline.name = common_name(scenarios);
+    for (column, &v) in varying.iter().enumerate() {
+        let (step, param) = members[0].positions[v];
+        let placeholder = match members[0].kinds[v] {
+            ParameterType::String => format!("\"<{}>\"", headers[column]),
+            _ => format!("<{}>", headers[column]),
+        };
+        outline.steps[step].text =
+            replace_parameter(&outline.steps[step].text, param, &placeholder);
+    }
+    outline.examples = Some(Examples {
+        headers,
+        rows: members
+            .iter()
+            .map(|m| {
+                varying
+                    .iter()
+                    .map(|&v| match m.kinds[v] {
+                        ParameterType::String => m.values[v].trim_matches('"').to_string(),
+                        _ => m.values[v].clone(),
+                    })
+                    .collect()
+            })
+            .collect(),
+    });
+    for scenario in &scenarios[1..] {
+        union(&mut outline.tags, &scenario.tags);
+        union(&mut outline.verification_of, &scenario.verification_of);
+        union(
+            &mut outline.test_data_suggestions,
+            &scenario.test_data_suggestions,
+        );
+        if outline.coverage_technique.is_none() {
+            outline.coverage_technique = scenario.coverage_technique;
+        }
+    }
+    outline
+}
+
+/// Remplace le parametre `index` d'un texte d'etape (valeur brute) par `replacement`
+fn replace_parameter(text: &str, index: usize, replacement: &str) -> String {
+    let (parts, _, values) = parse_step_with_values(text.trim(), &HashMap::new());
+    parts
+        .iter()
+        .map(|part| match part {
+            ExpressionPart::Text(text) => text.as_str(),
+            ExpressionPart::Parameter(i) if *i == index => replacement,
+            ExpressionPart::Parameter(i) => values[*i].as_str(),
+        })
+        .collect()
+}
+
+/// Prefixe commun des noms (sans ponctuation finale), sinon le premier nom
+fn common_name(scenarios: &[&Scenario]) -> String {
+    let first = &scenarios[0].name;
+    let mut end = first.len();
+    for scenario in &scenarios[1..] {
+        end = first
+            .char_indices()
+            .zip(scenario.name.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map(|((i, c), _)| i + c.len_utf8())
+            .unwrap_or(0)
+            .min(end);
+    }
+    // Couper au dernier mot complet
+    let mut prefix = &first[..end];
+    if first[..end].ends_with(char::is_alphanumeric)
+        && first[end..].starts_with(char::is_alphanumeric)
+    {
+        prefix = prefix.rsplit_once(' ').map(|(head, _)| head).unwrap_or("");
+    }
+    let prefix = prefix.trim_end_matches(|c: char| !c.is_alphanumeric());
+    if prefix.chars().count() < 3 {
+        first.clone()
+    } else {
+        prefix.to_string()
+    }
+}
+
+fn union(target: &mut Vec<String>, extra: &[String]) {
+    for value in extra {
+        if !target.contains(value) {
+            target.push(value.clone());
+        }
+    }
+}
+
+/// Longueur de la serie initiale d'etapes Given (And/But inclus)
+fn leading_given_len(steps: &[Step]) -> usize {
+    match steps.first() {
+        Some(step) if step.keyword == StepKeyword::Given => {
+            1 + steps[1..]
+                .iter()
+                .take_while(|s| matches!(s.keyword, StepKeyword::And | StepKeyword::But))
+                .count()
+        }
+        _ => 0,
+    }
+}
+
+/// Meme etape (les mots-cles Given/And d'une serie Given sont equivalents)
+fn same_step(a: &Step, b: &Step) -> bool {
+    (a.keyword == b.keyword
+        || (a.keyword != StepKeyword::But
+            && b.keyword != StepKeyword::But
+            && matches!(a.keyword, StepKeyword::Given | StepKeyword::And)
+            && matches!(b.keyword, StepKeyword::Given | StepKeyword::And)))
+        && a.text.trim() == b.text.trim()
+        && a.doc_string == b.doc_string
+        && a.data_table == b.data_table
+}
+
+fn uses_placeholder(scenario: &Scenario, text: &str) -> bool {
+    scenario.examples.as_ref().is_some_and(|examples| {
+        examples
+            .headers
+            .iter()
+            .any(|h| text.contains(&format!("<{}>", h.trim())))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_case::{ScenarioType, TestCoverage};
+    use pretty_assertions::assert_eq;
+
+    fn step(keyword: StepKeyword, text: &str) -> Step {
+        Step {
+            keyword,
+            text: text.into(),
+            doc_string: None,
+            data_table: None,
+        }
+    }
+
+    fn scenario(name: &str, tags: &[&str], frs: &[&str], steps: Vec<Step>) -> Scenario {
+        Scenario {
+            name: name.into(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            scenario_type: ScenarioType::HappyPath,
+            steps,
+            examples: None,
+            test_data_suggestions: Vec::new(),
+            verification_of: frs.iter().map(|t| t.to_string()).collect(),
+            coverage_technique: None,
+        }
+    }
+
+    fn texts(steps: &[Step]) -> Vec<&str> {
+        steps.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_extract_background_hoists_common_given_prefix() {
+        let mut feature = Feature::new("Virement".into(), String::new());
+        for (name, then) in [("A", "le solde baisse"), ("B", "un avis est envoye")] {
+            feature.scenarios.push(scenario(
+                name,
+                &[],
+                &[],
+                vec![
+                    step(StepKeyword::Given, "un client connecte"),
+                    step(StepKeyword::And, "un compte courant"),
+                    step(StepKeyword::And, &format!("un beneficiaire {name}")),
+                    step(StepKeyword::When, "il valide le virement"),
+                    step(StepKeyword::Then, then),
+                ],
+            ));
+        }
+
+        assert_eq!(extract_background(&mut feature), 2);
+        let background = feature.background.as_ref().unwrap();
+        assert_eq!(
+            texts(&background.steps),
+            vec!["un client connecte", "un compte courant"]
+        );
+        assert_eq!(background.steps[1].keyword, StepKeyword::And);
+        assert_eq!(feature.scenarios[0].steps.len(), 3);
+        assert_eq!(feature.scenarios[0].steps[0].keyword, StepKeyword::Given);
+        assert_eq!(feature.scenarios[1].steps[0].text, "un beneficiaire B");
+    }
+
+    #[test]
+    fn test_extract_background_keeps_one_step_per_scenario() {
+        let mut feature = Feature::new("F".into(), String::new());
+        feature.scenarios.push(scenario(
+            "A",
+            &[],
+            &[],
+            vec![step(StepKeyword::Given, "un contexte")],
+        ));
+        feature.scenarios.push(scenario(
+            "B",
+            &[],
+            &[],
+            vec![
+                step(StepKeyword::Given, "un contexte"),
+                step(StepKeyword::When, "une action"),
+            ],
+        ));
+        assert_eq!(extract_background(&mut feature), 0);
+        assert!(feature.background.is_none());
+    }
+
+    #[test]
+    fn test_fold_into_outline_with_union_of_tags_and_frs() {
+        let mut feature = Feature::new("Virement".into(), String::new());
+        for (name, amount, account, tag, fr) in [
+            ("Virement de 10 EUR", "10", "Epargne", "@P1", "FR-001"),
+            ("Virement de 250 EUR", "250", "Epargne", "@P2", "FR-002"),
+            ("Virement de 999 EUR", "999", "Courant", "@P1", "FR-001"),
+        ] {
+            feature.scenarios.push(scenario(
+                name,
+                &[tag],
+                &[fr],
+                vec![
+                    step(StepKeyword::Given, "un solde de 1000 EUR"),
+                    step(
+                        StepKeyword::When,
+                        &format!("je vire {amount} EUR vers \"{account}\""),
+                    ),
+                    step(StepKeyword::Then, "le virement est accepte"),
+                ],
+            ));
+        }
+        feature.scenarios.push(scenario(
+            "Solde insuffisant",
+            &[],
+            &[],
+            vec![step(StepKeyword::Then, "le virement est refuse")],
+        ));
+
+        assert_eq!(fold_into_outlines(&mut feature), (1, 3));
+        assert_eq!(feature.scenarios.len(), 2);
+        let outline = &feature.scenarios[0];
+        assert_eq!(outline.name, "Virement de");
+        assert_eq!(outline.steps[0].text, "un solde de 1000 EUR");
+        assert_eq!(
+            outline.steps[1].text,
+            "je vire <valeur1> EUR vers \"<valeur2>\""
+        );
+        let examples = outline.examples.as_ref().unwrap();
+        assert_eq!(examples.headers, vec!["valeur1", "valeur2"]);
+        assert_eq!(
+            examples.rows,
+            vec![
+                vec!["10".to_string(), "Epargne".to_string()],
+                vec!["250".to_string(), "Epargne".to_string()],
+                vec!["999".to_string(), "Courant".to_string()],
+            ]
+        );
+        assert_eq!(outline.tags, vec!["@P1", "@P2"]);
+        assert_eq!(outline.verification_of, vec!["FR-001", "FR-002"]);
+    }
+
+    #[test]
+    fn test_refactor_suite_report() {
+        let mut feature = Feature::new("F".into(), String::new());
+        for n in ["1", "2"] {
+            feature.scenarios.push(scenario(
+                &format!("Saisie {n}"),
+                &[],
+                &[],
+                vec![
+                    step(StepKeyword::Given, "un formulaire vide"),
+                    step(StepKeyword::When, &format!("je saisis {n}")),
+                    step(StepKeyword::Then, "la saisie est acceptee"),
+                ],
+            ));
+        }
+        feature.scenarios.push(scenario(
+            "Annulation",
+            &[],
+            &[],
+            vec![
+                step(StepKeyword::Given, "un formulaire vide"),
+                step(StepKeyword::When, "j'annule"),
+            ],
+        ));
+        let mut suite = TestSuite {
+            features: vec![feature],
+            source_spec_id: uuid::Uuid::nil(),
+            total_scenarios: 3,
+            coverage: TestCoverage {
+                requirements_covered: Vec::new(),
+                requirements_total: 0,
+                coverage_percentage: 0.0,
+                scenarios_by_type: Default::default(),
+            },
+        };
+
+        let report = refactor_test_suite(&mut suite);
+        assert_eq!(
+            report,
+            RefactorReport {
+                backgrounds_extracted: 1,
+                hoisted_steps: 2,
+                outlines_created: 1,
+                scenarios_folded: 2,
+            }
+        );
+        let feature = &suite.features[0];
+        assert_eq!(feature.scenarios[0].steps[0].text, "je saisis <valeur1>");
+        assert_eq!(feature.scenarios[0].name, "Saisie");
+    }
+}
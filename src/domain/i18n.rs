@@ -0,0 +1,304 @@
+//! Tables de langue (i18n)
+//!
+//! Une table par langue supportee : mots-cles Gherkin officiels (jeu
+//! `gherkin-languages.json` de Cucumber), formulations de User Story,
+//! indicateurs de detection de langue, mots ambigus et mots normatifs
+//! (ISO 29148). Ajouter une langue = ajouter une table et une variante
+//! de `Language`.
+
+use super::user_story::Language;
+
+/// Mots-cles Gherkin d'une langue (premiere forme officielle)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GherkinKeywords {
+    pub feature: &'static str,
+    pub background: &'static str,
+    pub scenario: &'static str,
+    pub scenario_outline: &'static str,
+    pub examples: &'static str,
+    pub given: &'static str,
+    pub when: &'static str,
+    pub then: &'static str,
+    pub and: &'static str,
+    pub but: &'static str,
+}
+
+/// Formulations du gabarit "acteur / action / benefice", en minuscules
+///
+/// Les formes les plus longues sont essayees en premier par le parseur.
+/// Les connecteurs de benefice sont essayes dans l'ordre : les plus
+/// specifiques d'abord, sans preposition generique seule ("para", "um") qui
+/// apparaitrait aussi dans l'action. Un `…` separe un connecteur en deux
+/// parties ("um … zu") : le benefice doit contenir la seconde.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoryPhrases {
+    pub actor: &'static [&'static str],
+    pub action: &'static [&'static str],
+    pub benefit: &'static [&'static str],
+}
+
+/// Table complete d'une langue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LanguageKeywords {
+    /// Code ISO 639-1 (header Gherkin `# language:`)
+    pub code: &'static str,
+    /// Nom de la langue tel qu'indique au LLM
+    pub name: &'static str,
+    pub gherkin: GherkinKeywords,
+    pub story: StoryPhrases,
+    /// Indicateurs de detection de langue, en minuscules
+    pub indicators: &'static [&'static str],
+    /// Mots ambigus (ISO 29148), en minuscules
+    pub ambiguous_words: &'static [&'static str],
+    /// Mots normatifs (ISO 29148 section 5.2.4), en majuscules
+    pub normative_keywords: &'static [&'static str],
+    /// Connecteurs d'exigences composees (violation Singular), en minuscules
+    pub compound_connectors: &'static [&'static str],
+}
+
+pub const FRENCH: LanguageKeywords = LanguageKeywords {
+    code: "fr",
+    name: "francais",
+    gherkin: GherkinKeywords {
+        feature: "Fonctionnalité",
+        background: "Contexte",
+        scenario: "Scénario",
+        scenario_outline: "Plan du Scénario",
+        examples: "Exemples",
+        given: "Soit",
+        when: "Quand",
+        then: "Alors",
+        and: "Et",
+        but: "Mais",
+    },
+    story: StoryPhrases {
+        actor: &["en tant que"],
+        action: &["je veux"],
+        benefit: &["afin de"],
+    },
+    indicators: &["en tant que", "je veux", "afin de", "critères", "scénario"],
+    ambiguous_words: &[
+        "environ",
+        "quelques",
+        "peut-etre",
+        "certains",
+        "parfois",
+        "souvent",
+        "approximativement",
+    ],
+    normative_keywords: &["DOIT", "DEVRAIT", "POURRAIT"],
+    compound_connectors: &[" et doit ", " et le systeme "],
+};
+
+pub const ENGLISH: LanguageKeywords = LanguageKeywords {
+    code: "en",
+    name: "english",
+    gherkin: GherkinKeywords {
+        feature: "Feature",
+        background: "Background",
+        scenario: "Scenario",
+        scenario_outline: "Scenario Outline",
+        examples: "Examples",
+        given: "Given",
+        when: "When",
+        then: "Then",
+        and: "And",
+        but: "But",
+    },
+    story: StoryPhrases {
+        actor: &["as an", "as a"],
+        action: &["i want to", "i want"],
+        benefit: &["so that"],
+    },
+    indicators: &["as a", "i want", "so that", "criteria", "scenario"],
+    ambiguous_words: &[
+        "approximately",
+        "some",
+        "maybe",
+        "sometimes",
+        "usually",
+        "often",
+        "few",
+        "several",
+        "many",
+        "etc",
+        "adequate",
+        "as appropriate",
+    ],
+    normative_keywords: &["MUST", "SHALL", "SHOULD", "COULD", "WILL"],
+    compound_connectors: &[" and shall ", " and must ", " and the system "],
+};
+
+pub const SPANISH: LanguageKeywords = LanguageKeywords {
+    code: "es",
+    name: "español",
+    gherkin: GherkinKeywords {
+        feature: "Característica",
+        background: "Antecedentes",
+        scenario: "Escenario",
+        scenario_outline: "Esquema del escenario",
+        examples: "Ejemplos",
+        given: "Dado",
+        when: "Cuando",
+        then: "Entonces",
+        and: "Y",
+        but: "Pero",
+    },
+    story: StoryPhrases {
+        actor: &["como"],
+        action: &["quiero", "deseo", "necesito"],
+        benefit: &["con el fin de", "para que"],
+    },
+    indicators: &["como", "quiero", "para que", "criterios", "escenario"],
+    ambiguous_words: &[
+        "aproximadamente",
+        "algunos",
+        "algunas",
+        "quizas",
+        "quizás",
+        "a veces",
+        "varios",
+        "muchos",
+        "normalmente",
+        "adecuado",
+    ],
+    normative_keywords: &["DEBE", "DEBERÍA", "DEBERIA", "PODRÍA", "PODRIA"],
+    compound_connectors: &[" y debe ", " y el sistema "],
+};
+
+pub const GERMAN: LanguageKeywords = LanguageKeywords {
+    code: "de",
+    name: "deutsch",
+    gherkin: GherkinKeywords {
+        feature: "Funktionalität",
+        background: "Grundlage",
+        scenario: "Szenario",
+        scenario_outline: "Szenariogrundriss",
+        examples: "Beispiele",
+        given: "Angenommen",
+        when: "Wenn",
+        then: "Dann",
+        and: "Und",
+        but: "Aber",
+    },
+    story: StoryPhrases {
+        actor: &["als"],
+        action: &["möchte ich", "moechte ich", "will ich"],
+        benefit: &["damit", "um … zu"],
+    },
+    indicators: &["als ", "möchte ich", "damit", "kriterien", "szenario"],
+    ambiguous_words: &[
+        "ungefähr",
+        "ungefaehr",
+        "etwa",
+        "einige",
+        "vielleicht",
+        "manchmal",
+        "oft",
+        "häufig",
+        "mehrere",
+        "viele",
+        "angemessen",
+        "usw",
+        "gegebenenfalls",
+    ],
+    normative_keywords: &["MUSS", "SOLL", "KÖNNTE", "KOENNTE"],
+    compound_connectors: &[" und muss ", " und das system "],
+};
+
+/// Table d'une langue
+pub fn keywords(language: Language) -> &'static LanguageKeywords {
+    match language {
+        Language::French => &FRENCH,
+        Language::English => &ENGLISH,
+        Language::Spanish => &SPANISH,
+        Language::German => &GERMAN,
+    }
+}
+
+/// Union sans doublon d'une liste de toutes les langues (ordre de `Language::ALL`)
+fn union(list: impl Fn(&'static LanguageKeywords) -> &'static [&'static str]) -> Vec<&'static str> {
+    let mut words: Vec<&'static str> = Vec::new();
+    for language in Language::ALL {
+        for word in list(keywords(language)) {
+            if !words.contains(word) {
+                words.push(word);
+            }
+        }
+    }
+    words
+}
+
+/// Mots ambigus de toutes les langues (texte de langue inconnue)
+pub fn all_ambiguous_words() -> Vec<&'static str> {
+    union(|k| k.ambiguous_words)
+}
+
+/// Mots normatifs de toutes les langues
+pub fn all_normative_keywords() -> Vec<&'static str> {
+    union(|k| k.normative_keywords)
+}
+
+/// Connecteurs d'exigences composees de toutes les langues
+pub fn all_compound_connectors() -> Vec<&'static str> {
+    union(|k| k.compound_connectors)
+}
+
+/// Mots ambigus de `language` (toutes les langues si elle est inconnue)
+pub fn ambiguous_words_for(language: Option<Language>) -> Vec<&'static str> {
+    match language {
+        Some(language) => keywords(language).ambiguous_words.to_vec(),
+        None => all_ambiguous_words(),
+    }
+}
+
+/// Mots normatifs de `language` (toutes les langues si elle est inconnue)
+pub fn normative_keywords_for(language: Option<Language>) -> Vec<&'static str> {
+    match language {
+        Some(language) => keywords(language).normative_keywords.to_vec(),
+        None => all_normative_keywords(),
+    }
+}
+
+/// Connecteurs d'exigences composees de `language` (toutes les langues si
+/// elle est inconnue)
+pub fn compound_connectors_for(language: Option<Language>) -> Vec<&'static str> {
+    match language {
+        Some(language) => keywords(language).compound_connectors.to_vec(),
+        None => all_compound_connectors(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_tables_match_language_codes() {
+        for language in Language::ALL {
+            let table = language.keywords();
+            assert_eq!(table.code, language.gherkin_code());
+            assert!(!table.story.actor.is_empty());
+            assert!(
+                table
+                    .normative_keywords
+                    .iter()
+                    .all(|k| k.to_uppercase() == *k)
+            );
+            assert!(table.ambiguous_words.iter().all(|w| w.to_lowercase() == *w));
+        }
+    }
+
+    #[test]
+    fn test_unions_keep_first_language_order() {
+        let words = all_ambiguous_words();
+        assert_eq!(words[0], "environ");
+        assert!(words.contains(&"some"));
+        assert!(words.contains(&"algunos"));
+        assert!(words.contains(&"vielleicht"));
+        let normative = all_normative_keywords();
+        assert!(normative.contains(&"MUST"));
+        assert!(normative.contains(&"MUSS"));
+    }
+}
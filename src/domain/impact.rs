@@ -0,0 +1,521 @@
+//! Analyse d'impact d'un changement d'exigences
+//!
+//! Compare deux versions d'une specification, puis remonte les liens de
+//! tracabilite (`verification_of`, tags `@FR-`, `covered_requirements`) pour
+//! classer les scenarios existants : a revoir, a regenerer ou a supprimer.
+//!
+//! Conformite : ISO/IEC/IEEE 29148:2018 section 6.6 (gestion des changements)
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::regeneration::merge_features;
+use super::specification::{FunctionalRequirement, Specification};
+use super::test_case::{Feature, Scenario, TestSuite};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Nature du changement d'une exigence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeKind {
+    Added,
+    Modified,
+    Removed,
+}
+
+impl std::fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeKind::Added => write!(f, "Ajoutee"),
+            ChangeKind::Modified => write!(f, "Modifiee"),
+            ChangeKind::Removed => write!(f, "Supprimee"),
+        }
+    }
+}
+
+/// Changement d'une exigence entre deux versions de la spec
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequirementChange {
+    pub requirement_id: String,
+    pub kind: ChangeKind,
+    /// Champs modifies (`statement`, `priority`, ...), vide si ajout/suppression
+    pub fields: Vec<String>,
+}
+
+impl RequirementChange {
+    /// Le comportement attendu change : les scenarios doivent etre regeneres
+    pub fn changes_behaviour(&self) -> bool {
+        self.kind == ChangeKind::Added || self.fields.iter().any(|f| f == "statement")
+    }
+}
+
+/// Action recommandee pour un scenario impacte
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ImpactAction {
+    /// Lien ou metadonnees a verifier manuellement
+    Review,
+    /// Enonce modifie : scenario a regenerer
+    Regenerate,
+    /// Toutes les exigences verifiees ont disparu
+    Delete,
+}
+
+impl std::fmt::Display for ImpactAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImpactAction::Review => write!(f, "A revoir"),
+            ImpactAction::Regenerate => write!(f, "A regenerer"),
+            ImpactAction::Delete => write!(f, "A supprimer"),
+        }
+    }
+}
+
+/// Scenario existant touche par un changement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImpactedScenario {
+    pub feature: String,
+    pub scenario: String,
+    pub action: ImpactAction,
+    /// Exigences modifiees a l'origine de l'impact
+    pub requirements: Vec<String>,
+    pub reason: String,
+}
+
+/// Rapport d'impact complet
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImpactReport {
+    pub changes: Vec<RequirementChange>,
+    pub scenarios: Vec<ImpactedScenario>,
+    /// Exigences ajoutees ou dont l'enonce a change, dans l'ordre de la nouvelle spec
+    pub requirements_to_generate: Vec<String>,
+}
+
+impl ImpactReport {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn count(&self, action: ImpactAction) -> usize {
+        self.scenarios.iter().filter(|s| s.action == action).count()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Analyse
+// ---------------------------------------------------------------------------
+
+/// Compare les exigences de deux versions d'une specification (par ID)
+pub fn diff_requirements(old: &Specification, new: &Specification) -> Vec<RequirementChange> {
+    let old_by_id: HashMap<&str, &FunctionalRequirement> = old
+        .functional_requirements
+        .iter()
+        .map(|fr| (fr.id.as_str(), fr))
+        .collect();
+    let new_ids: HashSet<&str> = new
+        .functional_requirements
+        .iter()
+        .map(|fr| fr.id.as_str())
+        .collect();
+
+    let mut changes = Vec::new();
+    for fr in &new.functional_requirements {
+        match old_by_id.get(fr.id.as_str()) {
+            None => changes.push(RequirementChange {
+                requirement_id: fr.id.clone(),
+                kind: ChangeKind::Added,
+                fields: Vec::new(),
+            }),
+            Some(previous) => {
+                let fields = changed_fields(previous, fr);
+                if !fields.is_empty() {
+                    changes.push(RequirementChange {
+                        requirement_id: fr.id.clone(),
+                        kind: ChangeKind::Modified,
+                        fields,
+                    });
+                }
+            }
+        }
+    }
+    for fr in &old.functional_requirements {
+        if !new_ids.contains(fr.id.as_str()) {
+            changes.push(RequirementChange {
+                requirement_id: fr.id.clone(),
+                kind: ChangeKind::Removed,
+                fields: Vec::new(),
+            });
+        }
+    }
+    changes
+}
+
+/// Champs ayant une incidence sur les tests (la justification est ignoree)
+fn changed_fields(old: &FunctionalRequirement, new: &FunctionalRequirement) -> Vec<String> {
+    let mut fields = Vec::new();
+    let normalize = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+    if normalize(&old.statement) != normalize(&new.statement) {
+        fields.push("statement");
+    }
+    if old.priority != new.priority {
+        fields.push("priority");
+    }
+    if old.category != new.category {
+        fields.push("category");
+    }
+    if old.testable != new.testable {
+        fields.push("testable");
+    }
+    if old.verification_method != new.verification_method {
+        fields.push("verification_method");
+    }
+    if old.risk_level != new.risk_level {
+        fields.push("risk_level");
+    }
+    if old.parent_requirement != new.parent_requirement {
+        fields.push("parent_requirement");
+    }
+    if old.quality_characteristic != new.quality_characteristic {
+        fields.push("quality_characteristic");
+    }
+    fields.into_iter().map(String::from).collect()
+}
+
+/// Analyse l'impact d'un changement de spec sur une suite de tests existante
+pub fn analyze_impact(old: &Specification, new: &Specification, suite: &TestSuite) -> ImpactReport {
+    let changes = diff_requirements(old, new);
+    let by_id: HashMap<&str, &RequirementChange> = changes
+        .iter()
+        .map(|c| (c.requirement_id.as_str(), c))
+        .collect();
+
+    let mut scenarios = Vec::new();
+    for feature in &suite.features {
+        for scenario in &feature.scenarios {
+            if let Some(impacted) = impact_of(feature, scenario, &by_id) {
+                scenarios.push(impacted);
+            }
+        }
+    }
+
+    let requirements_to_generate = changes
+        .iter()
+        .filter(|c| c.changes_behaviour())
+        .map(|c| c.requirement_id.clone())
+        .collect();
+
+    ImpactReport {
+        changes,
+        scenarios,
+        requirements_to_generate,
+    }
+}
+
+fn impact_of(
+    feature: &Feature,
+    scenario: &Scenario,
+    changes: &HashMap<&str, &RequirementChange>,
+) -> Option<ImpactedScenario> {
+    let linked = scenario.linked_requirements();
+    let changed: Vec<&RequirementChange> = linked
+        .iter()
+        .filter_map(|id| changes.get(id.as_str()).copied())
+        .collect();
+
+    let (action, requirements, reason) = if !changed.is_empty() {
+        let removed = changed
+            .iter()
+            .filter(|c| c.kind == ChangeKind::Removed)
+            .count();
+        let (action, reason) = if removed == linked.len() {
+            (
+                ImpactAction::Delete,
+                "exigence(s) verifiee(s) supprimee(s)".to_string(),
+            )
+        } else if changed.iter().any(|c| c.changes_behaviour()) {
+            (ImpactAction::Regenerate, "enonce modifie".to_string())
+        } else if removed > 0 {
+            (
+                ImpactAction::Review,
+                "lien vers une exigence supprimee".to_string(),
+            )
+        } else {
+            let mut fields: Vec<&str> = changed
+                .iter()
+                .flat_map(|c| c.fields.iter().map(String::as_str))
+                .collect();
+            fields.sort_unstable();
+            fields.dedup();
+            (
+                ImpactAction::Review,
+                format!("modifie: {}", fields.join(", ")),
+            )
+        };
+        let ids = changed.iter().map(|c| c.requirement_id.clone()).collect();
+        (action, ids, reason)
+    } else if !linked.is_empty() {
+        return None;
+    } else {
+        // Scenario sans lien direct : la feature couvre une exigence modifiee
+        let ids: Vec<String> = feature
+            .covered_requirements
+            .iter()
+            .filter(|id| {
+                changes
+                    .get(id.as_str())
+                    .is_some_and(|c| c.kind != ChangeKind::Added)
+            })
+            .cloned()
+            .collect();
+        if ids.is_empty() {
+            return None;
+        }
+        (
+            ImpactAction::Review,
+            ids,
+            "feature couvrant une exigence modifiee".to_string(),
+        )
+    };
+
+    Some(ImpactedScenario {
+        feature: feature.name.clone(),
+        scenario: scenario.name.clone(),
+        action,
+        requirements,
+        reason,
+    })
+}
+
+/// Sous-specification limitee aux exigences a regenerer
+///
+/// Conserve les scenarios utilisateur sources de ces exigences (tous si aucune
+/// source n'est renseignee) et leurs cas limites.
+pub fn impacted_subspec(new: &Specification, requirement_ids: &[String]) -> Specification {
+    let mut sub = new.clone();
+    sub.functional_requirements
+        .retain(|fr| requirement_ids.contains(&fr.id));
+
+    let sources: Vec<&str> = sub
+        .functional_requirements
+        .iter()
+        .filter_map(|fr| fr.source.as_deref())
+        .collect();
+    let related: Vec<_> = new
+        .user_scenarios
+        .iter()
+        .filter(|us| sources.iter().any(|s| s.contains(&us.id)))
+        .cloned()
+        .collect();
+    if !related.is_empty() {
+        sub.user_scenarios = related;
+        let kept: HashSet<&str> = sub.user_scenarios.iter().map(|us| us.id.as_str()).collect();
+        sub.edge_cases.retain(|ec| {
+            ec.related_scenario
+                .as_deref()
+                .is_none_or(|id| kept.contains(id))
+        });
+    }
+    sub
+}
+
+/// Remplace dans la suite les scenarios a regenerer ou supprimer par les
+/// scenarios regeneres (fusionnes par nom de feature)
+pub fn splice_regenerated(
+    suite: &mut TestSuite,
+    report: &ImpactReport,
+    regenerated: TestSuite,
+    total_requirements: usize,
+) {
+    let obsolete: HashSet<(&str, &str)> = report
+        .scenarios
+        .iter()
+        .filter(|s| s.action != ImpactAction::Review)
+        .map(|s| (s.feature.as_str(), s.scenario.as_str()))
+        .collect();
+    for feature in &mut suite.features {
+        let name = feature.name.clone();
+        feature
+            .scenarios
+            .retain(|s| !obsolete.contains(&(name.as_str(), s.name.as_str())));
+    }
+    merge_features(suite, regenerated);
+    suite.compute_coverage(total_requirements);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::specification::{RequirementCategory, VerificationMethod};
+    use crate::domain::test_case::{ScenarioType, TestCoverage};
+    use crate::domain::user_story::Priority;
+    use pretty_assertions::assert_eq;
+
+    fn fr(id: &str, statement: &str) -> FunctionalRequirement {
+        FunctionalRequirement {
+            id: id.into(),
+            statement: statement.into(),
+            priority: Priority::P2,
+            category: RequirementCategory::Functional,
+            testable: true,
+            rationale: None,
+            source: None,
+            verification_method: VerificationMethod::Test,
+            risk_level: None,
+            parent_requirement: None,
+            allocated_to: vec![],
+            quality_characteristic: None,
+        }
+    }
+
+    fn scenario(name: &str, verifies: &[&str]) -> Scenario {
+        Scenario {
+            name: name.into(),
+            tags: vec![],
+            scenario_type: ScenarioType::HappyPath,
+            steps: vec![],
+            examples: None,
+            test_data_suggestions: vec![],
+            verification_of: verifies.iter().map(|s| s.to_string()).collect(),
+            coverage_technique: None,
+        }
+    }
+
+    fn fixture() -> (Specification, Specification, TestSuite) {
+        let mut old = Specification::new("Spec".into());
+        old.functional_requirements = vec![
+            fr("FR-001", "Le systeme DOIT lister les livres"),
+            fr("FR-002", "Le systeme DOIT filtrer par genre"),
+            fr("FR-003", "Le systeme DOIT exporter en CSV"),
+            fr("FR-004", "Le systeme DOIT trier par titre"),
+        ];
+        let mut new = old.clone();
+        new.functional_requirements[1].statement = "Le systeme DOIT filtrer par auteur".into();
+        new.functional_requirements[3].priority = Priority::P1;
+        new.functional_requirements.remove(2);
+        new.functional_requirements
+            .push(fr("FR-005", "Le systeme DOIT paginer"));
+
+        let mut feature = Feature::new("Catalogue".into(), String::new());
+        feature.covered_requirements = vec!["FR-001".into(), "FR-004".into()];
+        feature.scenarios = vec![
+            scenario("Lister", &["FR-001"]),
+            scenario("Filtrer", &["FR-002"]),
+            scenario("Exporter", &["FR-003"]),
+            scenario("Exporter et lister", &["FR-001", "FR-003"]),
+            scenario("Trier", &["FR-004"]),
+            scenario("Rechercher", &[]),
+        ];
+        let suite = TestSuite {
+            features: vec![feature],
+            source_spec_id: old.id,
+            total_scenarios: 6,
+            coverage: TestCoverage {
+                requirements_covered: vec![],
+                requirements_total: 4,
+                coverage_percentage: 0.0,
+                scenarios_by_type: Default::default(),
+            },
+        };
+        (old, new, suite)
+    }
+
+    #[test]
+    fn test_diff_requirements() {
+        let (old, new, _) = fixture();
+        let changes = diff_requirements(&old, &new);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.requirement_id.as_str(), c.kind, c.fields.join(",")))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("FR-002", ChangeKind::Modified, "statement".to_string()),
+                ("FR-004", ChangeKind::Modified, "priority".to_string()),
+                ("FR-005", ChangeKind::Added, String::new()),
+                ("FR-003", ChangeKind::Removed, String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_analyze_impact_classifies_scenarios() {
+        let (old, new, suite) = fixture();
+        let report = analyze_impact(&old, &new, &suite);
+        let actions: Vec<_> = report
+            .scenarios
+            .iter()
+            .map(|s| (s.scenario.as_str(), s.action))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("Filtrer", ImpactAction::Regenerate),
+                ("Exporter", ImpactAction::Delete),
+                ("Exporter et lister", ImpactAction::Review),
+                ("Trier", ImpactAction::Review),
+                // Lien indirect via covered_requirements (FR-004)
+                ("Rechercher", ImpactAction::Review),
+            ]
+        );
+        assert_eq!(report.requirements_to_generate, vec!["FR-002", "FR-005"]);
+    }
+
+    #[test]
+    fn test_splice_regenerated_replaces_obsolete_scenarios() {
+        let (old, new, mut suite) = fixture();
+        let report = analyze_impact(&old, &new, &suite);
+
+        let mut generated_feature = Feature::new("Catalogue".into(), String::new());
+        generated_feature.covered_requirements = vec!["FR-005".into()];
+        generated_feature.scenarios = vec![
+            scenario("Filtrer par auteur", &["FR-002"]),
+            scenario("Paginer", &["FR-005"]),
+        ];
+        let regenerated = TestSuite {
+            features: vec![generated_feature],
+            source_spec_id: new.id,
+            total_scenarios: 2,
+            coverage: suite.coverage.clone(),
+        };
+
+        splice_regenerated(
+            &mut suite,
+            &report,
+            regenerated,
+            new.functional_requirements.len(),
+        );
+        let names: Vec<_> = suite.features[0]
+            .scenarios
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "Lister",
+                "Exporter et lister",
+                "Trier",
+                "Rechercher",
+                "Filtrer par auteur",
+                "Paginer"
+            ]
+        );
+        assert_eq!(suite.total_scenarios, 6);
+        assert!(
+            suite.features[0]
+                .covered_requirements
+                .contains(&"FR-005".to_string())
+        );
+    }
+
+    #[test]
+    fn test_impacted_subspec_keeps_only_impacted_requirements() {
+        let (_, new, _) = fixture();
+        let sub = impacted_subspec(&new, &["FR-005".to_string()]);
+        assert_eq!(sub.functional_requirements.len(), 1);
+        assert_eq!(sub.functional_requirements[0].id, "FR-005");
+    }
+}
@@ -1,6 +1,21 @@
+pub mod dedupe;
+pub mod epic;
 pub mod errors;
+pub mod feature_merge;
+pub mod gherkin_refactor;
+pub mod i18n;
+pub mod impact;
+pub mod naming;
+pub mod output_format;
+pub mod output_manifest;
+pub mod regeneration;
 pub mod specification;
+pub mod step_definitions;
+pub mod step_vocabulary;
+pub mod story_lint;
 pub mod test_case;
 pub mod traceability;
+pub mod translation;
 pub mod user_story;
 pub mod validation;
+pub mod verification;
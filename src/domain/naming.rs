@@ -0,0 +1,90 @@
+//! Noms de fichiers deterministes
+//!
+//! Les noms des artefacts derivent de textes choisis par le LLM (nom de
+//! feature, titre de spec) : ils sont translitteres en ASCII pour qu'un
+//! accent de plus ou de moins ne produise pas deux fichiers distincts
+//! (`depot_de_cheque` et `dépôt_de_chèque`).
+
+/// Translittere un caractere latin accentue en ASCII (`None` si inconnu)
+fn fold_char(c: char) -> Option<&'static str> {
+    let folded = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' | 'ě' => "e",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' => "i",
+        'ł' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'œ' => "oe",
+        'ř' => "r",
+        'ś' | 'š' => "s",
+        'ß' => "ss",
+        'ť' => "t",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+/// Translittere un texte en ASCII minuscule ; les caracteres non latins
+/// sont conserves tels quels
+pub fn transliterate(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        match fold_char(c) {
+            Some(folded) => out.push_str(folded),
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+/// Slug ASCII : minuscules, accents translitteres, tout autre caractere
+/// remplace par `separator` (sans repetition ni separateur en bordure)
+pub fn ascii_slug(text: &str, separator: char) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in transliterate(text).chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with(separator) {
+            slug.push(separator);
+        }
+    }
+    slug.trim_end_matches(separator).to_string()
+}
+
+/// Identifiant source utilisable dans un nom de fichier (`US-003`)
+pub fn file_id(id: &str) -> String {
+    id.chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_slug_folds_accents() {
+        assert_eq!(
+            ascii_slug("Dépôt de chèque par photo", '_'),
+            ascii_slug("Depot de cheque par photo", '_')
+        );
+        assert_eq!(
+            ascii_slug("Recherche d'un livre (ISBN)", '_'),
+            "recherche_d_un_livre_isbn"
+        );
+        assert_eq!(ascii_slug("  Cœur & Straße  ", '-'), "coeur-strasse");
+        assert_eq!(ascii_slug("!!!", '_'), "");
+    }
+
+    #[test]
+    fn test_file_id_keeps_ascii_id() {
+        assert_eq!(file_id("US-003"), "US-003");
+        assert_eq!(file_id("US 003/é"), "US003");
+    }
+}
@@ -0,0 +1,95 @@
+//! Formats de sortie du pipeline (`output.formats`)
+
+use crate::domain::test_case::TestExportFormat;
+
+/// Format de sortie produit apres la generation des tests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Specification Markdown (`specs/spec-*.md`)
+    Markdown,
+    /// Specification Word (`specs/spec-*.docx`)
+    Docx,
+    /// Fichiers `.feature` Gherkin
+    Feature,
+    /// Matrice de tracabilite Markdown
+    Traceability,
+    /// `specification.json` et `test_suite.json`
+    Json,
+    /// Rapport HTML autonome
+    Html,
+    /// Export vers un outil de gestion de tests
+    TestExport(TestExportFormat),
+}
+
+impl OutputFormat {
+    /// Construit un format a partir d'un code ("markdown", "docx", "feature",
+    /// "traceability", "json", "html", "xray", "testrail", "junit")
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_lowercase().as_str() {
+            "markdown" | "md" => Some(OutputFormat::Markdown),
+            "docx" | "word" => Some(OutputFormat::Docx),
+            "feature" | "gherkin" => Some(OutputFormat::Feature),
+            "traceability" => Some(OutputFormat::Traceability),
+            "json" => Some(OutputFormat::Json),
+            "html" => Some(OutputFormat::Html),
+            other => TestExportFormat::from_code(other).map(OutputFormat::TestExport),
+        }
+    }
+
+    /// Code canonique du format
+    pub fn code(&self) -> &'static str {
+        match self {
+            OutputFormat::Markdown => "markdown",
+            OutputFormat::Docx => "docx",
+            OutputFormat::Feature => "feature",
+            OutputFormat::Traceability => "traceability",
+            OutputFormat::Json => "json",
+            OutputFormat::Html => "html",
+            OutputFormat::TestExport(format) => format.code(),
+        }
+    }
+
+    /// Le format ecrit la specification elle-meme
+    pub fn is_specification(&self) -> bool {
+        matches!(self, OutputFormat::Markdown | OutputFormat::Docx)
+    }
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_format_from_code() {
+        assert_eq!(OutputFormat::from_code("md"), Some(OutputFormat::Markdown));
+        assert_eq!(
+            OutputFormat::from_code(" Gherkin "),
+            Some(OutputFormat::Feature)
+        );
+        assert_eq!(
+            OutputFormat::from_code("testrail"),
+            Some(OutputFormat::TestExport(TestExportFormat::TestRail))
+        );
+        assert_eq!(OutputFormat::from_code("pdf"), None);
+
+        for code in [
+            "markdown",
+            "docx",
+            "feature",
+            "traceability",
+            "json",
+            "html",
+            "junit",
+        ] {
+            assert_eq!(OutputFormat::from_code(code).unwrap().code(), code);
+        }
+        assert!(OutputFormat::Docx.is_specification());
+        assert!(!OutputFormat::Json.is_specification());
+    }
+}
@@ -0,0 +1,192 @@
+//! Manifeste des artefacts produits par le pipeline
+//!
+//! `manifest.json` liste chaque fichier ecrit (chemin relatif au dossier de
+//! sortie, empreinte, nature, IDs source). Compare au manifeste du run
+//! precedent, il identifie les artefacts qui ne sont plus produits (feature
+//! renommee, epic supprimee) sans jamais toucher aux fichiers inconnus.
+
+use std::path::{Component, Path};
+
+use serde::{Deserialize, Serialize};
+
+/// Nom du manifeste a la racine du dossier de sortie
+pub const OUTPUT_MANIFEST_FILE: &str = "manifest.json";
+
+/// Nature d'un artefact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    Specification,
+    EpicIndex,
+    Feature,
+    Traceability,
+    StepGlossary,
+    TestExport,
+    HtmlReport,
+    /// Specification et suite serialisees en JSON
+    Json,
+    /// Artefact d'un writer injecte par l'application
+    Custom,
+}
+
+impl std::fmt::Display for ArtifactKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArtifactKind::Specification => write!(f, "Specification"),
+            ArtifactKind::EpicIndex => write!(f, "Index des epics"),
+            ArtifactKind::Feature => write!(f, "Feature Gherkin"),
+            ArtifactKind::Traceability => write!(f, "Tracabilite"),
+            ArtifactKind::StepGlossary => write!(f, "Glossaire des etapes"),
+            ArtifactKind::TestExport => write!(f, "Export de tests"),
+            ArtifactKind::HtmlReport => write!(f, "Rapport HTML"),
+            ArtifactKind::Json => write!(f, "Donnees JSON"),
+            ArtifactKind::Custom => write!(f, "Artefact externe"),
+        }
+    }
+}
+
+/// Artefact ecrit lors d'un run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Chemin relatif au dossier de sortie (separateurs `/`)
+    pub path: String,
+    /// Empreinte FNV-1a 64 bits du contenu
+    pub hash: String,
+    pub kind: ArtifactKind,
+    /// User Stories a l'origine de l'artefact
+    #[serde(default)]
+    pub source_ids: Vec<String>,
+}
+
+/// Manifeste d'un dossier de sortie
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OutputManifest {
+    pub artifacts: Vec<ManifestEntry>,
+}
+
+impl OutputManifest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enregistre un artefact (remplace une entree de meme chemin)
+    pub fn record(
+        &mut self,
+        path: String,
+        content: &[u8],
+        kind: ArtifactKind,
+        source_ids: Vec<String>,
+    ) {
+        self.artifacts.retain(|a| a.path != path);
+        self.artifacts.push(ManifestEntry {
+            path,
+            hash: content_hash(content),
+            kind,
+            source_ids,
+        });
+        self.artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+
+    pub fn get(&self, path: &str) -> Option<&ManifestEntry> {
+        self.artifacts.iter().find(|a| a.path == path)
+    }
+
+    /// Artefacts du manifeste precedent qui ne sont plus produits
+    ///
+    /// Les chemins absolus ou remontant hors du dossier de sortie sont
+    /// ignores : le nettoyage ne doit jamais en sortir.
+    pub fn stale_since<'a>(&self, previous: &'a OutputManifest) -> Vec<&'a ManifestEntry> {
+        previous
+            .artifacts
+            .iter()
+            .filter(|a| self.get(&a.path).is_none())
+            .filter(|a| is_contained(&a.path))
+            .collect()
+    }
+}
+
+/// Empreinte FNV-1a 64 bits (hexadecimal), stable d'une version a l'autre
+pub fn content_hash(content: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in content {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{hash:016x}")
+}
+
+fn is_contained(relative: &str) -> bool {
+    !relative.is_empty()
+        && Path::new(relative)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_record_is_sorted_and_replaces_same_path() {
+        let mut manifest = OutputManifest::new();
+        manifest.record(
+            "specs/b.md".into(),
+            b"b",
+            ArtifactKind::Specification,
+            vec![],
+        );
+        manifest.record(
+            "features/a.feature".into(),
+            b"a",
+            ArtifactKind::Feature,
+            vec!["US-001".into()],
+        );
+        manifest.record(
+            "specs/b.md".into(),
+            b"b2",
+            ArtifactKind::Specification,
+            vec![],
+        );
+
+        let paths: Vec<_> = manifest.artifacts.iter().map(|a| a.path.as_str()).collect();
+        assert_eq!(paths, vec!["features/a.feature", "specs/b.md"]);
+        assert_eq!(
+            manifest.get("specs/b.md").unwrap().hash,
+            content_hash(b"b2")
+        );
+    }
+
+    #[test]
+    fn test_stale_since_lists_only_contained_missing_paths() {
+        let mut previous = OutputManifest::new();
+        for path in [
+            "features/US-001_ancien_nom.feature",
+            "features/US-002_garde.feature",
+            "../hors_sortie.md",
+            "/etc/passwd",
+        ] {
+            previous.record(path.into(), b"x", ArtifactKind::Feature, vec![]);
+        }
+        let mut current = OutputManifest::new();
+        current.record(
+            "features/US-002_garde.feature".into(),
+            b"x",
+            ArtifactKind::Feature,
+            vec![],
+        );
+
+        let stale: Vec<_> = current
+            .stale_since(&previous)
+            .iter()
+            .map(|a| a.path.as_str())
+            .collect();
+        assert_eq!(stale, vec!["features/US-001_ancien_nom.feature"]);
+    }
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        assert_eq!(content_hash(b""), "cbf29ce484222325");
+        assert_ne!(content_hash(b"a"), content_hash(b"b"));
+    }
+}
@@ -0,0 +1,268 @@
+//! Regeneration ciblee des tests d'un sous-ensemble d'exigences
+//!
+//! Selection des exigences (liste d'IDs, priorite, exigences non couvertes)
+//! et fusion des scenarios regeneres dans une suite existante : seuls les
+//! scenarios verifiant exclusivement des exigences selectionnees sont
+//! remplaces, les autres (eventuellement retouches a la main) sont conserves.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::specification::Specification;
+use super::test_case::{Scenario, TestSuite};
+use super::user_story::Priority;
+
+/// Criteres de selection des exigences a regenerer (cumulatifs)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RequirementSelection {
+    /// IDs explicites (`--only FR-003,FR-017`)
+    pub only: Vec<String>,
+    /// Priorite (`--priority P1`)
+    pub priority: Option<Priority>,
+    /// Exigences sans scenario dans la suite existante (`--uncovered`)
+    pub uncovered: bool,
+}
+
+impl RequirementSelection {
+    pub fn is_empty(&self) -> bool {
+        self.only.is_empty() && self.priority.is_none() && !self.uncovered
+    }
+
+    /// IDs des exigences selectionnees, dans l'ordre de la spec
+    ///
+    /// Les criteres se combinent (intersection). Un ID de `only` absent de
+    /// la spec est ignore.
+    pub fn select(&self, spec: &Specification, suite: &TestSuite) -> Vec<String> {
+        let covered: HashSet<String> = suite
+            .features
+            .iter()
+            .flat_map(|f| &f.scenarios)
+            .flat_map(Scenario::linked_requirements)
+            .collect();
+        spec.functional_requirements
+            .iter()
+            .filter(|fr| self.only.is_empty() || self.only.contains(&fr.id))
+            .filter(|fr| self.priority.is_none_or(|p| fr.priority == p))
+            .filter(|fr| !self.uncovered || !covered.contains(&fr.id))
+            .map(|fr| fr.id.clone())
+            .collect()
+    }
+}
+
+/// Bilan de la fusion des scenarios regeneres
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MergeOutcome {
+    /// Scenarios existants remplaces
+    pub replaced: usize,
+    /// Scenarios regeneres ajoutes
+    pub added: usize,
+    /// Scenarios conserves car ils verifient aussi des exigences non selectionnees
+    pub shared: Vec<String>,
+}
+
+/// Remplace les scenarios des exigences selectionnees par les scenarios regeneres
+pub fn merge_regenerated_requirements(
+    suite: &mut TestSuite,
+    selected: &[String],
+    regenerated: TestSuite,
+    total_requirements: usize,
+) -> MergeOutcome {
+    let mut outcome = MergeOutcome::default();
+    for feature in &mut suite.features {
+        let before = feature.scenarios.len();
+        feature.scenarios.retain(|scenario| {
+            let linked = scenario.linked_requirements();
+            let touched = linked.iter().filter(|id| selected.contains(id)).count();
+            if touched == 0 {
+                return true;
+            }
+            if touched < linked.len() {
+                outcome.shared.push(scenario.name.clone());
+                return true;
+            }
+            false
+        });
+        outcome.replaced += before - feature.scenarios.len();
+    }
+
+    outcome.added = regenerated.features.iter().map(|f| f.scenarios.len()).sum();
+    merge_features(suite, regenerated);
+    suite.compute_coverage(total_requirements);
+    outcome
+}
+
+/// Garde les scenarios regeneres qui verifient au moins une exigence selectionnee
+pub fn retain_selected_scenarios(suite: &mut TestSuite, selected: &[String]) -> usize {
+    let mut dropped = 0;
+    for feature in &mut suite.features {
+        let before = feature.scenarios.len();
+        feature.scenarios.retain(|scenario| {
+            scenario
+                .linked_requirements()
+                .iter()
+                .any(|id| selected.contains(id))
+        });
+        dropped += before - feature.scenarios.len();
+        feature
+            .covered_requirements
+            .retain(|id| selected.contains(id));
+    }
+    suite.features.retain(|f| !f.scenarios.is_empty());
+    dropped
+}
+
+/// Fusionne des features dans la suite (par nom) ; les features devenues
+/// vides sont retirees
+pub(crate) fn merge_features(suite: &mut TestSuite, incoming: TestSuite) {
+    suite.features.retain(|f| !f.scenarios.is_empty());
+    for generated in incoming.features {
+        match suite.features.iter_mut().find(|f| f.name == generated.name) {
+            Some(existing) => {
+                for id in generated.covered_requirements {
+                    if !existing.covered_requirements.contains(&id) {
+                        existing.covered_requirements.push(id);
+                    }
+                }
+                existing.scenarios.extend(generated.scenarios);
+            }
+            None => suite.features.push(generated),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::specification::{
+        FunctionalRequirement, RequirementCategory, VerificationMethod,
+    };
+    use crate::domain::test_case::{Feature, Scenario, ScenarioType, TestCoverage};
+    use pretty_assertions::assert_eq;
+
+    fn fr(id: &str, priority: Priority) -> FunctionalRequirement {
+        FunctionalRequirement {
+            id: id.into(),
+            statement: format!("Exigence {id}"),
+            priority,
+            category: RequirementCategory::Functional,
+            testable: true,
+            rationale: None,
+            source: None,
+            verification_method: VerificationMethod::Test,
+            risk_level: None,
+            parent_requirement: None,
+            allocated_to: vec![],
+            quality_characteristic: None,
+        }
+    }
+
+    fn scenario(name: &str, verifies: &[&str]) -> Scenario {
+        Scenario {
+            name: name.into(),
+            tags: vec![],
+            scenario_type: ScenarioType::HappyPath,
+            steps: vec![],
+            examples: None,
+            test_data_suggestions: vec![],
+            verification_of: verifies.iter().map(|s| s.to_string()).collect(),
+            coverage_technique: None,
+        }
+    }
+
+    fn suite(features: Vec<Feature>) -> TestSuite {
+        TestSuite {
+            features,
+            source_spec_id: uuid::Uuid::new_v4(),
+            total_scenarios: 0,
+            coverage: TestCoverage {
+                requirements_covered: vec![],
+                requirements_total: 0,
+                coverage_percentage: 0.0,
+                scenarios_by_type: Default::default(),
+            },
+        }
+    }
+
+    fn fixture() -> (Specification, TestSuite) {
+        let mut spec = Specification::new("Spec".into());
+        spec.functional_requirements = vec![
+            fr("FR-001", Priority::P1),
+            fr("FR-002", Priority::P2),
+            fr("FR-003", Priority::P1),
+        ];
+        let mut feature = Feature::new("Catalogue".into(), String::new());
+        feature.scenarios = vec![
+            scenario("Lister", &["FR-001"]),
+            scenario("Lister (retouche)", &["FR-001"]),
+            scenario("Filtrer", &["FR-002"]),
+            scenario("Lister et filtrer", &["FR-001", "FR-002"]),
+        ];
+        (spec, suite(vec![feature]))
+    }
+
+    #[test]
+    fn test_select_combines_criteria() {
+        let (spec, existing) = fixture();
+        let only_p1 = RequirementSelection {
+            priority: Some(Priority::P1),
+            ..Default::default()
+        };
+        assert_eq!(only_p1.select(&spec, &existing), vec!["FR-001", "FR-003"]);
+
+        let uncovered_p1 = RequirementSelection {
+            priority: Some(Priority::P1),
+            uncovered: true,
+            ..Default::default()
+        };
+        assert_eq!(uncovered_p1.select(&spec, &existing), vec!["FR-003"]);
+
+        let only = RequirementSelection {
+            only: vec!["FR-002".into(), "FR-999".into()],
+            ..Default::default()
+        };
+        assert_eq!(only.select(&spec, &existing), vec!["FR-002"]);
+    }
+
+    #[test]
+    fn test_merge_replaces_only_exclusive_scenarios() {
+        let (_, mut existing) = fixture();
+        let mut generated = Feature::new("Catalogue".into(), String::new());
+        generated.scenarios = vec![scenario("Lister les livres", &["FR-001"])];
+
+        let outcome = merge_regenerated_requirements(
+            &mut existing,
+            &["FR-001".to_string()],
+            suite(vec![generated]),
+            3,
+        );
+        assert_eq!(outcome.replaced, 2);
+        assert_eq!(outcome.added, 1);
+        assert_eq!(outcome.shared, vec!["Lister et filtrer"]);
+        let names: Vec<_> = existing.features[0]
+            .scenarios
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["Filtrer", "Lister et filtrer", "Lister les livres"]
+        );
+        assert_eq!(existing.total_scenarios, 3);
+    }
+
+    #[test]
+    fn test_retain_selected_scenarios_drops_others() {
+        let mut generated = Feature::new("Catalogue".into(), String::new());
+        generated.covered_requirements = vec!["FR-001".into(), "FR-002".into()];
+        generated.scenarios = vec![scenario("A", &["FR-001"]), scenario("B", &["FR-002"])];
+        let mut generated = suite(vec![generated]);
+
+        assert_eq!(
+            retain_selected_scenarios(&mut generated, &["FR-001".into()]),
+            1
+        );
+        assert_eq!(generated.features[0].scenarios.len(), 1);
+        assert_eq!(generated.features[0].covered_requirements, vec!["FR-001"]);
+    }
+}
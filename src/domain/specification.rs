@@ -9,7 +9,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::user_story::Priority;
+use super::user_story::{Language, Priority};
 
 // ---------------------------------------------------------------------------
 // Enums ISO 29148 / 25010 / Safety standards
@@ -198,6 +198,10 @@ pub struct Specification {
     #[serde(default)]
     pub compliance_profile: Option<ComplianceProfile>,
 
+    /// Langue des User Stories sources (mots normatifs et ambigus verifies)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<Language>,
+
     /// Scenarios utilisateur raffines avec priorites
     pub user_scenarios: Vec<UserScenario>,
 
@@ -246,6 +250,7 @@ impl Specification {
             author: None,
             tool_version: default_tool_version(),
             compliance_profile: None,
+            language: None,
             user_scenarios: Vec::new(),
             functional_requirements: Vec::new(),
             key_entities: Vec::new(),
@@ -286,6 +291,35 @@ impl std::fmt::Display for SpecStatus {
     }
 }
 
+/// Format d'ecriture de la specification (`output.spec_format`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecFormat {
+    Markdown,
+    Docx,
+    /// Markdown et DOCX
+    Both,
+}
+
+impl SpecFormat {
+    /// Construit un format a partir d'un code ("markdown", "docx", "both")
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_lowercase().as_str() {
+            "markdown" | "md" => Some(SpecFormat::Markdown),
+            "docx" | "word" => Some(SpecFormat::Docx),
+            "both" => Some(SpecFormat::Both),
+            _ => None,
+        }
+    }
+
+    pub fn includes_markdown(&self) -> bool {
+        matches!(self, SpecFormat::Markdown | SpecFormat::Both)
+    }
+
+    pub fn includes_docx(&self) -> bool {
+        matches!(self, SpecFormat::Docx | SpecFormat::Both)
+    }
+}
+
 /// Scenario utilisateur raffine (mappe depuis UserStory + enrichissement LLM)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserScenario {
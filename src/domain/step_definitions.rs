@@ -0,0 +1,559 @@
+//! Catalogue des step definitions d'une suite de tests
+//!
+//! Chaque texte d'etape distinct est converti en Cucumber Expression : les
+//! litteraux entre guillemets deviennent `{string}`, les nombres `{int}` /
+//! `{float}` et les placeholders `<nom>` un parametre type d'apres les
+//! valeurs de la table Examples.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::dedupe::fold_accent;
+use super::specification::KeyEntity;
+use super::test_case::{Scenario, Step, StepKeyword, TestSuite};
+
+/// Groupe des steps sans entite reconnue
+pub const COMMON_GROUP: &str = "commun";
+
+/// Type de parametre Cucumber Expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParameterType {
+    /// `{int}`
+    Int,
+    /// `{float}`
+    Float,
+    /// `{string}` (valeur entre guillemets, guillemets inclus)
+    String,
+    /// `{word}` (un mot sans espace)
+    Word,
+    /// `{}` (parametre anonyme)
+    Anonymous,
+}
+
+impl ParameterType {
+    /// Syntaxe Cucumber Expression
+    pub fn expression(&self) -> &'static str {
+        match self {
+            ParameterType::Int => "{int}",
+            ParameterType::Float => "{float}",
+            ParameterType::String => "{string}",
+            ParameterType::Word => "{word}",
+            ParameterType::Anonymous => "{}",
+        }
+    }
+
+    /// Type deduit des valeurs d'une colonne Examples
+    fn infer(values: &[&str]) -> Self {
+        if values.is_empty() {
+            ParameterType::Anonymous
+        } else if values.iter().all(|v| v.parse::<i64>().is_ok()) {
+            ParameterType::Int
+        } else if values
+            .iter()
+            .all(|v| v.parse::<i64>().is_ok() || is_decimal(v))
+        {
+            ParameterType::Float
+        } else if values
+            .iter()
+            .all(|v| !v.is_empty() && !v.contains(char::is_whitespace))
+        {
+            ParameterType::Word
+        } else {
+            ParameterType::Anonymous
+        }
+    }
+}
+
+/// Parametre d'une step definition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepParameter {
+    /// Nom d'argument (placeholder d'origine ou `argN`)
+    pub name: String,
+    pub kind: ParameterType,
+}
+
+/// Morceau d'expression : texte litteral ou parametre (index dans `parameters`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExpressionPart {
+    Text(String),
+    Parameter(usize),
+}
+
+/// Step definition a generer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepDefinition {
+    /// Given, When ou Then (And/But resolus sur l'etape precedente)
+    pub keyword: StepKeyword,
+    pub parts: Vec<ExpressionPart>,
+    pub parameters: Vec<StepParameter>,
+    /// Premier texte d'etape rencontre
+    pub sample: String,
+    /// Groupe (feature ou entite) : un fichier de steps par groupe
+    pub group: String,
+}
+
+impl StepDefinition {
+    /// Cucumber Expression (`je vire {int} EUR`)
+    pub fn cucumber_expression(&self) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                ExpressionPart::Text(text) => escape_expression_text(text),
+                ExpressionPart::Parameter(i) => self.parameters[*i].kind.expression().to_string(),
+            })
+            .collect()
+    }
+}
+
+/// Regroupement des step definitions en fichiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StepGrouping {
+    /// Un fichier par feature (premiere feature utilisant le step)
+    #[default]
+    Feature,
+    /// Un fichier par `KeyEntity` citee dans le texte du step
+    Entity,
+}
+
+impl StepGrouping {
+    /// Construit un StepGrouping a partir d'un libelle ("feature", "entity")
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_lowercase().as_str() {
+            "feature" => Some(StepGrouping::Feature),
+            "entity" | "entite" => Some(StepGrouping::Entity),
+            _ => None,
+        }
+    }
+}
+
+/// Framework cible des squelettes de step definitions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StepTarget {
+    /// cucumber-rs
+    Rust,
+    /// Cucumber-JVM
+    Java,
+    /// cucumber-js
+    TypeScript,
+    /// behave
+    Python,
+}
+
+impl StepTarget {
+    /// Construit un StepTarget a partir d'un code ("rust", "java", "typescript", "python")
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_lowercase().as_str() {
+            "rust" | "rs" => Some(StepTarget::Rust),
+            "java" => Some(StepTarget::Java),
+            "typescript" | "ts" => Some(StepTarget::TypeScript),
+            "python" | "py" => Some(StepTarget::Python),
+            _ => None,
+        }
+    }
+
+    /// Nom du framework Cucumber correspondant
+    pub fn framework(&self) -> &'static str {
+        match self {
+            StepTarget::Rust => "cucumber-rs",
+            StepTarget::Java => "Cucumber-JVM",
+            StepTarget::TypeScript => "cucumber-js",
+            StepTarget::Python => "behave",
+        }
+    }
+}
+
+impl std::fmt::Display for StepTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.framework())
+    }
+}
+
+/// Caracteres reserves des Cucumber Expressions
+fn escape_expression_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '(' | ')' | '{' | '}' | '/' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn is_decimal(value: &str) -> bool {
+    value.split_once(['.', ',']).is_some_and(|(int, frac)| {
+        let int = int.strip_prefix('-').unwrap_or(int);
+        !int.is_empty()
+            && !frac.is_empty()
+            && int.chars().all(|c| c.is_ascii_digit())
+            && frac.chars().all(|c| c.is_ascii_digit())
+    })
+}
+
+/// Nom d'argument ASCII (`<Montant TTC>` -> `montant_ttc`)
+pub fn argument_name(name: &str) -> String {
+    let mut ident = String::new();
+    for c in name.trim().to_lowercase().chars().map(fold_accent) {
+        if c.is_ascii_alphanumeric() {
+            ident.push(c);
+        } else if !ident.ends_with('_') {
+            ident.push('_');
+        }
+    }
+    let ident = ident.trim_matches('_').to_string();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("arg_{}", ident)
+    } else {
+        ident
+    }
+}
+
+/// Longueur (en octets) d'un nombre en tete de `text`, s'il est isole
+fn leading_number(text: &str) -> Option<(usize, ParameterType)> {
+    let bytes = text.as_bytes();
+    let mut end = usize::from(bytes.first() == Some(&b'-'));
+    let digits_start = end;
+    while end < bytes.len() && bytes[end].is_ascii_digit() {
+        end += 1;
+    }
+    if end == digits_start {
+        return None;
+    }
+    let mut kind = ParameterType::Int;
+    if end + 1 < bytes.len() && matches!(bytes[end], b'.' | b',') && bytes[end + 1].is_ascii_digit()
+    {
+        end += 1;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+        kind = ParameterType::Float;
+    }
+    // Le nombre doit etre suivi d'une fin de mot (pas "13h" ni "978-2")
+    let next = text[end..].chars().next();
+    let boundary = match next {
+        None => true,
+        Some(c) => c.is_whitespace() || matches!(c, ',' | ';' | ':' | '!' | '?' | ')' | '%'),
+    } || (next == Some('.')
+        && !text[end + 1..].starts_with(|c: char| c.is_ascii_digit()));
+    boundary.then_some((end, kind))
+}
+
+/// Ajoute un parametre (nom rendu unique) apres le texte litteral en cours
+fn push_parameter(
+    parts: &mut Vec<ExpressionPart>,
+    parameters: &mut Vec<StepParameter>,
+    literal: &mut String,
+    name: String,
+    kind: ParameterType,
+) {
+    if !literal.is_empty() {
+        parts.push(ExpressionPart::Text(std::mem::take(literal)));
+    }
+    let mut unique = name.clone();
+    let mut n = 2;
+    while parameters.iter().any(|p| p.name == unique) {
+        unique = format!("{}_{}", name, n);
+        n += 1;
+    }
+    parts.push(ExpressionPart::Parameter(parameters.len()));
+    parameters.push(StepParameter { name: unique, kind });
+}
+
+/// Convertit un texte d'etape en morceaux d'expression et parametres
+pub fn parse_step_text(
+    text: &str,
+    placeholder_types: &HashMap<String, ParameterType>,
+) -> (Vec<ExpressionPart>, Vec<StepParameter>) {
+    let (parts, parameters, _) = parse_step_with_values(text, placeholder_types);
+    (parts, parameters)
+}
+
+/// Comme [`parse_step_text`], avec le texte brut de chaque parametre
+/// (guillemets et chevrons inclus), dans l'ordre de `parameters`
+pub fn parse_step_with_values(
+    text: &str,
+    placeholder_types: &HashMap<String, ParameterType>,
+) -> (Vec<ExpressionPart>, Vec<StepParameter>, Vec<String>) {
+    let mut parts = Vec::new();
+    let mut parameters: Vec<StepParameter> = Vec::new();
+    let mut values = Vec::new();
+    let mut literal = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let at_word_start = literal
+            .chars()
+            .last()
+            .is_none_or(|p| p.is_whitespace() || matches!(p, '(' | '\'' | '/'));
+
+        // Litteral entre guillemets -> {string}
+        if c == '"'
+            && let Some(end) = rest[1..].find('"')
+        {
+            let inner = &rest[1..1 + end];
+            let name = match inner.strip_prefix('<').and_then(|i| i.strip_suffix('>')) {
+                Some(placeholder) => argument_name(placeholder),
+                None => format!("arg{}", parameters.len() + 1),
+            };
+            push_parameter(
+                &mut parts,
+                &mut parameters,
+                &mut literal,
+                name,
+                ParameterType::String,
+            );
+            values.push(rest[..end + 2].to_string());
+            rest = &rest[end + 2..];
+            continue;
+        }
+
+        // Placeholder de Scenario Outline -> type deduit des Examples
+        if c == '<'
+            && let Some(end) = rest[1..].find('>')
+            && end > 0
+            && !rest[1..1 + end].contains(['<', '\n', '"'])
+        {
+            let placeholder = &rest[1..1 + end];
+            let kind = placeholder_types
+                .get(placeholder)
+                .copied()
+                .unwrap_or(ParameterType::Anonymous);
+            push_parameter(
+                &mut parts,
+                &mut parameters,
+                &mut literal,
+                argument_name(placeholder),
+                kind,
+            );
+            values.push(rest[..end + 2].to_string());
+            rest = &rest[end + 2..];
+            continue;
+        }
+
+        // Nombre isole -> {int} / {float}
+        if at_word_start && let Some((len, kind)) = leading_number(rest) {
+            let name = format!("arg{}", parameters.len() + 1);
+            push_parameter(&mut parts, &mut parameters, &mut literal, name, kind);
+            values.push(rest[..len].to_string());
+            rest = &rest[len..];
+            continue;
+        }
+
+        literal.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    if !literal.is_empty() {
+        parts.push(ExpressionPart::Text(literal));
+    }
+    (parts, parameters, values)
+}
+
+/// Types des placeholders d'un scenario d'apres sa table Examples
+pub fn placeholder_types(scenario: &Scenario) -> HashMap<String, ParameterType> {
+    let Some(examples) = &scenario.examples else {
+        return HashMap::new();
+    };
+    examples
+        .headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            let values: Vec<&str> = examples
+                .rows
+                .iter()
+                .filter_map(|row| row.get(i))
+                .map(|v| v.trim())
+                .collect();
+            (header.trim().to_string(), ParameterType::infer(&values))
+        })
+        .collect()
+}
+
+/// Premiere entite dont le nom apparait dans le texte (sans casse)
+fn entity_group(text: &str, entities: &[KeyEntity]) -> Option<String> {
+    let lower = text.to_lowercase();
+    entities
+        .iter()
+        .find(|e| !e.name.trim().is_empty() && lower.contains(&e.name.trim().to_lowercase()))
+        .map(|e| e.name.trim().to_string())
+}
+
+/// Step definitions distinctes d'une suite, dans l'ordre de premiere
+/// apparition. Cucumber apparie l'expression sans tenir compte du mot-cle :
+/// une meme expression sous Given et When donnerait un step ambigu, seule la
+/// premiere est gardee.
+pub fn collect_step_definitions(
+    suite: &TestSuite,
+    entities: &[KeyEntity],
+    grouping: StepGrouping,
+) -> Vec<StepDefinition> {
+    let mut definitions = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+
+    let mut collect = |steps: &[Step],
+                       types: &HashMap<String, ParameterType>,
+                       feature_name: &str,
+                       definitions: &mut Vec<StepDefinition>| {
+        let mut previous = StepKeyword::Given;
+        for step in steps {
+            let keyword = match step.keyword {
+                StepKeyword::And | StepKeyword::But => previous,
+                keyword => keyword,
+            };
+            previous = keyword;
+
+            let (parts, parameters) = parse_step_text(step.text.trim(), types);
+            let definition = StepDefinition {
+                keyword,
+                parts,
+                parameters,
+                sample: step.text.trim().to_string(),
+                group: match grouping {
+                    StepGrouping::Feature => feature_name.to_string(),
+                    StepGrouping::Entity => entity_group(&step.text, entities)
+                        .unwrap_or_else(|| COMMON_GROUP.to_string()),
+                },
+            };
+            if seen.insert(definition.cucumber_expression()) {
+                definitions.push(definition);
+            }
+        }
+    };
+
+    for feature in &suite.features {
+        if let Some(background) = &feature.background {
+            collect(
+                &background.steps,
+                &HashMap::new(),
+                &feature.name,
+                &mut definitions,
+            );
+        }
+        for scenario in &feature.scenarios {
+            collect(
+                &scenario.steps,
+                &placeholder_types(scenario),
+                &feature.name,
+                &mut definitions,
+            );
+        }
+    }
+    definitions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_case::{Examples, Feature, ScenarioType};
+    use pretty_assertions::assert_eq;
+
+    fn step(keyword: StepKeyword, text: &str) -> Step {
+        Step {
+            keyword,
+            text: text.into(),
+            doc_string: None,
+            data_table: None,
+        }
+    }
+
+    fn expression(text: &str) -> String {
+        let types: HashMap<String, ParameterType> = [
+            ("montant".to_string(), ParameterType::Int),
+            ("devise".to_string(), ParameterType::Word),
+        ]
+        .into_iter()
+        .collect();
+        let (parts, parameters) = parse_step_text(text, &types);
+        StepDefinition {
+            keyword: StepKeyword::Given,
+            parts,
+            parameters,
+            sample: text.into(),
+            group: COMMON_GROUP.into(),
+        }
+        .cucumber_expression()
+    }
+
+    #[test]
+    fn test_cucumber_expressions() {
+        assert_eq!(
+            expression("l'ouvrage \"Le Petit Prince\" est affiche"),
+            "l'ouvrage {string} est affiche"
+        );
+        assert_eq!(
+            expression("je vire <montant> <devise> (frais 1,5 %)"),
+            "je vire {int} {word} \\(frais {float} %\\)"
+        );
+        assert_eq!(
+            expression("un catalogue de 1000 ouvrages, ISBN-13 et 978-2"),
+            "un catalogue de {int} ouvrages, ISBN-13 et 978-2"
+        );
+        assert_eq!(expression("a 14h ou <inconnu>"), "a 14h ou {}");
+    }
+
+    #[test]
+    fn test_parameter_type_inference() {
+        assert_eq!(ParameterType::infer(&["10", "-3"]), ParameterType::Int);
+        assert_eq!(ParameterType::infer(&["10.5", "3"]), ParameterType::Float);
+        assert_eq!(ParameterType::infer(&["10,5", "3.0"]), ParameterType::Float);
+        assert_eq!(ParameterType::infer(&["EUR", "USD"]), ParameterType::Word);
+        assert_eq!(
+            ParameterType::infer(&["mot de passe"]),
+            ParameterType::Anonymous
+        );
+    }
+
+    #[test]
+    fn test_collect_dedupes_and_resolves_and() {
+        let mut feature = Feature::new("Virement".into(), String::new());
+        for (name, amount) in [("A", "10"), ("B", "20")] {
+            feature.scenarios.push(Scenario {
+                name: name.into(),
+                tags: Vec::new(),
+                scenario_type: ScenarioType::HappyPath,
+                steps: vec![
+                    step(StepKeyword::Given, "un compte Courant"),
+                    step(StepKeyword::When, &format!("je vire {amount} EUR")),
+                    step(StepKeyword::Then, "le solde est debite"),
+                    step(StepKeyword::And, "un avis est envoye"),
+                    step(StepKeyword::When, "un compte Courant"),
+                ],
+                examples: None,
+                test_data_suggestions: Vec::new(),
+                verification_of: Vec::new(),
+                coverage_technique: None,
+            });
+        }
+        feature.scenarios[1].examples = Some(Examples {
+            headers: vec!["x".into()],
+            rows: vec![vec!["1".into()]],
+        });
+        let suite = TestSuite {
+            features: vec![feature],
+            source_spec_id: uuid::Uuid::nil(),
+            total_scenarios: 2,
+            coverage: crate::domain::test_case::TestCoverage {
+                requirements_covered: Vec::new(),
+                requirements_total: 0,
+                coverage_percentage: 0.0,
+                scenarios_by_type: Default::default(),
+            },
+        };
+        let entities = vec![KeyEntity {
+            name: "Compte".into(),
+            description: String::new(),
+            attributes: Vec::new(),
+            relationships: Vec::new(),
+        }];
+
+        let definitions = collect_step_definitions(&suite, &entities, StepGrouping::Entity);
+        // "un compte Courant" sous When : meme expression que sous Given
+        assert_eq!(definitions.len(), 4);
+        assert_eq!(definitions[0].keyword, StepKeyword::Given);
+        assert_eq!(definitions[1].cucumber_expression(), "je vire {int} EUR");
+        assert_eq!(definitions[3].keyword, StepKeyword::Then);
+        assert_eq!(definitions[0].group, "Compte");
+        assert_eq!(definitions[1].group, COMMON_GROUP);
+    }
+}
@@ -0,0 +1,554 @@
+//! Normalisation du vocabulaire des etapes Gherkin
+//!
+//! Les batches de generation formulent la meme action de plusieurs facons.
+//! Les gabarits d'etapes (texte hors parametres) sont regroupes par mot-cle
+//! et par signature de parametres, puis compares apres normalisation (minuscules,
+//! sans accents, ponctuation ni articles) par distance d'edition sur les mots :
+//! un mot different compte entier ("connecte" / "deconnecte"). Deux gabarits
+//! opposes (negation, antonymes) ne sont jamais fusionnes. Chaque groupe
+//! retient la formulation la plus frequente comme formulation canonique.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::dedupe::fold_accent;
+use super::step_definitions::{
+    ExpressionPart, ParameterType, StepDefinition, parse_step_with_values, placeholder_types,
+};
+use super::test_case::{Step, StepKeyword, TestSuite};
+use super::user_story::Language;
+
+/// Similarite minimale (0.0 - 1.0) pour fusionner deux formulations
+pub const DEFAULT_STEP_SIMILARITY: f64 = 0.8;
+
+/// Mots vides ignores lors de la comparaison (articles et liaisons fr, en, es, de)
+const STOP_WORDS: &[&str] = &[
+    "le", "la", "les", "l", "un", "une", "des", "de", "du", "d", "a", "au", "aux", "est", "sont",
+    "the", "an", "of", "is", "are", "to", "el", "los", "las", "del", "una", "unos", "unas", "der",
+    "die", "das", "den", "dem", "ein", "eine", "einen", "ist",
+];
+
+/// Marqueurs de negation (fr, en, es, de)
+const NEGATIONS: &[&str] = &[
+    "ne", "n", "pas", "jamais", "aucun", "aucune", "not", "no", "never", "nunca", "nicht", "kein",
+    "keine", "nie",
+];
+
+/// Prefixes d'un mot contraire ("deconnecte", "inactif", "non-valide")
+const NEGATIVE_PREFIXES: &[&str] = &["des", "de", "dis", "in", "im", "il", "ir", "non", "un"];
+
+/// Paires d'antonymes sans prefixe commun, sans accents
+const ANTONYMS: &[(&str, &str)] = &[
+    ("inferieur", "superieur"),
+    ("inferieure", "superieure"),
+    ("minimum", "maximum"),
+    ("min", "max"),
+    ("moins", "plus"),
+    ("avant", "apres"),
+    ("ouvert", "ferme"),
+    ("accepte", "refuse"),
+    ("autorise", "interdit"),
+    ("less", "greater"),
+    ("lower", "higher"),
+    ("below", "above"),
+    ("before", "after"),
+    ("enabled", "disabled"),
+    ("menor", "mayor"),
+    ("kleiner", "grosser"),
+];
+
+/// Traitement du vocabulaire des etapes apres generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StepVocabularyMode {
+    /// Aucune analyse
+    Off,
+    /// Glossaire seul, etapes inchangees
+    Report,
+    /// Etapes reecrites vers leur formulation canonique, avec glossaire
+    Rewrite,
+}
+
+impl StepVocabularyMode {
+    /// Construit un mode a partir d'un code ("off", "report", "rewrite")
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_lowercase().as_str() {
+            "off" => Some(StepVocabularyMode::Off),
+            "report" => Some(StepVocabularyMode::Report),
+            "rewrite" => Some(StepVocabularyMode::Rewrite),
+            _ => None,
+        }
+    }
+}
+
+/// Formulation alternative fusionnee dans une formulation canonique
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepVariant {
+    pub text: String,
+    pub occurrences: usize,
+    /// Similarite avec la formulation canonique (0.0 - 1.0)
+    pub similarity: f64,
+}
+
+/// Entree du glossaire : une formulation canonique et ses variantes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GlossaryEntry {
+    /// Given, When ou Then (And/But resolus sur l'etape precedente)
+    pub keyword: StepKeyword,
+    /// Premier texte d'etape de la formulation retenue
+    pub canonical: String,
+    /// Cucumber Expression de la formulation retenue
+    pub expression: String,
+    /// Occurrences de la formulation retenue avant normalisation
+    pub occurrences: usize,
+    pub variants: Vec<StepVariant>,
+}
+
+impl GlossaryEntry {
+    /// Occurrences totales apres normalisation
+    pub fn total_occurrences(&self) -> usize {
+        self.occurrences + self.variants.iter().map(|v| v.occurrences).sum::<usize>()
+    }
+}
+
+/// Glossaire des etapes d'une suite de tests
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StepGlossary {
+    pub entries: Vec<GlossaryEntry>,
+    /// Etapes reecrites vers leur formulation canonique
+    pub rewritten_steps: usize,
+    /// Formulations distinctes avant normalisation
+    pub distinct_before: usize,
+}
+
+impl StepGlossary {
+    /// Entrees ayant absorbe au moins une variante
+    pub fn merged_entries(&self) -> impl Iterator<Item = &GlossaryEntry> {
+        self.entries.iter().filter(|e| !e.variants.is_empty())
+    }
+
+    /// Formulations canoniques les plus utilisees (pour guider les batches suivants)
+    pub fn canonical_phrases(&self, language: Language, limit: usize) -> Vec<String> {
+        let mut entries: Vec<&GlossaryEntry> = self.entries.iter().collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.total_occurrences()));
+        entries
+            .into_iter()
+            .take(limit)
+            .map(|e| format!("{} {}", e.keyword.localized(language), e.canonical))
+            .collect()
+    }
+}
+
+/// Gabarit d'etape distinct (mot-cle + Cucumber Expression)
+struct Template {
+    keyword: StepKeyword,
+    expression: String,
+    parts: Vec<ExpressionPart>,
+    kinds: Vec<ParameterType>,
+    sample: String,
+    normalized: String,
+    occurrences: usize,
+}
+
+/// Etape analysee : position du gabarit et texte brut de ses parametres
+struct ParsedStep {
+    template: usize,
+    values: Vec<String>,
+}
+
+/// Construit le glossaire sans modifier la suite
+pub fn build_step_glossary(suite: &TestSuite, threshold: f64) -> StepGlossary {
+    let (templates, _) = analyze(suite);
+    let clusters = cluster(&templates, threshold);
+    glossary(&templates, &clusters, 0)
+}
+
+/// Reecrit chaque etape vers la formulation canonique de son groupe, en
+/// conservant ses valeurs de parametres, et retourne le glossaire
+pub fn normalize_step_vocabulary(suite: &mut TestSuite, threshold: f64) -> StepGlossary {
+    let (templates, parsed) = analyze(suite);
+    let clusters = cluster(&templates, threshold);
+
+    let mut canonical_of = vec![0; templates.len()];
+    for members in &clusters {
+        let canonical = canonical_member(&templates, members);
+        for &m in members {
+            canonical_of[m] = canonical;
+        }
+    }
+
+    let mut rewritten = 0;
+    let mut parsed = parsed.into_iter();
+    for_each_step_mut(suite, |step| {
+        let Some(ParsedStep { template, values }) = parsed.next() else {
+            return;
+        };
+        let canonical = canonical_of[template];
+        if canonical == template {
+            return;
+        }
+        step.text = templates[canonical]
+            .parts
+            .iter()
+            .map(|part| match part {
+                ExpressionPart::Text(text) => text.as_str(),
+                ExpressionPart::Parameter(i) => values[*i].as_str(),
+            })
+            .collect();
+        rewritten += 1;
+    });
+
+    glossary(&templates, &clusters, rewritten)
+}
+
+/// Gabarits distincts (ordre de premiere apparition) et analyse de chaque etape
+fn analyze(suite: &TestSuite) -> (Vec<Template>, Vec<ParsedStep>) {
+    let mut templates: Vec<Template> = Vec::new();
+    let mut index: HashMap<(StepKeyword, String), usize> = HashMap::new();
+    let mut parsed = Vec::new();
+
+    let mut visit = |steps: &[Step], types: &HashMap<String, ParameterType>| {
+        let mut previous = StepKeyword::Given;
+        for step in steps {
+            let keyword = match step.keyword {
+                StepKeyword::And | StepKeyword::But => previous,
+                keyword => keyword,
+            };
+            previous = keyword;
+
+            let text = step.text.trim();
+            let (parts, parameters, values) = parse_step_with_values(text, types);
+            let kinds = parameters.iter().map(|p| p.kind).collect();
+            let expression = StepDefinition {
+                keyword,
+                parts: parts.clone(),
+                parameters,
+                sample: text.to_string(),
+                group: String::new(),
+            }
+            .cucumber_expression();
+
+            let position = *index
+                .entry((keyword, expression.clone()))
+                .or_insert_with(|| {
+                    templates.push(Template {
+                        keyword,
+                        expression,
+                        normalized: normalize(&parts),
+                        parts,
+                        kinds,
+                        sample: text.to_string(),
+                        occurrences: 0,
+                    });
+                    templates.len() - 1
+                });
+            templates[position].occurrences += 1;
+            parsed.push(ParsedStep {
+                template: position,
+                values,
+            });
+        }
+    };
+
+    for feature in &suite.features {
+        if let Some(background) = &feature.background {
+            visit(&background.steps, &HashMap::new());
+        }
+        for scenario in &feature.scenarios {
+            visit(&scenario.steps, &placeholder_types(scenario));
+        }
+    }
+    (templates, parsed)
+}
+
+/// Parcourt les etapes dans le meme ordre que [`analyze`]
+fn for_each_step_mut(suite: &mut TestSuite, mut f: impl FnMut(&mut Step)) {
+    for feature in &mut suite.features {
+        if let Some(background) = &mut feature.background {
+            background.steps.iter_mut().for_each(&mut f);
+        }
+        for scenario in &mut feature.scenarios {
+            scenario.steps.iter_mut().for_each(&mut f);
+        }
+    }
+}
+
+/// Regroupement glouton : chaque gabarit rejoint le premier groupe dont le
+/// gabarit fondateur est assez proche (meme mot-cle, memes types de parametres)
+fn cluster(templates: &[Template], threshold: f64) -> Vec<Vec<usize>> {
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    for (i, template) in templates.iter().enumerate() {
+        let found = clusters.iter_mut().find(|members| {
+            let seed = &templates[members[0]];
+            seed.keyword == template.keyword
+                && seed.kinds == template.kinds
+                && !opposite(&seed.normalized, &template.normalized)
+                && similarity(&seed.normalized, &template.normalized) >= threshold
+        });
+        match found {
+            Some(members) => members.push(i),
+            None => clusters.push(vec![i]),
+        }
+    }
+    clusters
+}
+
+/// Formulation la plus frequente du groupe (la premiere en cas d'egalite)
+fn canonical_member(templates: &[Template], members: &[usize]) -> usize {
+    members.iter().copied().fold(members[0], |best, m| {
+        if templates[m].occurrences > templates[best].occurrences {
+            m
+        } else {
+            best
+        }
+    })
+}
+
+fn glossary(templates: &[Template], clusters: &[Vec<usize>], rewritten: usize) -> StepGlossary {
+    let entries = clusters
+        .iter()
+        .map(|members| {
+            let canonical = &templates[canonical_member(templates, members)];
+            GlossaryEntry {
+                keyword: canonical.keyword,
+                canonical: canonical.sample.clone(),
+                expression: canonical.expression.clone(),
+                occurrences: canonical.occurrences,
+                variants: members
+                    .iter()
+                    .map(|&m| &templates[m])
+                    .filter(|t| t.expression != canonical.expression)
+                    .map(|t| StepVariant {
+                        text: t.sample.clone(),
+                        occurrences: t.occurrences,
+                        similarity: similarity(&canonical.normalized, &t.normalized),
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+    StepGlossary {
+        entries,
+        rewritten_steps: rewritten,
+        distinct_before: templates.len(),
+    }
+}
+
+/// Texte litteral normalise, un `#` par parametre
+fn normalize(parts: &[ExpressionPart]) -> String {
+    let text: String = parts
+        .iter()
+        .map(|part| match part {
+            ExpressionPart::Text(text) => text.to_lowercase().chars().map(fold_accent).collect(),
+            ExpressionPart::Parameter(_) => " # ".to_string(),
+        })
+        .collect();
+    text.split(|c: char| !c.is_alphanumeric() && c != '#')
+        .filter(|w| !w.is_empty() && !STOP_WORDS.contains(w))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Gabarits de sens oppose : negation presente d'un seul cote, ou mot
+/// remplace par son contraire (prefixe negatif ou antonyme connu)
+fn opposite(a: &str, b: &str) -> bool {
+    let negated = |text: &str| text.split(' ').any(|w| NEGATIONS.contains(&w));
+    if negated(a) != negated(b) {
+        return true;
+    }
+    let only_a: Vec<&str> = a
+        .split(' ')
+        .filter(|w| !b.split(' ').any(|x| x == *w))
+        .collect();
+    let only_b: Vec<&str> = b
+        .split(' ')
+        .filter(|w| !a.split(' ').any(|x| x == *w))
+        .collect();
+    only_a.iter().any(|x| {
+        only_b.iter().any(|y| {
+            let prefixed = |word: &str, base: &str| {
+                NEGATIVE_PREFIXES.iter().any(|prefix| {
+                    word.strip_prefix(prefix)
+                        .is_some_and(|rest| rest == base || rest.trim_start_matches('-') == base)
+                })
+            };
+            prefixed(x, y)
+                || prefixed(y, x)
+                || ANTONYMS
+                    .iter()
+                    .any(|&(p, q)| (*x == p && *y == q) || (*x == q && *y == p))
+        })
+    })
+}
+
+/// Similarite de Levenshtein normalisee sur les mots (1.0 = identiques)
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<&str> = a.split_whitespace().collect();
+    let b: Vec<&str> = b.split_whitespace().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    1.0 - row[b.len()] as f64 / longest as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::test_case::{Feature, Scenario, ScenarioType, TestCoverage};
+    use pretty_assertions::assert_eq;
+
+    fn step(keyword: StepKeyword, text: &str) -> Step {
+        Step {
+            keyword,
+            text: text.into(),
+            doc_string: None,
+            data_table: None,
+        }
+    }
+
+    fn scenario(name: &str, steps: Vec<Step>) -> Scenario {
+        Scenario {
+            name: name.into(),
+            tags: Vec::new(),
+            scenario_type: ScenarioType::HappyPath,
+            steps,
+            examples: None,
+            test_data_suggestions: Vec::new(),
+            verification_of: Vec::new(),
+            coverage_technique: None,
+        }
+    }
+
+    fn suite(scenarios: Vec<Scenario>) -> TestSuite {
+        let mut feature = Feature::new("Connexion".into(), String::new());
+        feature.scenarios = scenarios;
+        TestSuite {
+            features: vec![feature],
+            source_spec_id: uuid::Uuid::nil(),
+            total_scenarios: 0,
+            coverage: TestCoverage {
+                requirements_covered: Vec::new(),
+                requirements_total: 0,
+                coverage_percentage: 0.0,
+                scenarios_by_type: Default::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_normalize_ignores_case_accents_and_articles() {
+        let (parts, _, _) =
+            parse_step_with_values("L'utilisateur saisit le Mot de passe 42", &HashMap::new());
+        assert_eq!(normalize(&parts), "utilisateur saisit mot passe #");
+        assert!(similarity("utilisateur est connecte", "utilisateur connecte") < 1.0);
+        assert_eq!(similarity("abc", "abc"), 1.0);
+    }
+
+    #[test]
+    fn test_normalize_rewrites_variants_keeping_values() {
+        let mut suite = suite(vec![
+            scenario(
+                "A",
+                vec![
+                    step(StepKeyword::Given, "l'utilisateur est connecté"),
+                    step(StepKeyword::When, "il vire 10 EUR vers \"Epargne\""),
+                ],
+            ),
+            scenario(
+                "B",
+                vec![
+                    step(StepKeyword::Given, "l'utilisateur est connecte"),
+                    step(
+                        StepKeyword::When,
+                        "il vire 25 EUR vers le compte \"Courant\"",
+                    ),
+                ],
+            ),
+            scenario(
+                "C",
+                vec![
+                    step(StepKeyword::Given, "l'utilisateur est connecté"),
+                    step(StepKeyword::Then, "l'utilisateur est connecté"),
+                ],
+            ),
+        ]);
+
+        let glossary = normalize_step_vocabulary(&mut suite, 0.7);
+        let scenarios = &suite.features[0].scenarios;
+        assert_eq!(scenarios[1].steps[0].text, "l'utilisateur est connecté");
+        assert_eq!(
+            scenarios[1].steps[1].text,
+            "il vire 25 EUR vers \"Courant\""
+        );
+        // Then n'est jamais fusionne avec Given
+        assert_eq!(scenarios[2].steps[1].keyword, StepKeyword::Then);
+        assert_eq!(glossary.rewritten_steps, 2);
+        assert_eq!(glossary.distinct_before, 5);
+        assert_eq!(glossary.entries.len(), 3);
+        assert_eq!(glossary.merged_entries().count(), 2);
+        assert_eq!(glossary.entries[0].total_occurrences(), 3);
+    }
+
+    #[test]
+    fn test_antonyms_and_negations_are_not_merged() {
+        let pairs = [
+            ("l'utilisateur est connecté", "l'utilisateur est déconnecté"),
+            ("le compte est actif", "le compte est inactif"),
+            (
+                "le solde du compte courant est inférieur a 10",
+                "le solde du compte courant est supérieur a 10",
+            ),
+            (
+                "le virement du client est valide",
+                "le virement du client n'est pas valide",
+            ),
+            (
+                "la carte bancaire du client est activée",
+                "la carte bancaire du client est désactivée",
+            ),
+        ];
+        for (a, b) in pairs {
+            let normalized =
+                |text: &str| normalize(&parse_step_with_values(text, &HashMap::new()).0);
+            assert!(opposite(&normalized(a), &normalized(b)), "{a} / {b}");
+            let suite = suite(vec![scenario(
+                "A",
+                vec![step(StepKeyword::Given, a), step(StepKeyword::Given, b)],
+            )]);
+            let glossary = build_step_glossary(&suite, 0.5);
+            assert_eq!(glossary.entries.len(), 2, "{a} / {b}");
+        }
+    }
+
+    #[test]
+    fn test_similarity_compares_whole_words() {
+        // Caractere par caractere, ces formulations seraient a plus de 0.8
+        assert!(similarity("utilisateur connecte", "utilisateur deconnecte") < 0.8);
+        assert!(similarity("il vire # eur vers #", "il vire # eur vers compte #") > 0.8);
+    }
+
+    #[test]
+    fn test_different_parameter_types_are_not_merged() {
+        let suite = suite(vec![scenario(
+            "A",
+            vec![
+                step(StepKeyword::When, "je saisis 10"),
+                step(StepKeyword::When, "je saisis \"10\""),
+            ],
+        )]);
+        let glossary = build_step_glossary(&suite, 0.5);
+        assert_eq!(glossary.entries.len(), 2);
+        assert_eq!(glossary.rewritten_steps, 0);
+    }
+}
@@ -0,0 +1,378 @@
+//! Lint INVEST des User Stories
+//!
+//! Heuristiques deterministes appliquees avant tout appel LLM, pour eviter
+//! de lancer un raffinement long sur une entree manifestement incomplete.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::user_story::{Language, UserStory, UserStorySet};
+use super::validation::contains_ambiguous_word;
+
+/// Severite d'un constat de lint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LintSeverity {
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for LintSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LintSeverity::Warning => write!(f, "avertissement"),
+            LintSeverity::Error => write!(f, "erreur"),
+        }
+    }
+}
+
+/// Regle de lint, rattachee a un critere INVEST
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StoryLintRule {
+    /// Pas de "Afin de ..." (Valuable)
+    MissingBenefit,
+    /// Action composee "X et Y" (Small)
+    CompoundAction,
+    /// Aucun critere d'acceptation (Testable)
+    NoAcceptanceCriteria,
+    /// Story plus grosse qu'un lot LLM (Small)
+    TooLarge,
+    /// Mot vague de la langue de la story (Estimable)
+    VagueWording,
+    /// Titre deja utilise par une autre story (Independent)
+    DuplicateTitle,
+}
+
+impl StoryLintRule {
+    /// Code court utilise dans les rapports
+    pub fn code(&self) -> &'static str {
+        match self {
+            StoryLintRule::MissingBenefit => "missing-benefit",
+            StoryLintRule::CompoundAction => "compound-action",
+            StoryLintRule::NoAcceptanceCriteria => "no-acceptance-criteria",
+            StoryLintRule::TooLarge => "too-large",
+            StoryLintRule::VagueWording => "vague-wording",
+            StoryLintRule::DuplicateTitle => "duplicate-title",
+        }
+    }
+
+    /// Critere INVEST concerne
+    pub fn invest_criterion(&self) -> &'static str {
+        match self {
+            StoryLintRule::DuplicateTitle => "Independent",
+            StoryLintRule::MissingBenefit => "Valuable",
+            StoryLintRule::VagueWording => "Estimable",
+            StoryLintRule::CompoundAction | StoryLintRule::TooLarge => "Small",
+            StoryLintRule::NoAcceptanceCriteria => "Testable",
+        }
+    }
+
+    /// Severite par defaut de la regle
+    pub fn severity(&self) -> LintSeverity {
+        match self {
+            StoryLintRule::MissingBenefit | StoryLintRule::TooLarge => LintSeverity::Error,
+            _ => LintSeverity::Warning,
+        }
+    }
+}
+
+/// Constat de lint sur une story
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoryLintIssue {
+    /// ID externe de la story (ou titre a defaut)
+    pub story: String,
+    /// Fichier source de la story
+    pub source_file: Option<String>,
+    pub rule: StoryLintRule,
+    pub severity: LintSeverity,
+    pub message: String,
+}
+
+/// Rapport de lint d'un ensemble de stories
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StoryLintReport {
+    pub stories_checked: usize,
+    pub issues: Vec<StoryLintIssue>,
+}
+
+impl StoryLintReport {
+    pub fn error_count(&self) -> usize {
+        self.count(LintSeverity::Error)
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.count(LintSeverity::Warning)
+    }
+
+    /// Aucun constat
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Le rapport fait-il echouer le lint ? (strict : tout constat est bloquant)
+    pub fn is_failure(&self, strict: bool) -> bool {
+        if strict {
+            !self.is_clean()
+        } else {
+            self.error_count() > 0
+        }
+    }
+
+    fn count(&self, severity: LintSeverity) -> usize {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == severity)
+            .count()
+    }
+}
+
+/// Lint INVEST de plusieurs fichiers de stories (un `UserStorySet` par fichier).
+///
+/// `token_budget` est le budget d'un lot de raffinement (`pipeline.token_budget`).
+pub fn lint_story_sets(sets: &[UserStorySet], token_budget: usize) -> StoryLintReport {
+    let mut report = StoryLintReport::default();
+    // Titre normalise -> premiere occurrence (story, fichier)
+    let mut seen_titles: HashMap<String, (String, Option<String>)> = HashMap::new();
+
+    for set in sets {
+        let source_file = set.source_files.first().cloned();
+        for story in &set.stories {
+            report.stories_checked += 1;
+            let label = story_label(story);
+            let mut push = |rule: StoryLintRule, message: String| {
+                report.issues.push(StoryLintIssue {
+                    story: label.clone(),
+                    source_file: source_file.clone(),
+                    rule,
+                    severity: rule.severity(),
+                    message,
+                });
+            };
+
+            for (rule, message) in lint_story(story, set.language, token_budget) {
+                push(rule, message);
+            }
+
+            let title_key = normalize_title(&story.title);
+            if title_key.is_empty() {
+                continue;
+            }
+            match seen_titles.get(&title_key) {
+                Some((first_story, first_file)) => push(
+                    StoryLintRule::DuplicateTitle,
+                    format!(
+                        "Titre \"{}\" deja utilise par {}{}",
+                        story.title.trim(),
+                        first_story,
+                        first_file
+                            .as_deref()
+                            .map(|f| format!(" ({})", f))
+                            .unwrap_or_default()
+                    ),
+                ),
+                None => {
+                    seen_titles.insert(title_key, (label.clone(), source_file.clone()));
+                }
+            }
+        }
+    }
+
+    report
+}
+
+/// Regles applicables a une story isolee
+fn lint_story(
+    story: &UserStory,
+    language: Language,
+    token_budget: usize,
+) -> Vec<(StoryLintRule, String)> {
+    let mut issues = Vec::new();
+    let keywords = language.keywords();
+
+    if story.benefit.trim().is_empty() {
+        issues.push((
+            StoryLintRule::MissingBenefit,
+            "Benefice absent (\"Afin de ...\" / \"So that ...\")".to_string(),
+        ));
+    }
+
+    // Conjonction de la langue, et "and" (stories souvent redigees en anglais)
+    let conjunction = keywords.gherkin.and.to_lowercase();
+    let action = story.action.to_lowercase();
+    if action
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|w| w == conjunction || w == "and")
+    {
+        issues.push((
+            StoryLintRule::CompoundAction,
+            format!(
+                "Action composee, envisager de decouper la story: \"{}\"",
+                story.action.trim()
+            ),
+        ));
+    }
+
+    if story.acceptance_criteria.is_empty() {
+        issues.push((
+            StoryLintRule::NoAcceptanceCriteria,
+            "Aucun critere d'acceptation".to_string(),
+        ));
+    }
+
+    let tokens = story.estimated_tokens();
+    if tokens > token_budget {
+        issues.push((
+            StoryLintRule::TooLarge,
+            format!(
+                "Story estimee a ~{} tokens, au-dela du budget d'un lot ({})",
+                tokens, token_budget
+            ),
+        ));
+    }
+
+    let text = std::iter::once(story.title.as_str())
+        .chain([story.action.as_str(), story.benefit.as_str()])
+        .chain(story.acceptance_criteria.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .to_lowercase();
+    let vague: Vec<&str> = keywords
+        .ambiguous_words
+        .iter()
+        .copied()
+        .filter(|w| contains_ambiguous_word(&text, w))
+        .collect();
+    if !vague.is_empty() {
+        issues.push((
+            StoryLintRule::VagueWording,
+            format!("Mot(s) vague(s): {}", vague.join(", ")),
+        ));
+    }
+
+    issues
+}
+
+fn story_label(story: &UserStory) -> String {
+    story
+        .external_id
+        .clone()
+        .unwrap_or_else(|| story.title.trim().to_string())
+}
+
+fn normalize_title(title: &str) -> String {
+    title
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::user_story::Language;
+    use pretty_assertions::assert_eq;
+
+    fn make_story(id: &str, title: &str, action: &str, benefit: &str) -> UserStory {
+        let mut us = UserStory::new(title.into(), "client".into(), action.into(), benefit.into());
+        us.external_id = Some(id.into());
+        us.acceptance_criteria = vec!["Le virement est refuse au-dela du plafond".into()];
+        us
+    }
+
+    fn make_set(file: &str, stories: Vec<UserStory>) -> UserStorySet {
+        UserStorySet {
+            stories,
+            source_files: vec![file.into()],
+            language: Language::French,
+        }
+    }
+
+    fn rules(report: &StoryLintReport) -> Vec<StoryLintRule> {
+        report.issues.iter().map(|i| i.rule).collect()
+    }
+
+    #[test]
+    fn test_clean_story() {
+        let set = make_set(
+            "a.md",
+            vec![make_story(
+                "US-001",
+                "Virement",
+                "virer de l'argent",
+                "payer",
+            )],
+        );
+        let report = lint_story_sets(&[set], 2000);
+        assert!(report.is_clean());
+        assert_eq!(report.stories_checked, 1);
+        assert!(!report.is_failure(true));
+    }
+
+    #[test]
+    fn test_invest_rules() {
+        let mut vague = make_story("US-002", "Export", "exporter quelques releves", "archiver");
+        vague.acceptance_criteria.clear();
+        let set = make_set(
+            "a.md",
+            vec![
+                make_story("US-001", "Carte", "bloquer et debloquer ma carte", ""),
+                vague,
+            ],
+        );
+        let report = lint_story_sets(&[set], 2000);
+        assert_eq!(
+            rules(&report),
+            vec![
+                StoryLintRule::MissingBenefit,
+                StoryLintRule::CompoundAction,
+                StoryLintRule::NoAcceptanceCriteria,
+                StoryLintRule::VagueWording,
+            ]
+        );
+        assert_eq!(report.error_count(), 1);
+        assert_eq!(report.warning_count(), 3);
+        assert!(report.issues[3].message.contains("quelques"));
+        assert!(report.is_failure(false));
+    }
+
+    #[test]
+    fn test_compound_action_whole_word_only() {
+        let story = make_story(
+            "US-001",
+            "Etat",
+            "consulter l'etat de mes comptes",
+            "suivre",
+        );
+        assert!(lint_story(&story, Language::French, 2000).is_empty());
+    }
+
+    #[test]
+    fn test_too_large_for_batch() {
+        let mut story = make_story("US-001", "Import", "importer un fichier", "gagner du temps");
+        story.raw_text = "x".repeat(800);
+        let issues = lint_story(&story, Language::French, 100);
+        assert_eq!(issues[0].0, StoryLintRule::TooLarge);
+        assert_eq!(StoryLintRule::TooLarge.severity(), LintSeverity::Error);
+    }
+
+    #[test]
+    fn test_duplicate_titles_across_files() {
+        let a = make_set(
+            "a.md",
+            vec![make_story("US-001", "Virement SEPA", "virer", "payer")],
+        );
+        let b = make_set(
+            "b.yaml",
+            vec![make_story("PAY-7", "virement  sepa", "virer", "payer")],
+        );
+        let report = lint_story_sets(&[a, b], 2000);
+        assert_eq!(rules(&report), vec![StoryLintRule::DuplicateTitle]);
+        let issue = &report.issues[0];
+        assert_eq!(issue.story, "PAY-7");
+        assert_eq!(issue.source_file.as_deref(), Some("b.yaml"));
+        assert!(issue.message.contains("US-001 (a.md)"));
+        assert!(!report.is_failure(false));
+        assert!(report.is_failure(true));
+    }
+}
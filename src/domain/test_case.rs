@@ -7,6 +7,9 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::naming::{ascii_slug, file_id};
+use super::user_story::Language;
+
 // ---------------------------------------------------------------------------
 // Enums ISO 29119 — niveaux de test et techniques de couverture
 // ---------------------------------------------------------------------------
@@ -54,6 +57,50 @@ impl std::fmt::Display for CoverageTechnique {
     }
 }
 
+/// Format d'export des cas de test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestExportFormat {
+    Xray,
+    TestRail,
+    JUnit,
+}
+
+impl TestExportFormat {
+    /// Construit un format a partir d'un code ("xray", "testrail", "junit")
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_lowercase().as_str() {
+            "xray" => Some(TestExportFormat::Xray),
+            "testrail" => Some(TestExportFormat::TestRail),
+            "junit" => Some(TestExportFormat::JUnit),
+            _ => None,
+        }
+    }
+
+    /// Code du format
+    pub fn code(&self) -> &'static str {
+        match self {
+            TestExportFormat::Xray => "xray",
+            TestExportFormat::TestRail => "testrail",
+            TestExportFormat::JUnit => "junit",
+        }
+    }
+
+    /// Nom du fichier exporte
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            TestExportFormat::Xray => "xray_tests.json",
+            TestExportFormat::TestRail => "testrail_cases.csv",
+            TestExportFormat::JUnit => "junit_skeleton.xml",
+        }
+    }
+}
+
+impl std::fmt::Display for TestExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
 /// Feature Gherkin (un fichier .feature)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Feature {
@@ -104,6 +151,23 @@ impl Feature {
             test_level: TestLevel::default(),
         }
     }
+
+    /// Nom du fichier `.feature` (sans extension) : premier scenario source
+    /// puis nom de la feature translittere (`US-003_virement_instantane`)
+    pub fn file_stem(&self) -> String {
+        let slug = ascii_slug(&self.name, '_');
+        let id = self
+            .source_scenario_ids
+            .first()
+            .map(|id| file_id(id))
+            .unwrap_or_default();
+        match (id.is_empty(), slug.is_empty()) {
+            (false, false) => format!("{id}_{slug}"),
+            (false, true) => id,
+            (true, false) => slug,
+            (true, true) => "feature".to_string(),
+        }
+    }
 }
 
 /// Section Background Gherkin
@@ -145,6 +209,31 @@ pub struct Scenario {
     pub coverage_technique: Option<CoverageTechnique>,
 }
 
+impl Scenario {
+    /// Exigences directement verifiees : `verification_of` puis tags
+    /// `@FR-xxx`, sans doublon. Regle commune a la matrice, aux exports, a la
+    /// verification des resultats et a l'analyse d'impact.
+    pub fn linked_requirements(&self) -> Vec<String> {
+        let tagged = self
+            .tags
+            .iter()
+            .map(|t| t.strip_prefix('@').unwrap_or(t))
+            .filter(|t| t.starts_with("FR-"));
+        let mut ids: Vec<String> = Vec::new();
+        for id in self
+            .verification_of
+            .iter()
+            .map(String::as_str)
+            .chain(tagged)
+        {
+            if !ids.iter().any(|known| known == id) {
+                ids.push(id.to_string());
+            }
+        }
+        ids
+    }
+}
+
 /// Type de scenario
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ScenarioType {
@@ -184,7 +273,7 @@ pub struct Step {
 }
 
 /// Mot-cle Gherkin
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum StepKeyword {
     Given,
     When,
@@ -196,23 +285,23 @@ pub enum StepKeyword {
 impl StepKeyword {
     /// Mot-cle en francais pour Gherkin
     pub fn to_french(self) -> &'static str {
-        match self {
-            StepKeyword::Given => "Soit",
-            StepKeyword::When => "Quand",
-            StepKeyword::Then => "Alors",
-            StepKeyword::And => "Et",
-            StepKeyword::But => "Mais",
-        }
+        self.localized(Language::French)
     }
 
     /// Mot-cle en anglais pour Gherkin
     pub fn to_english(self) -> &'static str {
+        self.localized(Language::English)
+    }
+
+    /// Mot-cle Gherkin dans une langue quelconque
+    pub fn localized(self, language: Language) -> &'static str {
+        let keywords = &language.keywords().gherkin;
         match self {
-            StepKeyword::Given => "Given",
-            StepKeyword::When => "When",
-            StepKeyword::Then => "Then",
-            StepKeyword::And => "And",
-            StepKeyword::But => "But",
+            StepKeyword::Given => keywords.given,
+            StepKeyword::When => keywords.when,
+            StepKeyword::Then => keywords.then,
+            StepKeyword::And => keywords.and,
+            StepKeyword::But => keywords.but,
         }
     }
 }
@@ -324,6 +413,31 @@ mod tests {
         assert_eq!(StepKeyword::Then.to_french(), "Alors");
         assert_eq!(StepKeyword::And.to_french(), "Et");
         assert_eq!(StepKeyword::But.to_french(), "Mais");
+        assert_eq!(StepKeyword::Given.localized(Language::Spanish), "Dado");
+        assert_eq!(StepKeyword::Then.localized(Language::German), "Dann");
+    }
+
+    #[test]
+    fn test_scenario_linked_requirements() {
+        let scenario = Scenario {
+            name: "Virement".into(),
+            tags: vec![
+                "@FR-002".into(),
+                "@smoke".into(),
+                "@US-001".into(),
+                "FR-003".into(),
+            ],
+            scenario_type: ScenarioType::HappyPath,
+            steps: Vec::new(),
+            examples: None,
+            test_data_suggestions: Vec::new(),
+            verification_of: vec!["FR-001".into(), "FR-002".into()],
+            coverage_technique: None,
+        };
+        assert_eq!(
+            scenario.linked_requirements(),
+            vec!["FR-001", "FR-002", "FR-003"]
+        );
     }
 
     #[test]
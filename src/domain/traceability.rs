@@ -5,11 +5,14 @@
 //!
 //! Conformite : ISO/IEC/IEEE 29148:2018 section 6.6, ASPICE BP6
 
+use std::collections::{BTreeMap, HashMap};
+
 use serde::{Deserialize, Serialize};
 
 use super::specification::{ComplianceProfile, RiskLevel, Specification, VerificationMethod};
 use super::test_case::{CoverageTechnique, TestSuite};
 use super::user_story::Priority;
+use super::verification::{ScenarioVerification, VerificationSummary, VerificationVerdict};
 
 // ---------------------------------------------------------------------------
 // Types
@@ -21,6 +24,12 @@ pub struct TraceabilityMatrix {
     pub entries: Vec<TraceabilityEntry>,
     pub summary: TraceabilitySummary,
     pub compliance_notes: Vec<ComplianceNote>,
+    /// Resume de verification, renseigne par `apply_execution_results`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification: Option<VerificationSummary>,
+    /// Exigences et tests par composant (`allocated_to`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allocations: Vec<ComponentAllocation>,
 }
 
 /// Une ligne de la matrice de tracabilite (1 par FR)
@@ -36,6 +45,27 @@ pub struct TraceabilityEntry {
     pub covering_scenarios: Vec<String>,
     pub coverage_techniques: Vec<CoverageTechnique>,
     pub status: TraceabilityStatus,
+    /// Statut d'execution des scenarios couvrants (resultats ingeres)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scenario_results: Vec<ScenarioVerification>,
+    /// Verdict de verification agrege (resultats ingeres)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verdict: Option<VerificationVerdict>,
+    /// Exigence parente (ISO 29148 — exigence derivee)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_requirement: Option<String>,
+    /// Exigences derivees directes, dans l'ordre de la matrice
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub derived_requirements: Vec<String>,
+    /// Profondeur dans l'arbre des exigences (0 = racine)
+    #[serde(default)]
+    pub depth: usize,
+    /// Composants auxquels l'exigence est allouee
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allocated_to: Vec<String>,
+    /// Statut degrade par une exigence derivee non couverte
+    #[serde(default)]
+    pub rolled_up: bool,
 }
 
 /// Statut de couverture d'une exigence
@@ -80,6 +110,17 @@ pub struct TraceabilitySummary {
     pub orphan_tests: Vec<String>,
 }
 
+/// Allocation des exigences et des tests sur un composant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentAllocation {
+    pub component: String,
+    pub requirements: Vec<String>,
+    /// Scenarios couvrant au moins une exigence du composant (sans doublon)
+    pub scenarios: Vec<String>,
+    /// Exigences du composant sans couverture (GAP)
+    pub not_covered: Vec<String>,
+}
+
 /// Note de conformite normative
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComplianceNote {
@@ -112,7 +153,7 @@ impl std::fmt::Display for ComplianceStatus {
 
 /// Construit la matrice de tracabilite complete a partir d'une spec et d'un test suite
 pub fn build_traceability_matrix(spec: &Specification, suite: &TestSuite) -> TraceabilityMatrix {
-    let mut entries = Vec::new();
+    let mut entries = Vec::with_capacity(spec.functional_requirements.len());
 
     for fr in &spec.functional_requirements {
         // Trouver les features/scenarios qui couvrent cette exigence
@@ -183,9 +224,22 @@ pub fn build_traceability_matrix(spec: &Specification, suite: &TestSuite) -> Tra
             covering_scenarios,
             coverage_techniques,
             status,
+            scenario_results: Vec::new(),
+            verdict: None,
+            parent_requirement: fr.parent_requirement.clone(),
+            derived_requirements: Vec::new(),
+            depth: 0,
+            allocated_to: fr.allocated_to.clone(),
+            rolled_up: false,
         });
     }
 
+    // Arbre des exigences derivees : ordre parent → enfants, puis
+    // propagation de la couverture des enfants vers les parents
+    let mut entries = order_as_tree(entries);
+    roll_up_coverage(&mut entries);
+    let allocations = build_allocations(&entries);
+
     // Detect orphan tests
     let fr_ids: std::collections::HashSet<&str> = spec
         .functional_requirements
@@ -257,7 +311,132 @@ pub fn build_traceability_matrix(spec: &Specification, suite: &TestSuite) -> Tra
         entries,
         summary,
         compliance_notes,
+        verification: None,
+        allocations,
+    }
+}
+
+/// Ordonne les entrees en profondeur d'abord (chaque parent suivi de ses
+/// derivees) et renseigne `depth` et `derived_requirements`.
+///
+/// Les racines sont les exigences sans parent ou dont le parent est absent ;
+/// les membres d'un cycle (signale par `check_requirement_hierarchy`) sont
+/// rattaches a la premiere exigence du cycle rencontree.
+fn order_as_tree(entries: Vec<TraceabilityEntry>) -> Vec<TraceabilityEntry> {
+    let index: HashMap<&str, usize> = entries
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, e)| (e.requirement_id.as_str(), i))
+        .collect();
+    let parent_of = |i: usize| -> Option<usize> {
+        entries[i]
+            .parent_requirement
+            .as_deref()
+            .and_then(|p| index.get(p).copied())
+            .filter(|&p| p != i)
+    };
+
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); entries.len()];
+    for i in 0..entries.len() {
+        if let Some(p) = parent_of(i) {
+            children[p].push(i);
+        }
     }
+
+    let roots = (0..entries.len()).filter(|&i| parent_of(i).is_none());
+    // Les membres d'un cycle n'ont pas de racine : ils sont parcourus apres
+    let starts: Vec<usize> = roots.chain(0..entries.len()).collect();
+
+    let mut visited = vec![false; entries.len()];
+    let mut order: Vec<(usize, usize)> = Vec::with_capacity(entries.len());
+    for start in starts {
+        let mut stack = vec![(start, 0)];
+        while let Some((i, depth)) = stack.pop() {
+            if visited[i] {
+                continue;
+            }
+            visited[i] = true;
+            order.push((i, depth));
+            for &child in children[i].iter().rev() {
+                stack.push((child, depth + 1));
+            }
+        }
+    }
+
+    let derived: Vec<Vec<String>> = children
+        .iter()
+        .map(|c| {
+            c.iter()
+                .map(|&i| entries[i].requirement_id.clone())
+                .collect()
+        })
+        .collect();
+    let mut slots: Vec<Option<TraceabilityEntry>> = entries.into_iter().map(Some).collect();
+    order
+        .into_iter()
+        .filter_map(|(i, depth)| {
+            let mut entry = slots[i].take()?;
+            entry.depth = depth;
+            entry.derived_requirements = derived[i].clone();
+            Some(entry)
+        })
+        .collect()
+}
+
+/// Couverture consolidee : une exigence n'est `FullyCovered` que si toutes
+/// ses derivees sont couvertes (ou verifiees autrement que par test).
+///
+/// Les entrees sont ordonnees par `order_as_tree` : en les parcourant a
+/// rebours, chaque derivee est consolidee avant son parent.
+fn roll_up_coverage(entries: &mut [TraceabilityEntry]) {
+    let mut satisfied: HashMap<String, bool> = HashMap::new();
+    for entry in entries.iter_mut().rev() {
+        let children_ok = entry
+            .derived_requirements
+            .iter()
+            .all(|c| satisfied.get(c).copied().unwrap_or(true));
+        if entry.status == TraceabilityStatus::FullyCovered && !children_ok {
+            entry.status = TraceabilityStatus::PartiallyCovered;
+            entry.rolled_up = true;
+        }
+        let ok = matches!(
+            entry.status,
+            TraceabilityStatus::FullyCovered
+                | TraceabilityStatus::VerifiedByAnalysis
+                | TraceabilityStatus::VerifiedByInspection
+                | TraceabilityStatus::VerifiedByDemo
+        );
+        satisfied.insert(entry.requirement_id.clone(), ok);
+    }
+}
+
+/// Regroupe exigences et scenarios par composant alloue (ordre alphabetique)
+fn build_allocations(entries: &[TraceabilityEntry]) -> Vec<ComponentAllocation> {
+    let mut by_component: BTreeMap<&str, ComponentAllocation> = BTreeMap::new();
+    for entry in entries {
+        for component in &entry.allocated_to {
+            let allocation =
+                by_component
+                    .entry(component.as_str())
+                    .or_insert_with(|| ComponentAllocation {
+                        component: component.clone(),
+                        requirements: Vec::new(),
+                        scenarios: Vec::new(),
+                        not_covered: Vec::new(),
+                    });
+            allocation.requirements.push(entry.requirement_id.clone());
+            for scenario in &entry.covering_scenarios {
+                if !allocation.scenarios.contains(scenario) {
+                    allocation.scenarios.push(scenario.clone());
+                }
+            }
+            if entry.status == TraceabilityStatus::NotCovered {
+                allocation.not_covered.push(entry.requirement_id.clone());
+            }
+        }
+    }
+    by_component.into_values().collect()
 }
 
 /// Determine le statut de couverture d'une exigence
@@ -312,11 +491,12 @@ fn build_compliance_notes(
     });
 
     // ISO 29148 — Well-formedness (verifier tous les mots ambigus)
+    let ambiguous_words = super::i18n::ambiguous_words_for(spec.language);
     let all_normative = entries.iter().all(|e| {
         let lower = e.statement.to_lowercase();
-        !super::validation::AMBIGUOUS_WORDS
+        !ambiguous_words
             .iter()
-            .any(|w| lower.contains(w))
+            .any(|w| super::validation::contains_ambiguous_word(&lower, w))
     });
     notes.push(ComplianceNote {
         standard: "ISO/IEC/IEEE 29148:2018".into(),
@@ -775,4 +955,149 @@ mod tests {
                 .any(|n| n.standard.contains("29148"))
         );
     }
+
+    fn make_derived_spec() -> (Specification, TestSuite) {
+        let mut spec = Specification::new("Test".into());
+        let child = |id: &str, parent: &str, component: &str| {
+            let mut fr = make_fr(id, Priority::P2);
+            fr.parent_requirement = Some(parent.into());
+            fr.allocated_to = vec![component.into()];
+            fr
+        };
+        // FR-003 (derivee de FR-001) est declaree avant sa soeur FR-002
+        let mut root = make_fr("FR-001", Priority::P2);
+        root.allocated_to = vec!["Auth".into()];
+        spec.functional_requirements = vec![
+            child("FR-003", "FR-001", "API"),
+            make_fr("FR-004", Priority::P2),
+            child("FR-002", "FR-001", "Auth"),
+            child("FR-005", "FR-002", "Auth"),
+            root,
+        ];
+
+        let mut feature = Feature::new("Test".into(), "".into());
+        for (name, fr) in [
+            ("S1", "FR-001"),
+            ("S2", "FR-002"),
+            ("S3", "FR-003"),
+            ("S4", "FR-004"),
+        ] {
+            feature
+                .scenarios
+                .push(make_scenario_with_verif(name, vec![fr.into()]));
+        }
+        let suite = TestSuite {
+            features: vec![feature],
+            source_spec_id: spec.id,
+            total_scenarios: 4,
+            coverage: TestCoverage {
+                requirements_covered: vec![],
+                requirements_total: 5,
+                coverage_percentage: 0.0,
+                scenarios_by_type: ScenarioCounts::default(),
+            },
+        };
+        (spec, suite)
+    }
+
+    #[test]
+    fn test_matrix_orders_derived_requirements_as_tree() {
+        let (spec, suite) = make_derived_spec();
+        let matrix = build_traceability_matrix(&spec, &suite);
+
+        let tree: Vec<(&str, usize)> = matrix
+            .entries
+            .iter()
+            .map(|e| (e.requirement_id.as_str(), e.depth))
+            .collect();
+        assert_eq!(
+            tree,
+            vec![
+                ("FR-004", 0),
+                ("FR-001", 0),
+                ("FR-003", 1),
+                ("FR-002", 1),
+                ("FR-005", 2),
+            ]
+        );
+        assert_eq!(
+            matrix.entries[1].derived_requirements,
+            vec!["FR-003", "FR-002"]
+        );
+    }
+
+    #[test]
+    fn test_matrix_rolls_up_child_coverage() {
+        let (spec, suite) = make_derived_spec();
+        let matrix = build_traceability_matrix(&spec, &suite);
+        let status = |id: &str| {
+            let e = matrix
+                .entries
+                .iter()
+                .find(|e| e.requirement_id == id)
+                .unwrap();
+            (e.status, e.rolled_up)
+        };
+
+        // FR-005 non couverte : FR-002 puis FR-001 sont degradees
+        assert_eq!(status("FR-005"), (TraceabilityStatus::NotCovered, false));
+        assert_eq!(
+            status("FR-002"),
+            (TraceabilityStatus::PartiallyCovered, true)
+        );
+        assert_eq!(
+            status("FR-001"),
+            (TraceabilityStatus::PartiallyCovered, true)
+        );
+        assert_eq!(status("FR-003"), (TraceabilityStatus::FullyCovered, false));
+        assert_eq!(matrix.summary.covered, 2);
+        assert_eq!(matrix.summary.partially_covered, 2);
+        assert_eq!(matrix.summary.not_covered, 1);
+    }
+
+    #[test]
+    fn test_matrix_component_allocations() {
+        let (spec, suite) = make_derived_spec();
+        let matrix = build_traceability_matrix(&spec, &suite);
+
+        let components: Vec<&str> = matrix
+            .allocations
+            .iter()
+            .map(|a| a.component.as_str())
+            .collect();
+        assert_eq!(components, vec!["API", "Auth"]);
+        let auth = &matrix.allocations[1];
+        assert_eq!(auth.requirements, vec!["FR-001", "FR-002", "FR-005"]);
+        assert_eq!(auth.scenarios, vec!["S1", "S2"]);
+        assert_eq!(auth.not_covered, vec!["FR-005"]);
+    }
+
+    #[test]
+    fn test_matrix_tree_survives_cycle() {
+        let mut spec = Specification::new("Test".into());
+        for (id, parent) in [("FR-001", "FR-002"), ("FR-002", "FR-001")] {
+            let mut fr = make_fr(id, Priority::P2);
+            fr.parent_requirement = Some(parent.into());
+            spec.functional_requirements.push(fr);
+        }
+        let suite = TestSuite {
+            features: vec![],
+            source_spec_id: spec.id,
+            total_scenarios: 0,
+            coverage: TestCoverage {
+                requirements_covered: vec![],
+                requirements_total: 2,
+                coverage_percentage: 0.0,
+                scenarios_by_type: ScenarioCounts::default(),
+            },
+        };
+
+        let matrix = build_traceability_matrix(&spec, &suite);
+        let tree: Vec<(&str, usize)> = matrix
+            .entries
+            .iter()
+            .map(|e| (e.requirement_id.as_str(), e.depth))
+            .collect();
+        assert_eq!(tree, vec![("FR-001", 0), ("FR-002", 1)]);
+    }
 }
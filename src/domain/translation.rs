@@ -0,0 +1,415 @@
+//! Traduction d'une specification et de ses tests
+//!
+//! Les textes traduisibles (enonces, scenarios, etapes et cellules de leurs
+//! tableaux de donnees) sont extraits sous forme de segments a cle stable ;
+//! les IDs, tags, `verification_of` et tables d'Examples ne sont jamais
+//! soumis au LLM. Une verification deterministe compare ensuite le squelette
+//! tracable avant/apres traduction.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::specification::Specification;
+use super::test_case::{Step, TestSuite};
+
+/// Texte traduisible identifie par une cle stable (`fr.0.statement`,
+/// `feature.1.sc.0.step.2`, ...)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationSegment {
+    pub key: String,
+    pub text: String,
+}
+
+/// Parcourt tous les textes traduisibles non vides
+fn visit_texts(
+    spec: &mut Specification,
+    suite: &mut TestSuite,
+    visit: &mut dyn FnMut(String, &mut String),
+) {
+    let mut text = |key: String, value: &mut String| {
+        if !value.trim().is_empty() {
+            visit(key, value);
+        }
+    };
+
+    text("spec.title".into(), &mut spec.title);
+    for (i, us) in spec.user_scenarios.iter_mut().enumerate() {
+        text(format!("us.{i}.title"), &mut us.title);
+        text(format!("us.{i}.description"), &mut us.description);
+        text(format!("us.{i}.why_priority"), &mut us.why_priority);
+        text(format!("us.{i}.independent_test"), &mut us.independent_test);
+        for (j, ac) in us.acceptance_scenarios.iter_mut().enumerate() {
+            text(format!("us.{i}.ac.{j}.given"), &mut ac.given);
+            text(format!("us.{i}.ac.{j}.when"), &mut ac.when);
+            text(format!("us.{i}.ac.{j}.then"), &mut ac.then);
+        }
+    }
+    for (i, fr) in spec.functional_requirements.iter_mut().enumerate() {
+        text(format!("fr.{i}.statement"), &mut fr.statement);
+        if let Some(ref mut rationale) = fr.rationale {
+            text(format!("fr.{i}.rationale"), rationale);
+        }
+    }
+    for (i, entity) in spec.key_entities.iter_mut().enumerate() {
+        text(format!("entity.{i}.description"), &mut entity.description);
+    }
+    for (i, ec) in spec.edge_cases.iter_mut().enumerate() {
+        text(format!("edge.{i}.description"), &mut ec.description);
+    }
+    for (i, sc) in spec.success_criteria.iter_mut().enumerate() {
+        text(format!("sc.{i}.description"), &mut sc.description);
+        text(format!("sc.{i}.metric"), &mut sc.measurable_metric);
+    }
+    for (i, clarification) in spec.clarifications_needed.iter_mut().enumerate() {
+        text(format!("clar.{i}.question"), &mut clarification.question);
+        text(format!("clar.{i}.context"), &mut clarification.context);
+        text(format!("clar.{i}.impact"), &mut clarification.impact);
+    }
+
+    for (f, feature) in suite.features.iter_mut().enumerate() {
+        text(format!("feature.{f}.name"), &mut feature.name);
+        text(format!("feature.{f}.description"), &mut feature.description);
+        if let Some(ref mut background) = feature.background {
+            visit_steps(&format!("feature.{f}.bg"), &mut background.steps, &mut text);
+        }
+        for (s, scenario) in feature.scenarios.iter_mut().enumerate() {
+            text(format!("feature.{f}.sc.{s}.name"), &mut scenario.name);
+            visit_steps(
+                &format!("feature.{f}.sc.{s}"),
+                &mut scenario.steps,
+                &mut text,
+            );
+        }
+    }
+}
+
+fn visit_steps(prefix: &str, steps: &mut [Step], text: &mut dyn FnMut(String, &mut String)) {
+    for (k, step) in steps.iter_mut().enumerate() {
+        text(format!("{prefix}.step.{k}"), &mut step.text);
+        if let Some(ref mut doc) = step.doc_string {
+            text(format!("{prefix}.step.{k}.doc"), doc);
+        }
+        // Cellules sans lettre (montants, dates, codes) non soumises
+        for (r, row) in step.data_table.iter_mut().flatten().enumerate() {
+            for (c, cell) in row.iter_mut().enumerate() {
+                if cell.chars().any(char::is_alphabetic) {
+                    text(format!("{prefix}.step.{k}.table.{r}.{c}"), cell);
+                }
+            }
+        }
+    }
+}
+
+/// Segments a traduire, dans l'ordre du document
+pub fn translatable_segments(spec: &Specification, suite: &TestSuite) -> Vec<TranslationSegment> {
+    let (mut spec, mut suite) = (spec.clone(), suite.clone());
+    let mut segments = Vec::new();
+    visit_texts(&mut spec, &mut suite, &mut |key, text| {
+        segments.push(TranslationSegment {
+            key,
+            text: text.clone(),
+        });
+    });
+    segments
+}
+
+/// Remplace les textes par leur traduction (cle -> texte) ; retourne le
+/// nombre de segments remplaces. Les cles absentes gardent le texte original.
+pub fn apply_translations(
+    spec: &mut Specification,
+    suite: &mut TestSuite,
+    translations: &HashMap<String, String>,
+) -> usize {
+    let mut applied = 0;
+    visit_texts(spec, suite, &mut |key, text| {
+        if let Some(translated) = translations.get(&key)
+            && !translated.trim().is_empty()
+        {
+            *text = translated.clone();
+            applied += 1;
+        }
+    });
+    applied
+}
+
+/// Jetons qu'une traduction doit conserver a l'identique : placeholders
+/// `<nom>`, tags `@tag` et references d'IDs (`FR-001`, `PAY-42`).
+/// Tries et sans doublon.
+pub fn protected_tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+
+    // Placeholders de Scenario Outline
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        let after = &rest[start + 1..];
+        match after.find(['>', '<', '\n']) {
+            Some(end) if after[end..].starts_with('>') && end > 0 && end <= 64 => {
+                tokens.push(format!("<{}>", &after[..end]));
+                rest = &after[end + 1..];
+            }
+            _ => rest = after,
+        }
+    }
+
+    for word in text.split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '@'))) {
+        if (word.len() > 1 && word.starts_with('@')) || is_id_reference(word) {
+            tokens.push(word.to_string());
+        }
+    }
+
+    tokens.sort();
+    tokens.dedup();
+    tokens
+}
+
+/// `PREFIXE-123` : au moins deux majuscules, un tiret, des chiffres
+fn is_id_reference(word: &str) -> bool {
+    word.split_once('-').is_some_and(|(prefix, number)| {
+        prefix.len() >= 2
+            && prefix.chars().all(|c| c.is_ascii_uppercase())
+            && !number.is_empty()
+            && number.chars().all(|c| c.is_ascii_digit())
+    })
+}
+
+/// Raison du rejet d'un segment traduit (jetons proteges modifies)
+pub fn segment_mismatch(original: &str, translated: &str) -> Option<String> {
+    let expected = protected_tokens(original);
+    let actual = protected_tokens(translated);
+    (expected != actual).then(|| format!("jetons {:?} devenus {:?}", expected, actual))
+}
+
+/// Squelette tracable : IDs, tags, liens et jetons proteges, une ligne par element
+fn structure_skeleton(spec: &Specification, suite: &TestSuite) -> Vec<String> {
+    let mut lines = Vec::new();
+    for us in &spec.user_scenarios {
+        lines.push(format!(
+            "US {} {} ac={}",
+            us.id,
+            us.priority,
+            us.acceptance_scenarios.len()
+        ));
+    }
+    for fr in &spec.functional_requirements {
+        lines.push(format!(
+            "FR {} {} parent={:?} alloue={:?}",
+            fr.id, fr.priority, fr.parent_requirement, fr.allocated_to
+        ));
+    }
+    for ec in &spec.edge_cases {
+        lines.push(format!("EDGE lie={:?}", ec.related_scenario));
+    }
+    for sc in &spec.success_criteria {
+        lines.push(format!("SC {}", sc.id));
+    }
+    for (f, feature) in suite.features.iter().enumerate() {
+        lines.push(format!(
+            "FEATURE {} tags={:?} sources={:?} couvre={:?}",
+            f, feature.tags, feature.source_scenario_ids, feature.covered_requirements
+        ));
+        for (s, scenario) in feature.scenarios.iter().enumerate() {
+            lines.push(format!(
+                "SCENARIO {}.{} tags={:?} verifie={:?} etapes={:?} examples={:?}",
+                f,
+                s,
+                scenario.tags,
+                scenario.verification_of,
+                scenario
+                    .steps
+                    .iter()
+                    .map(|st| (
+                        st.keyword,
+                        st.data_table
+                            .as_ref()
+                            .map(|t| t.iter().map(Vec::len).collect::<Vec<_>>())
+                    ))
+                    .collect::<Vec<_>>(),
+                scenario.examples.as_ref().map(|e| (&e.headers, &e.rows)),
+            ));
+        }
+    }
+    for segment in translatable_segments(spec, suite) {
+        let tokens = protected_tokens(&segment.text);
+        if !tokens.is_empty() {
+            lines.push(format!("TEXTE {} {:?}", segment.key, tokens));
+        }
+    }
+    lines
+}
+
+/// Differences de structure entre l'original et la traduction (vide si
+/// IDs, tags, liens, Examples et placeholders sont identiques)
+pub fn structure_differences(
+    before_spec: &Specification,
+    before_suite: &TestSuite,
+    after_spec: &Specification,
+    after_suite: &TestSuite,
+) -> Vec<String> {
+    let before = structure_skeleton(before_spec, before_suite);
+    let after = structure_skeleton(after_spec, after_suite);
+
+    let mut differences: Vec<String> = before
+        .iter()
+        .zip(&after)
+        .filter(|(b, a)| b != a)
+        .map(|(b, a)| format!("avant: {} / apres: {}", b, a))
+        .collect();
+    if before.len() != after.len() {
+        differences.push(format!(
+            "{} element(s) tracable(s) avant, {} apres",
+            before.len(),
+            after.len()
+        ));
+    }
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::specification::{FunctionalRequirement, RequirementCategory, UserScenario};
+    use crate::domain::test_case::{
+        Examples, Feature, Scenario, ScenarioType, StepKeyword, TestCoverage,
+    };
+    use crate::domain::user_story::Priority;
+    use pretty_assertions::assert_eq;
+
+    fn sample() -> (Specification, TestSuite) {
+        let mut spec = Specification::new("Paiements".into());
+        spec.user_scenarios.push(UserScenario {
+            id: "US-001".into(),
+            title: "Virement".into(),
+            priority: Priority::P1,
+            description: "Le client vire de l'argent".into(),
+            why_priority: String::new(),
+            independent_test: String::new(),
+            acceptance_scenarios: Vec::new(),
+            source_story_id: uuid::Uuid::nil(),
+        });
+        spec.functional_requirements.push(FunctionalRequirement {
+            id: "FR-001".into(),
+            statement: "Le systeme DOIT valider l'IBAN".into(),
+            priority: Priority::P1,
+            category: RequirementCategory::Functional,
+            testable: true,
+            rationale: Some("Voir US-001".into()),
+            source: None,
+            verification_method: Default::default(),
+            risk_level: None,
+            parent_requirement: None,
+            allocated_to: Vec::new(),
+            quality_characteristic: None,
+        });
+
+        let mut feature = Feature::new("Virement".into(), String::new());
+        feature.tags = vec!["@US-001".into()];
+        feature.scenarios.push(Scenario {
+            name: "Montant <montant>".into(),
+            tags: vec!["@FR-001".into()],
+            scenario_type: ScenarioType::BoundaryCondition,
+            steps: vec![Step {
+                keyword: StepKeyword::When,
+                text: "je vire <montant> EUR".into(),
+                doc_string: None,
+                data_table: Some(vec![
+                    vec!["beneficiaire".into(), "montant".into()],
+                    vec!["Alice".into(), "10,50".into()],
+                ]),
+            }],
+            examples: Some(Examples {
+                headers: vec!["montant".into()],
+                rows: vec![vec!["10".into()]],
+            }),
+            test_data_suggestions: Vec::new(),
+            verification_of: vec!["FR-001".into()],
+            coverage_technique: None,
+        });
+        let suite = TestSuite {
+            features: vec![feature],
+            source_spec_id: spec.id,
+            total_scenarios: 1,
+            coverage: TestCoverage {
+                requirements_covered: Vec::new(),
+                requirements_total: 1,
+                coverage_percentage: 0.0,
+                scenarios_by_type: Default::default(),
+            },
+        };
+        (spec, suite)
+    }
+
+    #[test]
+    fn test_segments_skip_ids_and_empty_texts() {
+        let (spec, suite) = sample();
+        let keys: Vec<String> = translatable_segments(&spec, &suite)
+            .into_iter()
+            .map(|s| s.key)
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "spec.title",
+                "us.0.title",
+                "us.0.description",
+                "fr.0.statement",
+                "fr.0.rationale",
+                "feature.0.name",
+                "feature.0.sc.0.name",
+                "feature.0.sc.0.step.0",
+                "feature.0.sc.0.step.0.table.0.0",
+                "feature.0.sc.0.step.0.table.0.1",
+                "feature.0.sc.0.step.0.table.1.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_protected_tokens() {
+        assert_eq!(
+            protected_tokens("je vire <montant> EUR (FR-001, @smoke) a < 3 ISBN-13"),
+            vec!["<montant>", "@smoke", "FR-001", "ISBN-13"]
+        );
+        assert_eq!(segment_mismatch("<a> FR-1", "FR-1 <a> !"), None);
+        assert!(segment_mismatch("<montant>", "<amount>").is_some());
+    }
+
+    #[test]
+    fn test_apply_and_structure_check() {
+        let (spec, suite) = sample();
+        let (mut en_spec, mut en_suite) = (spec.clone(), suite.clone());
+        let translations: HashMap<String, String> = [
+            ("us.0.title", "Transfer"),
+            ("feature.0.sc.0.step.0", "I transfer <montant> EUR"),
+            ("feature.0.sc.0.step.0.table.0.0", "payee"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(
+            apply_translations(&mut en_spec, &mut en_suite, &translations),
+            3
+        );
+        assert_eq!(en_spec.user_scenarios[0].title, "Transfer");
+        assert_eq!(
+            en_suite.features[0].scenarios[0].steps[0].data_table,
+            Some(vec![
+                vec!["payee".to_string(), "montant".to_string()],
+                vec!["Alice".to_string(), "10,50".to_string()],
+            ])
+        );
+        assert!(structure_differences(&spec, &suite, &en_spec, &en_suite).is_empty());
+
+        let broken: HashMap<String, String> = [(
+            "feature.0.sc.0.step.0".to_string(),
+            "I transfer <amount> EUR".to_string(),
+        )]
+        .into_iter()
+        .collect();
+        apply_translations(&mut en_spec, &mut en_suite, &broken);
+        en_suite.features[0].scenarios[0].tags.push("@extra".into());
+        let differences = structure_differences(&spec, &suite, &en_spec, &en_suite);
+        assert_eq!(differences.len(), 2);
+        assert!(differences[1].contains("<amount>"));
+    }
+}
@@ -5,6 +5,8 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::i18n::LanguageKeywords;
+
 /// Priorite MoSCoW alignee avec spec-kit P1/P2/P3
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Priority {
@@ -26,7 +28,27 @@ impl std::fmt::Display for Priority {
     }
 }
 
+impl Priority {
+    /// Construit une Priority a partir d'un libelle ("P1", "must", "Should have", ...)
+    pub fn from_code(code: &str) -> Option<Self> {
+        let lower = code.trim().to_lowercase();
+        match lower.split_whitespace().next().unwrap_or("") {
+            "p1" | "must" | "haute" | "high" => Some(Priority::P1),
+            "p2" | "should" | "moyenne" | "medium" => Some(Priority::P2),
+            "p3" | "could" | "basse" | "low" => Some(Priority::P3),
+            _ => None,
+        }
+    }
+
+    /// Vrai si `self` est plus critique que `other` (P1 > P2 > P3)
+    pub fn is_higher_than(self, other: Priority) -> bool {
+        (self as u8) < (other as u8)
+    }
+}
+
 /// Langues supportees pour les entrees/sorties
+///
+/// Les mots-cles et formulations de chaque langue sont dans `domain::i18n`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum Language {
     #[default]
@@ -34,32 +56,53 @@ pub enum Language {
     French,
     #[serde(alias = "en", alias = "EN", alias = "english")]
     English,
+    #[serde(alias = "es", alias = "ES", alias = "spanish")]
+    Spanish,
+    #[serde(alias = "de", alias = "DE", alias = "german")]
+    German,
 }
 
 impl Language {
+    /// Toutes les langues, dans l'ordre de preference en cas d'egalite
+    pub const ALL: [Language; 4] = [
+        Language::French,
+        Language::English,
+        Language::Spanish,
+        Language::German,
+    ];
+
     /// Code Gherkin pour le header `# language:`
     pub fn gherkin_code(&self) -> &'static str {
-        match self {
-            Language::French => "fr",
-            Language::English => "en",
-        }
+        self.keywords().code
     }
 
-    /// Construit une Language a partir d'un code ("fr", "en")
+    /// Table de mots-cles de la langue
+    pub fn keywords(&self) -> &'static LanguageKeywords {
+        super::i18n::keywords(*self)
+    }
+
+    /// Construit une Language a partir d'un code ("fr", "en", "es", "de")
+    ///
+    /// Un code inconnu retombe sur le francais.
     pub fn from_code(code: &str) -> Self {
-        match code {
-            "en" | "EN" | "english" => Language::English,
-            _ => Language::French,
+        Self::parse_code(code).unwrap_or_default()
+    }
+
+    /// Comme `from_code`, mais `None` pour un code inconnu
+    pub fn parse_code(code: &str) -> Option<Self> {
+        match code.trim().to_lowercase().as_str() {
+            "fr" | "french" => Some(Language::French),
+            "en" | "english" => Some(Language::English),
+            "es" | "spanish" => Some(Language::Spanish),
+            "de" | "german" => Some(Language::German),
+            _ => None,
         }
     }
 }
 
 impl std::fmt::Display for Language {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Language::French => write!(f, "fr"),
-            Language::English => write!(f, "en"),
-        }
+        write!(f, "{}", self.gherkin_code())
     }
 }
 
@@ -104,6 +147,10 @@ pub struct UserStory {
     /// Stakeholder/partie prenante (ISO 29148)
     #[serde(default)]
     pub stakeholder: Option<String>,
+
+    /// Epic/feature parente (titre H1, groupe YAML, colonne CSV)
+    #[serde(default)]
+    pub epic: Option<String>,
 }
 
 impl UserStory {
@@ -121,6 +168,7 @@ impl UserStory {
             raw_text: String::new(),
             tags: Vec::new(),
             stakeholder: None,
+            epic: None,
         }
     }
 
@@ -131,6 +179,19 @@ impl UserStory {
             self.actor, self.action, self.benefit
         )
     }
+
+    /// Estime le nombre de tokens de la story (heuristique: chars / 4)
+    pub fn estimated_tokens(&self) -> usize {
+        let mut len = self.title.len()
+            + self.actor.len()
+            + self.action.len()
+            + self.benefit.len()
+            + self.raw_text.len();
+        for ac in &self.acceptance_criteria {
+            len += ac.len();
+        }
+        len / 4
+    }
 }
 
 /// Collection de user stories en entree
@@ -187,6 +248,34 @@ impl UserStorySet {
             language,
         }
     }
+
+    /// Regroupe les stories par epic, dans l'ordre de premiere apparition.
+    /// Les stories sans epic forment un groupe `None`.
+    pub fn group_by_epic(&self) -> Vec<(Option<String>, UserStorySet)> {
+        let mut groups: Vec<(Option<String>, UserStorySet)> = Vec::new();
+
+        for story in &self.stories {
+            let epic = story
+                .epic
+                .as_deref()
+                .map(str::trim)
+                .filter(|e| !e.is_empty())
+                .map(str::to_string);
+            match groups.iter_mut().find(|(name, _)| *name == epic) {
+                Some((_, set)) => set.stories.push(story.clone()),
+                None => groups.push((
+                    epic,
+                    UserStorySet {
+                        stories: vec![story.clone()],
+                        source_files: self.source_files.clone(),
+                        language: self.language,
+                    },
+                )),
+            }
+        }
+
+        groups
+    }
 }
 
 #[cfg(test)]
@@ -228,10 +317,30 @@ mod tests {
         assert_eq!(Priority::P3.to_string(), "P3 (Could)");
     }
 
+    #[test]
+    fn test_priority_from_code() {
+        assert_eq!(Priority::from_code("P1"), Some(Priority::P1));
+        assert_eq!(Priority::from_code(" p2 "), Some(Priority::P2));
+        assert_eq!(Priority::from_code("Could have"), Some(Priority::P3));
+        assert_eq!(Priority::from_code("Haute"), Some(Priority::P1));
+        assert_eq!(Priority::from_code("urgent"), None);
+        assert_eq!(Priority::from_code(""), None);
+    }
+
     #[test]
     fn test_language_gherkin_code() {
         assert_eq!(Language::French.gherkin_code(), "fr");
         assert_eq!(Language::English.gherkin_code(), "en");
+        assert_eq!(Language::Spanish.gherkin_code(), "es");
+        assert_eq!(Language::German.gherkin_code(), "de");
+    }
+
+    #[test]
+    fn test_language_from_code() {
+        assert_eq!(Language::from_code("DE"), Language::German);
+        assert_eq!(Language::from_code("spanish"), Language::Spanish);
+        assert_eq!(Language::from_code("xx"), Language::French);
+        assert_eq!(Language::parse_code("xx"), None);
     }
 
     #[test]
@@ -271,6 +380,34 @@ mod tests {
         assert_eq!(merged.language, Language::French);
     }
 
+    #[test]
+    fn test_group_by_epic_preserves_order() {
+        let mut a = UserStory::new("A".into(), "u".into(), "a".into(), "b".into());
+        a.epic = Some("Paiements".into());
+        let mut b = UserStory::new("B".into(), "u".into(), "a".into(), "b".into());
+        b.epic = Some("Cartes".into());
+        let c = UserStory::new("C".into(), "u".into(), "a".into(), "b".into());
+        let mut d = UserStory::new("D".into(), "u".into(), "a".into(), "b".into());
+        d.epic = Some("Paiements ".into());
+
+        let set = UserStorySet {
+            stories: vec![a, b, c, d],
+            source_files: vec!["backlog.md".into()],
+            language: Language::French,
+        };
+        let groups = set.group_by_epic();
+        let names: Vec<Option<&str>> = groups.iter().map(|(n, _)| n.as_deref()).collect();
+        assert_eq!(names, vec![Some("Paiements"), Some("Cartes"), None]);
+        let titles: Vec<&str> = groups[0]
+            .1
+            .stories
+            .iter()
+            .map(|s| s.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["A", "D"]);
+        assert_eq!(groups[0].1.source_files, vec!["backlog.md"]);
+    }
+
     #[test]
     fn test_merge_empty() {
         let merged = UserStorySet::merge(vec![]);
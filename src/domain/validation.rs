@@ -7,12 +7,13 @@ use std::collections::{HashMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 
+use super::i18n::{ambiguous_words_for, compound_connectors_for, normative_keywords_for};
 use super::specification::{
     ChecklistItem, FunctionalRequirement, RequirementCategory, RiskLevel, SpecValidation,
     Specification, VerificationMethod,
 };
 use super::test_case::TestSuite;
-use super::user_story::Priority;
+use super::user_story::{Language, Priority};
 
 // ---------------------------------------------------------------------------
 // ISO 29148 — Well-formedness check (9 criteres)
@@ -49,7 +50,10 @@ pub enum WellFormednessCriterion {
     Traceable,
 }
 
-/// Mots ambigus (ISO 29148 — interdit dans les exigences)
+/// Mots ambigus historiques (francais et anglais)
+#[deprecated(
+    note = "utiliser `i18n::keywords(language).ambiguous_words` ou `i18n::ambiguous_words_for`"
+)]
 pub const AMBIGUOUS_WORDS: &[&str] = &[
     "environ",
     "quelques",
@@ -74,7 +78,7 @@ pub const AMBIGUOUS_WORDS: &[&str] = &[
 
 /// Verifie si un mot ambigu est present en tant que mot entier (word boundary)
 /// Evite les faux positifs: "some" ne matche pas "something"
-fn contains_ambiguous_word(text: &str, word: &str) -> bool {
+pub(crate) fn contains_ambiguous_word(text: &str, word: &str) -> bool {
     // "as appropriate" est multi-mots, utiliser contains directement
     if word.contains(' ') {
         return text.contains(word);
@@ -91,24 +95,26 @@ fn contains_ambiguous_word(text: &str, word: &str) -> bool {
     false
 }
 
-/// Mots normatifs (ISO 29148 section 5.2.4)
-const NORMATIVE_KEYWORDS: &[&str] = &[
-    "MUST", "SHALL", "SHOULD", "COULD", "WILL", "DOIT", "DEVRAIT", "POURRAIT",
-];
-
-/// Connecteurs indiquant des exigences multiples (violation Singular)
-const COMPOUND_CONNECTORS: &[&str] = &[
-    " and shall ",
-    " et doit ",
-    " and must ",
-    " et le systeme ",
-    " and the system ",
-];
+/// Verifie les 9 criteres de bien-formation d'une exigence (ISO 29148),
+/// avec les mots de toutes les langues
+#[deprecated(
+    note = "utiliser `check_requirement_wellformedness_for` avec la langue de la specification"
+)]
+pub fn check_requirement_wellformedness(
+    fr: &FunctionalRequirement,
+    all_ids: &HashSet<&str>,
+) -> Vec<WellFormednessWarning> {
+    check_requirement_wellformedness_for(fr, all_ids, None)
+}
 
 /// Verifie les 9 criteres de bien-formation d'une exigence (ISO 29148)
-pub fn check_requirement_wellformedness(
+///
+/// Mots ambigus, connecteurs et mots normatifs sont ceux de `language` ;
+/// toutes les langues sont acceptees si elle est inconnue.
+pub fn check_requirement_wellformedness_for(
     fr: &FunctionalRequirement,
     all_ids: &HashSet<&str>,
+    language: Option<Language>,
 ) -> Vec<WellFormednessWarning> {
     let mut warnings = Vec::new();
 
@@ -117,7 +123,7 @@ pub fn check_requirement_wellformedness(
 
     // 2. Unambiguous — pas de mots vagues (avec word boundary)
     let lower = fr.statement.to_lowercase();
-    for word in AMBIGUOUS_WORDS {
+    for word in ambiguous_words_for(language) {
         if contains_ambiguous_word(&lower, word) {
             warnings.push(WellFormednessWarning {
                 requirement_id: fr.id.clone(),
@@ -139,7 +145,7 @@ pub fn check_requirement_wellformedness(
 
     // 4. Singular — une seule exigence par statement
     let lower_stmt = fr.statement.to_lowercase();
-    for connector in COMPOUND_CONNECTORS {
+    for connector in compound_connectors_for(language) {
         if lower_stmt.contains(connector) {
             warnings.push(WellFormednessWarning {
                 requirement_id: fr.id.clone(),
@@ -173,7 +179,10 @@ pub fn check_requirement_wellformedness(
 
     // 7. Correct — syntaxe normative (MUST/SHOULD/COULD)
     let upper = fr.statement.to_uppercase();
-    if !NORMATIVE_KEYWORDS.iter().any(|kw| upper.contains(kw)) {
+    if !normative_keywords_for(language)
+        .iter()
+        .any(|kw| upper.contains(kw))
+    {
         warnings.push(WellFormednessWarning {
             requirement_id: fr.id.clone(),
             criterion: WellFormednessCriterion::Correct,
@@ -455,6 +464,133 @@ pub fn compute_quality_metrics(spec: &Specification, suite: &TestSuite) -> Quali
     }
 }
 
+// ---------------------------------------------------------------------------
+// ISO 29148 — Hierarchie des exigences (exigences derivees)
+// ---------------------------------------------------------------------------
+
+/// Anomalie dans l'arbre `parent_requirement` des exigences
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HierarchyIssue {
+    /// Le parent reference n'existe pas dans la specification
+    DanglingParent {
+        requirement_id: String,
+        parent_id: String,
+    },
+    /// La chaine des parents reboucle (IDs dans l'ordre du cycle)
+    Cycle { requirement_ids: Vec<String> },
+    /// Exigence derivee plus prioritaire que son parent (ex: P1 sous un P2)
+    PriorityExceedsParent {
+        requirement_id: String,
+        priority: Priority,
+        parent_id: String,
+        parent_priority: Priority,
+    },
+}
+
+impl HierarchyIssue {
+    /// Exigence a laquelle l'anomalie est rattachee (premiere du cycle)
+    pub fn requirement_id(&self) -> &str {
+        match self {
+            HierarchyIssue::DanglingParent { requirement_id, .. }
+            | HierarchyIssue::PriorityExceedsParent { requirement_id, .. } => requirement_id,
+            HierarchyIssue::Cycle { requirement_ids } => {
+                requirement_ids.first().map(String::as_str).unwrap_or("")
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for HierarchyIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HierarchyIssue::DanglingParent {
+                requirement_id,
+                parent_id,
+            } => write!(
+                f,
+                "{}: exigence parente \"{}\" introuvable",
+                requirement_id, parent_id
+            ),
+            HierarchyIssue::Cycle { requirement_ids } => {
+                let mut chain = requirement_ids.clone();
+                chain.extend(requirement_ids.first().cloned());
+                write!(f, "Cycle de derivation: {}", chain.join(" -> "))
+            }
+            HierarchyIssue::PriorityExceedsParent {
+                requirement_id,
+                priority,
+                parent_id,
+                parent_priority,
+            } => write!(
+                f,
+                "{}: priorite {} superieure a celle du parent {} ({})",
+                requirement_id, priority, parent_id, parent_priority
+            ),
+        }
+    }
+}
+
+/// Verifie l'arbre des exigences derivees : parents inexistants, cycles et
+/// enfants plus prioritaires que leur parent
+pub fn check_requirement_hierarchy(spec: &Specification) -> Vec<HierarchyIssue> {
+    let frs = &spec.functional_requirements;
+    // Premiere occurrence de chaque ID (les doublons sont signales ailleurs)
+    let mut by_id: HashMap<&str, &FunctionalRequirement> = HashMap::new();
+    for fr in frs {
+        by_id.entry(fr.id.as_str()).or_insert(fr);
+    }
+
+    let mut issues = Vec::new();
+    for fr in frs {
+        let Some(ref parent_id) = fr.parent_requirement else {
+            continue;
+        };
+        match by_id.get(parent_id.as_str()) {
+            None => issues.push(HierarchyIssue::DanglingParent {
+                requirement_id: fr.id.clone(),
+                parent_id: parent_id.clone(),
+            }),
+            Some(parent) if fr.priority.is_higher_than(parent.priority) => {
+                issues.push(HierarchyIssue::PriorityExceedsParent {
+                    requirement_id: fr.id.clone(),
+                    priority: fr.priority,
+                    parent_id: parent.id.clone(),
+                    parent_priority: parent.priority,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    // Chaque exigence a au plus un parent : on remonte la chaine depuis
+    // chaque exigence, un cycle est detecte quand la remontee recroise le
+    // chemin en cours. `done` evite de signaler deux fois le meme cycle.
+    let mut done: HashSet<&str> = HashSet::new();
+    for fr in frs {
+        let mut path: Vec<&str> = Vec::new();
+        let mut current = Some(fr.id.as_str());
+        while let Some(id) = current {
+            if done.contains(id) {
+                break;
+            }
+            if let Some(pos) = path.iter().position(|p| *p == id) {
+                issues.push(HierarchyIssue::Cycle {
+                    requirement_ids: path[pos..].iter().map(|p| p.to_string()).collect(),
+                });
+                break;
+            }
+            path.push(id);
+            current = by_id
+                .get(id)
+                .and_then(|r| r.parent_requirement.as_deref())
+                .filter(|p| by_id.contains_key(p));
+        }
+        done.extend(path);
+    }
+
+    issues
+}
+
 // ---------------------------------------------------------------------------
 // Validation existante enrichie
 // ---------------------------------------------------------------------------
@@ -532,9 +668,10 @@ pub fn validate_specification(spec: &Specification) -> SpecValidation {
     });
 
     // Tous les enonces ont un mot normatif
+    let normative_keywords = normative_keywords_for(spec.language);
     let all_normative = spec.functional_requirements.iter().all(|fr| {
         let upper = fr.statement.to_uppercase();
-        NORMATIVE_KEYWORDS.iter().any(|kw| upper.contains(kw))
+        normative_keywords.iter().any(|kw| upper.contains(kw))
     });
     checklist.push(ChecklistItem {
         description: "ISO-29148: Syntaxe normative (MUST/SHALL/SHOULD)".into(),
@@ -543,9 +680,10 @@ pub fn validate_specification(spec: &Specification) -> SpecValidation {
     });
 
     // Pas de mots ambigus (avec word boundary matching)
+    let ambiguous_words = ambiguous_words_for(spec.language);
     let no_ambiguous = spec.functional_requirements.iter().all(|fr| {
         let lower = fr.statement.to_lowercase();
-        !AMBIGUOUS_WORDS
+        !ambiguous_words
             .iter()
             .any(|w| contains_ambiguous_word(&lower, w))
     });
@@ -579,6 +717,26 @@ pub fn validate_specification(spec: &Specification) -> SpecValidation {
         category: "Conformite".into(),
     });
 
+    // Arbre des exigences derivees coherent
+    let hierarchy_issues = check_requirement_hierarchy(spec);
+    checklist.push(ChecklistItem {
+        description: "ISO-29148: Parents d'exigences existants et sans cycle".into(),
+        passed: !hierarchy_issues.iter().any(|i| {
+            matches!(
+                i,
+                HierarchyIssue::DanglingParent { .. } | HierarchyIssue::Cycle { .. }
+            )
+        }),
+        category: "Conformite".into(),
+    });
+    checklist.push(ChecklistItem {
+        description: "ISO-29148: Exigences derivees pas plus prioritaires que leur parent".into(),
+        passed: !hierarchy_issues
+            .iter()
+            .any(|i| matches!(i, HierarchyIssue::PriorityExceedsParent { .. })),
+        category: "Conformite".into(),
+    });
+
     // --- Calcul des scores ---
     let score_for = |cat: &str| -> f32 {
         let items: Vec<&ChecklistItem> = checklist.iter().filter(|c| c.category == cat).collect();
@@ -742,7 +900,7 @@ mod tests {
     fn test_wellformedness_normative() {
         let fr = make_fr("FR-001", "The system does something", Priority::P1);
         let ids = HashSet::from(["FR-001"]);
-        let warnings = check_requirement_wellformedness(&fr, &ids);
+        let warnings = check_requirement_wellformedness_for(&fr, &ids, None);
         assert!(
             warnings
                 .iter()
@@ -758,7 +916,7 @@ mod tests {
             Priority::P1,
         );
         let ids = HashSet::from(["FR-001"]);
-        let warnings = check_requirement_wellformedness(&fr, &ids);
+        let warnings = check_requirement_wellformedness_for(&fr, &ids, None);
         assert!(
             warnings
                 .iter()
@@ -774,7 +932,7 @@ mod tests {
             Priority::P1,
         );
         let ids = HashSet::from(["FR-001"]);
-        let warnings = check_requirement_wellformedness(&fr, &ids);
+        let warnings = check_requirement_wellformedness_for(&fr, &ids, None);
         // Should only have the Traceable warning (no source)
         assert!(
             warnings
@@ -909,7 +1067,7 @@ mod tests {
             Priority::P1,
         );
         let ids = HashSet::from(["FR-001"]);
-        let warnings = check_requirement_wellformedness(&fr_doit, &ids);
+        let warnings = check_requirement_wellformedness_for(&fr_doit, &ids, Some(Language::French));
         assert!(
             !warnings
                 .iter()
@@ -923,7 +1081,8 @@ mod tests {
             Priority::P2,
         );
         let ids2 = HashSet::from(["FR-002"]);
-        let warnings2 = check_requirement_wellformedness(&fr_devrait, &ids2);
+        let warnings2 =
+            check_requirement_wellformedness_for(&fr_devrait, &ids2, Some(Language::French));
         assert!(
             !warnings2
                 .iter()
@@ -932,6 +1091,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_wellformedness_uses_spec_language() {
+        // "SOLL" est normatif en allemand seulement
+        let fr = make_fr("FR-001", "The system SOLL export reports", Priority::P1);
+        let ids = HashSet::from(["FR-001"]);
+        let english = check_requirement_wellformedness_for(&fr, &ids, Some(Language::English));
+        assert!(
+            english
+                .iter()
+                .any(|w| w.criterion == WellFormednessCriterion::Correct)
+        );
+        let unknown = check_requirement_wellformedness_for(&fr, &ids, None);
+        assert!(
+            !unknown
+                .iter()
+                .any(|w| w.criterion == WellFormednessCriterion::Correct)
+        );
+
+        // Ancienne signature : toutes les langues
+        #[allow(deprecated)]
+        let legacy = check_requirement_wellformedness(&fr, &ids);
+        assert_eq!(legacy.len(), unknown.len());
+
+        // Un mot anglais n'est pas ambigu dans une spec francaise
+        let mut spec = make_complete_spec();
+        spec.language = Some(Language::French);
+        spec.functional_requirements[0].statement = "Le systeme DOIT afficher some".into();
+        let validation = validate_specification(&spec);
+        assert!(
+            validation
+                .checklist_items
+                .iter()
+                .filter(|c| c.description.contains("ambigu"))
+                .all(|c| c.passed)
+        );
+    }
+
     #[test]
     fn test_wellformedness_ambiguous_french() {
         let fr = make_fr(
@@ -940,7 +1136,7 @@ mod tests {
             Priority::P1,
         );
         let ids = HashSet::from(["FR-001"]);
-        let warnings = check_requirement_wellformedness(&fr, &ids);
+        let warnings = check_requirement_wellformedness_for(&fr, &ids, None);
         assert!(
             warnings
                 .iter()
@@ -957,7 +1153,7 @@ mod tests {
             Priority::P1,
         );
         let ids = HashSet::from(["FR-001"]);
-        let warnings = check_requirement_wellformedness(&fr, &ids);
+        let warnings = check_requirement_wellformedness_for(&fr, &ids, None);
         assert!(
             warnings
                 .iter()
@@ -970,7 +1166,7 @@ mod tests {
     fn test_wellformedness_invalid_id_format() {
         let fr = make_fr("REQ-001", "System MUST do X", Priority::P1);
         let ids = HashSet::from(["REQ-001"]);
-        let warnings = check_requirement_wellformedness(&fr, &ids);
+        let warnings = check_requirement_wellformedness_for(&fr, &ids, None);
         assert!(
             warnings
                 .iter()
@@ -984,7 +1180,7 @@ mod tests {
         let mut fr = make_fr("FR-002", "System MUST do Y", Priority::P2);
         fr.parent_requirement = Some("FR-999".into());
         let ids = HashSet::from(["FR-002"]);
-        let warnings = check_requirement_wellformedness(&fr, &ids);
+        let warnings = check_requirement_wellformedness_for(&fr, &ids, None);
         assert!(
             warnings
                 .iter()
@@ -998,7 +1194,7 @@ mod tests {
         let mut fr = make_fr("FR-001", "System MUST do X", Priority::P1);
         fr.testable = false;
         let ids = HashSet::from(["FR-001"]);
-        let warnings = check_requirement_wellformedness(&fr, &ids);
+        let warnings = check_requirement_wellformedness_for(&fr, &ids, None);
         assert!(
             warnings
                 .iter()
@@ -1085,4 +1281,70 @@ mod tests {
         assert!(ambiguity_check.is_some());
         assert!(!ambiguity_check.unwrap().passed);
     }
+
+    fn make_child(id: &str, parent: &str, priority: Priority) -> FunctionalRequirement {
+        let mut fr = make_fr(id, "System MUST do X", priority);
+        fr.parent_requirement = Some(parent.into());
+        fr
+    }
+
+    #[test]
+    fn test_requirement_hierarchy_issues() {
+        let mut spec = Specification::new("Test".into());
+        spec.functional_requirements = vec![
+            make_fr("FR-001", "System MUST do X", Priority::P2),
+            make_child("FR-002", "FR-001", Priority::P1),
+            make_child("FR-003", "FR-999", Priority::P3),
+            make_child("FR-004", "FR-005", Priority::P2),
+            make_child("FR-005", "FR-004", Priority::P2),
+            make_child("FR-006", "FR-006", Priority::P3),
+        ];
+
+        let issues = check_requirement_hierarchy(&spec);
+        assert_eq!(
+            issues,
+            vec![
+                HierarchyIssue::PriorityExceedsParent {
+                    requirement_id: "FR-002".into(),
+                    priority: Priority::P1,
+                    parent_id: "FR-001".into(),
+                    parent_priority: Priority::P2,
+                },
+                HierarchyIssue::DanglingParent {
+                    requirement_id: "FR-003".into(),
+                    parent_id: "FR-999".into(),
+                },
+                HierarchyIssue::Cycle {
+                    requirement_ids: vec!["FR-004".into(), "FR-005".into()],
+                },
+                HierarchyIssue::Cycle {
+                    requirement_ids: vec!["FR-006".into()],
+                },
+            ]
+        );
+        assert_eq!(
+            issues[2].to_string(),
+            "Cycle de derivation: FR-004 -> FR-005 -> FR-004"
+        );
+
+        let validation = validate_specification(&spec);
+        let failed: Vec<&str> = validation
+            .checklist_items
+            .iter()
+            .filter(|c| !c.passed && c.description.to_lowercase().contains("parent"))
+            .map(|c| c.description.as_str())
+            .collect();
+        assert_eq!(failed.len(), 2);
+    }
+
+    #[test]
+    fn test_requirement_hierarchy_clean_tree() {
+        let mut spec = make_complete_spec();
+        spec.functional_requirements
+            .push(make_child("FR-002", "FR-001", Priority::P2));
+        spec.functional_requirements
+            .push(make_child("FR-003", "FR-002", Priority::P2));
+
+        assert!(check_requirement_hierarchy(&spec).is_empty());
+    }
 }
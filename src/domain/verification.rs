@@ -0,0 +1,503 @@
+//! Verification des exigences par les resultats d'execution
+//!
+//! Rapproche les scenarios executes (Cucumber JSON, JUnit XML, sortie
+//! cucumber-rs) des scenarios de la `TestSuite` par nom, feature et tags,
+//! puis etend la matrice de tracabilite avec le statut de chaque scenario
+//! et un verdict de verification par exigence.
+//!
+//! Conformite : ISO/IEC/IEEE 29148:2018 section 6.6 (verification)
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::test_case::{Feature, Scenario, TestSuite};
+use super::traceability::TraceabilityMatrix;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Statut d'execution d'un scenario (vocabulaire Cucumber)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ExecutionStatus {
+    Passed,
+    Skipped,
+    Undefined,
+    Failed,
+}
+
+impl ExecutionStatus {
+    /// Statut Cucumber (`passed`, `failed`, `pending`, `ambiguous`, ...)
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_lowercase().as_str() {
+            "passed" => Some(Self::Passed),
+            "skipped" => Some(Self::Skipped),
+            "undefined" | "pending" => Some(Self::Undefined),
+            "failed" | "ambiguous" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+
+    /// Statut le plus defavorable (echec > non defini > ignore > succes)
+    pub fn worst(self, other: Self) -> Self {
+        self.max(other)
+    }
+}
+
+impl std::fmt::Display for ExecutionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExecutionStatus::Passed => write!(f, "passed"),
+            ExecutionStatus::Skipped => write!(f, "skipped"),
+            ExecutionStatus::Undefined => write!(f, "undefined"),
+            ExecutionStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// Scenario execute, tel que rapporte par l'outil de test
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutedScenario {
+    pub feature: String,
+    /// Fichier `.feature` execute, si connu
+    pub feature_file: Option<String>,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub status: ExecutionStatus,
+}
+
+/// Resultats d'une execution de la suite
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionResults {
+    pub scenarios: Vec<ExecutedScenario>,
+}
+
+/// Statut d'un scenario de la suite apres rapprochement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioVerification {
+    pub feature: String,
+    pub scenario: String,
+    /// `None` : scenario absent des resultats (non execute)
+    pub status: Option<ExecutionStatus>,
+}
+
+/// Verdict de verification d'une exigence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerificationVerdict {
+    /// Tous les scenarios couvrants ont reussi
+    Verified,
+    /// Au moins un scenario couvrant a echoue
+    Failed,
+    /// Certains scenarios non executes, ignores ou non definis
+    Incomplete,
+    /// Aucun scenario couvrant execute
+    NotExecuted,
+}
+
+impl std::fmt::Display for VerificationVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerificationVerdict::Verified => write!(f, "Verifie"),
+            VerificationVerdict::Failed => write!(f, "Echec"),
+            VerificationVerdict::Incomplete => write!(f, "Incomplet"),
+            VerificationVerdict::NotExecuted => write!(f, "Non execute"),
+        }
+    }
+}
+
+/// Resume de la verification
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VerificationSummary {
+    pub verified: usize,
+    pub failed: usize,
+    pub incomplete: usize,
+    pub not_executed: usize,
+    /// Scenarios executes sans correspondance dans la suite
+    pub unmatched_results: Vec<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Rapprochement
+// ---------------------------------------------------------------------------
+
+/// Etend la matrice avec les statuts d'execution et un verdict par exigence
+pub fn apply_execution_results(
+    matrix: &mut TraceabilityMatrix,
+    suite: &TestSuite,
+    results: &ExecutionResults,
+) {
+    let mut matched = HashSet::new();
+    let statuses: Vec<Vec<Option<ExecutionStatus>>> = suite
+        .features
+        .iter()
+        .map(|feature| {
+            feature
+                .scenarios
+                .iter()
+                .map(|scenario| {
+                    let indices = match_scenario(suite, feature, scenario, results);
+                    let status = indices
+                        .iter()
+                        .map(|&i| results.scenarios[i].status)
+                        .reduce(ExecutionStatus::worst);
+                    matched.extend(indices);
+                    status
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut summary = VerificationSummary::default();
+    for entry in &mut matrix.entries {
+        entry.scenario_results.clear();
+        for (f_idx, feature) in suite.features.iter().enumerate() {
+            for (s_idx, scenario) in feature.scenarios.iter().enumerate() {
+                if scenario
+                    .linked_requirements()
+                    .contains(&entry.requirement_id)
+                {
+                    entry.scenario_results.push(ScenarioVerification {
+                        feature: feature.name.clone(),
+                        scenario: scenario.name.clone(),
+                        status: statuses[f_idx][s_idx],
+                    });
+                }
+            }
+        }
+
+        let verdict = verdict(&entry.scenario_results);
+        match verdict {
+            VerificationVerdict::Verified => summary.verified += 1,
+            VerificationVerdict::Failed => summary.failed += 1,
+            VerificationVerdict::Incomplete => summary.incomplete += 1,
+            VerificationVerdict::NotExecuted => summary.not_executed += 1,
+        }
+        entry.verdict = Some(verdict);
+    }
+
+    summary.unmatched_results = results
+        .scenarios
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !matched.contains(i))
+        .map(|(_, s)| format!("{} / {}", s.feature, s.name))
+        .collect();
+    matrix.verification = Some(summary);
+}
+
+/// Verdict agrege a partir des statuts des scenarios couvrants
+pub fn verdict(results: &[ScenarioVerification]) -> VerificationVerdict {
+    let executed: Vec<ExecutionStatus> = results.iter().filter_map(|r| r.status).collect();
+    if executed.contains(&ExecutionStatus::Failed) {
+        VerificationVerdict::Failed
+    } else if executed.is_empty() {
+        VerificationVerdict::NotExecuted
+    } else if executed.len() == results.len()
+        && executed.iter().all(|s| *s == ExecutionStatus::Passed)
+    {
+        VerificationVerdict::Verified
+    } else {
+        VerificationVerdict::Incomplete
+    }
+}
+
+/// Resultats correspondant a un scenario de la suite
+///
+/// Le nom est determinant (un Scenario Outline accepte ses exemples dont
+/// les `<placeholders>` ont ete substitues). Entre homonymes, la feature
+/// (nom ou fichier `.feature`) puis les tags departagent.
+fn match_scenario(
+    suite: &TestSuite,
+    feature: &Feature,
+    scenario: &Scenario,
+    results: &ExecutionResults,
+) -> Vec<usize> {
+    let candidates: Vec<usize> = results
+        .scenarios
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| name_matches(&scenario.name, &r.name))
+        .map(|(i, _)| i)
+        .collect();
+    if candidates.is_empty() {
+        return candidates;
+    }
+
+    let same_feature: Vec<usize> = candidates
+        .iter()
+        .copied()
+        .filter(|&i| feature_matches(feature, &results.scenarios[i]))
+        .collect();
+    if !same_feature.is_empty() {
+        return same_feature;
+    }
+
+    // Feature inconnue cote resultats : accepte si le nom est unique dans la
+    // suite, sinon departage par les tags
+    let homonyms = suite
+        .features
+        .iter()
+        .flat_map(|f| &f.scenarios)
+        .filter(|s| normalize(&s.name) == normalize(&scenario.name))
+        .count();
+    if homonyms == 1 {
+        return candidates;
+    }
+    let own_tags: HashSet<String> = scenario.tags.iter().map(|t| normalize_tag(t)).collect();
+    candidates
+        .into_iter()
+        .filter(|&i| {
+            let tags = &results.scenarios[i].tags;
+            !tags.is_empty() && tags.iter().all(|t| own_tags.contains(&normalize_tag(t)))
+        })
+        .collect()
+}
+
+fn feature_matches(feature: &Feature, result: &ExecutedScenario) -> bool {
+    if normalize(&feature.name) == normalize(&result.feature) {
+        return true;
+    }
+    result.feature_file.as_deref().is_some_and(|file| {
+        let stem = file
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or(file)
+            .trim_end_matches(".feature");
+        stem == feature.file_stem()
+    })
+}
+
+/// Comparaison de noms tolerant la casse, les espaces et les exemples
+/// d'un Scenario Outline (`<placeholder>` remplace par une valeur)
+fn name_matches(expected: &str, executed: &str) -> bool {
+    let expected = normalize(expected);
+    let executed = normalize(executed);
+    if expected == executed {
+        return true;
+    }
+    if !expected.contains('<') {
+        return false;
+    }
+
+    // Les segments litteraux doivent apparaitre dans l'ordre
+    let mut rest = executed.as_str();
+    let mut literals = Vec::new();
+    let mut remaining = expected.as_str();
+    while let Some(open) = remaining.find('<') {
+        literals.push(&remaining[..open]);
+        match remaining[open..].find('>') {
+            Some(close) => remaining = &remaining[open + close + 1..],
+            None => return false,
+        }
+    }
+    let last = remaining;
+    for (i, literal) in literals.iter().enumerate() {
+        if i == 0 {
+            match rest.strip_prefix(literal) {
+                Some(r) => rest = r,
+                None => return false,
+            }
+        } else if !literal.is_empty() {
+            match rest.find(literal) {
+                Some(pos) => rest = &rest[pos + literal.len()..],
+                None => return false,
+            }
+        }
+    }
+    rest.ends_with(last)
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('@').to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::specification::{
+        FunctionalRequirement, RequirementCategory, Specification, VerificationMethod,
+    };
+    use crate::domain::test_case::{ScenarioType, TestCoverage};
+    use crate::domain::traceability::build_traceability_matrix;
+    use crate::domain::user_story::Priority;
+
+    fn scenario(name: &str, verifies: &[&str]) -> Scenario {
+        Scenario {
+            name: name.into(),
+            tags: verifies.iter().map(|id| format!("@{id}")).collect(),
+            scenario_type: ScenarioType::HappyPath,
+            steps: vec![],
+            examples: None,
+            test_data_suggestions: vec![],
+            verification_of: verifies.iter().map(|s| s.to_string()).collect(),
+            coverage_technique: None,
+        }
+    }
+
+    fn executed(feature: &str, name: &str, status: ExecutionStatus) -> ExecutedScenario {
+        ExecutedScenario {
+            feature: feature.into(),
+            feature_file: None,
+            name: name.into(),
+            tags: vec![],
+            status,
+        }
+    }
+
+    fn fixture() -> (Specification, TestSuite) {
+        let mut spec = Specification::new("Spec".into());
+        for id in ["FR-001", "FR-002", "FR-003"] {
+            spec.functional_requirements.push(FunctionalRequirement {
+                id: id.into(),
+                statement: format!("Exigence {id}"),
+                priority: Priority::P2,
+                category: RequirementCategory::Functional,
+                testable: true,
+                rationale: None,
+                source: None,
+                verification_method: VerificationMethod::Test,
+                risk_level: None,
+                parent_requirement: None,
+                allocated_to: vec![],
+                quality_characteristic: None,
+            });
+        }
+        let mut catalogue = Feature::new("Catalogue".into(), String::new());
+        catalogue.scenarios = vec![
+            scenario("Lister les livres", &["FR-001"]),
+            scenario("Filtrer par <genre>", &["FR-001", "FR-002"]),
+        ];
+        let mut panier = Feature::new("Panier".into(), String::new());
+        panier.scenarios = vec![
+            scenario("Lister les livres", &["FR-002"]),
+            scenario("Vider le panier", &["FR-003"]),
+        ];
+        let suite = TestSuite {
+            features: vec![catalogue, panier],
+            source_spec_id: spec.id,
+            total_scenarios: 4,
+            coverage: TestCoverage {
+                requirements_covered: vec![],
+                requirements_total: 3,
+                coverage_percentage: 0.0,
+                scenarios_by_type: Default::default(),
+            },
+        };
+        (spec, suite)
+    }
+
+    #[test]
+    fn test_apply_results_aggregates_verdict_per_requirement() {
+        let (spec, suite) = fixture();
+        let results = ExecutionResults {
+            scenarios: vec![
+                executed("Catalogue", "Lister les livres", ExecutionStatus::Passed),
+                executed("Catalogue", "Filtrer par roman", ExecutionStatus::Passed),
+                executed("Catalogue", "Filtrer par essai", ExecutionStatus::Passed),
+                executed("Panier", "Lister les livres", ExecutionStatus::Failed),
+                executed("Autre", "Inconnu", ExecutionStatus::Passed),
+            ],
+        };
+        let mut matrix = build_traceability_matrix(&spec, &suite);
+        apply_execution_results(&mut matrix, &suite, &results);
+
+        let verdicts: Vec<_> = matrix.entries.iter().map(|e| e.verdict).collect();
+        assert_eq!(
+            verdicts,
+            vec![
+                Some(VerificationVerdict::Verified),
+                Some(VerificationVerdict::Failed),
+                Some(VerificationVerdict::NotExecuted),
+            ]
+        );
+        assert_eq!(matrix.entries[2].scenario_results[0].status, None);
+        let summary = matrix.verification.unwrap();
+        assert_eq!(summary.verified, 1);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.unmatched_results, vec!["Autre / Inconnu"]);
+    }
+
+    #[test]
+    fn test_only_requirement_tags_link_scenarios() {
+        let (spec, mut suite) = fixture();
+        suite.features[1].scenarios[1].tags.push("@P1".into());
+        let mut matrix = build_traceability_matrix(&spec, &suite);
+        // Entree dont l'identifiant coincide avec un tag non-exigence
+        matrix.entries[2].requirement_id = "P1".into();
+        let results = ExecutionResults {
+            scenarios: vec![executed(
+                "Panier",
+                "Vider le panier",
+                ExecutionStatus::Passed,
+            )],
+        };
+        apply_execution_results(&mut matrix, &suite, &results);
+        assert!(matrix.entries[2].scenario_results.is_empty());
+    }
+
+    #[test]
+    fn test_match_by_feature_file_and_outline_examples() {
+        let (spec, suite) = fixture();
+        let mut first = executed("?", "Lister les livres", ExecutionStatus::Skipped);
+        first.feature_file = Some("features/catalogue.feature".into());
+        let results = ExecutionResults {
+            scenarios: vec![
+                first,
+                executed("Catalogue", "Filtrer par roman", ExecutionStatus::Undefined),
+            ],
+        };
+        let mut matrix = build_traceability_matrix(&spec, &suite);
+        apply_execution_results(&mut matrix, &suite, &results);
+
+        let fr1 = &matrix.entries[0];
+        assert_eq!(
+            fr1.scenario_results[0].status,
+            Some(ExecutionStatus::Skipped)
+        );
+        assert_eq!(
+            fr1.scenario_results[1].status,
+            Some(ExecutionStatus::Undefined)
+        );
+        assert_eq!(fr1.verdict, Some(VerificationVerdict::Incomplete));
+        // Homonyme de l'autre feature : non rapproche
+        assert_eq!(matrix.entries[1].scenario_results[1].status, None);
+    }
+
+    #[test]
+    fn test_name_matches_outline() {
+        assert!(name_matches("Filtrer par <genre>", "filtrer  par Roman"));
+        assert!(name_matches(
+            "Payer <montant> en <devise>",
+            "Payer 10 en EUR"
+        ));
+        assert!(!name_matches(
+            "Payer <montant> en <devise>",
+            "Rembourser 10 en EUR"
+        ));
+        assert!(!name_matches("Lister", "Lister les livres"));
+    }
+
+    #[test]
+    fn test_status_ordering() {
+        assert_eq!(
+            ExecutionStatus::Passed.worst(ExecutionStatus::Skipped),
+            ExecutionStatus::Skipped
+        );
+        assert_eq!(
+            ExecutionStatus::Failed.worst(ExecutionStatus::Undefined),
+            ExecutionStatus::Failed
+        );
+        assert_eq!(
+            ExecutionStatus::from_code("pending"),
+            Some(ExecutionStatus::Undefined)
+        );
+    }
+}
@@ -7,7 +7,12 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-use crate::domain::specification::ComplianceProfile;
+use crate::domain::dedupe::DedupeStrategy;
+use crate::domain::output_format::OutputFormat;
+use crate::domain::specification::{ComplianceProfile, SpecFormat};
+use crate::domain::step_vocabulary::StepVocabularyMode;
+use crate::domain::test_case::TestExportFormat;
+use crate::domain::user_story::Language;
 use crate::ports::llm_service::LlmConfig;
 
 /// Configuration principale
@@ -50,28 +55,136 @@ pub struct PipelineConfig {
     /// Budget max de tokens pour le user prompt par batch LLM
     #[serde(default = "default_token_budget")]
     pub token_budget: usize,
+    /// Lint INVEST des stories avant tout appel LLM (off, warn, strict)
+    #[serde(default = "default_story_lint")]
+    pub story_lint: String,
+    /// Traitement des stories quasi-dupliquees (report, keep-first, merge-criteria)
+    #[serde(default = "default_dedupe")]
+    pub dedupe: String,
+    /// Normalisation du vocabulaire des etapes generees (off, report, rewrite)
+    #[serde(default = "default_step_vocabulary")]
+    pub step_vocabulary: String,
+    /// Fournir le vocabulaire canonique des batches precedents au LLM
+    #[serde(default)]
+    pub step_vocabulary_prompt: bool,
+    /// Background commun et Scenario Outline extraits des tests generes
+    /// (opt-in : reecrit les scenarios produits par le LLM)
+    #[serde(default)]
+    pub gherkin_refactor: bool,
 }
 
 fn default_token_budget() -> usize {
     2000
 }
 
+fn default_story_lint() -> String {
+    "warn".to_string()
+}
+
+fn default_dedupe() -> String {
+    "report".to_string()
+}
+
+/// Cle de projet Jira : une majuscule puis au moins une majuscule, un
+/// chiffre ou `_` (format par defaut de Jira)
+fn is_jira_project_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars.next().is_some_and(|c| c.is_ascii_uppercase())
+        && key.len() >= 2
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn default_step_vocabulary() -> String {
+    "report".to_string()
+}
+
 /// Configuration des templates
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TemplatesConfig {
     /// Repertoire des templates
     pub directory: PathBuf,
+    /// Repertoire propre au projet dont les templates (`*.md`, `*.md.hbs`)
+    /// remplacent ceux du meme nom
+    #[serde(default)]
+    pub overrides: Option<PathBuf>,
 }
 
 /// Configuration des sorties
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OutputConfig {
-    /// Format de la spec (markdown)
+    /// Sorties ecrites apres la generation (markdown, docx, feature,
+    /// traceability, json, html, xray, testrail, junit). Vide : deduites de
+    /// `spec_format`, `traceability`, `html_report` et `test_exports`
+    #[serde(default)]
+    pub formats: Vec<String>,
+    /// Format de la spec (markdown, docx, both)
     pub spec_format: String,
+    /// Document Word de reference (`.dotx` / `.docx`) dont les styles sont
+    /// repris par l'export DOCX
+    #[serde(default)]
+    pub docx_reference: Option<PathBuf>,
     /// Inclure le rapport de tracabilite
     pub traceability: bool,
     /// Code langue Gherkin
     pub gherkin_language: String,
+    /// Exports des cas de test apres generation (xray, testrail, junit)
+    #[serde(default)]
+    pub test_exports: Vec<String>,
+    /// Cle du projet Jira des tests importes par Xray (`PAY`)
+    #[serde(default)]
+    pub xray_project_key: Option<String>,
+    /// Rapport HTML autonome (spec + tests + tracabilite) lors de `full`
+    #[serde(default)]
+    pub html_report: bool,
+    /// Conserver les scenarios .feature retouches a la main lors des re-ecritures
+    #[serde(default = "default_true")]
+    pub preserve_manual_edits: bool,
+    /// Supprimer les artefacts d'un run precedent qui ne sont plus produits
+    #[serde(default)]
+    pub clean_stale: bool,
+}
+
+impl OutputConfig {
+    /// Formats effectivement ecrits : `formats` s'il est renseigne, sinon
+    /// les options historiques (spec, .feature, tracabilite, rapport, exports)
+    pub fn resolved_formats(&self) -> Vec<OutputFormat> {
+        if !self.formats.is_empty() {
+            let mut formats: Vec<OutputFormat> = Vec::with_capacity(self.formats.len());
+            for format in self
+                .formats
+                .iter()
+                .filter_map(|c| OutputFormat::from_code(c))
+            {
+                if !formats.contains(&format) {
+                    formats.push(format);
+                }
+            }
+            return formats;
+        }
+
+        let spec_format = SpecFormat::from_code(&self.spec_format).unwrap_or(SpecFormat::Markdown);
+        let mut formats = Vec::new();
+        if spec_format.includes_markdown() {
+            formats.push(OutputFormat::Markdown);
+        }
+        formats.push(OutputFormat::Feature);
+        if self.traceability {
+            formats.push(OutputFormat::Traceability);
+        }
+        formats.extend(
+            self.test_exports
+                .iter()
+                .filter_map(|code| TestExportFormat::from_code(code))
+                .map(OutputFormat::TestExport),
+        );
+        if self.html_report {
+            formats.push(OutputFormat::Html);
+        }
+        if spec_format.includes_docx() {
+            formats.push(OutputFormat::Docx);
+        }
+        formats
+    }
 }
 
 /// Configuration de la validation
@@ -263,6 +376,18 @@ pub struct PathsConfig {
     pub specs_dir: PathBuf,
     /// Repertoire des features
     pub features_dir: PathBuf,
+    /// Parcourir recursivement les dossiers d'entree
+    #[serde(default = "default_true")]
+    pub recursive: bool,
+    /// Inclure les fichiers/dossiers caches lors du parcours
+    #[serde(default)]
+    pub include_hidden: bool,
+    /// Suivre les liens symboliques (boucles detectees)
+    #[serde(default)]
+    pub follow_symlinks: bool,
+    /// Motifs d'exclusion des entrees (syntaxe .specforgeignore)
+    #[serde(default)]
+    pub exclude: Vec<String>,
 }
 
 impl Default for Config {
@@ -272,15 +397,28 @@ impl Default for Config {
                 max_retries: 2,
                 default_language: "fr".to_string(),
                 token_budget: default_token_budget(),
+                story_lint: default_story_lint(),
+                dedupe: default_dedupe(),
+                step_vocabulary: default_step_vocabulary(),
+                step_vocabulary_prompt: false,
+                gherkin_refactor: false,
             },
             llm: LlmConfig::default(),
             templates: TemplatesConfig {
                 directory: PathBuf::from("templates"),
+                overrides: None,
             },
             output: OutputConfig {
+                formats: Vec::new(),
                 spec_format: "markdown".to_string(),
+                docx_reference: None,
                 traceability: true,
                 gherkin_language: "fr".to_string(),
+                test_exports: Vec::new(),
+                xray_project_key: None,
+                html_report: false,
+                preserve_manual_edits: true,
+                clean_stale: false,
             },
             validation: ValidationConfig {
                 min_coverage_percent: 80,
@@ -298,6 +436,10 @@ impl Default for Config {
                 output_dir: PathBuf::from("output"),
                 specs_dir: PathBuf::from("output/specs"),
                 features_dir: PathBuf::from("output/features"),
+                recursive: true,
+                include_hidden: false,
+                follow_symlinks: false,
+                exclude: Vec::new(),
             },
         }
     }
@@ -381,6 +523,82 @@ impl Config {
             anyhow::bail!("pipeline.token_budget doit etre > 0");
         }
 
+        let valid_lint_modes = ["off", "warn", "strict"];
+        if !valid_lint_modes.contains(&self.pipeline.story_lint.as_str()) {
+            anyhow::bail!(
+                "pipeline.story_lint invalide: {}. Acceptes: {:?}",
+                self.pipeline.story_lint,
+                valid_lint_modes
+            );
+        }
+
+        if DedupeStrategy::from_code(&self.pipeline.dedupe).is_none() {
+            anyhow::bail!(
+                "pipeline.dedupe invalide: {}. Acceptes: report, keep-first, merge-criteria",
+                self.pipeline.dedupe
+            );
+        }
+
+        if StepVocabularyMode::from_code(&self.pipeline.step_vocabulary).is_none() {
+            anyhow::bail!(
+                "pipeline.step_vocabulary invalide: {}. Acceptes: off, report, rewrite",
+                self.pipeline.step_vocabulary
+            );
+        }
+
+        if SpecFormat::from_code(&self.output.spec_format).is_none() {
+            anyhow::bail!(
+                "output.spec_format invalide: {}. Acceptes: markdown, docx, both",
+                self.output.spec_format
+            );
+        }
+
+        for format in &self.output.test_exports {
+            if TestExportFormat::from_code(format).is_none() {
+                anyhow::bail!(
+                    "output.test_exports invalide: {}. Acceptes: xray, testrail, junit",
+                    format
+                );
+            }
+        }
+
+        if let Some(key) = &self.output.xray_project_key
+            && !is_jira_project_key(key)
+        {
+            anyhow::bail!(
+                "output.xray_project_key invalide: {}. Acceptes: majuscule puis \
+                 majuscules, chiffres ou _ (ex. PAY)",
+                key
+            );
+        }
+
+        for format in &self.output.formats {
+            if OutputFormat::from_code(format).is_none() {
+                anyhow::bail!(
+                    "output.formats invalide: {}. Acceptes: markdown, docx, feature, \
+                     traceability, json, html, xray, testrail, junit",
+                    format
+                );
+            }
+        }
+        if !self
+            .output
+            .resolved_formats()
+            .iter()
+            .any(OutputFormat::is_specification)
+        {
+            anyhow::bail!("output.formats doit inclure markdown ou docx");
+        }
+
+        for (key, code) in [
+            ("pipeline.default_language", &self.pipeline.default_language),
+            ("output.gherkin_language", &self.output.gherkin_language),
+        ] {
+            if Language::parse_code(code).is_none() {
+                anyhow::bail!("{} invalide: {}. Acceptes: fr, en, es, de", key, code);
+            }
+        }
+
         // Timeout LLM doit etre > 0
         if self.llm.timeout_secs == 0 {
             anyhow::bail!("llm.timeout_secs doit etre > 0");
@@ -420,6 +638,8 @@ mod tests {
         assert_eq!(config.llm.model_name, "qwen3:8b");
         assert_eq!(config.output.gherkin_language, "fr");
         assert_eq!(config.validation.min_coverage_percent, 80);
+        // Refactoring Gherkin opt-in
+        assert!(!config.pipeline.gherkin_refactor);
     }
 
     #[test]
@@ -435,6 +655,114 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_config_validate_invalid_story_lint() {
+        let mut config = Config::default();
+        assert_eq!(config.pipeline.story_lint, "warn");
+        config.pipeline.story_lint = "pedantic".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_validate_invalid_step_vocabulary() {
+        let mut config = Config::default();
+        assert_eq!(config.pipeline.step_vocabulary, "report");
+        config.pipeline.step_vocabulary = "synonyms".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_validate_invalid_xray_project_key() {
+        let mut config = Config::default();
+        config.output.xray_project_key = Some("PAY_2".into());
+        assert!(config.validate().is_ok());
+        for key in ["pay", "P", "2PAY", "PAY-1", ""] {
+            config.output.xray_project_key = Some(key.into());
+            let err = config.validate().unwrap_err();
+            assert!(err.to_string().contains("output.xray_project_key invalide"));
+        }
+    }
+
+    #[test]
+    fn test_config_validate_invalid_test_export() {
+        let mut config = Config::default();
+        config.output.test_exports = vec!["xray".into(), "junit".into()];
+        assert!(config.validate().is_ok());
+        config.output.test_exports.push("qtest".into());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_validate_invalid_spec_format() {
+        let mut config = Config::default();
+        config.output.spec_format = "both".to_string();
+        assert!(config.validate().is_ok());
+        config.output.spec_format = "pdf".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_validate_invalid_output_formats() {
+        let mut config = Config::default();
+        config.output.formats = vec!["docx".into(), "feature".into(), "xray".into()];
+        assert!(config.validate().is_ok());
+        config.output.formats.push("pdf".into());
+        assert!(config.validate().is_err());
+        // Sans format de specification
+        config.output.formats = vec!["feature".into(), "json".into()];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_output_formats_default_to_legacy_options() {
+        let mut output = Config::default().output;
+        assert_eq!(
+            output.resolved_formats(),
+            vec![
+                OutputFormat::Markdown,
+                OutputFormat::Feature,
+                OutputFormat::Traceability
+            ]
+        );
+        output.spec_format = "docx".into();
+        output.traceability = false;
+        output.html_report = true;
+        output.test_exports = vec!["junit".into()];
+        assert_eq!(
+            output.resolved_formats(),
+            vec![
+                OutputFormat::Feature,
+                OutputFormat::TestExport(TestExportFormat::JUnit),
+                OutputFormat::Html,
+                OutputFormat::Docx
+            ]
+        );
+        // `formats` renseigne : seul pris en compte, sans doublon
+        output.formats = vec!["json".into(), "md".into(), "markdown".into()];
+        assert_eq!(
+            output.resolved_formats(),
+            vec![OutputFormat::Json, OutputFormat::Markdown]
+        );
+    }
+
+    #[test]
+    fn test_config_validate_invalid_dedupe() {
+        let mut config = Config::default();
+        config.pipeline.dedupe = "merge-criteria".to_string();
+        assert!(config.validate().is_ok());
+        config.pipeline.dedupe = "drop-all".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_validate_invalid_language() {
+        let mut config = Config::default();
+        config.output.gherkin_language = "de".to_string();
+        assert!(config.validate().is_ok());
+        config.pipeline.default_language = "it".to_string();
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_config_validate_invalid_coverage() {
         let mut config = Config::default();
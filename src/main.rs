@@ -3,13 +3,21 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use console::style;
 
 use spec_forge::adapters::llm::ollama_adapter::OllamaAdapter;
+use spec_forge::adapters::output::gherkin_writer::FeatureWriteOutcome;
 use spec_forge::adapters::templates::file_template_engine::FileTemplateEngine;
-use spec_forge::application::pipeline::Pipeline;
+use spec_forge::application::pipeline::{ManifestOutcome, Pipeline};
+use spec_forge::domain::output_manifest::ArtifactKind;
+use spec_forge::domain::regeneration::RequirementSelection;
+use spec_forge::domain::specification::Specification;
+use spec_forge::domain::step_definitions::{StepGrouping, StepTarget};
+use spec_forge::domain::story_lint::LintSeverity;
+use spec_forge::domain::test_case::{TestExportFormat, TestSuite};
+use spec_forge::domain::user_story::{Language, Priority};
 use spec_forge::infrastructure::config::Config;
 use spec_forge::infrastructure::logging;
 use spec_forge::ports::llm_service::LlmService;
@@ -37,10 +45,14 @@ struct Cli {
 enum Commands {
     /// Raffiner des User Stories en specification complete
     Refine {
-        /// Fichier(s) ou dossier(s) d'entree (Markdown, YAML, PDF, DOCX)
+        /// Fichier(s), dossier(s) ou glob(s) d'entree (Markdown, YAML, PDF, DOCX, ODT, HTML, AsciiDoc, CSV), `-` pour stdin
         #[arg(short, long, num_args = 1..)]
         input: Vec<String>,
 
+        /// Motif(s) d'exclusion des entrees (syntaxe .specforgeignore)
+        #[arg(long, num_args = 1..)]
+        exclude: Vec<String>,
+
         /// Repertoire de sortie
         #[arg(short, long, default_value = "output/specs")]
         output: String,
@@ -48,25 +60,49 @@ enum Commands {
         /// Fichier de constitution (principes du projet)
         #[arg(long)]
         constitution: Option<String>,
+
+        /// Stories quasi-dupliquees entre fichiers (remplace pipeline.dedupe)
+        #[arg(long, value_parser = ["report", "keep-first", "merge-criteria"])]
+        dedupe: Option<String>,
     },
 
     /// Generer des tests Gherkin/BDD depuis une specification
     GenerateTests {
-        /// Fichier de specification (Markdown)
+        /// Specification au format JSON
         #[arg(short, long)]
         spec: String,
 
-        /// Repertoire de sortie pour les .feature
-        #[arg(short, long, default_value = "output/features")]
+        /// Suite de tests existante (JSON) dans laquelle fusionner les scenarios regeneres
+        #[arg(short, long)]
+        tests: Option<String>,
+
+        /// Ne regenerer que ces exigences (ex: FR-003,FR-017)
+        #[arg(long, value_delimiter = ',', requires = "tests")]
+        only: Vec<String>,
+
+        /// Ne regenerer que les exigences de cette priorite (P1, P2, P3)
+        #[arg(long, requires = "tests")]
+        priority: Option<String>,
+
+        /// Ne regenerer que les exigences sans scenario dans la suite existante
+        #[arg(long, requires = "tests")]
+        uncovered: bool,
+
+        /// Repertoire de sortie (features/, test_suite.json)
+        #[arg(short, long, default_value = "output")]
         output: String,
     },
 
     /// Pipeline complet: US -> Specs -> Gherkin
     Pipeline {
-        /// Fichier(s) ou dossier(s) d'entree (User Stories)
+        /// Fichier(s), dossier(s) ou glob(s) d'entree (User Stories), `-` pour stdin
         #[arg(short, long, num_args = 1..)]
         input: Vec<String>,
 
+        /// Motif(s) d'exclusion des entrees (syntaxe .specforgeignore)
+        #[arg(long, num_args = 1..)]
+        exclude: Vec<String>,
+
         /// Repertoire de sortie
         #[arg(short, long, default_value = "output")]
         output: String,
@@ -74,6 +110,151 @@ enum Commands {
         /// Fichier de constitution
         #[arg(long)]
         constitution: Option<String>,
+
+        /// Une specification et un dossier de features par epic
+        #[arg(long)]
+        by_epic: bool,
+
+        /// Stories quasi-dupliquees entre fichiers (remplace pipeline.dedupe)
+        #[arg(long, value_parser = ["report", "keep-first", "merge-criteria"])]
+        dedupe: Option<String>,
+
+        /// Supprimer les artefacts d'un run precedent qui ne sont plus produits
+        #[arg(long)]
+        clean: bool,
+    },
+
+    /// Traduire une specification et ses tests (JSON) dans une autre langue
+    Translate {
+        /// Specification au format JSON
+        #[arg(short, long)]
+        spec: String,
+
+        /// Suite de tests au format JSON
+        #[arg(short, long)]
+        tests: String,
+
+        /// Langue cible (fr, en, es, de)
+        #[arg(long)]
+        to: String,
+
+        /// Repertoire de sortie
+        #[arg(short, long, default_value = "output")]
+        output: String,
+    },
+
+    /// Generer les squelettes de step definitions Cucumber d'une suite de tests
+    ScaffoldSteps {
+        /// Suite de tests au format JSON
+        #[arg(short, long)]
+        tests: String,
+
+        /// Specification au format JSON (entites pour --group-by entity)
+        #[arg(short, long)]
+        spec: Option<String>,
+
+        /// Langage cible (rust, java, typescript, python)
+        #[arg(long, value_parser = ["rust", "java", "typescript", "python"])]
+        lang: String,
+
+        /// Un fichier de steps par feature ou par entite
+        #[arg(long, default_value = "feature", value_parser = ["feature", "entity"])]
+        group_by: String,
+
+        /// Repertoire de sortie (les steps deja presents sont conserves)
+        #[arg(short, long, default_value = "output/steps")]
+        output: String,
+    },
+
+    /// Exporter les cas de test vers Xray (JSON), TestRail (CSV) ou JUnit (XML)
+    ExportTests {
+        /// Suite de tests au format JSON
+        #[arg(short, long)]
+        tests: String,
+
+        /// Format(s) d'export
+        #[arg(short, long, num_args = 1.., required = true, value_parser = ["xray", "testrail", "junit"])]
+        format: Vec<String>,
+
+        /// Repertoire de sortie
+        #[arg(short, long, default_value = "output/exports")]
+        output: String,
+    },
+
+    /// Analyser l'impact d'un changement d'exigences sur les tests existants
+    Impact {
+        /// Ancienne specification (JSON)
+        #[arg(long)]
+        old: String,
+
+        /// Nouvelle specification (JSON)
+        #[arg(long)]
+        new: String,
+
+        /// Suite de tests actuelle (JSON)
+        #[arg(short, long)]
+        tests: String,
+
+        /// Regenerer les scenarios des exigences impactees et les reinjecter
+        #[arg(long)]
+        regenerate: bool,
+
+        /// Repertoire de sortie (impact.md, features/, test_suite.json)
+        #[arg(short, long, default_value = "output/impact")]
+        output: String,
+    },
+
+    /// Verifier les exigences a partir des resultats d'execution Cucumber
+    Verify {
+        /// Resultats d'execution (Cucumber JSON, JUnit XML ou sortie cucumber-rs)
+        #[arg(short, long)]
+        results: String,
+
+        /// Specification au format JSON
+        #[arg(short, long)]
+        spec: String,
+
+        /// Suite de tests au format JSON
+        #[arg(short, long)]
+        tests: String,
+
+        /// Repertoire de sortie (verification.md)
+        #[arg(short, long, default_value = "output")]
+        output: String,
+
+        /// Code de sortie non nul si une exigence est en echec
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Generer un rapport HTML autonome (spec, features, tracabilite, metriques)
+    Report {
+        /// Specification au format JSON
+        #[arg(short, long)]
+        spec: String,
+
+        /// Suite de tests au format JSON
+        #[arg(short, long)]
+        tests: String,
+
+        /// Repertoire de sortie
+        #[arg(short, long, default_value = "output")]
+        output: String,
+    },
+
+    /// Verifier les User Stories (heuristiques INVEST) sans appel LLM
+    LintStories {
+        /// Fichier(s), dossier(s) ou glob(s) d'entree (User Stories), `-` pour stdin
+        #[arg(short, long, num_args = 1..)]
+        input: Vec<String>,
+
+        /// Motif(s) d'exclusion des entrees (syntaxe .specforgeignore)
+        #[arg(long, num_args = 1..)]
+        exclude: Vec<String>,
+
+        /// Code de sortie non nul aussi sur les avertissements
+        #[arg(long)]
+        strict: bool,
     },
 
     /// Verifier la connectivite LLM
@@ -85,7 +266,7 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     // Charger la config
-    let config = Config::load().unwrap_or_else(|_| {
+    let mut config = Config::load().unwrap_or_else(|_| {
         eprintln!(
             "{} config.yaml non trouve, utilisation des valeurs par defaut",
             style("!").yellow()
@@ -123,11 +304,305 @@ async fn main() -> Result<()> {
         Commands::Check => {
             check_llm(&config).await?;
         }
+        Commands::LintStories {
+            input,
+            exclude,
+            strict,
+        } => {
+            config.paths.exclude.extend(exclude);
+            let pipeline = create_pipeline(&config)?;
+            let input_paths: Vec<PathBuf> = input.iter().map(PathBuf::from).collect();
+
+            println!(
+                "{} Lint INVEST de {} fichier(s) ...",
+                style(">>").cyan().bold(),
+                style(input_paths.len()).green()
+            );
+
+            let report = pipeline.lint_stories(&input_paths).await?;
+            for issue in &report.issues {
+                let severity = match issue.severity {
+                    LintSeverity::Error => style("erreur").red().bold(),
+                    LintSeverity::Warning => style("avertissement").yellow().bold(),
+                };
+                println!(
+                    "  {} [{}] {}{}: {} ({})",
+                    severity,
+                    issue.rule.code(),
+                    style(&issue.story).cyan(),
+                    issue
+                        .source_file
+                        .as_deref()
+                        .map(|f| format!(" ({})", f))
+                        .unwrap_or_default(),
+                    issue.message,
+                    issue.rule.invest_criterion()
+                );
+            }
+
+            println!();
+            println!(
+                "{} {} stories, {} erreur(s), {} avertissement(s)",
+                if report.is_failure(strict) {
+                    style("!!").red().bold()
+                } else {
+                    style("OK").green().bold()
+                },
+                report.stories_checked,
+                report.error_count(),
+                report.warning_count()
+            );
+
+            if report.is_failure(strict) {
+                std::process::exit(1);
+            }
+        }
+        Commands::Translate {
+            spec,
+            tests,
+            to,
+            output,
+        } => {
+            let Some(language) = Language::parse_code(&to) else {
+                anyhow::bail!("Langue cible inconnue: {}. Acceptees: fr, en, es, de", to);
+            };
+            let specification: Specification = load_json(&spec).await?;
+            let test_suite: TestSuite = load_json(&tests).await?;
+            let pipeline = create_pipeline(&config)?;
+
+            println!(
+                "{} Traduction de {} exigences et {} scenarios vers {} ...",
+                style(">>").cyan().bold(),
+                specification.functional_requirements.len(),
+                test_suite.total_scenarios,
+                style(language).green()
+            );
+
+            let result = pipeline
+                .translate(
+                    &specification,
+                    &test_suite,
+                    language,
+                    &PathBuf::from(&output),
+                )
+                .await?;
+
+            println!(
+                "{} Traduction terminee, IDs et tags verifies",
+                style("OK").green().bold()
+            );
+            println!(
+                "  Specification: {}",
+                style(result.spec_path.display()).cyan()
+            );
+            for path in result.feature_paths.iter().chain(&result.json_paths) {
+                println!("  - {}", style(path.display()).cyan());
+            }
+        }
+        Commands::ExportTests {
+            tests,
+            format,
+            output,
+        } => {
+            let formats: Vec<TestExportFormat> = format
+                .iter()
+                .filter_map(|code| TestExportFormat::from_code(code))
+                .collect();
+            let test_suite: TestSuite = load_json(&tests).await?;
+            let pipeline = create_pipeline(&config)?;
+
+            println!(
+                "{} Export de {} scenarios ...",
+                style(">>").cyan().bold(),
+                test_suite.total_scenarios
+            );
+
+            let paths = pipeline
+                .export_tests(&test_suite, &formats, &PathBuf::from(&output))
+                .await?;
+
+            println!("{} Export termine", style("OK").green().bold());
+            for path in &paths {
+                println!("  - {}", style(path.display()).cyan());
+            }
+        }
+        Commands::Impact {
+            old,
+            new,
+            tests,
+            regenerate,
+            output,
+        } => {
+            let old_spec: Specification = load_json(&old).await?;
+            let new_spec: Specification = load_json(&new).await?;
+            let test_suite: TestSuite = load_json(&tests).await?;
+            let pipeline = create_pipeline(&config)?;
+
+            let result = pipeline
+                .impact(
+                    &old_spec,
+                    &new_spec,
+                    &test_suite,
+                    regenerate,
+                    &PathBuf::from(&output),
+                )
+                .await?;
+
+            let report = &result.report;
+            println!(
+                "{} {} exigence(s) modifiee(s), {} scenario(s) impacte(s)",
+                style(">>").cyan().bold(),
+                report.changes.len(),
+                report.scenarios.len()
+            );
+            for scenario in &report.scenarios {
+                println!(
+                    "  [{}] {} / {} ({})",
+                    scenario.action,
+                    scenario.feature,
+                    scenario.scenario,
+                    scenario.requirements.join(", ")
+                );
+            }
+            println!("  Rapport: {}", style(result.report_path.display()).cyan());
+            if let Some(updated) = &result.test_suite {
+                println!(
+                    "{} Suite mise a jour: {} scenarios",
+                    style("OK").green().bold(),
+                    updated.total_scenarios
+                );
+                for path in &result.written_paths {
+                    println!("  - {}", style(path.display()).cyan());
+                }
+            }
+        }
+        Commands::Verify {
+            results,
+            spec,
+            tests,
+            output,
+            strict,
+        } => {
+            let specification: Specification = load_json(&spec).await?;
+            let test_suite: TestSuite = load_json(&tests).await?;
+            let pipeline = create_pipeline(&config)?;
+
+            let result = pipeline
+                .verify(
+                    &specification,
+                    &test_suite,
+                    &PathBuf::from(&results),
+                    &PathBuf::from(&output),
+                )
+                .await?;
+
+            let summary = &result.summary;
+            println!(
+                "{} Verifiees: {} | Echec: {} | Incompletes: {} | Non executees: {}",
+                style(">>").cyan().bold(),
+                summary.verified,
+                summary.failed,
+                summary.incomplete,
+                summary.not_executed
+            );
+            if !summary.unmatched_results.is_empty() {
+                println!(
+                    "  {} resultat(s) sans scenario correspondant",
+                    summary.unmatched_results.len()
+                );
+            }
+            println!("  Rapport: {}", style(result.report_path.display()).cyan());
+
+            if strict && !result.failed_requirements.is_empty() {
+                anyhow::bail!(
+                    "Exigence(s) en echec: {}",
+                    result.failed_requirements.join(", ")
+                );
+            }
+        }
+        Commands::Report {
+            spec,
+            tests,
+            output,
+        } => {
+            let specification: Specification = load_json(&spec).await?;
+            let test_suite: TestSuite = load_json(&tests).await?;
+            let pipeline = create_pipeline(&config)?;
+
+            let path = pipeline
+                .write_html_report(&specification, &test_suite, &PathBuf::from(&output))
+                .await?;
+
+            println!(
+                "{} Rapport HTML: {}",
+                style("OK").green().bold(),
+                style(path.display()).cyan()
+            );
+        }
+        Commands::ScaffoldSteps {
+            tests,
+            spec,
+            lang,
+            group_by,
+            output,
+        } => {
+            let Some(target) = StepTarget::from_code(&lang) else {
+                anyhow::bail!(
+                    "Langage inconnu: {}. Acceptes: rust, java, typescript, python",
+                    lang
+                );
+            };
+            let grouping = StepGrouping::from_code(&group_by).unwrap_or_default();
+            let test_suite: TestSuite = load_json(&tests).await?;
+            let specification: Option<Specification> = match &spec {
+                Some(path) => Some(load_json(path).await?),
+                None => None,
+            };
+            let pipeline = create_pipeline(&config)?;
+
+            println!(
+                "{} Squelettes de steps {} pour {} scenarios ...",
+                style(">>").cyan().bold(),
+                style(target).green(),
+                test_suite.total_scenarios
+            );
+
+            let result = pipeline
+                .scaffold_steps(
+                    specification.as_ref(),
+                    &test_suite,
+                    target,
+                    grouping,
+                    &PathBuf::from(&output),
+                )
+                .await?;
+
+            println!(
+                "{} {} step definitions distinctes dans {} fichier(s)",
+                style("OK").green().bold(),
+                result.definitions,
+                result.files.len()
+            );
+            for file in &result.files {
+                println!(
+                    "  - {} ({} ajoute(s), {} conserve(s))",
+                    style(file.path.display()).cyan(),
+                    file.added,
+                    file.preserved
+                );
+            }
+        }
         Commands::Refine {
             input,
             output,
+            exclude,
             constitution,
+            dedupe,
         } => {
+            config.paths.exclude.extend(exclude);
+            if let Some(dedupe) = dedupe {
+                config.pipeline.dedupe = dedupe;
+            }
             let pipeline = create_pipeline(&config)?;
             let constitution_text = load_constitution(constitution.as_deref()).await?;
             let input_paths: Vec<PathBuf> = input.iter().map(PathBuf::from).collect();
@@ -165,30 +640,164 @@ async fn main() -> Result<()> {
                 );
             }
         }
-        Commands::GenerateTests { spec, output: _ } => {
+        Commands::GenerateTests {
+            spec,
+            tests,
+            only,
+            priority,
+            uncovered,
+            output,
+        } => {
+            let priority = match priority {
+                Some(code) => Some(Priority::from_code(&code).with_context(|| {
+                    format!("Priorite inconnue: {}. Acceptees: P1, P2, P3", code)
+                })?),
+                None => None,
+            };
+            let selection = RequirementSelection {
+                only,
+                priority,
+                uncovered,
+            };
+            let specification: Specification = load_json(&spec).await?;
+            let pipeline = create_pipeline(&config)?;
+            let output_dir = PathBuf::from(&output);
+
             println!(
                 "{} Generation de tests depuis {} ...",
                 style(">>").cyan().bold(),
                 style(&spec).green()
             );
 
-            // Pour cette commande, on devrait lire la spec depuis le fichier
-            // Pour l'instant, on indique que c'est un TODO
-            println!(
-                "{} La commande generate-tests standalone sera implementee prochainement.",
-                style("!").yellow()
-            );
-            println!("  Utilisez 'spec-forge pipeline' pour le flux complet.");
+            match tests {
+                Some(tests) if !selection.is_empty() => {
+                    let test_suite: TestSuite = load_json(&tests).await?;
+                    let result = pipeline
+                        .regenerate_requirements(
+                            &specification,
+                            &test_suite,
+                            &selection,
+                            &output_dir,
+                        )
+                        .await?;
+                    println!(
+                        "{} {} exigence(s) regeneree(s): {} scenario(s) remplace(s), {} ajoute(s)",
+                        style("OK").green().bold(),
+                        result.requirements.len(),
+                        result.outcome.replaced,
+                        result.outcome.added
+                    );
+                    if !result.requirements.is_empty() {
+                        println!("  Exigences: {}", result.requirements.join(", "));
+                    }
+                    for name in &result.outcome.shared {
+                        println!(
+                            "  {} Conserve (verifie aussi d'autres exigences): {}",
+                            style("!").yellow(),
+                            name
+                        );
+                    }
+                    for path in &result.written_paths {
+                        println!("  - {}", style(path.display()).cyan());
+                    }
+                }
+                _ => {
+                    let test_suite = pipeline
+                        .generate_tests(&specification, &output_dir.join("features"))
+                        .await?;
+                    let suite_path = output_dir.join("test_suite.json");
+                    tokio::fs::write(&suite_path, serde_json::to_string_pretty(&test_suite)?)
+                        .await
+                        .with_context(|| format!("Ecriture de {}", suite_path.display()))?;
+                    println!(
+                        "{} {} scenarios generes ({:.0}% de couverture)",
+                        style("OK").green().bold(),
+                        test_suite.total_scenarios,
+                        test_suite.coverage.coverage_percentage
+                    );
+                    println!("  - {}", style(suite_path.display()).cyan());
+                }
+            }
         }
         Commands::Pipeline {
             input,
             output,
+            exclude,
             constitution,
+            by_epic,
+            dedupe,
+            clean,
         } => {
+            config.paths.exclude.extend(exclude);
+            config.output.clean_stale |= clean;
+            if let Some(dedupe) = dedupe {
+                config.pipeline.dedupe = dedupe;
+            }
             let pipeline = create_pipeline(&config)?;
             let constitution_text = load_constitution(constitution.as_deref()).await?;
             let input_paths: Vec<PathBuf> = input.iter().map(PathBuf::from).collect();
 
+            if by_epic {
+                println!(
+                    "{} Pipeline par epic: {} fichier(s) -> specs -> gherkin",
+                    style(">>").cyan().bold(),
+                    style(input_paths.len()).green()
+                );
+
+                let result = pipeline
+                    .run_by_epic(
+                        &input_paths,
+                        &PathBuf::from(&output),
+                        constitution_text.as_deref(),
+                    )
+                    .await?;
+
+                println!();
+                println!(
+                    "{} Pipeline termine avec succes! ({} epic(s))",
+                    style("OK").green().bold(),
+                    result.epics.len()
+                );
+                for epic in &result.epics {
+                    println!();
+                    println!("  Epic: {}", style(&epic.epic).bold());
+                    println!(
+                        "  - Specification: {}",
+                        style(epic.spec_path.display()).cyan()
+                    );
+                    if let Some(docx_path) = epic
+                        .spec_docx_path
+                        .as_ref()
+                        .filter(|p| **p != epic.spec_path)
+                    {
+                        println!(
+                            "  - Specification DOCX: {}",
+                            style(docx_path.display()).cyan()
+                        );
+                    }
+                    println!(
+                        "  - {} exigences, {} scenarios Gherkin dans {} fichier(s)",
+                        epic.specification.functional_requirements.len(),
+                        epic.test_suite.total_scenarios,
+                        epic.feature_paths.len()
+                    );
+                    if let Some(report_path) = &epic.report_path {
+                        println!("  - Rapport HTML: {}", style(report_path.display()).cyan());
+                    }
+                    print_manual_edits(&epic.manual_edits);
+                }
+                println!();
+                println!("  Index: {}", style(result.index_path.display()).cyan());
+                print_manifest(&result.manifest);
+                if !result.cross_references.is_empty() {
+                    println!(
+                        "  - {} reference(s) inter-epics",
+                        result.cross_references.len()
+                    );
+                }
+                return Ok(());
+            }
+
             println!(
                 "{} Pipeline complet: {} fichier(s) -> specs -> gherkin",
                 style(">>").cyan().bold(),
@@ -213,6 +822,16 @@ async fn main() -> Result<()> {
                 "  Specification: {}",
                 style(result.spec_path.display()).cyan()
             );
+            if let Some(docx_path) = result
+                .spec_docx_path
+                .as_ref()
+                .filter(|p| **p != result.spec_path)
+            {
+                println!(
+                    "  Specification DOCX: {}",
+                    style(docx_path.display()).cyan()
+                );
+            }
             println!(
                 "  - {} scenarios utilisateur",
                 result.specification.user_scenarios.len()
@@ -237,11 +856,32 @@ async fn main() -> Result<()> {
                 "  - Couverture: {:.0}%",
                 result.test_suite.coverage.coverage_percentage
             );
+            print_manual_edits(&result.manual_edits);
 
             if let Some(trace_path) = &result.traceability_path {
                 println!();
                 println!("  Tracabilite: {}", style(trace_path.display()).cyan());
             }
+            if let Some(glossary_path) = &result.glossary_path {
+                println!(
+                    "  Glossaire des etapes: {}",
+                    style(glossary_path.display()).cyan()
+                );
+            }
+            for path in &result.export_paths {
+                println!("  Export: {}", style(path.display()).cyan());
+            }
+            if let Some(report_path) = &result.report_path {
+                println!("  Rapport HTML: {}", style(report_path.display()).cyan());
+            }
+            for artifact in result
+                .artifacts
+                .iter()
+                .filter(|a| a.kind == ArtifactKind::Json)
+            {
+                println!("  JSON: {}", style(artifact.path.display()).cyan());
+            }
+            print_manifest(&result.manifest);
         }
     }
 
@@ -250,7 +890,7 @@ async fn main() -> Result<()> {
 
 fn create_pipeline(config: &Config) -> Result<Pipeline> {
     let llm = Arc::new(OllamaAdapter::new(config.llm.clone())?);
-    let templates = Arc::new(FileTemplateEngine::new(&config.templates.directory)?);
+    let templates = Arc::new(FileTemplateEngine::from_config(&config.templates)?);
     Ok(Pipeline::new(llm, templates, config.clone()))
 }
 
@@ -305,6 +945,57 @@ async fn check_llm(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Affiche le manifeste et les artefacts obsoletes (supprimes ou conserves)
+fn print_manifest(manifest: &ManifestOutcome) {
+    println!(
+        "  Manifeste: {}",
+        style(manifest.manifest_path.display()).cyan()
+    );
+    for path in &manifest.removed_paths {
+        println!("  - Supprime (obsolete): {}", path.display());
+    }
+    if !manifest.stale_paths.is_empty() {
+        println!(
+            "  {} {} artefact(s) obsolete(s) conserve(s) (--clean pour les supprimer s'ils sont intacts):",
+            style("!").yellow(),
+            manifest.stale_paths.len()
+        );
+        for path in &manifest.stale_paths {
+            println!("    - {}", path.display());
+        }
+    }
+}
+
+/// Signale les scenarios retouches a la main conserves et les conflits
+fn print_manual_edits(outcomes: &[FeatureWriteOutcome]) {
+    for outcome in outcomes {
+        let Some(report) = &outcome.merge else {
+            continue;
+        };
+        println!(
+            "  {} {}: {} scenario(s) retouche(s) conserve(s)",
+            style("!").yellow(),
+            outcome.path.display(),
+            report.preserved.len()
+        );
+        for conflict in &report.conflicts {
+            println!(
+                "    {} Conflit \"{}\": {}",
+                style("!").yellow().bold(),
+                conflict.scenario,
+                conflict.reason
+            );
+        }
+    }
+}
+
+async fn load_json<T: serde::de::DeserializeOwned>(path: &str) -> Result<T> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Lecture de {}", path))?;
+    serde_json::from_str(&content).with_context(|| format!("JSON invalide: {}", path))
+}
+
 async fn load_constitution(path: Option<&str>) -> Result<Option<String>> {
     match path {
         Some(p) => {
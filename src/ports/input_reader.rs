@@ -17,4 +17,10 @@ pub trait InputReader: Send + Sync {
 
     /// Extensions de fichier supportees
     fn supported_extensions(&self) -> &[&str];
+
+    /// Reconnait un fichier a son contenu quand l'extension est inconnue
+    /// (`head` : premiers octets du fichier)
+    fn sniff(&self, _path: &Path, _head: &[u8]) -> bool {
+        false
+    }
 }
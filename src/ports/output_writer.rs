@@ -1,33 +1,101 @@
 //! Port OutputWriter - Interface pour l'ecriture des sorties
+//!
+//! Le pipeline appelle chaque writer configure (`output.formats`) puis les
+//! writers injectes par l'application (`Pipeline::with_writer`), une fois la
+//! specification raffinee et la suite de tests generee.
 
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
 
+use crate::domain::feature_merge::FeatureMergeReport;
+use crate::domain::output_manifest::ArtifactKind;
 use crate::domain::specification::Specification;
 use crate::domain::test_case::TestSuite;
 
-/// Trait pour l'ecriture des specifications et tests
+/// Dossiers de destination des sorties d'un run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputLayout {
+    /// Dossier de sortie (racine du manifeste)
+    pub root: PathBuf,
+    pub specs_dir: PathBuf,
+    pub features_dir: PathBuf,
+    pub traceability_dir: PathBuf,
+    pub exports_dir: PathBuf,
+    pub report_dir: PathBuf,
+    /// `specification.json` et `test_suite.json`
+    pub data_dir: PathBuf,
+}
+
+impl OutputLayout {
+    /// Arborescence de `full` : rapports a la racine du dossier de sortie
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            specs_dir: root.join("specs"),
+            features_dir: root.join("features"),
+            traceability_dir: root.to_path_buf(),
+            exports_dir: root.join("exports"),
+            report_dir: root.to_path_buf(),
+            data_dir: root.to_path_buf(),
+        }
+    }
+
+    /// Arborescence d'un epic (`full --by-epic`) : un sous-dossier par epic,
+    /// les specs restent cote a cote dans `specs/`
+    pub fn for_epic(root: &Path, epic_dir: &str) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            specs_dir: root.join("specs"),
+            features_dir: root.join("features").join(epic_dir),
+            traceability_dir: root.join("traceability").join(epic_dir),
+            exports_dir: root.join("exports").join(epic_dir),
+            report_dir: root.join("reports").join(epic_dir),
+            data_dir: root.join("data").join(epic_dir),
+        }
+    }
+}
+
+/// Fichier ecrit par un writer
+#[derive(Debug, Clone)]
+pub struct WrittenArtifact {
+    pub path: PathBuf,
+    pub kind: ArtifactKind,
+    /// User Stories a l'origine de l'artefact (inscrites au manifeste)
+    pub source_ids: Vec<String>,
+    /// Bilan de fusion d'un `.feature` retouche a la main
+    pub merge: Option<FeatureMergeReport>,
+}
+
+impl WrittenArtifact {
+    pub fn new(path: PathBuf, kind: ArtifactKind, source_ids: Vec<String>) -> Self {
+        Self {
+            path,
+            kind,
+            source_ids,
+            merge: None,
+        }
+    }
+
+    /// Artefact couvrant toute la specification (toutes ses User Stories)
+    pub fn for_spec(path: PathBuf, kind: ArtifactKind, spec: &Specification) -> Self {
+        let source_ids = spec.user_scenarios.iter().map(|s| s.id.clone()).collect();
+        Self::new(path, kind, source_ids)
+    }
+}
+
+/// Trait pour l'ecriture d'un format de sortie
 #[async_trait]
 pub trait OutputWriter: Send + Sync {
-    /// Ecrit une specification raffinee en Markdown
-    async fn write_specification(
-        &self,
-        spec: &Specification,
-        output_dir: &Path,
-    ) -> Result<PathBuf, anyhow::Error>;
-
-    /// Ecrit les fichiers .feature Gherkin
-    async fn write_features(
-        &self,
-        test_suite: &TestSuite,
-        output_dir: &Path,
-    ) -> Result<Vec<PathBuf>, anyhow::Error>;
+    /// Code du format produit (ex: "markdown", "xray")
+    fn format(&self) -> &str;
 
-    /// Ecrit le rapport de tracabilite
-    async fn write_traceability(
+    /// Ecrit la specification et sa suite de tests dans `layout` ; les
+    /// fichiers retournes sont inscrits au manifeste s'ils sont sous
+    /// `layout.root`
+    async fn write_outputs(
         &self,
         spec: &Specification,
-        test_suite: &TestSuite,
-        output_dir: &Path,
-    ) -> Result<PathBuf, anyhow::Error>;
+        suite: &TestSuite,
+        layout: &OutputLayout,
+    ) -> Result<Vec<WrittenArtifact>, anyhow::Error>;
 }
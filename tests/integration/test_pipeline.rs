@@ -1,10 +1,21 @@
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use async_trait::async_trait;
 use spec_forge::adapters::llm::mock_adapter::MockLlmAdapter;
 use spec_forge::adapters::templates::file_template_engine::FileTemplateEngine;
 use spec_forge::application::pipeline::Pipeline;
+use spec_forge::domain::errors::{DomainError, InputError};
+use spec_forge::domain::output_manifest::{ArtifactKind, OutputManifest};
+use spec_forge::domain::regeneration::RequirementSelection;
+use spec_forge::domain::specification::Specification;
+use spec_forge::domain::step_definitions::{StepGrouping, StepTarget};
+use spec_forge::domain::test_case::{Scenario, ScenarioType, TestExportFormat, TestSuite};
+use spec_forge::domain::translation::translatable_segments;
+use spec_forge::domain::user_story::{Language, UserStory, UserStorySet};
 use spec_forge::infrastructure::config::Config;
+use spec_forge::ports::input_reader::InputReader;
+use spec_forge::ports::output_writer::{OutputLayout, OutputWriter, WrittenArtifact};
 
 fn mock_refine_response() -> String {
     std::fs::read_to_string("tests/fixtures/mock_refine_response.json").unwrap()
@@ -45,6 +56,64 @@ async fn test_pipeline_read_stories_yaml() {
     assert_eq!(story_set.stories.len(), 2);
 }
 
+#[tokio::test]
+async fn test_pipeline_read_stories_unsupported_format() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("backlog.bin");
+    std::fs::write(&path, [0u8, 1, 2, 3]).unwrap();
+
+    let pipeline = create_test_pipeline(vec![]);
+    let err = pipeline.read_stories(&path).await.unwrap_err();
+    assert!(matches!(
+        err,
+        DomainError::Input(InputError::UnsupportedFormat { .. })
+    ));
+}
+
+/// Lecteur d'une bibliotheque cliente : une story par ligne `.txt`
+struct LineReader;
+
+#[async_trait]
+impl InputReader for LineReader {
+    async fn read_stories(&self, path: &Path) -> Result<UserStorySet, InputError> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| InputError::ReadError(e.to_string()))?;
+        let stories = content
+            .lines()
+            .map(|l| UserStory::new(l.into(), "user".into(), l.into(), "ship".into()))
+            .collect();
+        Ok(UserStorySet {
+            stories,
+            source_files: vec![path.display().to_string()],
+            language: Language::English,
+        })
+    }
+
+    fn detect_language(&self, _content: &str) -> Language {
+        Language::English
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &["txt"]
+    }
+}
+
+#[tokio::test]
+async fn test_pipeline_custom_reader() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("backlog.txt");
+    std::fs::write(&path, "export data\nimport data\n").unwrap();
+
+    let pipeline = create_test_pipeline(vec![]).with_reader(Arc::new(LineReader));
+    let sets = pipeline
+        .read_story_sets(&[dir.path().to_path_buf()])
+        .await
+        .unwrap();
+    assert_eq!(sets.len(), 1);
+    assert_eq!(sets[0].stories.len(), 2);
+    assert_eq!(sets[0].stories[1].action, "import data");
+}
+
 #[tokio::test]
 async fn test_pipeline_refine() {
     let pipeline = create_test_pipeline(vec![mock_refine_response()]);
@@ -98,3 +167,738 @@ async fn test_pipeline_full_run() {
     assert!(pipeline_result.traceability_path.is_some());
     assert!(pipeline_result.traceability_path.unwrap().exists());
 }
+
+#[tokio::test]
+async fn test_pipeline_full_run_keeps_specification_when_generation_fails() {
+    let mut config = Config::default();
+    config.output.formats = vec!["markdown".into(), "docx".into(), "json".into()];
+    let pipeline = Pipeline::new(
+        Arc::new(MockLlmAdapter::new(vec![
+            mock_refine_response(),
+            "reponse invalide".into(),
+        ])),
+        Arc::new(FileTemplateEngine::new(Path::new("templates")).unwrap()),
+        config,
+    );
+    let dir = tempfile::TempDir::new().unwrap();
+
+    let result = pipeline
+        .run_full(
+            &[PathBuf::from("tests/fixtures/sample_us_fr.md")],
+            dir.path(),
+            None,
+        )
+        .await;
+
+    assert!(
+        matches!(result, Err(DomainError::Generation(_))),
+        "Generation aurait du echouer: {:?}",
+        result.map(|r| r.test_suite.total_scenarios)
+    );
+    // La specification raffinee n'est pas perdue
+    let specs: Vec<PathBuf> = std::fs::read_dir(dir.path().join("specs"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert!(
+        specs
+            .iter()
+            .any(|p| p.extension().is_some_and(|e| e == "md"))
+    );
+    assert!(
+        specs
+            .iter()
+            .any(|p| p.extension().is_some_and(|e| e == "docx"))
+    );
+    let json = std::fs::read_to_string(dir.path().join("specification.json")).unwrap();
+    let spec: Specification = serde_json::from_str(&json).unwrap();
+    assert_eq!(spec.functional_requirements.len(), 3);
+    assert!(!dir.path().join("features").exists());
+}
+
+#[tokio::test]
+async fn test_pipeline_translate_to_english() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let fr = create_test_pipeline(vec![mock_refine_response(), mock_generate_response()])
+        .run_full(
+            &[PathBuf::from("tests/fixtures/sample_us_fr.md")],
+            &dir.path().join("fr"),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Traduction simulee : chaque segment est prefixe, jetons proteges intacts
+    let segments: Vec<serde_json::Value> = translatable_segments(&fr.specification, &fr.test_suite)
+        .into_iter()
+        .map(|s| serde_json::json!({ "key": s.key, "text": format!("EN {}", s.text) }))
+        .collect();
+    let response = serde_json::json!({ "segments": segments }).to_string();
+
+    let mut config = Config::default();
+    config.pipeline.token_budget = 100_000;
+    let llm = Arc::new(MockLlmAdapter::new(vec![response]));
+    let templates = Arc::new(FileTemplateEngine::new(Path::new("templates")).unwrap());
+    let result = Pipeline::new(llm, templates, config)
+        .translate(
+            &fr.specification,
+            &fr.test_suite,
+            Language::English,
+            &dir.path().join("en"),
+        )
+        .await
+        .unwrap();
+
+    assert!(
+        result.specification.functional_requirements[0]
+            .statement
+            .starts_with("EN ")
+    );
+    assert_eq!(
+        result.test_suite.features[0].tags,
+        fr.test_suite.features[0].tags
+    );
+    assert_eq!(result.json_paths.len(), 2);
+    let feature = std::fs::read_to_string(&result.feature_paths[0]).unwrap();
+    assert!(feature.starts_with("# language: en"));
+    assert!(feature.contains("Feature: EN "));
+}
+
+#[tokio::test]
+async fn test_pipeline_writes_step_glossary() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let result = create_test_pipeline(vec![mock_refine_response(), mock_generate_response()])
+        .run_full(
+            &[PathBuf::from("tests/fixtures/sample_us_fr.md")],
+            dir.path(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let glossary_path = result.glossary_path.expect("glossaire attendu par defaut");
+    let glossary = std::fs::read_to_string(glossary_path).unwrap();
+    assert!(glossary.starts_with("# Glossaire des etapes"));
+    assert!(glossary.contains("## Vocabulaire canonique"));
+}
+
+#[tokio::test]
+async fn test_pipeline_hoists_common_given_into_background() {
+    let dir = tempfile::TempDir::new().unwrap();
+    // Par defaut, les scenarios generes sont laisses tels quels
+    let untouched = create_test_pipeline(vec![mock_refine_response(), mock_generate_response()])
+        .run_full(
+            &[PathBuf::from("tests/fixtures/sample_us_fr.md")],
+            &dir.path().join("defaut"),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(
+        untouched.test_suite.features[0]
+            .scenarios
+            .iter()
+            .all(|s| s.steps[0].text == "Un catalogue de livres")
+    );
+
+    let mut config = Config::default();
+    config.pipeline.gherkin_refactor = true;
+    let result = Pipeline::new(
+        Arc::new(MockLlmAdapter::new(vec![
+            mock_refine_response(),
+            mock_generate_response(),
+        ])),
+        Arc::new(FileTemplateEngine::new(Path::new("templates")).unwrap()),
+        config,
+    )
+    .run_full(
+        &[PathBuf::from("tests/fixtures/sample_us_fr.md")],
+        dir.path(),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let feature = &result.test_suite.features[0];
+    let background = feature.background.as_ref().unwrap();
+    assert_eq!(
+        background.steps.last().unwrap().text,
+        "Un catalogue de livres"
+    );
+    assert!(
+        feature
+            .scenarios
+            .iter()
+            .all(|s| s.steps[0].text != "Un catalogue de livres")
+    );
+}
+
+#[tokio::test]
+async fn test_pipeline_scaffold_steps_is_idempotent() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let pipeline = create_test_pipeline(vec![mock_refine_response(), mock_generate_response()]);
+    let result = pipeline
+        .run_full(
+            &[PathBuf::from("tests/fixtures/sample_us_fr.md")],
+            &dir.path().join("out"),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let steps_dir = dir.path().join("steps");
+    let first = pipeline
+        .scaffold_steps(
+            Some(&result.specification),
+            &result.test_suite,
+            StepTarget::Python,
+            StepGrouping::Feature,
+            &steps_dir,
+        )
+        .await
+        .unwrap();
+    assert!(first.definitions > 0);
+    let added: usize = first.files.iter().map(|f| f.added).sum();
+    assert_eq!(added, first.definitions);
+
+    // Un step implemente a la main survit a la regeneration
+    let path = &first.files[0].path;
+    let content = std::fs::read_to_string(path).unwrap();
+    let implemented = content.replacen("raise NotImplementedError", "assert context.ok  #", 1);
+    std::fs::write(path, &implemented).unwrap();
+
+    let second = pipeline
+        .scaffold_steps(
+            Some(&result.specification),
+            &result.test_suite,
+            StepTarget::Python,
+            StepGrouping::Feature,
+            &steps_dir,
+        )
+        .await
+        .unwrap();
+    assert!(second.files.iter().all(|f| f.added == 0));
+    assert_eq!(std::fs::read_to_string(path).unwrap(), implemented);
+}
+
+#[tokio::test]
+async fn test_pipeline_exports_configured_test_formats() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut config = Config::default();
+    config.output.test_exports = vec!["xray".into(), "testrail".into(), "junit".into()];
+    let llm = Arc::new(MockLlmAdapter::new(vec![
+        mock_refine_response(),
+        mock_generate_response(),
+    ]));
+    let templates = Arc::new(FileTemplateEngine::new(Path::new("templates")).unwrap());
+    let result = Pipeline::new(llm, templates, config)
+        .run_full(
+            &[PathBuf::from("tests/fixtures/sample_us_fr.md")],
+            dir.path(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let exports = dir.path().join("exports");
+    assert_eq!(
+        result.export_paths,
+        vec![
+            exports.join(TestExportFormat::Xray.file_name()),
+            exports.join(TestExportFormat::TestRail.file_name()),
+            exports.join(TestExportFormat::JUnit.file_name()),
+        ]
+    );
+
+    let xray: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&result.export_paths[0]).unwrap()).unwrap();
+    let tests = xray.as_array().unwrap();
+    assert_eq!(tests.len(), result.test_suite.total_scenarios);
+    assert!(tests.iter().all(|t| t["testtype"] == "Cucumber"));
+
+    let junit = std::fs::read_to_string(&result.export_paths[2]).unwrap();
+    assert_eq!(
+        junit.matches("<testcase ").count(),
+        result.test_suite.total_scenarios
+    );
+}
+
+#[tokio::test]
+async fn test_pipeline_writes_self_contained_html_report() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut config = Config::default();
+    config.output.html_report = true;
+    let llm = Arc::new(MockLlmAdapter::new(vec![
+        mock_refine_response(),
+        mock_generate_response(),
+    ]));
+    let templates = Arc::new(FileTemplateEngine::new(Path::new("templates")).unwrap());
+    let result = Pipeline::new(llm, templates, config)
+        .run_full(
+            &[PathBuf::from("tests/fixtures/sample_us_fr.md")],
+            dir.path(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let report_path = result.report_path.expect("rapport HTML attendu");
+    let html = std::fs::read_to_string(report_path).unwrap();
+    assert!(html.contains("<style>") && html.contains("<script>"));
+    assert!(!html.contains("<link ") && !html.contains("src=\""));
+    for fr in &result.specification.functional_requirements {
+        assert!(html.contains(&format!("id=\"req-{}\"", fr.id)));
+    }
+    assert_eq!(
+        html.matches("<details id=\"sc-").count(),
+        result.test_suite.total_scenarios
+    );
+}
+
+#[tokio::test]
+async fn test_pipeline_verify_ingests_cucumber_json_results() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let pipeline = create_test_pipeline(vec![mock_refine_response(), mock_generate_response()]);
+    let result = pipeline
+        .run_full(
+            &[PathBuf::from("tests/fixtures/sample_us_fr.md")],
+            dir.path(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Tous les scenarios passent sauf le premier
+    let features: Vec<serde_json::Value> = result
+        .test_suite
+        .features
+        .iter()
+        .enumerate()
+        .map(|(f_idx, feature)| {
+            let elements: Vec<serde_json::Value> = feature
+                .scenarios
+                .iter()
+                .enumerate()
+                .map(|(s_idx, scenario)| {
+                    let status = if f_idx == 0 && s_idx == 0 {
+                        "failed"
+                    } else {
+                        "passed"
+                    };
+                    serde_json::json!({
+                        "type": "scenario",
+                        "name": scenario.name,
+                        "steps": [{"result": {"status": status}}]
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "uri": format!("features/{}.feature", feature.file_stem()),
+                "name": feature.name,
+                "elements": elements
+            })
+        })
+        .collect();
+    let results_path = dir.path().join("cucumber.json");
+    std::fs::write(&results_path, serde_json::to_string(&features).unwrap()).unwrap();
+
+    let verification = pipeline
+        .verify(
+            &result.specification,
+            &result.test_suite,
+            &results_path,
+            dir.path(),
+        )
+        .await
+        .unwrap();
+
+    let first = &result.test_suite.features[0].scenarios[0];
+    assert!(!verification.failed_requirements.is_empty());
+    assert!(
+        verification
+            .failed_requirements
+            .iter()
+            .all(|id| first.verification_of.contains(id)
+                || first.tags.iter().any(|t| t.ends_with(id.as_str())))
+    );
+    assert!(verification.summary.unmatched_results.is_empty());
+    let report = std::fs::read_to_string(&verification.report_path).unwrap();
+    assert!(report.contains("## Verification par execution"));
+    assert!(report.contains("| failed |"));
+}
+
+#[tokio::test]
+async fn test_pipeline_impact_regenerates_only_changed_requirements() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let pipeline = create_test_pipeline(vec![
+        mock_refine_response(),
+        mock_generate_response(),
+        mock_generate_response(),
+    ]);
+    let result = pipeline
+        .run_full(
+            &[PathBuf::from("tests/fixtures/sample_us_fr.md")],
+            dir.path(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let old = result.specification;
+    let mut new = old.clone();
+    let changed_id = new.functional_requirements[0].id.clone();
+    new.functional_requirements[0].statement = "Le systeme DOIT afficher 20 livres par page".into();
+
+    let impact = pipeline
+        .impact(
+            &old,
+            &new,
+            &result.test_suite,
+            true,
+            &dir.path().join("impact"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        impact.report.requirements_to_generate,
+        vec![changed_id.clone()]
+    );
+    assert!(
+        impact
+            .report
+            .scenarios
+            .iter()
+            .any(|s| s.requirements.contains(&changed_id))
+    );
+    let markdown = std::fs::read_to_string(&impact.report_path).unwrap();
+    assert!(markdown.contains(&format!("| {} | Modifiee | statement |", changed_id)));
+
+    let updated = impact.test_suite.expect("suite regeneree");
+    assert_eq!(updated.source_spec_id, new.id);
+    assert!(
+        impact
+            .written_paths
+            .iter()
+            .any(|p| p.ends_with("test_suite.json"))
+    );
+}
+
+#[tokio::test]
+async fn test_pipeline_regenerates_selected_requirements_only() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let pipeline = create_test_pipeline(vec![mock_refine_response(), mock_generate_response()]);
+    let result = pipeline
+        .run_full(
+            &[PathBuf::from("tests/fixtures/sample_us_fr.md")],
+            dir.path(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let mut existing = result.test_suite;
+    let kept = Scenario {
+        name: "Scenario retouche a la main".into(),
+        tags: vec!["FR-002".into()],
+        scenario_type: ScenarioType::HappyPath,
+        steps: vec![],
+        examples: None,
+        test_data_suggestions: vec![],
+        verification_of: vec!["FR-002".into()],
+        coverage_technique: None,
+    };
+    existing.features[0].scenarios.push(kept);
+    let before = existing
+        .features
+        .iter()
+        .flat_map(|f| &f.scenarios)
+        .filter(|s| s.linked_requirements() == vec!["FR-001".to_string()])
+        .count();
+
+    let regenerator = create_test_pipeline(vec![mock_generate_response()]);
+    let selection = RequirementSelection {
+        only: vec!["FR-001".into()],
+        ..Default::default()
+    };
+    let regenerated = regenerator
+        .regenerate_requirements(
+            &result.specification,
+            &existing,
+            &selection,
+            &dir.path().join("regen"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(regenerated.requirements, vec!["FR-001"]);
+    assert_eq!(regenerated.outcome.replaced, before);
+    assert!(regenerated.outcome.added > 0);
+    assert!(
+        regenerated
+            .test_suite
+            .features
+            .iter()
+            .flat_map(|f| &f.scenarios)
+            .any(|s| s.name == "Scenario retouche a la main")
+    );
+    assert!(
+        regenerated
+            .written_paths
+            .iter()
+            .any(|p| p.ends_with("test_suite.json"))
+    );
+}
+
+#[tokio::test]
+async fn test_pipeline_rerun_preserves_hand_edited_scenarios() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let inputs = [PathBuf::from("tests/fixtures/sample_us_fr.md")];
+    let first = create_test_pipeline(vec![mock_refine_response(), mock_generate_response()])
+        .run_full(&inputs, dir.path(), None)
+        .await
+        .unwrap();
+    assert!(first.manual_edits.is_empty());
+
+    let feature_path = &first.feature_paths[0];
+    let original = std::fs::read_to_string(feature_path).unwrap();
+    let step = original
+        .lines()
+        .find(|l| l.trim_start().starts_with("Alors"))
+        .unwrap()
+        .to_string();
+    let edited = original.replacen(&step, &format!("{step} (verifie par la QA)"), 1);
+    std::fs::write(feature_path, &edited).unwrap();
+
+    let second = create_test_pipeline(vec![mock_refine_response(), mock_generate_response()])
+        .run_full(&inputs, dir.path(), None)
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read_to_string(feature_path).unwrap(), edited);
+    assert_eq!(second.manual_edits.len(), 1);
+    let report = second.manual_edits[0].merge.as_ref().unwrap();
+    assert_eq!(report.preserved.len(), 1);
+    assert!(report.conflicts.is_empty());
+}
+
+#[tokio::test]
+async fn test_pipeline_manifest_and_clean_remove_renamed_features() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let inputs = [PathBuf::from("tests/fixtures/sample_us_fr.md")];
+    let first = create_test_pipeline(vec![mock_refine_response(), mock_generate_response()])
+        .run_full(&inputs, dir.path(), None)
+        .await
+        .unwrap();
+    let old_feature = first.feature_paths[0].clone();
+    assert_eq!(
+        old_feature.file_name().unwrap(),
+        "US-001_recherche_d_un_livre_par_isbn_pour_le_bibliothecaire.feature"
+    );
+    let manifest: OutputManifest =
+        serde_json::from_str(&std::fs::read_to_string(&first.manifest.manifest_path).unwrap())
+            .unwrap();
+    let entry = manifest
+        .get("features/US-001_recherche_d_un_livre_par_isbn_pour_le_bibliothecaire.feature")
+        .unwrap();
+    assert_eq!(entry.kind, ArtifactKind::Feature);
+    assert_eq!(entry.source_ids, vec!["US-001"]);
+
+    // Le LLM renomme la feature (accents compris) au run suivant
+    let renamed = mock_generate_response().replace(
+        "Recherche d'un livre par ISBN pour le bibliothecaire",
+        "Recherche d'un ouvrage par ISBN",
+    );
+    let mut config = Config::default();
+    config.output.clean_stale = true;
+    let pipeline = Pipeline::new(
+        Arc::new(MockLlmAdapter::new(vec![mock_refine_response(), renamed])),
+        Arc::new(FileTemplateEngine::new(Path::new("templates")).unwrap()),
+        config,
+    );
+    let second = pipeline.run_full(&inputs, dir.path(), None).await.unwrap();
+
+    assert!(!old_feature.exists());
+    assert_eq!(second.manifest.removed_paths, vec![old_feature]);
+    assert!(second.manifest.stale_paths.is_empty());
+    assert!(
+        dir.path()
+            .join("features/US-001_recherche_d_un_ouvrage_par_isbn.feature")
+            .exists()
+    );
+}
+
+#[tokio::test]
+async fn test_pipeline_strict_story_lint_blocks_before_llm() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let input = dir.path().join("stories.csv");
+    std::fs::write(
+        &input,
+        "title;actor;action;benefit\nExport;client;exporter et imprimer mes releves;\n",
+    )
+    .unwrap();
+
+    let mut config = Config::default();
+    config.pipeline.story_lint = "strict".to_string();
+    // Aucune reponse LLM : le lint doit echouer avant tout appel
+    let llm = Arc::new(MockLlmAdapter::new(vec![]));
+    let templates = Arc::new(FileTemplateEngine::new(Path::new("templates")).unwrap());
+    let pipeline = Pipeline::new(llm, templates, config);
+
+    let inputs = vec![input];
+    let report = pipeline.lint_stories(&inputs).await.unwrap();
+    assert_eq!(report.error_count(), 1);
+    assert!(report.warning_count() >= 1);
+
+    let err = pipeline
+        .refine(&inputs, &dir.path().join("specs"), None)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Lint des user stories"));
+}
+
+#[tokio::test]
+async fn test_pipeline_writes_docx_specification() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut config = Config::default();
+    config.output.spec_format = "docx".into();
+    let pipeline = Pipeline::new(
+        Arc::new(MockLlmAdapter::new(vec![
+            mock_refine_response(),
+            mock_generate_response(),
+        ])),
+        Arc::new(FileTemplateEngine::new(Path::new("templates")).unwrap()),
+        config,
+    );
+    let result = pipeline
+        .run_full(
+            &[PathBuf::from("tests/fixtures/sample_us_fr.md")],
+            dir.path(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Format docx seul : pas de spec Markdown
+    assert_eq!(result.spec_docx_path.as_ref(), Some(&result.spec_path));
+    assert_eq!(result.spec_path.extension().unwrap(), "docx");
+    let specs: Vec<_> = std::fs::read_dir(dir.path().join("specs"))
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    assert!(specs.iter().all(|name| !name.ends_with(".md")), "{specs:?}");
+
+    let mut archive =
+        zip::ZipArchive::new(std::fs::File::open(&result.spec_path).unwrap()).unwrap();
+    let mut document = String::new();
+    std::io::Read::read_to_string(
+        &mut archive.by_name("word/document.xml").unwrap(),
+        &mut document,
+    )
+    .unwrap();
+    assert!(document.contains("Annexe A - Tracabilite"));
+    assert!(document.contains("Couverture forward"));
+    for fr in &result.specification.functional_requirements {
+        assert!(document.contains(&fr.id));
+    }
+
+    let manifest: OutputManifest =
+        serde_json::from_str(&std::fs::read_to_string(&result.manifest.manifest_path).unwrap())
+            .unwrap();
+    let relative = result
+        .spec_path
+        .strip_prefix(dir.path())
+        .unwrap()
+        .to_string_lossy()
+        .replace('\\', "/");
+    assert_eq!(
+        manifest.get(&relative).unwrap().kind,
+        ArtifactKind::Specification
+    );
+}
+
+/// Writer d'une bibliotheque cliente : liste des exigences en CSV
+struct RequirementsCsvWriter;
+
+#[async_trait]
+impl OutputWriter for RequirementsCsvWriter {
+    fn format(&self) -> &str {
+        "requirements-csv"
+    }
+
+    async fn write_outputs(
+        &self,
+        spec: &Specification,
+        _suite: &TestSuite,
+        layout: &OutputLayout,
+    ) -> Result<Vec<WrittenArtifact>, anyhow::Error> {
+        let path = layout.root.join("requirements.csv");
+        let rows: Vec<String> = spec
+            .functional_requirements
+            .iter()
+            .map(|fr| format!("{};{}", fr.id, fr.statement))
+            .collect();
+        std::fs::write(&path, rows.join("\n"))?;
+        Ok(vec![WrittenArtifact::for_spec(
+            path,
+            ArtifactKind::Custom,
+            spec,
+        )])
+    }
+}
+
+#[tokio::test]
+async fn test_pipeline_configured_formats_and_custom_writer() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = Config::default();
+    config.output.formats = vec!["markdown".into(), "feature".into(), "json".into()];
+    let pipeline = Pipeline::new(
+        Arc::new(MockLlmAdapter::new(vec![
+            mock_refine_response(),
+            mock_generate_response(),
+        ])),
+        Arc::new(FileTemplateEngine::new(Path::new("templates")).unwrap()),
+        config,
+    )
+    .with_writer(Arc::new(RequirementsCsvWriter));
+    let result = pipeline
+        .run_full(
+            &[PathBuf::from("tests/fixtures/sample_us_fr.md")],
+            dir.path(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // `traceability` absent de output.formats
+    assert!(result.traceability_path.is_none());
+    assert!(!dir.path().join("traceability.md").exists());
+    assert!(result.spec_path.exists());
+    assert!(!result.feature_paths.is_empty());
+
+    let spec: Specification = serde_json::from_str(
+        &std::fs::read_to_string(dir.path().join("specification.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(spec.id, result.specification.id);
+    assert!(dir.path().join("test_suite.json").exists());
+
+    let csv = std::fs::read_to_string(dir.path().join("requirements.csv")).unwrap();
+    assert!(csv.starts_with("FR-001;"));
+
+    let manifest: OutputManifest =
+        serde_json::from_str(&std::fs::read_to_string(&result.manifest.manifest_path).unwrap())
+            .unwrap();
+    assert_eq!(
+        manifest.get("requirements.csv").unwrap().kind,
+        ArtifactKind::Custom
+    );
+    assert_eq!(
+        manifest.get("specification.json").unwrap().kind,
+        ArtifactKind::Json
+    );
+    assert_eq!(
+        result.artifacts.last().map(|a| a.kind),
+        Some(ArtifactKind::Custom)
+    );
+}
@@ -1,8 +1,12 @@
 use std::path::Path;
 
+use spec_forge::adapters::input::asciidoc_reader::AsciiDocReader;
+use spec_forge::adapters::input::docx_reader::DocxReader;
+use spec_forge::adapters::input::html_reader::HtmlReader;
 use spec_forge::adapters::input::markdown_reader::MarkdownReader;
+use spec_forge::adapters::input::odt_reader::OdtReader;
 use spec_forge::adapters::input::yaml_reader::YamlReader;
-use spec_forge::domain::user_story::Language;
+use spec_forge::domain::user_story::{Language, Priority};
 use spec_forge::ports::input_reader::InputReader;
 
 #[tokio::test]
@@ -61,3 +65,102 @@ async fn test_read_nonexistent_file() {
     let result = reader.read_stories(path).await;
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn test_read_markdown_with_metadata() {
+    let reader = MarkdownReader::new();
+    let path = Path::new("tests/fixtures/sample_us_metadata.md");
+    let story_set = reader.read_stories(path).await.unwrap();
+
+    assert_eq!(story_set.stories.len(), 2);
+    let first = &story_set.stories[0];
+    assert_eq!(first.external_id.as_deref(), Some("PAY-42"));
+    assert_eq!(first.priority, Some(Priority::P1));
+    assert_eq!(first.tags, vec!["paiement", "sepa", "virement"]);
+    assert_eq!(first.stakeholder.as_deref(), Some("Compliance"));
+    assert_eq!(first.acceptance_criteria.len(), 2);
+
+    let second = &story_set.stories[1];
+    assert_eq!(second.external_id.as_deref(), Some("PAY-43"));
+    assert_eq!(second.priority, Some(Priority::P2));
+    assert_eq!(second.tags, vec!["paiement"]);
+    assert_eq!(
+        second.stakeholder.as_deref(),
+        Some("Direction des paiements")
+    );
+}
+
+#[tokio::test]
+async fn test_read_html() {
+    let reader = HtmlReader::new();
+    let path = Path::new("tests/fixtures/sample_us.html");
+    let story_set = reader.read_stories(path).await.unwrap();
+
+    assert_eq!(story_set.stories.len(), 2);
+    assert_eq!(story_set.language, Language::French);
+    let first = &story_set.stories[0];
+    assert_eq!(first.external_id.as_deref(), Some("LIB-1"));
+    assert_eq!(first.actor, "bibliothécaire");
+    assert_eq!(first.epic.as_deref(), Some("Bibliotheque"));
+    assert_eq!(first.acceptance_criteria.len(), 2);
+    assert_eq!(story_set.stories[1].acceptance_criteria.len(), 2);
+}
+
+#[tokio::test]
+async fn test_read_asciidoc() {
+    let reader = AsciiDocReader::new();
+    let path = Path::new("tests/fixtures/sample_us.adoc");
+    let story_set = reader.read_stories(path).await.unwrap();
+
+    assert_eq!(story_set.stories.len(), 2);
+    assert_eq!(story_set.language, Language::French);
+    let first = &story_set.stories[0];
+    assert_eq!(first.external_id.as_deref(), Some("LIB-1"));
+    assert_eq!(first.acceptance_criteria.len(), 2);
+    let second = &story_set.stories[1];
+    assert_eq!(second.external_id.as_deref(), Some("LIB-2"));
+    assert_eq!(second.actor, "futur adherent");
+    assert_eq!(second.epic.as_deref(), Some("Bibliotheque"));
+    assert_eq!(second.acceptance_criteria.len(), 2);
+}
+
+#[tokio::test]
+async fn test_read_odt() {
+    let reader = OdtReader::new();
+    let path = Path::new("tests/fixtures/sample_us.odt");
+    let story_set = reader.read_stories(path).await.unwrap();
+
+    assert_eq!(story_set.stories.len(), 2);
+    assert_eq!(story_set.language, Language::French);
+    assert_eq!(story_set.stories[0].title, "Recherche par ISBN");
+    assert_eq!(
+        story_set.stories[0].acceptance_criteria[1],
+        "Les résultats s'affichent en moins de 2 secondes"
+    );
+    assert_eq!(
+        story_set.stories[1].acceptance_criteria[1],
+        "Un email de confirmation est envoyé automatiquement"
+    );
+}
+
+#[tokio::test]
+async fn test_read_docx_heading1_stories_without_heading2() {
+    let reader = DocxReader::new();
+    let path = Path::new("tests/fixtures/sample_us_h1.docx");
+    let story_set = reader.read_stories(path).await.unwrap();
+
+    // Chaque Heading1 sans Heading2 delimite une story ; le preambule est ignore
+    assert_eq!(story_set.stories.len(), 2);
+    let first = &story_set.stories[0];
+    assert_eq!(first.title, "Virement SEPA");
+    assert_eq!(first.epic.as_deref(), Some("Virement SEPA"));
+    assert_eq!(first.action, "virer de l'argent");
+    assert_eq!(
+        first.acceptance_criteria,
+        vec!["Le plafond est de 10 000 EUR", "L'IBAN est valide"]
+    );
+    let second = &story_set.stories[1];
+    assert_eq!(second.title, "Opposition carte");
+    assert_eq!(second.epic.as_deref(), Some("Opposition carte"));
+    assert_eq!(second.acceptance_criteria, vec!["Le blocage est immediat"]);
+}
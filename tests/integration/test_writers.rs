@@ -1,6 +1,10 @@
+use std::path::Path;
+use std::sync::Arc;
+
 use spec_forge::adapters::output::gherkin_writer::GherkinWriter;
 use spec_forge::adapters::output::markdown_writer::MarkdownWriter;
 use spec_forge::adapters::output::traceability_writer::TraceabilityWriter;
+use spec_forge::adapters::templates::file_template_engine::FileTemplateEngine;
 use spec_forge::domain::specification::*;
 use spec_forge::domain::test_case::*;
 use spec_forge::domain::user_story::{Language, Priority};
@@ -139,3 +143,40 @@ async fn test_traceability_writer_to_disk() {
     assert!(content.contains("FR-001"));
     assert!(content.contains("Couvert"));
 }
+
+#[tokio::test]
+async fn test_writers_use_project_template_overrides() {
+    let overrides = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        overrides.path().join("output_traceability.md.hbs"),
+        "# Tracabilite {{spec.title}}\n\
+         {{#each matrix.entries}}{{row requirement_id labels.status}}\n{{/each}}\
+         Score: {{percent metrics.overall_score}}\n",
+    )
+    .unwrap();
+    let templates = Arc::new(
+        FileTemplateEngine::new(Path::new("templates"))
+            .unwrap()
+            .with_overrides(overrides.path())
+            .unwrap(),
+    );
+    let spec = make_test_spec();
+    let suite = make_test_suite();
+    let dir = tempfile::TempDir::new().unwrap();
+
+    let trace_path = TraceabilityWriter::with_templates(templates.clone())
+        .write(&spec, &suite, dir.path())
+        .await
+        .unwrap();
+    let content = tokio::fs::read_to_string(&trace_path).await.unwrap();
+    assert!(content.starts_with("# Tracabilite Test Spec\n| FR-001 | **GAP** |\n"));
+    assert!(content.contains("Score: "));
+
+    // Le template de specification non surcharge reste celui par defaut
+    let spec_path = MarkdownWriter::with_templates(templates)
+        .write(&spec, dir.path())
+        .await
+        .unwrap();
+    let content = tokio::fs::read_to_string(&spec_path).await.unwrap();
+    assert!(content.starts_with("# Feature Specification: Test Spec"));
+}